@@ -9,6 +9,7 @@
 //!
 //! [`tackler-core`]: ../../tackler_core/index.html
 mod filter_definition;
+mod filter_expr;
 pub mod logic;
 pub mod posting;
 pub mod txn;
@@ -4,6 +4,7 @@
  */
 use crate::filters::IndentDisplay;
 use crate::filters::TxnFilter;
+use crate::filters::filter_expr;
 use crate::tackler;
 use base64::{Engine as _, engine::general_purpose};
 use jiff::tz::TimeZone;
@@ -59,6 +60,64 @@ impl Display for FilterDefZoned<'_> {
 impl FilterDefinition {
     const FILTER_ARMOR: &'static str = "base64:";
 
+    /// Generate filter from filter expression
+    ///
+    /// See [`filter_expr`](super::filter_expr) for the syntax of filter expressions.
+    /// Dates and local date-times of the expression are in the timezone `tz`.
+    ///
+    /// # Errors
+    ///
+    /// Return `Err` if the filter expression is not valid
+    ///
+    /// # Examples
+    /// ```
+    /// # use tackler_api::tackler;
+    /// # use tackler_api::filters::FilterDefinition;
+    /// # use jiff::tz::TimeZone;
+    ///
+    /// let expr = r#"account =~ "Expenses:.*" and ts >= 2025-01-01 and not tag:"work""#;
+    ///
+    /// let tf = FilterDefinition::from_expr(expr, &TimeZone::UTC)?;
+    ///
+    /// assert_eq!(
+    ///     tf.to_expr(&TimeZone::UTC),
+    ///     r#"account =~ "Expenses:.*" and ts >= 2025-01-01 and not tag =~ "work""#
+    /// );
+    /// # Ok::<(), tackler::Error>(())
+    /// ```
+    pub fn from_expr(expr: &str, tz: &TimeZone) -> Result<FilterDefinition, tackler::Error> {
+        Ok(FilterDefinition {
+            txn_filter: filter_expr::parse(expr, tz)?,
+        })
+    }
+
+    /// Generate filter from JSON, ascii armored JSON or filter expression
+    ///
+    /// JSON must start with `{`, and ascii armor must have prefix `base64:`,
+    /// otherwise the filter is parsed as filter expression.
+    ///
+    /// # Errors
+    ///
+    /// Return `Err` if the filter definition is not valid
+    pub fn parse(filt_str: &str, tz: &TimeZone) -> Result<FilterDefinition, tackler::Error> {
+        if FilterDefinition::is_armored(filt_str) {
+            FilterDefinition::from_armor(filt_str)
+        } else if filt_str.trim_start().starts_with('{') {
+            FilterDefinition::from_json_str(filt_str)
+        } else {
+            FilterDefinition::from_expr(filt_str, tz)
+        }
+    }
+
+    /// Filter as filter expression
+    ///
+    /// Timestamps are printed in the timezone `tz`, and
+    /// the expression can be parsed back with [`FilterDefinition::from_expr`].
+    #[must_use]
+    pub fn to_expr(&self, tz: &TimeZone) -> String {
+        filter_expr::to_expr(&self.txn_filter, tz)
+    }
+
     /// Generate filter from JSON String
     ///
     /// # Errors
@@ -0,0 +1,653 @@
+/*
+ * Tackler-NG 2026
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Txn Filter expressions
+//!
+//! Filter expression is textual form of [`TxnFilter`], for example:
+//!
+//! ```text
+//! account =~ "Expenses:.*" and ts >= 2025-01-01 and not tag:"work"
+//! ```
+//!
+//! | Expression                        | Filter                             |
+//! |-----------------------------------|------------------------------------|
+//! | `a and b`, `a or b`, `not a`      | AND, OR, NOT                       |
+//! | `true`, `false`                   | All pass, None pass                |
+//! | `ts >= TS`                        | Txn TS: begin (inclusive)          |
+//! | `ts < TS`                         | Txn TS: end (exclusive)            |
+//! | `code =~ "re"`                    | Txn Code                           |
+//! | `desc =~ "re"`                    | Txn Description                    |
+//! | `uuid = UUID`                     | Txn UUID                           |
+//! | `extid =~ "re"`                   | Txn Ext-Id                         |
+//! | `tag =~ "re"`                     | Txn Tags                           |
+//! | `comment =~ "re"`                 | Txn Comments                       |
+//! | `bbox(s, w, n, e)`                | Txn Bounding Box 2D                |
+//! | `bbox(s, w, depth, n, e, height)` | Txn Bounding Box 3D                |
+//! | `account =~ "re"`                 | Posting Account                    |
+//! | `commodity =~ "re"`               | Posting Commodity                  |
+//! | `posting.comment =~ "re"`         | Posting Comment                    |
+//! | `amount("re") = N`                | Posting Amount (also `<` and `>`)  |
+//!
+//! `field:"re"` is the same as `field =~ "re"`. Regular expressions
+//! must match the full value, as with all other txn filters.
+//!
+//! `not` binds tighter than `and`, which binds tighter than `or`.
+//!
+//! Timestamp is either a date (`2025-01-01`), local date-time
+//! (`2025-01-01T10:00:00`) or date-time with offset
+//! (`2025-01-01T10:00:00+02:00`). Date and local date-time
+//! are in the timezone given to the parser.
+
+use crate::filters::logic::{TxnFilterAND, TxnFilterNOT, TxnFilterOR};
+use crate::filters::posting::{
+    TxnFilterPostingAccount, TxnFilterPostingAmountEqual, TxnFilterPostingAmountGreater,
+    TxnFilterPostingAmountLess, TxnFilterPostingComment, TxnFilterPostingCommodity,
+};
+use crate::filters::txn::{
+    TxnFilterBBoxLatLon, TxnFilterBBoxLatLonAlt, TxnFilterTxnCode, TxnFilterTxnComments,
+    TxnFilterTxnDescription, TxnFilterTxnExtId, TxnFilterTxnTSBegin, TxnFilterTxnTSEnd,
+    TxnFilterTxnTags, TxnFilterTxnUUID,
+};
+use crate::filters::{NullaryFALSE, NullaryTRUE, TxnFilter};
+use crate::tackler;
+use crate::txn_ts::rfc_3339;
+use jiff::civil::{Date, DateTime};
+use jiff::tz::TimeZone;
+use jiff::{Timestamp, Zoned};
+use regex::Regex;
+use rust_decimal::Decimal;
+use std::str::FromStr;
+use tackler_rs::regex::{new_full_haystack_regex, peeled_pattern};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    LParen,
+    RParen,
+    Comma,
+    Op(&'static str),
+    Str(String),
+    Word(String),
+}
+
+const OPS: [&str; 8] = [">=", "<=", "=~", "==", ">", "<", "=", ":"];
+
+fn is_word_char(c: char, is_literal: bool) -> bool {
+    c.is_alphanumeric() || "_.-+".contains(c) || (is_literal && c == ':')
+}
+
+/// Split expression into tokens, with char position of each token
+fn tokenize(expr: &str) -> Result<Vec<(usize, Token)>, tackler::Error> {
+    let chars: Vec<char> = expr.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        let token = match c {
+            '(' => {
+                i += 1;
+                Token::LParen
+            }
+            ')' => {
+                i += 1;
+                Token::RParen
+            }
+            ',' => {
+                i += 1;
+                Token::Comma
+            }
+            '"' => {
+                let mut s = String::new();
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        None => return Err(expr_error(start, "unterminated string")),
+                        Some('"') => break,
+                        Some('\\') if matches!(chars.get(i + 1), Some('"' | '\\')) => {
+                            s.push(chars[i + 1]);
+                            i += 2;
+                        }
+                        Some(ch) => {
+                            s.push(*ch);
+                            i += 1;
+                        }
+                    }
+                }
+                i += 1;
+                Token::Str(s)
+            }
+            c if is_word_char(c, false) => {
+                let is_literal = c.is_ascii_digit() || c == '-' || c == '+';
+                while i < chars.len() && is_word_char(chars[i], is_literal) {
+                    i += 1;
+                }
+                Token::Word(chars[start..i].iter().collect())
+            }
+            _ => {
+                let rest: String = chars[i..].iter().take(2).collect();
+                let Some(op) = OPS.iter().find(|op| rest.starts_with(**op)) else {
+                    return Err(expr_error(start, &format!("unexpected character '{c}'")));
+                };
+                i += op.chars().count();
+                Token::Op(op)
+            }
+        };
+        tokens.push((start, token));
+    }
+    Ok(tokens)
+}
+
+fn expr_error(pos: usize, msg: &str) -> tackler::Error {
+    format!("Filter expression error at column {}: {msg}", pos + 1).into()
+}
+
+struct Parser<'a> {
+    tokens: Vec<(usize, Token)>,
+    pos: usize,
+    end: usize,
+    tz: &'a TimeZone,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(_, t)| t)
+    }
+
+    fn column(&self) -> usize {
+        self.tokens.get(self.pos).map_or(self.end, |(p, _)| *p)
+    }
+
+    fn error(&self, msg: &str) -> tackler::Error {
+        expr_error(self.column(), msg)
+    }
+
+    fn next(&mut self, expected: &str) -> Result<Token, tackler::Error> {
+        match self.tokens.get(self.pos) {
+            Some((_, t)) => {
+                self.pos += 1;
+                Ok(t.clone())
+            }
+            None => Err(self.error(&format!("expected {expected}, found end of expression"))),
+        }
+    }
+
+    fn expect(&mut self, token: &Token, expected: &str) -> Result<(), tackler::Error> {
+        if self.peek() == Some(token) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected {expected}")))
+        }
+    }
+
+    fn is_keyword(&self, kw: &str) -> bool {
+        matches!(self.peek(), Some(Token::Word(w)) if w == kw)
+    }
+
+    fn parse_or(&mut self) -> Result<TxnFilter, tackler::Error> {
+        let mut filters = vec![self.parse_and()?];
+        while self.is_keyword("or") {
+            self.pos += 1;
+            filters.push(self.parse_and()?);
+        }
+        if filters.len() == 1 {
+            Ok(filters.remove(0))
+        } else {
+            Ok(TxnFilter::TxnFilterOR(TxnFilterOR::new(filters)?))
+        }
+    }
+
+    fn parse_and(&mut self) -> Result<TxnFilter, tackler::Error> {
+        let mut filters = vec![self.parse_not()?];
+        while self.is_keyword("and") {
+            self.pos += 1;
+            filters.push(self.parse_not()?);
+        }
+        if filters.len() == 1 {
+            Ok(filters.remove(0))
+        } else {
+            Ok(TxnFilter::TxnFilterAND(TxnFilterAND::new(filters)?))
+        }
+    }
+
+    fn parse_not(&mut self) -> Result<TxnFilter, tackler::Error> {
+        if self.is_keyword("not") {
+            self.pos += 1;
+            let tf = self.parse_not()?;
+            return Ok(TxnFilter::TxnFilterNOT(TxnFilterNOT {
+                txn_filter: Box::new(tf),
+            }));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<TxnFilter, tackler::Error> {
+        let column = self.column();
+        match self.next("filter")? {
+            Token::LParen => {
+                let tf = self.parse_or()?;
+                self.expect(&Token::RParen, "')'")?;
+                Ok(tf)
+            }
+            Token::Word(w) => self.parse_filter(&w, column),
+            _ => Err(expr_error(column, "expected filter")),
+        }
+    }
+
+    fn parse_filter(&mut self, field: &str, column: usize) -> Result<TxnFilter, tackler::Error> {
+        let tf = match field {
+            "true" => TxnFilter::NullaryTRUE(NullaryTRUE {}),
+            "false" => TxnFilter::NullaryFALSE(NullaryFALSE {}),
+            "ts" => {
+                let op = self.parse_op()?;
+                let ts = self.parse_ts()?;
+                match op {
+                    ">=" => TxnFilter::TxnFilterTxnTSBegin(TxnFilterTxnTSBegin { begin: ts }),
+                    "<" => TxnFilter::TxnFilterTxnTSEnd(TxnFilterTxnTSEnd { end: ts }),
+                    _ => {
+                        return Err(expr_error(
+                            column,
+                            "'ts' can be used only with '>=' and '<'",
+                        ));
+                    }
+                }
+            }
+            "uuid" => {
+                if !matches!(self.parse_op()?, "=" | "==") {
+                    return Err(expr_error(column, "'uuid' can be used only with '='"));
+                }
+                let value_column = self.column();
+                let value = self.parse_value("uuid")?;
+                let uuid = Uuid::parse_str(&value).map_err(|err| {
+                    expr_error(value_column, &format!("invalid uuid '{value}': {err}"))
+                })?;
+                TxnFilter::TxnFilterTxnUUID(TxnFilterTxnUUID { uuid })
+            }
+            "bbox" => self.parse_bbox()?,
+            "amount" => {
+                self.expect(&Token::LParen, "'('")?;
+                let regex = self.parse_regex()?;
+                self.expect(&Token::RParen, "')'")?;
+                let op = self.parse_op()?;
+                let amount = self.parse_decimal()?;
+                match op {
+                    "=" | "==" => {
+                        TxnFilter::TxnFilterPostingAmountEqual(TxnFilterPostingAmountEqual {
+                            regex,
+                            amount,
+                        })
+                    }
+                    "<" => TxnFilter::TxnFilterPostingAmountLess(TxnFilterPostingAmountLess {
+                        regex,
+                        amount,
+                    }),
+                    ">" => {
+                        TxnFilter::TxnFilterPostingAmountGreater(TxnFilterPostingAmountGreater {
+                            regex,
+                            amount,
+                        })
+                    }
+                    _ => {
+                        let msg = "'amount' can be used only with '=', '<' and '>'";
+                        return Err(expr_error(column, msg));
+                    }
+                }
+            }
+            "code" | "desc" | "extid" | "tag" | "comment" | "account" | "commodity"
+            | "posting.comment" => {
+                if !matches!(self.parse_op()?, "=~" | ":") {
+                    let msg = format!("'{field}' can be used only with '=~' or ':'");
+                    return Err(expr_error(column, &msg));
+                }
+                let regex = self.parse_regex()?;
+                match field {
+                    "code" => TxnFilter::TxnFilterTxnCode(TxnFilterTxnCode { regex }),
+                    "desc" => TxnFilter::TxnFilterTxnDescription(TxnFilterTxnDescription { regex }),
+                    "extid" => TxnFilter::TxnFilterTxnExtId(TxnFilterTxnExtId { regex }),
+                    "tag" => TxnFilter::TxnFilterTxnTags(TxnFilterTxnTags { regex }),
+                    "comment" => TxnFilter::TxnFilterTxnComments(TxnFilterTxnComments { regex }),
+                    "account" => {
+                        TxnFilter::TxnFilterPostingAccount(TxnFilterPostingAccount { regex })
+                    }
+                    "commodity" => {
+                        TxnFilter::TxnFilterPostingCommodity(TxnFilterPostingCommodity { regex })
+                    }
+                    _ => TxnFilter::TxnFilterPostingComment(TxnFilterPostingComment { regex }),
+                }
+            }
+            _ => return Err(expr_error(column, &format!("unknown filter '{field}'"))),
+        };
+        Ok(tf)
+    }
+
+    fn parse_op(&mut self) -> Result<&'static str, tackler::Error> {
+        let column = self.column();
+        match self.next("operator")? {
+            Token::Op(op) => Ok(op),
+            _ => Err(expr_error(column, "expected operator")),
+        }
+    }
+
+    fn parse_value(&mut self, expected: &str) -> Result<String, tackler::Error> {
+        let column = self.column();
+        match self.next(expected)? {
+            Token::Word(s) | Token::Str(s) => Ok(s),
+            _ => Err(expr_error(column, &format!("expected {expected}"))),
+        }
+    }
+
+    fn parse_regex(&mut self) -> Result<Regex, tackler::Error> {
+        let column = self.column();
+        match self.next("quoted regex")? {
+            Token::Str(s) => new_full_haystack_regex(&s)
+                .map_err(|err| expr_error(column, &format!("invalid regex: {err}"))),
+            _ => Err(expr_error(column, "expected quoted regex")),
+        }
+    }
+
+    fn parse_decimal(&mut self) -> Result<Decimal, tackler::Error> {
+        let column = self.column();
+        let value = self.parse_value("number")?;
+        Decimal::from_str(&value)
+            .map_err(|err| expr_error(column, &format!("invalid number '{value}': {err}")))
+    }
+
+    fn parse_ts(&mut self) -> Result<Timestamp, tackler::Error> {
+        let column = self.column();
+        let value = self.parse_value("timestamp")?;
+        let ts = if let Ok(ts) = Timestamp::from_str(&value) {
+            Ok(ts)
+        } else if let Ok(dt) = DateTime::from_str(&value) {
+            dt.to_zoned(self.tz.clone()).map(|z| z.timestamp())
+        } else {
+            Date::from_str(&value)
+                .and_then(|d| d.to_zoned(self.tz.clone()))
+                .map(|z| z.timestamp())
+        };
+        ts.map_err(|err| expr_error(column, &format!("invalid timestamp '{value}': {err}")))
+    }
+
+    fn parse_bbox(&mut self) -> Result<TxnFilter, tackler::Error> {
+        let column = self.column();
+        self.expect(&Token::LParen, "'('")?;
+        let mut values = vec![self.parse_decimal()?];
+        while self.peek() == Some(&Token::Comma) {
+            self.pos += 1;
+            values.push(self.parse_decimal()?);
+        }
+        self.expect(&Token::RParen, "')'")?;
+        match values.as_slice() {
+            [south, west, north, east] => Ok(TxnFilter::TxnFilterBBoxLatLon(
+                TxnFilterBBoxLatLon::new(*south, *west, *north, *east)?,
+            )),
+            [south, west, depth, north, east, height] => Ok(TxnFilter::TxnFilterBBoxLatLonAlt(
+                TxnFilterBBoxLatLonAlt::new(*south, *west, *depth, *north, *east, *height)?,
+            )),
+            _ => Err(expr_error(column, "'bbox' must have 4 or 6 coordinates")),
+        }
+    }
+}
+
+/// Parse filter expression
+///
+/// Date and local date-time are in the timezone `tz`.
+pub(crate) fn parse(expr: &str, tz: &TimeZone) -> Result<TxnFilter, tackler::Error> {
+    let mut parser = Parser {
+        tokens: tokenize(expr)?,
+        pos: 0,
+        end: expr.chars().count(),
+        tz,
+    };
+    let tf = parser.parse_or()?;
+    if parser.peek().is_some() {
+        return Err(parser.error("expected 'and', 'or' or end of expression"));
+    }
+    Ok(tf)
+}
+
+fn quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn quote_regex(regex: &Regex) -> String {
+    quote(peeled_pattern(regex))
+}
+
+fn ts_expr(ts: &Timestamp, tz: &TimeZone) -> String {
+    let zoned: Zoned = ts.to_zoned(tz.clone());
+    if zoned.time() == jiff::civil::Time::midnight() {
+        zoned.date().to_string()
+    } else {
+        rfc_3339(&zoned)
+    }
+}
+
+/// Logic filter as child of other logic filter
+fn sub_expr(tf: &TxnFilter, tz: &TimeZone, needs_parens: bool) -> String {
+    if needs_parens {
+        format!("({})", to_expr(tf, tz))
+    } else {
+        to_expr(tf, tz)
+    }
+}
+
+/// Filter as filter expression
+///
+/// Timestamps are printed in the timezone `tz`, and
+/// the expression can be parsed back with the same timezone.
+pub(crate) fn to_expr(tf: &TxnFilter, tz: &TimeZone) -> String {
+    match tf {
+        TxnFilter::NullaryTRUE(_) => "true".to_string(),
+        TxnFilter::NullaryFALSE(_) => "false".to_string(),
+
+        TxnFilter::TxnFilterAND(tf) => tf
+            .txn_filters
+            .iter()
+            .map(|f| {
+                let is_logic = matches!(f, TxnFilter::TxnFilterAND(_) | TxnFilter::TxnFilterOR(_));
+                sub_expr(f, tz, is_logic)
+            })
+            .collect::<Vec<_>>()
+            .join(" and "),
+        TxnFilter::TxnFilterOR(tf) => tf
+            .txn_filters
+            .iter()
+            .map(|f| sub_expr(f, tz, matches!(f, TxnFilter::TxnFilterOR(_))))
+            .collect::<Vec<_>>()
+            .join(" or "),
+        TxnFilter::TxnFilterNOT(tf) => {
+            let f = tf.txn_filter.as_ref();
+            let is_logic = matches!(f, TxnFilter::TxnFilterAND(_) | TxnFilter::TxnFilterOR(_));
+            format!("not {}", sub_expr(f, tz, is_logic))
+        }
+
+        TxnFilter::TxnFilterTxnTSBegin(tf) => format!("ts >= {}", ts_expr(&tf.begin, tz)),
+        TxnFilter::TxnFilterTxnTSEnd(tf) => format!("ts < {}", ts_expr(&tf.end, tz)),
+        TxnFilter::TxnFilterTxnCode(tf) => format!("code =~ {}", quote_regex(&tf.regex)),
+        TxnFilter::TxnFilterTxnDescription(tf) => format!("desc =~ {}", quote_regex(&tf.regex)),
+        TxnFilter::TxnFilterTxnUUID(tf) => format!("uuid = {}", tf.uuid),
+        TxnFilter::TxnFilterTxnExtId(tf) => format!("extid =~ {}", quote_regex(&tf.regex)),
+        TxnFilter::TxnFilterBBoxLatLon(tf) => {
+            format!("bbox({}, {}, {}, {})", tf.south, tf.west, tf.north, tf.east)
+        }
+        TxnFilter::TxnFilterBBoxLatLonAlt(tf) => format!(
+            "bbox({}, {}, {}, {}, {}, {})",
+            tf.south, tf.west, tf.depth, tf.north, tf.east, tf.height
+        ),
+        TxnFilter::TxnFilterTxnTags(tf) => format!("tag =~ {}", quote_regex(&tf.regex)),
+        TxnFilter::TxnFilterTxnComments(tf) => format!("comment =~ {}", quote_regex(&tf.regex)),
+
+        TxnFilter::TxnFilterPostingAccount(tf) => {
+            format!("account =~ {}", quote_regex(&tf.regex))
+        }
+        TxnFilter::TxnFilterPostingComment(tf) => {
+            format!("posting.comment =~ {}", quote_regex(&tf.regex))
+        }
+        TxnFilter::TxnFilterPostingAmountEqual(tf) => {
+            format!("amount({}) = {}", quote_regex(&tf.regex), tf.amount)
+        }
+        TxnFilter::TxnFilterPostingAmountLess(tf) => {
+            format!("amount({}) < {}", quote_regex(&tf.regex), tf.amount)
+        }
+        TxnFilter::TxnFilterPostingAmountGreater(tf) => {
+            format!("amount({}) > {}", quote_regex(&tf.regex), tf.amount)
+        }
+        TxnFilter::TxnFilterPostingCommodity(tf) => {
+            format!("commodity =~ {}", quote_regex(&tf.regex))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filters::{FilterDefZoned, FilterDefinition};
+    use indoc::indoc;
+    use tackler_rs::IndocUtils;
+
+    fn helsinki() -> TimeZone {
+        TimeZone::get("Europe/Helsinki").unwrap(/*:test:*/)
+    }
+
+    #[test]
+    // test: 4e7c1a9b-2d3f-4b5e-8c6a-9f0e1d2c3b4a
+    // desc: filter expression, all filters, round-trip
+    fn filter_expr_round_trip() {
+        let tz = helsinki();
+        let exprs = [
+            "true",
+            "false",
+            "ts >= 2025-01-01",
+            "ts < 2025-01-01T10:11:12.345+02:00",
+            r##"code =~ "#1.*""##,
+            r#"desc =~ "say \"hello\" \\d+""#,
+            "uuid = 9d2e2a5c-3c6e-4f0b-8a16-1c0a9e7b5d3f",
+            r#"extid =~ "bank-.*""#,
+            r#"tag =~ "work""#,
+            r#"comment =~ ".*note.*""#,
+            "bbox(59.85, 24.0, 60.5, 25.5)",
+            "bbox(59.85, 24.0, -10, 60.5, 25.5, 1000)",
+            r#"account =~ "Expenses:.*""#,
+            r#"commodity =~ "EUR""#,
+            r#"posting.comment =~ ".*refund.*""#,
+            r#"amount("Expenses:.*") = 12.50"#,
+            r#"amount("Expenses:.*") < -1"#,
+            r#"amount("Expenses:.*") > 100"#,
+            r#"account =~ "a" or tag =~ "b" and not code =~ "c""#,
+            r#"(account =~ "a" or tag =~ "b") and not (code =~ "c" or desc =~ "d")"#,
+            r#"account =~ "a" and (tag =~ "b" and code =~ "c")"#,
+            "not not true",
+        ];
+        for expr in exprs {
+            let tf = parse(expr, &tz).unwrap(/*:test:*/);
+            assert_eq!(to_expr(&tf, &tz), expr);
+        }
+    }
+
+    #[test]
+    // test: 1a3c5e7f-9b2d-4f6a-8c0e-2b4d6f8a0c1e
+    // desc: filter expression, shorthand forms and structure
+    fn filter_expr_parse() {
+        let tz = helsinki();
+        let tf = parse(
+            r#"account:"Expenses:.*" and ts >= 2025-01-01T00:00:00 and not tag:"work""#,
+            &tz,
+        )
+        .unwrap(/*:test:*/);
+
+        #[rustfmt::skip]
+        let reference = indoc!(
+           "|Filter
+            |  AND
+            |    Posting Account: \"Expenses:.*\"
+            |    Txn TS: begin 2025-01-01T00:00:00+02:00
+            |    NOT
+            |      Txn Tags: \"work\"
+            |"
+        ).strip_margin();
+        let filt_def = FilterDefinition { txn_filter: tf };
+        assert_eq!(
+            format!(
+                "{}",
+                FilterDefZoned {
+                    filt_def: &filt_def,
+                    tz: tz.clone()
+                }
+            ),
+            reference
+        );
+        assert_eq!(
+            to_expr(&filt_def.txn_filter, &tz),
+            r#"account =~ "Expenses:.*" and ts >= 2025-01-01 and not tag =~ "work""#
+        );
+    }
+
+    #[test]
+    // test: 7f9b1d3e-5a2c-4e8f-b0d6-3c5e7a9b1d2f
+    // desc: filter expression, errors
+    fn filter_expr_errors() {
+        let tz = helsinki();
+        let cases = [
+            (
+                "",
+                "Filter expression error at column 1: expected filter, found end of expression",
+            ),
+            (
+                "foo =~ \"a\"",
+                "Filter expression error at column 1: unknown filter 'foo'",
+            ),
+            (
+                "ts > 2025-01-01",
+                "Filter expression error at column 1: 'ts' can be used only with '>=' and '<'",
+            ),
+            (
+                "account =~ \"a",
+                "Filter expression error at column 12: unterminated string",
+            ),
+            (
+                "account =~ a",
+                "Filter expression error at column 12: expected quoted regex",
+            ),
+            (
+                "account =~ \"(\"",
+                "Filter expression error at column 12: invalid regex: regex parse error:\n    ^(?:()$\n     ^\nerror: unclosed group",
+            ),
+            ("(true", "Filter expression error at column 6: expected ')'"),
+            (
+                "true false",
+                "Filter expression error at column 6: expected 'and', 'or' or end of expression",
+            ),
+            (
+                "bbox(1, 2, 3)",
+                "Filter expression error at column 5: 'bbox' must have 4 or 6 coordinates",
+            ),
+            (
+                "ts >= 2025-13-01",
+                "Filter expression error at column 7: invalid timestamp '2025-13-01': failed to parse month in date: failed to parse two digit integer as month: parameter 'month' is not in the required range of 1..=12",
+            ),
+            (
+                "uuid = 1234",
+                "Filter expression error at column 8: invalid uuid '1234': invalid length: found 4",
+            ),
+            (
+                "true & false",
+                "Filter expression error at column 6: unexpected character '&'",
+            ),
+        ];
+        for (expr, msg) in cases {
+            let res = parse(expr, &tz);
+            assert_eq!(
+                res.err().map(|e| e.to_string()),
+                Some(msg.to_string()),
+                "{expr}"
+            );
+        }
+    }
+}
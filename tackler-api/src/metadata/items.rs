@@ -6,7 +6,7 @@
 //! This module contains various Metadata items
 //!
 
-use crate::filters::{FilterDefZoned, FilterDefinition};
+use crate::filters::FilterDefinition;
 use crate::metadata::Checksum;
 use crate::txn_ts;
 use jiff::Zoned;
@@ -40,6 +40,8 @@ pub enum MetadataItem {
     TxnFilterDescription(TxnFilterDescription),
     #[doc(hidden)]
     PriceRecords(PriceRecords),
+    #[doc(hidden)]
+    ForecastInfo(ForecastInfo),
 }
 
 impl MetadataItem {
@@ -56,6 +58,7 @@ impl Text for MetadataItem {
             Self::AccountSelectorChecksum(asc) => asc.text(tz),
             Self::TxnFilterDescription(tfd) => tfd.text(tz),
             Self::PriceRecords(pr) => pr.text(tz),
+            Self::ForecastInfo(fi) => fi.text(tz),
         }
     }
 }
@@ -164,6 +167,9 @@ impl Text for TimeZoneInfo {
 ///
 #[derive(Serialize, Debug, Clone)]
 pub struct TxnFilterDescription {
+    /// Name of the filter, if it's a named filter
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
     #[doc(hidden)]
     #[serde(rename = "txnFilterDef")]
     txn_filter_def: FilterDefinition,
@@ -174,24 +180,29 @@ impl TxnFilterDescription {
     ///
     #[must_use]
     pub fn from(tf: FilterDefinition) -> TxnFilterDescription {
-        TxnFilterDescription { txn_filter_def: tf }
+        TxnFilterDescription {
+            name: None,
+            txn_filter_def: tf,
+        }
+    }
+
+    /// Make Txn filter Description from named Filter Definition
+    ///
+    #[must_use]
+    pub fn from_named(name: &str, tf: FilterDefinition) -> TxnFilterDescription {
+        TxnFilterDescription {
+            name: Some(name.to_string()),
+            txn_filter_def: tf,
+        }
     }
 }
 impl Text for TxnFilterDescription {
     fn text(&self, tz: TimeZone) -> Vec<String> {
-        // todo: TxnFilterDescription needs proper implementation for Text
-        //       See equity_exporter::write_export
-        format!(
-            "{}",
-            FilterDefZoned {
-                filt_def: &self.txn_filter_def,
-                tz
-            }
-        )
-        .trim_end()
-        .split('\n')
-        .map(String::from)
-        .collect::<Vec<String>>()
+        let title = match &self.name {
+            Some(name) => format!("Filter: {name}"),
+            None => "Filter".to_string(),
+        };
+        vec![title, format!("  {}", self.txn_filter_def.to_expr(&tz))]
     }
 }
 
@@ -221,6 +232,53 @@ pub struct GitInputReference {
 
     /// Subject line of selected commit
     pub subject: String,
+
+    /// Base commit of git range input (`base..commit`)
+    ///
+    /// If this is set, then txn set is the delta between
+    /// base commit and selected commit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base: Option<Box<GitInputBase>>,
+
+    /// Uncommitted input on top of the commit
+    ///
+    /// If this is set, then txn set is read from index or
+    /// from working tree, and the commit is the current HEAD.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dirty: Option<GitInputDirty>,
+}
+
+/// Metadata information about uncommitted Git input
+///
+#[derive(Serialize, Debug, Clone)]
+pub struct GitInputDirty {
+    /// Source of uncommitted txns: `index` or `worktree`
+    pub input: String,
+
+    /// Count of txn shards which are different from the commit
+    pub changed: usize,
+}
+
+/// Metadata information about the base commit of Git range input
+///
+#[derive(Serialize, Debug, Clone)]
+pub struct GitInputBase {
+    /// commit id
+    pub commit: String,
+
+    /// Symbolic git reference `main`, `Y2023`, etc.
+    #[serde(rename = "ref")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
+
+    /// Commit author
+    pub author: String,
+
+    /// Commit date
+    pub date: String,
+
+    /// Subject line of base commit
+    pub subject: String,
 }
 
 impl Text for GitInputReference {
@@ -242,6 +300,29 @@ impl Text for GitInputReference {
             format!("{:>pad$} : {}", "date", self.date),
             format!("{:>pad$} : {}", "subject", self.subject),
         ]
+        .into_iter()
+        .chain(self.base.iter().flat_map(|base| {
+            vec![
+                format!(
+                    "{:>pad$} : {}",
+                    "base reference",
+                    base.reference
+                        .as_ref()
+                        .unwrap_or(&"FIXED by commit".to_string())
+                ),
+                format!("{:>pad$} : {}", "base commit", base.commit),
+                format!("{:>pad$} : {}", "base author", base.author),
+                format!("{:>pad$} : {}", "base date", base.date),
+                format!("{:>pad$} : {}", "base subject", base.subject),
+            ]
+        }))
+        .chain(self.dirty.iter().flat_map(|dirty| {
+            vec![
+                format!("{:>pad$} : {}", "dirty", dirty.input),
+                format!("{:>pad$} : {}", "changed shards", dirty.changed),
+            ]
+        }))
+        .collect()
     }
 }
 
@@ -306,3 +387,39 @@ impl Text for PriceRecords {
         txt
     }
 }
+
+/// Metadata information about forecast txns
+///
+/// Forecast txns are generated from recurring txns,
+/// and they are tagged with `forecast` tag.
+#[derive(Serialize, Debug, Clone)]
+pub struct ForecastInfo {
+    /// Begin of forecast window (inclusive)
+    ///
+    /// If this is missing, then forecast begins after the last txn.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub begin: Option<Zoned>,
+    /// End of forecast window (exclusive)
+    pub end: Zoned,
+    /// Count of generated forecast txns
+    pub count: usize,
+}
+impl Text for ForecastInfo {
+    fn text(&self, tz: TimeZone) -> Vec<String> {
+        let pad = MetadataItem::ITEM_PAD;
+        vec![
+            "Forecast".to_string(),
+            format!(
+                "{:>pad$} : {}",
+                "begin",
+                self.begin
+                    .as_ref()
+                    .map_or("after last txn".to_string(), |ts| {
+                        txn_ts::as_tz_full(ts, tz.clone())
+                    })
+            ),
+            format!("{:>pad$} : {}", "end", txn_ts::as_tz_full(&self.end, tz)),
+            format!("{:>pad$} : {}", "txn count", self.count),
+        ]
+    }
+}
@@ -13,3 +13,15 @@ pub mod balance_group_report;
 
 /// Register Report API objects
 pub mod register_report;
+
+/// `PnL` Report API objects
+pub mod pnl_report;
+
+/// Budget Report API objects
+pub mod budget_report;
+
+/// Income Statement API objects
+pub mod income_statement_report;
+
+/// Cash Flow Report API objects
+pub mod cash_flow_report;
@@ -19,3 +19,57 @@ pub struct BalanceGroupReport {
     /// Balance Groups
     pub groups: Vec<BalanceReport>,
 }
+
+/// Account row of Balance Group pivot table
+#[derive(Serialize, Debug)]
+pub struct BalancePivotRow {
+    /// Account
+    pub account: String,
+
+    /// Commodity of amounts
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commodity: Option<String>,
+
+    /// Amounts in the same order as groups of the report
+    pub amounts: Vec<String>,
+
+    /// Row total over all groups
+    pub total: String,
+}
+
+/// Column totals of a commodity in Balance Group pivot table
+#[derive(Serialize, Debug)]
+pub struct BalancePivotTotal {
+    /// Commodity of amounts
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commodity: Option<String>,
+
+    /// Totals in the same order as groups of the report
+    pub amounts: Vec<String>,
+
+    /// Grand total over all groups
+    pub total: String,
+}
+
+/// Balance Group report as pivot table
+///
+/// Accounts are rows and groups (periods) are columns.
+#[derive(Serialize, Debug)]
+pub struct BalancePivotReport {
+    /// Optional metadata
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Metadata>,
+
+    /// Title of Balance Report
+    pub title: String,
+
+    /// Groups (periods) in chronological order
+    pub groups: Vec<String>,
+
+    /// Account rows, sorted by account and commodity
+    pub rows: Vec<BalancePivotRow>,
+
+    /// Optional column totals by commodity
+    #[serde(rename = "columnTotals", skip_serializing_if = "Option::is_none")]
+    pub column_totals: Option<Vec<BalancePivotTotal>>,
+}
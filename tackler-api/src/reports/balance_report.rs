@@ -1,5 +1,5 @@
 /*
- * Tackler-NG 2025
+ * Tackler-NG 2025-2026
  * SPDX-License-Identifier: Apache-2.0
  */
 use crate::metadata::Metadata;
@@ -25,6 +25,43 @@ pub struct BalanceItem {
     pub commodity: Option<String>,
 }
 
+/// One commodity position of account's inventory
+#[derive(Serialize, Debug)]
+pub struct InventoryPosition {
+    /// Sum of txns for this account in this commodity
+    #[serde(rename = "accountSum")]
+    pub account_sum: String,
+
+    /// Recursive sum of all txns for this account and all of it's children
+    #[serde(rename = "accountTreeSum")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_tree_sum: Option<String>,
+
+    /// Optional commodity for this position
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commodity: Option<String>,
+
+    /// Converted value of account sum, if it's known
+    #[serde(rename = "accountSumValue")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_sum_value: Option<String>,
+
+    /// Converted value of account tree sum, if it's known
+    #[serde(rename = "accountTreeSumValue")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_tree_sum_value: Option<String>,
+}
+
+/// One item / row in the multi-commodity inventory balance report
+#[derive(Serialize, Debug)]
+pub struct InventoryItem {
+    /// Full account name
+    pub account: String,
+
+    /// Positions of the account, one per commodity
+    pub positions: Vec<InventoryPosition>,
+}
+
 /// One delta item / row of balance report (per commodity)
 #[derive(Serialize, Debug)]
 pub struct Delta {
@@ -33,6 +70,9 @@ pub struct Delta {
     /// Optional commodity, if it's multi-currency balance
     #[serde(skip_serializing_if = "Option::is_none")]
     pub commodity: Option<String>,
+    /// Converted value of delta, if it's known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
 }
 
 /// Balance report API object
@@ -48,6 +88,15 @@ pub struct BalanceReport {
     /// Balance rows / items
     pub balances: Vec<BalanceItem>,
 
+    /// Inventory rows / items, if this is multi-commodity inventory balance
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inventory: Option<Vec<InventoryItem>>,
+
+    /// Commodity of converted values, if the balance is valued
+    #[serde(rename = "valueCommodity")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_commodity: Option<String>,
+
     /// Balance deltas rows / items
     pub deltas: Vec<Delta>,
 }
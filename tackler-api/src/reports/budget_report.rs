@@ -0,0 +1,56 @@
+/*
+ * Tackler-NG 2026
+ * SPDX-License-Identifier: Apache-2.0
+ */
+use crate::metadata::Metadata;
+use serde::Serialize;
+
+/// Budget and actual amount of an account for a single period
+#[derive(Serialize, Debug)]
+pub struct BudgetItem {
+    /// Budgeted account (including its sub-accounts)
+    pub account: String,
+
+    /// Commodity of the budget
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commodity: Option<String>,
+
+    /// Budgeted amount
+    pub budget: String,
+
+    /// Actual amount
+    pub actual: String,
+
+    /// Actual amount minus budget
+    pub variance: String,
+
+    /// Actual amount as percent of budget
+    ///
+    /// This is missing, if budget is zero
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub percent: Option<String>,
+}
+
+/// Budget of a single period (group-by key)
+#[derive(Serialize, Debug)]
+pub struct BudgetPeriod {
+    /// Period of budget, e.g. "2024-12"
+    pub period: String,
+
+    /// Budget items of the period
+    pub items: Vec<BudgetItem>,
+}
+
+/// Budget report API object
+#[derive(Serialize, Debug)]
+pub struct BudgetReport {
+    /// Optional metadata
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Metadata>,
+
+    /// Title of Budget Report
+    pub title: String,
+
+    /// Budget periods in chronological order
+    pub periods: Vec<BudgetPeriod>,
+}
@@ -0,0 +1,42 @@
+/*
+ * Tackler-NG 2026
+ * SPDX-License-Identifier: Apache-2.0
+ */
+use crate::metadata::Metadata;
+use crate::reports::income_statement_report::{StatementRow, StatementTotal};
+use serde::Serialize;
+
+/// Cash Flow Report API object
+#[derive(Serialize, Debug)]
+pub struct CashFlowReport {
+    /// Optional metadata
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Metadata>,
+
+    /// Title of Cash Flow Report
+    pub title: String,
+
+    /// Account selectors of cash accounts
+    #[serde(rename = "cashAccounts")]
+    pub cash_accounts: Vec<String>,
+
+    /// Periods (group-by keys) in chronological order
+    ///
+    /// If the report is not grouped, then there is a single period "total"
+    pub periods: Vec<String>,
+
+    /// Cash movements by counterpart account
+    ///
+    /// Positive amount is inflow of cash, and negative amount is outflow.
+    pub counterparts: Vec<StatementRow>,
+
+    /// Inflows of cash by commodity
+    pub inflows: Vec<StatementTotal>,
+
+    /// Outflows of cash by commodity
+    pub outflows: Vec<StatementTotal>,
+
+    /// Net change of cash by commodity
+    #[serde(rename = "netChange")]
+    pub net_change: Vec<StatementTotal>,
+}
@@ -0,0 +1,70 @@
+/*
+ * Tackler-NG 2026
+ * SPDX-License-Identifier: Apache-2.0
+ */
+use crate::metadata::Metadata;
+use serde::Serialize;
+
+/// Amounts of an account, one for each period
+#[derive(Serialize, Debug)]
+pub struct StatementRow {
+    /// Account (including its sub-accounts)
+    pub account: String,
+
+    /// Commodity of amounts
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commodity: Option<String>,
+
+    /// Amounts in the same order as periods of the report
+    pub amounts: Vec<String>,
+}
+
+/// Total amounts of a commodity, one for each period
+#[derive(Serialize, Debug)]
+pub struct StatementTotal {
+    /// Commodity of amounts
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commodity: Option<String>,
+
+    /// Amounts in the same order as periods of the report
+    pub amounts: Vec<String>,
+}
+
+/// Section of Income Statement (revenues or expenses)
+#[derive(Serialize, Debug)]
+pub struct StatementSection {
+    /// Title of section
+    pub title: String,
+
+    /// Accounts of section, sorted by account and commodity
+    pub accounts: Vec<StatementRow>,
+
+    /// Totals of section by commodity
+    pub totals: Vec<StatementTotal>,
+}
+
+/// Income Statement API object
+#[derive(Serialize, Debug)]
+pub struct IncomeStatementReport {
+    /// Optional metadata
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Metadata>,
+
+    /// Title of Income Statement
+    pub title: String,
+
+    /// Periods (group-by keys) in chronological order
+    ///
+    /// If the report is not grouped, then there is a single period "total"
+    pub periods: Vec<String>,
+
+    /// Revenues
+    pub revenues: StatementSection,
+
+    /// Expenses
+    pub expenses: StatementSection,
+
+    /// Net income (revenues - expenses) by commodity
+    #[serde(rename = "netIncome")]
+    pub net_income: Vec<StatementTotal>,
+}
@@ -0,0 +1,109 @@
+/*
+ * Tackler-NG 2026
+ * SPDX-License-Identifier: Apache-2.0
+ */
+use crate::metadata::Metadata;
+use serde::Serialize;
+
+/// Realized profit or loss of closed lot
+#[derive(Serialize, Debug)]
+pub struct RealizedItem {
+    /// Opening time of the lot in display format
+    pub opened: String,
+
+    /// Closing time of the lot in display format
+    pub closed: String,
+
+    /// Account of the lot
+    pub account: String,
+
+    /// Closed quantity (negative for short position)
+    pub quantity: String,
+
+    /// Commodity of the lot
+    pub commodity: String,
+
+    /// Unit cost of the lot
+    #[serde(rename = "unitCost")]
+    pub unit_cost: String,
+
+    /// Closing price of one unit
+    #[serde(rename = "unitPrice")]
+    pub unit_price: String,
+
+    /// Realized profit (positive) or loss (negative)
+    pub pnl: String,
+
+    /// Commodity of cost, price and `PnL`
+    #[serde(rename = "costCommodity")]
+    pub cost_commodity: String,
+}
+
+/// Unrealized profit or loss of open lot
+#[derive(Serialize, Debug)]
+pub struct UnrealizedItem {
+    /// Opening time of the lot in display format
+    pub opened: String,
+
+    /// Account of the lot
+    pub account: String,
+
+    /// Open quantity (negative for short position)
+    pub quantity: String,
+
+    /// Commodity of the lot
+    pub commodity: String,
+
+    /// Unit cost of the lot
+    #[serde(rename = "unitCost")]
+    pub unit_cost: String,
+
+    /// Market price of one unit, if it's known
+    #[serde(rename = "marketPrice")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub market_price: Option<String>,
+
+    /// Unrealized profit (positive) or loss (negative), if market price is known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pnl: Option<String>,
+
+    /// Commodity of cost, price and `PnL`
+    #[serde(rename = "costCommodity")]
+    pub cost_commodity: String,
+}
+
+/// Total `PnL` per cost commodity
+#[derive(Serialize, Debug)]
+pub struct PnlTotal {
+    /// Total of realized `PnL`
+    pub realized: String,
+
+    /// Total of unrealized `PnL`
+    ///
+    /// This is missing, if market price is unknown for some of the open lots
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unrealized: Option<String>,
+
+    /// Commodity of totals
+    pub commodity: String,
+}
+
+/// `PnL` report API object
+#[derive(Serialize, Debug)]
+pub struct PnlReport {
+    /// Optional metadata
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Metadata>,
+
+    /// Title of `PnL` Report
+    pub title: String,
+
+    /// Realized `PnL` of closed lots
+    pub realized: Vec<RealizedItem>,
+
+    /// Unrealized `PnL` of open lots
+    pub unrealized: Vec<UnrealizedItem>,
+
+    /// Totals per cost commodity
+    pub totals: Vec<PnlTotal>,
+}
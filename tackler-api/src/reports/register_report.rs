@@ -38,11 +38,43 @@ pub struct RegisterPosting {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub base_commodity: Option<String>,
 
+    /// Running position of account and commodity
+    ///
+    /// This is controlled by conf key `report.register.cost-basis`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position: Option<RegisterPosition>,
+
     /// Posting comments, if any
     #[serde(skip_serializing_if = "Option::is_none")]
     pub comment: Option<String>,
 }
 
+/// Running position of register posting
+#[derive(Serialize, Debug)]
+pub struct RegisterPosition {
+    /// Running quantity in account's commodity
+    pub quantity: String,
+
+    /// Account's commodity
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commodity: Option<String>,
+
+    /// Running cost basis, if it's known
+    #[serde(rename = "costBasis")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cost_basis: Option<String>,
+
+    /// Average unit cost, if position is open and cost basis is known
+    #[serde(rename = "avgCost")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avg_cost: Option<String>,
+
+    /// Commodity of cost basis and average unit cost
+    #[serde(rename = "costCommodity")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cost_commodity: Option<String>,
+}
+
 /// Register transaction API object
 #[derive(Serialize, Debug)]
 pub struct RegisterTxn {
@@ -9,15 +9,17 @@ use std::path::PathBuf;
 use tackler_api::txn_ts;
 use tackler_core::config;
 use tackler_core::config::overlaps::{
-    AuditOverlap, FileInputOverlap, FsInputOverlap, GitInputOverlap, InputOverlap, OverlapConfig,
-    PriceOverlap, ReportOverlap, StorageOverlap, StrictOverlap, TargetOverlap,
+    AuditOverlap, FileInputOverlap, ForecastOverlap, FsInputOverlap, GitInputOverlap, InputOverlap,
+    OverlapConfig, PriceOverlap, ReportOverlap, StorageOverlap, StrictOverlap, TargetOverlap,
 };
-use tackler_core::config::{PriceLookupType, StorageType};
+use tackler_core::config::{DedupeMode, InputFormat, PriceLookupType, StorageType};
 use tackler_core::kernel::settings::GitInputSelector;
 
 use tackler_core::config::FormatType;
 
 pub(crate) const PRICE_BEFORE: &str = "price.before";
+const DIAGNOSTICS_HUMAN: &str = "human";
+pub(crate) const DIAGNOSTICS_JSON: &str = "json";
 //
 // Default subcommand setup:
 // https://github.com/clap-rs/clap/issues/975
@@ -43,7 +45,7 @@ impl Cli {
             Commands::Report(self.args.clone())
         };
 
-        if let Commands::Report(report_cmd) = &cmd {
+        if let Commands::Report(report_cmd) | Commands::Check(report_cmd) = &cmd {
             if report_cmd.conf_path.is_none() {
                 let mut cmd = Cli::command();
                 let msg = format!(
@@ -61,6 +63,7 @@ impl Cli {
 
 #[derive(Debug, Clone, clap::Args)]
 #[group(multiple = false)]
+#[allow(clippy::struct_field_names)]
 pub(crate) struct GitInputGroup {
     /// Git reference name
     #[arg(
@@ -77,6 +80,78 @@ pub(crate) struct GitInputGroup {
         group = "git_input_group"
     )]
     pub(crate) input_git_commit: Option<String>,
+
+    /// Range of git commits "base..head"
+    ///
+    /// Only txns which are changed between base and head are used,
+    /// and removed txns are reversed, e.g. the reports are the delta
+    /// what the range does to the books. Txn which is moved
+    /// to another txn file is shown as removed and added.
+    /// Base and head could be reference names or commit ids.
+    #[arg(
+        long = "input.git.range",
+        value_name = "base..head",
+        group = "git_input_group",
+        value_parser = GitRangeParser,
+        verbatim_doc_comment
+    )]
+    pub(crate) input_git_range: Option<GitInputSelector>,
+
+    /// Use staged txns (git index) on top of HEAD
+    #[arg(long = "input.git.index", group = "git_input_group")]
+    pub(crate) input_git_index: bool,
+
+    /// Use txns of the working tree on top of HEAD
+    ///
+    /// This includes unstaged changes of tracked files, and
+    /// untracked txn files of the txn directory (ignore rules
+    /// are not used with untracked files).
+    #[arg(
+        long = "input.git.worktree",
+        group = "git_input_group",
+        verbatim_doc_comment
+    )]
+    pub(crate) input_git_worktree: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct GitRangeParser;
+
+impl TypedValueParser for GitRangeParser {
+    type Value = GitInputSelector;
+
+    fn parse_ref(
+        &self,
+        cmd: &clap::Command,
+        arg: Option<&clap::Arg>,
+        value: &std::ffi::OsStr,
+    ) -> Result<Self::Value, clap::Error> {
+        let val = value
+            .to_str()
+            .ok_or_else(|| clap::Error::new(clap::error::ErrorKind::InvalidUtf8))?;
+
+        match val.split_once("..") {
+            Some((base, head))
+                if !base.is_empty() && !head.is_empty() && !head.starts_with('.') =>
+            {
+                Ok(GitInputSelector::Range(base.to_string(), head.to_string()))
+            }
+            _ => {
+                let mut err = clap::Error::new(ErrorKind::ValueValidation).with_cmd(cmd);
+                if let Some(arg) = arg {
+                    err.insert(
+                        ContextKind::InvalidArg,
+                        ContextValue::String(arg.to_string()),
+                    );
+                }
+                err.insert(
+                    ContextKind::InvalidValue,
+                    ContextValue::String(val.to_string()),
+                );
+                Err(err)
+            }
+        }
+    }
 }
 #[derive(Debug, Clone, Copy)]
 struct StorageTypeParser;
@@ -123,6 +198,96 @@ impl TypedValueParser for StorageTypeParser {
     }
 }
 
+#[derive(Debug, Clone, Copy)]
+struct DedupeModeParser;
+
+impl TypedValueParser for DedupeModeParser {
+    type Value = DedupeMode;
+
+    fn parse_ref(
+        &self,
+        cmd: &clap::Command,
+        arg: Option<&clap::Arg>,
+        value: &std::ffi::OsStr,
+    ) -> Result<Self::Value, clap::Error> {
+        let val = value
+            .to_str()
+            .ok_or_else(|| clap::Error::new(clap::error::ErrorKind::InvalidUtf8))?;
+
+        if let Ok(v) = DedupeMode::try_from(val) {
+            Ok(v)
+        } else {
+            let mut err = clap::Error::new(ErrorKind::ValueValidation).with_cmd(cmd);
+            if let Some(arg) = arg {
+                err.insert(
+                    ContextKind::InvalidArg,
+                    ContextValue::String(arg.to_string()),
+                );
+            }
+            err.insert(
+                ContextKind::InvalidValue,
+                ContextValue::String(val.to_string()),
+            );
+            Err(err)
+        }
+    }
+
+    fn possible_values(
+        &self,
+    ) -> Option<Box<dyn Iterator<Item = clap::builder::PossibleValue> + '_>> {
+        Some(Box::new(
+            [DedupeMode::REPORT, DedupeMode::SKIP]
+                .into_iter()
+                .map(clap::builder::PossibleValue::new),
+        ))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct InputFormatParser;
+
+impl TypedValueParser for InputFormatParser {
+    type Value = InputFormat;
+
+    fn parse_ref(
+        &self,
+        cmd: &clap::Command,
+        arg: Option<&clap::Arg>,
+        value: &std::ffi::OsStr,
+    ) -> Result<Self::Value, clap::Error> {
+        let val = value
+            .to_str()
+            .ok_or_else(|| clap::Error::new(clap::error::ErrorKind::InvalidUtf8))?;
+
+        if let Ok(v) = InputFormat::try_from(val) {
+            Ok(v)
+        } else {
+            let mut err = clap::Error::new(ErrorKind::ValueValidation).with_cmd(cmd);
+            if let Some(arg) = arg {
+                err.insert(
+                    ContextKind::InvalidArg,
+                    ContextValue::String(arg.to_string()),
+                );
+            }
+            err.insert(
+                ContextKind::InvalidValue,
+                ContextValue::String(val.to_string()),
+            );
+            Err(err)
+        }
+    }
+
+    fn possible_values(
+        &self,
+    ) -> Option<Box<dyn Iterator<Item = clap::builder::PossibleValue> + '_>> {
+        Some(Box::new(
+            [InputFormat::TACKLER, InputFormat::LEDGER]
+                .into_iter()
+                .map(clap::builder::PossibleValue::new),
+        ))
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 struct PriceLookupParser;
 
@@ -182,6 +347,91 @@ pub(crate) enum Commands {
     Init {},
     /// Run specified reports and exports - this is the default action
     Report(DefaultModeArgs),
+    /// Format txn files in canonical form
+    ///
+    /// Txn files are rewritten in place, and directories
+    /// are searched recursively for `.txn` files.
+    Fmt {
+        /// Don't rewrite files, but fail if any file is not formatted
+        #[arg(long = "check")]
+        check: bool,
+        /// Journal configuration, default settings are used without it
+        #[arg(long = "config", value_name = "filename")]
+        config: Option<PathBuf>,
+        /// Txn files or directories
+        #[arg(value_name = "path", required = true)]
+        paths: Vec<PathBuf>,
+    },
+    /// Check journal errors and lints, without reports
+    ///
+    /// Lints are configured with `[lint]` section of the configuration.
+    Check(DefaultModeArgs),
+    /// Import bank statement CSV files as txn shards
+    ///
+    /// Txns are written into `YYYY/MM/DD` directories under the output
+    /// directory, and already imported txns are not written again.
+    Import {
+        /// Column layout and formats of the bank statement
+        #[arg(long = "mapping", value_name = "mapping.toml")]
+        mapping: PathBuf,
+        /// Rules to select accounts, descriptions and tags
+        #[arg(long = "rules", value_name = "rules.toml")]
+        rules: PathBuf,
+        /// Root directory of txn shards
+        #[arg(long = "output", value_name = "dir")]
+        output: PathBuf,
+        /// Don't write txns, but print them
+        #[arg(long = "dry-run")]
+        dry_run: bool,
+        /// Journal configuration, used to find duplicates of imported txns
+        #[arg(long = "config", value_name = "filename")]
+        config: Option<PathBuf>,
+        /// Report probable duplicates of the journal, or skip them
+        #[arg(
+            long = "dedupe",
+            value_name = "report|skip",
+            value_parser = DedupeModeParser,
+            requires = "config"
+        )]
+        dedupe: Option<DedupeMode>,
+        /// Output format of probable duplicate diagnostics
+        ///
+        /// Diagnostics are written to stderr, so that
+        /// stdout has only the txns of dry run
+        #[arg(long = "diagnostics", value_name = "format", num_args(1),
+            value_parser([
+                PossibleValue::new(DIAGNOSTICS_HUMAN),
+                PossibleValue::new(DIAGNOSTICS_JSON),
+            ]),
+            verbatim_doc_comment
+        )]
+        diagnostics: Option<String>,
+        /// Bank statement CSV files
+        #[arg(value_name = "statement", required = true)]
+        paths: Vec<PathBuf>,
+    },
+    /// Append price entries from CSV files to price database
+    ///
+    /// CSV records have columns: timestamp (or date), commodity,
+    /// price and price commodity. Entries which are already
+    /// in the price database are not appended again.
+    Prices {
+        /// Price database file
+        #[arg(long = "db", value_name = "price.db")]
+        db_path: PathBuf,
+        /// Delimiter of CSV fields
+        #[arg(long = "delimiter", value_name = "char", default_value = ",")]
+        delimiter: char,
+        /// Count of rows to skip, e.g. header row
+        #[arg(long = "skip-rows", value_name = "count", default_value_t = 1)]
+        skip_rows: usize,
+        /// Date format (strptime) of the first column
+        #[arg(long = "date-format", value_name = "format")]
+        date_format: Option<String>,
+        /// Price CSV files
+        #[arg(value_name = "csv", required = true)]
+        paths: Vec<PathBuf>,
+    },
 }
 
 #[rustfmt::skip]
@@ -241,6 +491,9 @@ pub(crate) struct DefaultModeArgs {
             "input_git_repo",
             "input_git_ref",
             "input_git_commit",
+            "input_git_range",
+            "input_git_index",
+            "input_git_worktree",
             "input_git_dir",
             "input_git_ext",
         ])
@@ -259,6 +512,16 @@ pub(crate) struct DefaultModeArgs {
     )]
     pub(crate) input_storage: Option<StorageType>,
 
+    ///
+    /// Format of transaction journal
+    ///
+    /// Ledger format is the common subset of Ledger and hledger journals
+    #[arg(long="input.format",
+        value_name = "tackler|ledger",
+        value_parser = InputFormatParser
+    )]
+    pub(crate) input_format: Option<InputFormat>,
+
     /// Filesystem path to journal directory
     ///
     /// This is the root of journal, see also `--input.fs.dir`
@@ -270,6 +533,9 @@ pub(crate) struct DefaultModeArgs {
             "input_git_repo",
             "input_git_ref",
             "input_git_commit",
+            "input_git_range",
+            "input_git_index",
+            "input_git_worktree",
             "input_git_dir"
         ])
     )]
@@ -285,6 +551,9 @@ pub(crate) struct DefaultModeArgs {
             "input_git_repo",
             "input_git_ref",
             "input_git_commit",
+            "input_git_range",
+            "input_git_index",
+            "input_git_worktree",
             "input_git_dir"
         ]),
         verbatim_doc_comment
@@ -300,6 +569,9 @@ pub(crate) struct DefaultModeArgs {
             "input_git_repo",
             "input_git_ref",
             "input_git_commit",
+            "input_git_range",
+            "input_git_index",
+            "input_git_worktree",
             "input_git_dir"
         ])
     )]
@@ -364,6 +636,10 @@ pub(crate) struct DefaultModeArgs {
             PossibleValue::new("register"),
             PossibleValue::new("balance"),
             PossibleValue::new("balance-group"),
+            PossibleValue::new("pnl"),
+            PossibleValue::new("budget"),
+            PossibleValue::new("income-statement"),
+            PossibleValue::new("cash-flow"),
         ])
     )]
     pub(crate) reports: Option<Vec<String>>,
@@ -380,6 +656,15 @@ pub(crate) struct DefaultModeArgs {
     )]
     pub(crate) group_by: Option<String>,
 
+    /// Roll-up depth of accounts for 'balance' and 'balance-group' reports
+    ///
+    /// Sub-accounts below this depth are collapsed into their ancestor,
+    /// e.g. with depth 2 'Expenses:Travel:Hotel' is 'Expenses:Travel'
+    #[arg(long = "depth", value_name = "depth", num_args(1),
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..)
+    )]
+    pub(crate) depth: Option<usize>,
+
     /// List of report output format types
     ///
     /// This has only effect when used with `--output.*`
@@ -389,6 +674,8 @@ pub(crate) struct DefaultModeArgs {
         value_parser([
             PossibleValue::new(FormatType::TXT),
             PossibleValue::new(FormatType::JSON),
+            PossibleValue::new(FormatType::CSV),
+            PossibleValue::new(FormatType::TSV),
         ]),
         requires("output_directory"),
         requires("output_name"),
@@ -404,6 +691,7 @@ pub(crate) struct DefaultModeArgs {
             PossibleValue::new("identity"),
             PossibleValue::new("equity"),
             PossibleValue::new("accounts"),
+            PossibleValue::new("beancount"),
         ]),
         requires("output_directory"),
         requires("output_name"),
@@ -417,6 +705,16 @@ pub(crate) struct DefaultModeArgs {
     #[arg(long = "invert")]
     pub(crate) invert: bool,
 
+    /// Include closed accounts in reports and exports
+    ///
+    /// By default, balance and balance-group reports don't show accounts
+    /// with zero balance, which are closed over the whole report period,
+    /// and equity export fails if an account with non-zero balance is closed
+    /// at the time of equity txn.
+    /// See `open` and `close` of `[[account]]` in Chart of Accounts.
+    #[arg(long = "include-closed")]
+    pub(crate) include_closed: bool,
+
     /// Path to `PriceDB` file
     #[arg(
         long = "pricedb",
@@ -448,18 +746,78 @@ pub(crate) struct DefaultModeArgs {
     #[arg(long = PRICE_BEFORE, value_name = "price-before")]
     pub(crate) price_before_ts: Option<String>,
 
-    /// Txn Filter definition in JSON
+    /// Begin of forecast window "<ISO 8601 timestamp>"
+    ///
+    /// Default is after the last transaction
+    #[arg(long = "forecast.begin", value_name = "forecast-begin", requires = "forecast_end")]
+    pub(crate) forecast_begin: Option<String>,
+
+    /// End of forecast window "<ISO 8601 timestamp>"
+    ///
+    /// Recurring transactions are generated until this (exclusive)
+    #[arg(long = "forecast.end", value_name = "forecast-end")]
+    pub(crate) forecast_end: Option<String>,
+
+    /// Txn Filter definition in JSON or as filter expression
     ///
-    /// This could be ascii armored with base64 encoding
+    /// JSON could be ascii armored with base64 encoding
     ///
     /// The ascii armor must have prefix 'base64:'
     ///
     /// e.g. "base64:eyJ0eG5GaWx0ZXIiOnsiTnVsbGFyeVRSVUUiOnt9fX0K"
+    ///
+    /// Filter expression is e.g.
+    /// 'account =~ "Expenses:.*" and ts >= 2025-01-01 and not tag:"work"'
     #[arg(long = "api-filter-def", value_name = "txn_filter")]
     pub(crate) api_filter_def: Option<String>,
+
+    /// Named txn filters of the configuration
+    ///
+    /// The list is space separated, and txn must
+    /// be selected by all filters (logical AND)
+    #[arg(long = "filter", value_name = "name", num_args(1..))]
+    pub(crate) filters: Option<Vec<String>>,
+
+    /// Output format of error diagnostics
+    ///
+    /// With 'json', errors are also written to stdout as JSON,
+    /// with their code and location (path, line and column)
+    #[arg(long = "diagnostics", value_name = "format", num_args(1),
+        value_parser([
+            PossibleValue::new(DIAGNOSTICS_HUMAN),
+            PossibleValue::new(DIAGNOSTICS_JSON),
+        ]),
+        verbatim_doc_comment
+    )]
+    pub(crate) diagnostics: Option<String>,
+
+    /// Check journal, and report all errors and lints
+    ///
+    /// This is an alias for 'check' command.
+    /// Parsing continues after errors, and all parse and semantic
+    /// errors of all txn shards are reported, e.g. duplicate
+    /// txn uuids and ext-ids are reported together.
+    /// No reports or exports are produced in check mode.
+    #[arg(long = "check", verbatim_doc_comment)]
+    pub(crate) check: bool,
+
+    /// Find probable duplicate txns in check mode
+    ///
+    /// Txns with the same ext-id, or with the same amount,
+    /// close dates and similar descriptions are reported as warnings.
+    /// With 'skip', duplicates are also left out of the txn data.
+    #[arg(long = "dedupe", value_name = "report|skip",
+        value_parser = DedupeModeParser,
+        verbatim_doc_comment
+    )]
+    pub(crate) dedupe: Option<DedupeMode>,
 }
 
 impl DefaultModeArgs {
+    pub(crate) fn is_json_diagnostics(&self) -> bool {
+        self.diagnostics.as_deref() == Some(DIAGNOSTICS_JSON)
+    }
+
     fn verify_storage_mode(&self, allowed_type: StorageType) -> Result<(), clap::Error> {
         match self.input_storage {
             Some(st) => {
@@ -532,6 +890,7 @@ impl DefaultModeArgs {
             storage: StorageOverlap {
                 storage_type: self.input_storage,
                 input: self.input_overlap()?,
+                format: self.input_format,
             },
             price: PriceOverlap {
                 db_path: self.pricedb_filename.clone(),
@@ -542,25 +901,42 @@ impl DefaultModeArgs {
                 commodity: self.report_commodity.clone(),
                 account_overlap: self.accounts.clone(),
                 group_by: self.group_by.clone(),
+                depth: self.depth,
                 inverted: self.invert,
+                include_closed: self.include_closed,
             },
             target: TargetOverlap {
                 reports: self.reports.clone(),
                 exports: self.exports.clone(),
                 formats: self.formats.clone(),
             },
+            forecast: ForecastOverlap {
+                begin: self.forecast_begin.clone(),
+                end: self.forecast_end.clone(),
+            },
         })
     }
 
     fn git_selector(&self) -> Option<GitInputSelector> {
+        let gis = &self.git_input_selector;
         match (
-            &self.git_input_selector.input_git_commit,
-            &self.git_input_selector.input_git_ref,
+            &gis.input_git_commit,
+            &gis.input_git_ref,
+            &gis.input_git_range,
+            gis.input_git_index,
+            gis.input_git_worktree,
         ) {
-            (Some(commit), None) => Some(GitInputSelector::CommitId(commit.clone())),
-            (None, Some(git_ref)) => Some(GitInputSelector::Reference(git_ref.clone())),
-            (None, None) => None,
-            (Some(_), Some(_)) => {
+            (Some(commit), None, None, false, false) => {
+                Some(GitInputSelector::CommitId(commit.clone()))
+            }
+            (None, Some(git_ref), None, false, false) => {
+                Some(GitInputSelector::Reference(git_ref.clone()))
+            }
+            (None, None, Some(range), false, false) => Some(range.clone()),
+            (None, None, None, true, false) => Some(GitInputSelector::Index),
+            (None, None, None, false, true) => Some(GitInputSelector::WorkTree),
+            (None, None, None, false, false) => None,
+            _ => {
                 panic!("IE: invalid combination of git input selectors (Clap)")
             }
         }
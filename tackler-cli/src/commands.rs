@@ -3,6 +3,10 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+pub(crate) mod check;
 pub(crate) mod default;
+pub(crate) mod fmt;
+pub(crate) mod import;
 pub(crate) mod init;
 pub(crate) mod new;
+pub(crate) mod prices;
@@ -0,0 +1,26 @@
+/*
+ * Tackler-NG 2026
+ * SPDX-License-Identifier: Apache-2.0
+ */
+use crate::cli_args::DefaultModeArgs;
+use crate::commands::default;
+use tackler_core::{dedupe, lint, tackler};
+
+/// Check journal errors and lints, without reports
+pub(crate) fn exec(cli: &DefaultModeArgs) -> Result<Option<String>, tackler::Error> {
+    let mut settings = default::settings(cli)?;
+    settings.enable_lints()?;
+
+    let mut txn_data = default::txn_data(&settings)?;
+    settings.merge_journal_prices()?;
+    lint::check_txn_data(&txn_data, &settings)?;
+    if let Some(mode) = cli.dedupe {
+        dedupe::check_txn_data(&mut txn_data, &settings, mode)?;
+    }
+
+    default::check_result(
+        &txn_data,
+        settings.take_diagnostics(),
+        cli.is_json_diagnostics(),
+    )
+}
@@ -3,84 +3,59 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 use crate::cli_args::DefaultModeArgs;
+use crate::commands::check;
 use log::error;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
 use std::io;
+use std::path::Path;
 use tackler_api::filters::FilterDefinition;
 use tackler_core::config::Config;
+use tackler_core::config::overlaps::OverlapConfig;
+use tackler_core::diagnostic::{Diagnostic, DiagnosticReport, Diagnostics};
 use tackler_core::export::write_exports;
 use tackler_core::kernel::Settings;
 use tackler_core::kernel::settings::InputSettings;
+use tackler_core::model::{TxnData, TxnSet};
 use tackler_core::report::write_txt_reports;
 use tackler_core::{parser, tackler};
 
-pub(crate) fn exec(cli: DefaultModeArgs) -> Result<Option<String>, tackler::Error> {
-    let cfg = match Config::try_from(
-        cli.conf_path
-            .as_ref()
-            .expect("IE: logic error with CLI arguments"),
-    ) {
-        Ok(cfg) => cfg,
-        Err(err) => {
-            let msg = format!(
-                "Configuration error with '{}': {err}",
-                cli.conf_path.as_ref().unwrap().display()
-            );
-            error!("{msg}");
-            return Err(msg.into());
-        }
-    };
-
-    let overlaps = cli.overlaps()?;
-
-    let mut settings = Settings::try_from(cfg, overlaps)?;
+/// Error of txn data, the original error is the source of this error
+#[derive(Debug)]
+struct TxnDataError(tackler::Error);
 
-    let input_type = settings.input();
+impl Display for TxnDataError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Txn Data: {}", self.0)
+    }
+}
 
-    #[rustfmt::skip]
-    let result = match input_type {
-        InputSettings::File(f) => {
-            parser::paths_to_txns(&[f.path], &mut settings)
-        },
-        InputSettings::Fs(fs) => {
-            let journal = fs.path.join(fs.dir);
-            let paths = tackler_rs::get_paths_by_ext(&journal, fs.ext.as_str())?;
-            parser::paths_to_txns(&paths, &mut settings)
-        }
-        InputSettings::Git(git) => {
-            parser::git_to_txns(
-                git.repo.as_path(),
-                git.dir.as_str(),
-                git.ext.as_str(),
-                git.git_ref,
-                &mut settings,
-            )
-        },
-    };
+impl Error for TxnDataError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(self.0.as_ref())
+    }
+}
 
-    let txn_data = match result {
-        Ok(txn_data) => txn_data,
-        Err(err) => {
-            let msg = format!("Txn Data: {err}");
-            error!("{msg}");
-            return Err(msg.into());
-        }
-    };
+pub(crate) fn exec(cli: DefaultModeArgs) -> Result<Option<String>, tackler::Error> {
+    if cli.dedupe.is_some() && !cli.check {
+        let msg = "Dedupe is supported only in check mode ('--check' or 'check' command)";
+        return Err(msg.into());
+    }
+    if cli.check {
+        // `--check` is an alias for `check` command
+        return check::exec(&cli);
+    }
+    let mut settings = settings(&cli)?;
 
-    let txn_filt = match cli.api_filter_def {
-        Some(filt_str) => {
-            if FilterDefinition::is_armored(&filt_str) {
-                Some(FilterDefinition::from_armor(&filt_str)?)
-            } else {
-                Some(FilterDefinition::from_json_str(&filt_str)?)
-            }
-        }
-        None => None,
-    };
+    let txn_data = txn_data(&settings)?;
+    settings.merge_journal_prices()?;
 
-    let txn_set = match txn_filt {
-        Some(tf) => txn_data.filter(&tf)?,
-        None => txn_data.get_all()?,
-    };
+    let txn_set = filter_txns(
+        &txn_data,
+        cli.api_filter_def.as_deref(),
+        cli.filters.as_deref(),
+        &settings,
+    )?;
 
     if txn_set.is_empty() {
         let msg = "Txn Data: no transactions (txn set is empty)";
@@ -120,9 +95,124 @@ pub(crate) fn exec(cli: DefaultModeArgs) -> Result<Option<String>, tackler::Erro
                 .as_str(),
             &exports,
             &txn_set,
-            &mut settings,
+            &settings,
             &mut Some(Box::new(io::stdout())),
         )?;
     }
     Ok(None)
 }
+
+/// Load configuration and settings of the command
+pub(crate) fn settings(cli: &DefaultModeArgs) -> Result<Settings, tackler::Error> {
+    let conf_path = cli
+        .conf_path
+        .as_ref()
+        .expect("IE: logic error with CLI arguments");
+    config_settings(conf_path, cli.overlaps()?)
+}
+
+/// Load configuration and settings from the configuration file
+pub(crate) fn config_settings(
+    conf_path: &Path,
+    overlaps: OverlapConfig,
+) -> Result<Settings, tackler::Error> {
+    let cfg = match Config::try_from(conf_path) {
+        Ok(cfg) => cfg,
+        Err(err) => {
+            let msg = format!("Configuration error with '{}': {err}", conf_path.display());
+            error!("{msg}");
+            return Err(msg.into());
+        }
+    };
+
+    Settings::try_from(cfg, overlaps)
+}
+
+/// Parse txn data of the configured input
+pub(crate) fn txn_data(settings: &Settings) -> Result<TxnData, tackler::Error> {
+    match parse_txns(settings) {
+        Ok(txn_data) => Ok(txn_data),
+        Err(err) => {
+            let err = TxnDataError(err);
+            error!("{err}");
+            Err(err.into())
+        }
+    }
+}
+
+/// Parse txns of the configured input
+fn parse_txns(settings: &Settings) -> Result<TxnData, tackler::Error> {
+    match settings.input() {
+        InputSettings::File(f) => parser::paths_to_txns(&[f.path], settings),
+        InputSettings::Fs(fs) => {
+            let journal = fs.path.join(fs.dir);
+            let paths = tackler_rs::get_paths_by_ext(&journal, fs.ext.as_str())?;
+            parser::paths_to_txns(&paths, settings)
+        }
+        InputSettings::Git(git) => parser::git_to_txns(
+            git.repo.as_path(),
+            git.dir.as_str(),
+            git.ext.as_str(),
+            git.git_ref,
+            settings,
+        ),
+    }
+}
+
+/// Result of the check mode
+///
+/// Error contains all diagnostics of the journal, if there is any diagnostic
+/// with error severity. Otherwise the result is the summary of the txn data
+/// with possible warnings (or the JSON diagnostics report, which is written
+/// directly to stdout).
+pub(crate) fn check_result(
+    txn_data: &TxnData,
+    diagnostics: Vec<Diagnostic>,
+    json_diagnostics: bool,
+) -> Result<Option<String>, tackler::Error> {
+    let diagnostics = Diagnostics(diagnostics);
+    if diagnostics.error_count() > 0 {
+        let err = TxnDataError(diagnostics.into());
+        error!("{err}");
+        return Err(err.into());
+    }
+    if json_diagnostics {
+        DiagnosticReport {
+            diagnostics: &diagnostics.0,
+        }
+        .write_json(&mut io::stdout())?;
+        return Ok(None);
+    }
+    let summary = format!("Txn Data: no errors, {} txns", txn_data.len());
+    if diagnostics.0.is_empty() {
+        Ok(Some(summary))
+    } else {
+        Ok(Some(format!("{diagnostics}\n{summary}")))
+    }
+}
+
+/// Filter txns with API filter definition and named filters
+fn filter_txns<'a>(
+    txn_data: &'a TxnData,
+    api_filter_def: Option<&str>,
+    filters: Option<&[String]>,
+    settings: &Settings,
+) -> Result<TxnSet<'a>, tackler::Error> {
+    let api_filt = api_filter_def
+        .map(|filt_str| FilterDefinition::parse(filt_str, &settings.get_timezone()))
+        .transpose()?;
+
+    let mut txn_filters = Vec::new();
+    if let Some(tf) = &api_filt {
+        txn_filters.push((None, tf));
+    }
+    for name in filters.into_iter().flatten() {
+        txn_filters.push((Some(name.as_str()), settings.get_filter(name)?));
+    }
+
+    if txn_filters.is_empty() {
+        txn_data.get_all()
+    } else {
+        txn_data.filter_named(&txn_filters)
+    }
+}
@@ -0,0 +1,97 @@
+/*
+ * Tackler-NG 2026
+ * SPDX-License-Identifier: Apache-2.0
+ */
+use crate::commands::default;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tackler_core::config::InputFormat;
+use tackler_core::config::overlaps::OverlapConfig;
+use tackler_core::diagnostic::Diagnostic;
+use tackler_core::formatter::format_txns;
+use tackler_core::kernel::Settings;
+use tackler_core::tackler;
+
+/// Format txn files, or check that they are formatted
+///
+/// Settings are loaded from the configuration, if it's given.
+/// Only Tackler txn files can be formatted.
+pub(crate) fn exec(
+    check: bool,
+    conf_path: Option<&Path>,
+    paths: &[PathBuf],
+) -> Result<Option<String>, tackler::Error> {
+    let settings = match conf_path {
+        Some(conf_path) => default::config_settings(conf_path, OverlapConfig::default())?,
+        None => Settings::default(),
+    };
+    let input_format = settings.get_input_format();
+    if input_format != InputFormat::Tackler {
+        let msg = format!(
+            "Only '{}' input format can be formatted, configured input format is '{input_format}'",
+            InputFormat::TACKLER
+        );
+        return Err(msg.into());
+    }
+
+    let mut txn_paths = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            let mut dir_paths = tackler_rs::get_paths_by_ext(path, "txn")?;
+            dir_paths.sort();
+            txn_paths.extend(dir_paths);
+        } else {
+            txn_paths.push(path.clone());
+        }
+    }
+
+    let mut changed = Vec::new();
+    for path in &txn_paths {
+        if format_file(path, check, &settings)? {
+            changed.push(path.display().to_string());
+        }
+    }
+
+    if check && !changed.is_empty() {
+        let msg = format!(
+            "Txn files are not formatted:\n{}",
+            changed
+                .iter()
+                .map(|p| format!("   {p}"))
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+        return Err(msg.into());
+    }
+    if check {
+        Ok(Some(format!(
+            "Txn files are formatted: {} files",
+            txn_paths.len()
+        )))
+    } else {
+        Ok(Some(format!(
+            "Txn files are formatted: {} of {} files changed",
+            changed.len(),
+            txn_paths.len()
+        )))
+    }
+}
+
+/// Format txn file, returns `true` if the file is (or would be) changed
+fn format_file(path: &Path, check: bool, settings: &Settings) -> Result<bool, tackler::Error> {
+    let input = fs::read_to_string(path)
+        .map_err(|err| format!("Can't open file: '{}' - {}", path.display(), err))?;
+    let formatted = format_txns(&input, settings).map_err(|err| -> tackler::Error {
+        Diagnostic::from_error(err.as_ref())
+            .with_path(path.display().to_string())
+            .into()
+    })?;
+
+    if formatted == input {
+        return Ok(false);
+    }
+    if !check {
+        fs::write(path, formatted)?;
+    }
+    Ok(true)
+}
@@ -0,0 +1,128 @@
+/*
+ * Tackler-NG 2026
+ * SPDX-License-Identifier: Apache-2.0
+ */
+use crate::commands::default;
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+use tackler_core::config::overlaps::OverlapConfig;
+use tackler_core::config::{DedupeMode, ImportMapping, ImportRules};
+use tackler_core::dedupe::{DedupeIndex, TxnKey};
+use tackler_core::diagnostic;
+use tackler_core::diagnostic::{Diagnostic, DiagnosticReport, Diagnostics, Severity};
+use tackler_core::import::{ImportSummary, ImportedTxn, import_csv, write_txns};
+use tackler_core::tackler;
+
+/// Import bank statements as txn shards
+///
+/// With `dedupe`, imported txns are compared to the txns of the journal
+/// (given by the configuration) and to txns of the preceding statements.
+/// Probable duplicates are reported as diagnostics to stderr.
+pub(crate) fn exec(
+    mapping: &Path,
+    rules: &Path,
+    output: &Path,
+    dry_run: bool,
+    dedupe: Option<(&Path, DedupeMode)>,
+    json_diagnostics: bool,
+    paths: &[PathBuf],
+) -> Result<Option<String>, tackler::Error> {
+    let mapping = ImportMapping::try_from(mapping)?;
+    let rules = ImportRules::try_from(rules)?;
+
+    let mut dedupe = match dedupe {
+        Some((conf_path, mode)) => {
+            let settings = default::config_settings(conf_path, OverlapConfig::default())?;
+            let txn_data = default::txn_data(&settings)?;
+            Some((DedupeIndex::from_txn_data(&txn_data, &settings)?, mode))
+        }
+        None => None,
+    };
+
+    let mut summary = ImportSummary::default();
+    let mut diagnostics = Vec::new();
+    for path in paths {
+        let statement = fs::File::open(path)
+            .map_err(|err| format!("Can't open statement: '{}' - {err}", path.display()))?;
+        let mut txns = import_csv(statement, &mapping, &rules)
+            .map_err(|err| format!("Statement '{}': {err}", path.display()))?;
+        if let Some((index, mode)) = &mut dedupe {
+            txns = dedupe_txns(
+                txns,
+                path,
+                output,
+                index,
+                *mode,
+                &mut summary,
+                &mut diagnostics,
+            );
+        }
+        if dry_run {
+            for txn in &txns {
+                println!("{}", txn.text());
+            }
+        }
+        let s = write_txns(&txns, output, dry_run)?;
+        summary.new += s.new;
+        summary.existing += s.existing;
+    }
+
+    if json_diagnostics {
+        DiagnosticReport {
+            diagnostics: &diagnostics,
+        }
+        .write_json(&mut io::stderr())?;
+    } else if !diagnostics.is_empty() {
+        eprintln!("{}", Diagnostics(diagnostics));
+    }
+
+    Ok(Some(format!(
+        "Imported txns: {} new, {} already imported, {} duplicates skipped{}",
+        summary.new,
+        summary.existing,
+        summary.skipped,
+        if dry_run { " (dry run)" } else { "" }
+    )))
+}
+
+/// Report or skip probable duplicates of imported txns
+///
+/// Txns which are already imported (their shard exists) are not duplicates.
+/// Txns of the statement are added to the index after the whole statement is
+/// processed, so that identical records of the same statement are not duplicates.
+fn dedupe_txns(
+    txns: Vec<ImportedTxn>,
+    statement: &Path,
+    output: &Path,
+    index: &mut DedupeIndex,
+    mode: DedupeMode,
+    summary: &mut ImportSummary,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Vec<ImportedTxn> {
+    let mut kept = Vec::with_capacity(txns.len());
+    for txn in txns {
+        if !output.join(txn.path()).exists() {
+            let key = TxnKey::from(&txn);
+            if let Some(dup) = index.find(&key) {
+                let msg = match mode {
+                    DedupeMode::Report => "Probable duplicate txn",
+                    DedupeMode::Skip => "Probable duplicate txn is skipped",
+                };
+                let msg = format!("{msg}: {}\n   original txn: {dup}", key.to_label());
+                let mut diag = Diagnostic::error(diagnostic::PROBABLE_DUPLICATE, msg)
+                    .with_path(statement.display().to_string());
+                diag.severity = Severity::Warning;
+                diagnostics.push(diag);
+                if mode == DedupeMode::Skip {
+                    summary.skipped += 1;
+                    continue;
+                }
+            }
+        }
+        kept.push(txn);
+    }
+    for txn in &kept {
+        index.insert(TxnKey::from(txn));
+    }
+    kept
+}
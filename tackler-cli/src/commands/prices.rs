@@ -0,0 +1,46 @@
+/*
+ * Tackler-NG 2026
+ * SPDX-License-Identifier: Apache-2.0
+ */
+use std::fs;
+use std::path::{Path, PathBuf};
+use tackler_core::import::ImportSummary;
+use tackler_core::import::prices::{PriceCsv, append_prices, import_prices};
+use tackler_core::kernel::Settings;
+use tackler_core::tackler;
+
+/// Append price entries from CSV files to price database
+pub(crate) fn exec(
+    db_path: &Path,
+    delimiter: char,
+    skip_rows: usize,
+    date_format: Option<String>,
+    paths: &[PathBuf],
+) -> Result<Option<String>, tackler::Error> {
+    let Ok(delimiter) = u8::try_from(delimiter) else {
+        let msg = format!("Invalid delimiter: '{delimiter}', it must be an ASCII character");
+        return Err(msg.into());
+    };
+    let format = PriceCsv {
+        delimiter,
+        skip_rows,
+        date_format,
+    };
+    let settings = Settings::default();
+
+    let mut summary = ImportSummary::default();
+    for path in paths {
+        let csv = fs::File::open(path)
+            .map_err(|err| format!("Can't open file: '{}' - {err}", path.display()))?;
+        let prices = import_prices(csv, &format, &settings)
+            .map_err(|err| format!("Prices '{}': {err}", path.display()))?;
+        let s = append_prices(db_path, &path.display().to_string(), prices, &settings)?;
+        summary.new += s.new;
+        summary.existing += s.existing;
+    }
+
+    Ok(Some(format!(
+        "Price entries: {} new, {} already in price database",
+        summary.new, summary.existing
+    )))
+}
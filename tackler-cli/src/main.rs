@@ -11,6 +11,8 @@ use crate::cli_args::Commands;
 use clap::Parser;
 use log::error;
 use mimalloc::MiMalloc;
+use std::io;
+use tackler_core::diagnostic::{DiagnosticReport, Diagnostics};
 
 #[global_allocator]
 static GLOBAL: MiMalloc = MiMalloc;
@@ -20,11 +22,46 @@ fn main() {
     let cli = cli_args::Cli::parse();
 
     let command = cli.cmd();
+    let json_diagnostics = matches!(
+        &command,
+        Commands::Report(args) | Commands::Check(args) if args.is_json_diagnostics()
+    );
 
     let res = match command {
         Commands::New { name } => commands::new::exec(&exe_name, name.as_str()),
         Commands::Init {} => commands::init::exec(&exe_name, "."),
         Commands::Report(args) => commands::default::exec(args),
+        Commands::Check(args) => commands::check::exec(&args),
+        Commands::Fmt {
+            check,
+            config,
+            paths,
+        } => commands::fmt::exec(check, config.as_deref(), &paths),
+        Commands::Import {
+            mapping,
+            rules,
+            output,
+            dry_run,
+            config,
+            dedupe,
+            diagnostics,
+            paths,
+        } => commands::import::exec(
+            &mapping,
+            &rules,
+            &output,
+            dry_run,
+            config.as_deref().zip(dedupe),
+            diagnostics.as_deref() == Some(cli_args::DIAGNOSTICS_JSON),
+            &paths,
+        ),
+        Commands::Prices {
+            db_path,
+            delimiter,
+            skip_rows,
+            date_format,
+            paths,
+        } => commands::prices::exec(&db_path, delimiter, skip_rows, date_format, &paths),
     };
 
     match res {
@@ -38,6 +75,15 @@ fn main() {
             let msg = format!("Tackler error: {err}");
             error!("{msg}");
             eprintln!("{msg}");
+            if json_diagnostics {
+                let diagnostics = Diagnostics::from_error(err.as_ref());
+                let report = DiagnosticReport {
+                    diagnostics: &diagnostics.0,
+                };
+                if let Err(err) = report.write_json(&mut io::stdout()) {
+                    eprintln!("Tackler error: {err}");
+                }
+            }
             std::process::exit(1)
         }
     }
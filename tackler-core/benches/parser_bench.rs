@@ -10,7 +10,7 @@ use tackler_core::parser::string_to_txns;
 use tackler_rs::IndocUtils;
 
 fn cb_ts_date(c: &mut Criterion) {
-    let mut settings = Settings::default();
+    let settings = Settings::default();
 
     #[rustfmt::skip]
     let input = "2026-05-01";
@@ -24,7 +24,7 @@ fn cb_ts_date(c: &mut Criterion) {
 }
 
 fn cb_ts_datetime(c: &mut Criterion) {
-    let mut settings = Settings::default();
+    let settings = Settings::default();
 
     #[rustfmt::skip]
     let input = "2026-05-01T10:01:11";
@@ -38,7 +38,7 @@ fn cb_ts_datetime(c: &mut Criterion) {
 }
 
 fn cb_ts_datetime_offset(c: &mut Criterion) {
-    let mut settings = Settings::default();
+    let settings = Settings::default();
 
     #[rustfmt::skip]
     let input = "2026-05-01T10:01:11+03:00";
@@ -52,7 +52,7 @@ fn cb_ts_datetime_offset(c: &mut Criterion) {
 }
 
 fn cb_ts_datetime_zulu(c: &mut Criterion) {
-    let mut settings = Settings::default();
+    let settings = Settings::default();
 
     #[rustfmt::skip]
     let input = "2026-05-01T10:01:11Z";
@@ -66,7 +66,7 @@ fn cb_ts_datetime_zulu(c: &mut Criterion) {
 }
 
 fn criterion_benchmark_bare(c: &mut Criterion) {
-    let mut settings = Settings::default();
+    let settings = Settings::default();
 
     #[rustfmt::skip]
     let input =
@@ -79,14 +79,14 @@ fn criterion_benchmark_bare(c: &mut Criterion) {
 
     c.bench_function("bare", |b| {
         b.iter(|| {
-            let res = string_to_txns(&mut input.as_str(), &mut settings);
+            let res = string_to_txns(&mut input.as_str(), &settings);
             assert!(res.is_ok());
         });
     });
 }
 
 fn criterion_benchmark_header(c: &mut Criterion) {
-    let mut settings = Settings::default();
+    let settings = Settings::default();
 
     #[rustfmt::skip]
     let input =
@@ -103,14 +103,14 @@ fn criterion_benchmark_header(c: &mut Criterion) {
 
     c.bench_function("meta", |b| {
         b.iter(|| {
-            let res = string_to_txns(&mut input.as_str(), &mut settings);
+            let res = string_to_txns(&mut input.as_str(), &settings);
             assert!(res.is_ok());
         });
     });
 }
 
 fn criterion_benchmark(c: &mut Criterion) {
-    let mut settings = Settings::default();
+    let settings = Settings::default();
 
     #[rustfmt::skip]
     let input = 
@@ -129,7 +129,7 @@ fn criterion_benchmark(c: &mut Criterion) {
 
     c.bench_function("everything", |b| {
         b.iter(|| {
-            let res = string_to_txns(&mut input.as_str(), &mut settings);
+            let res = string_to_txns(&mut input.as_str(), &settings);
             assert!(res.is_ok());
         });
     });
@@ -67,7 +67,7 @@ fn verify_git_run(result: Result<TxnData, tackler::Error>, commit: &str, checksu
 #[allow(clippy::cast_precision_loss)]
 fn test_10_loops_with_txns_1e5() {
     eprintln!("\n\nMake 5 loops with set-1e5:");
-    let mut settings = Settings::default_audit();
+    let settings = Settings::default_audit();
     let mut all_txns_per_s = 0.0;
     for i in 1..=5 {
         let ts_start = SystemTime::now().duration_since(UNIX_EPOCH).unwrap(/*:test:*/);
@@ -76,7 +76,7 @@ fn test_10_loops_with_txns_1e5() {
             "txns/2016",
             "txn",
             GitInputSelector::Reference("set-1e5".to_string()),
-            &mut settings,
+            &settings,
         );
         let ts_end = SystemTime::now().duration_since(UNIX_EPOCH).unwrap(/*:test:*/);
         verify_git_run(result, TXN_SET_1E5_COMMIT_ID, TXN_SET_1E5_CHECKSUM);
@@ -100,7 +100,7 @@ Reference:  {txn_per_s_reference:8>.0} txn/s ({:>+6.0} txn/s)",
 
 // test: fae31eb0-bd4a-483e-9eb7-9e4c36e7f785
 fn test_10000_loops_with_txns_1e1() {
-    let mut settings = Settings::default_audit();
+    let settings = Settings::default_audit();
     let loops = 10_000;
     eprintln!("\n\nMake 10_000 loops with set-1e1:");
     let mut r = 0;
@@ -111,7 +111,7 @@ fn test_10000_loops_with_txns_1e1() {
             "txns/2016",
             "txn",
             GitInputSelector::Reference("set-1e1".to_string()),
-            &mut settings,
+            &settings,
         );
         verify_git_run(result, TXN_SET_1E1_COMMIT_ID, TXN_SET_1E1_CHECKSUM);
 
@@ -2,18 +2,33 @@
  * Tackler-NG 2024-2025
  * SPDX-License-Identifier: Apache-2.0
  */
+pub use items::AccountKind;
 pub(crate) use items::AccountSelectors;
+pub(crate) use items::AccountSpec;
 pub use items::BalanceType;
+pub(crate) use items::BudgetItem;
 pub use items::Config;
+pub(crate) use items::Dedupe;
+pub use items::DedupeMode;
 pub(crate) use items::Export;
 pub use items::ExportType;
 pub use items::FormatType;
+pub use items::ImportMapping;
+pub use items::ImportRules;
 pub use items::Input;
+pub use items::InputFormat;
 pub(crate) use items::Kernel;
+pub(crate) use items::Lint;
+pub use items::LintLevel;
+pub(crate) use items::LintRule;
+pub use items::LotMatching;
 pub use items::PriceLookupType;
+pub use items::RecurringPeriod;
+pub(crate) use items::RecurringSpec;
 pub(crate) use items::Report;
 pub use items::ReportType;
 pub(crate) use items::Scale;
+pub use items::SignConvention;
 pub use items::StorageType;
 
 use crate::tackler;
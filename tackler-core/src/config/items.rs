@@ -3,10 +3,13 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 use crate::config::raw_items::{
-    AccountsPathRaw, AccountsRaw, AuditRaw, BalanceGroupRaw, BalanceRaw, CommoditiesPathRaw,
-    CommoditiesRaw, ConfigRaw, EquityRaw, ExportRaw, ExtIdRaw, FsRaw, GitRaw, InputRaw, KernelRaw,
-    PriceRaw, RegisterRaw, ReportRaw, ScaleRaw, TagsPathRaw, TagsRaw, TimestampRaw, TimezoneRaw,
-    TransactionRaw,
+    AccountSpecRaw, AccountsPathRaw, AccountsRaw, AuditRaw, BalanceGroupRaw, BalanceRaw,
+    BeancountRaw, BudgetItemRaw, BudgetRaw, BudgetReportRaw, CashFlowRaw, CommoditiesPathRaw,
+    CommoditiesRaw, ConfigRaw, DbPathRaw, DedupeRaw, EquityRaw, ExportRaw, ExtIdRaw, FsRaw, GitRaw,
+    ImportColumnsRaw, ImportMappingRaw, ImportRulesRaw, IncomeStatementRaw, InputRaw, KernelRaw,
+    LintRaw, LintRuleRaw, LotsRaw, PnlRaw, PriceRaw, RecurringPathRaw, RecurringRaw,
+    RecurringSpecRaw, RegisterRaw, ReportRaw, ScaleRaw, TagsPathRaw, TagsRaw, TimestampRaw,
+    TimestampValueRaw, TimezoneRaw, TransactionRaw,
 };
 use crate::config::{to_export_targets, to_report_formats, to_report_targets};
 use crate::kernel::hash::Hash;
@@ -14,13 +17,17 @@ use crate::model::Commodity;
 use crate::tackler;
 use jiff::fmt::strtime::BrokenDownTime;
 use jiff::tz::TimeZone;
+use regex::Regex;
 use rust_decimal::Decimal;
+use std::collections::BTreeMap;
 use std::fmt::{Debug, Display, Formatter};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::{cmp, fs};
+use tackler_api::filters::FilterDefinition;
 use tackler_api::txn_ts::{GroupBy, TimestampStyle};
 use tackler_rs::get_abs_path;
+use tackler_rs::regex::new_full_haystack_regex;
 
 /// UI/CFG key value for none
 pub const NONE_VALUE: &str = "none";
@@ -66,6 +73,45 @@ impl Display for StorageType {
     }
 }
 
+/// Format of transaction journal
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub enum InputFormat {
+    #[default]
+    Tackler,
+    Ledger,
+}
+#[rustfmt::skip]
+impl InputFormat {
+    pub const TACKLER: &'static str = "tackler";
+    pub const LEDGER:  &'static str = "ledger";
+}
+
+impl Display for InputFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Tackler => f.write_str(InputFormat::TACKLER),
+            Self::Ledger => f.write_str(InputFormat::LEDGER),
+        }
+    }
+}
+
+impl TryFrom<&str> for InputFormat {
+    type Error = tackler::Error;
+
+    fn try_from(format: &str) -> Result<InputFormat, tackler::Error> {
+        match format {
+            InputFormat::TACKLER => Ok(InputFormat::Tackler),
+            InputFormat::LEDGER => Ok(InputFormat::Ledger),
+            _ => Err(format!(
+                "Unknown input format: '{format}'. Valid options are: {}, {}",
+                Self::TACKLER,
+                Self::LEDGER,
+            )
+            .into()),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq)]
 pub enum PriceLookupType {
     #[default]
@@ -114,17 +160,72 @@ impl TryFrom<&str> for PriceLookupType {
     }
 }
 
+/// Period of recurring transactions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecurringPeriod {
+    Day,
+    Week,
+    Month,
+    Year,
+}
+impl RecurringPeriod {
+    pub const DAY: &'static str = "day";
+    pub const WEEK: &'static str = "week";
+    pub const MONTH: &'static str = "month";
+    pub const YEAR: &'static str = "year";
+}
+
+impl Display for RecurringPeriod {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Day => f.write_str(RecurringPeriod::DAY),
+            Self::Week => f.write_str(RecurringPeriod::WEEK),
+            Self::Month => f.write_str(RecurringPeriod::MONTH),
+            Self::Year => f.write_str(RecurringPeriod::YEAR),
+        }
+    }
+}
+
+impl TryFrom<&str> for RecurringPeriod {
+    type Error = tackler::Error;
+
+    fn try_from(every: &str) -> Result<RecurringPeriod, tackler::Error> {
+        match every {
+            RecurringPeriod::DAY => Ok(RecurringPeriod::Day),
+            RecurringPeriod::WEEK => Ok(RecurringPeriod::Week),
+            RecurringPeriod::MONTH => Ok(RecurringPeriod::Month),
+            RecurringPeriod::YEAR => Ok(RecurringPeriod::Year),
+            _ => Err(format!(
+                "Unknown recurring period: '{every}'. Valid options are: {}, {}, {}, {}",
+                Self::DAY,
+                Self::WEEK,
+                Self::MONTH,
+                Self::YEAR,
+            )
+            .into()),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub enum ReportType {
     #[default]
     Balance,
     BalanceGroup,
     Register,
+    Pnl,
+    Budget,
+    IncomeStatement,
+    CashFlow,
 }
 impl ReportType {
     const BALANCE: &'static str = "balance";
     const BALANCE_GROUP: &'static str = "balance-group";
     const REGISTER: &'static str = "register";
+    const PNL: &'static str = "pnl";
+    const BUDGET: &'static str = "budget";
+    const INCOME_STATEMENT: &'static str = "income-statement";
+    const CASH_FLOW: &'static str = "cash-flow";
     /// Report type from string
     ///
     /// # Errors
@@ -134,11 +235,19 @@ impl ReportType {
             Self::BALANCE => Ok(ReportType::Balance),
             Self::BALANCE_GROUP => Ok(ReportType::BalanceGroup),
             Self::REGISTER => Ok(ReportType::Register),
+            Self::PNL => Ok(ReportType::Pnl),
+            Self::BUDGET => Ok(ReportType::Budget),
+            Self::INCOME_STATEMENT => Ok(ReportType::IncomeStatement),
+            Self::CASH_FLOW => Ok(ReportType::CashFlow),
             _ => Err(format!(
-                "Unknown report type: '{r}'. Valid options are: {}, {}, {}",
+                "Unknown report type: '{r}'. Valid options are: {}, {}, {}, {}, {}, {}, {}",
                 Self::BALANCE,
                 Self::BALANCE_GROUP,
                 Self::REGISTER,
+                Self::PNL,
+                Self::BUDGET,
+                Self::INCOME_STATEMENT,
+                Self::CASH_FLOW,
             )
             .into()),
         }
@@ -151,11 +260,13 @@ pub enum ExportType {
     Equity,
     Identity,
     Accounts,
+    Beancount,
 }
 impl ExportType {
     const EQUITY: &'static str = "equity";
     const IDENTITY: &'static str = "identity";
     const ACCOUNTS: &'static str = "accounts";
+    const BEANCOUNT: &'static str = "beancount";
 
     /// Export type from string
     ///
@@ -166,10 +277,134 @@ impl ExportType {
             Self::EQUITY => Ok(ExportType::Equity),
             Self::IDENTITY => Ok(ExportType::Identity),
             Self::ACCOUNTS => Ok(ExportType::Accounts),
+            Self::BEANCOUNT => Ok(ExportType::Beancount),
             _ => Err(format!(
-                "Unknown export type: '{e}'. Valid options are: {}, {}",
+                "Unknown export type: '{e}'. Valid options are: {}, {}, {}, {}",
                 Self::EQUITY,
                 Self::IDENTITY,
+                Self::ACCOUNTS,
+                Self::BEANCOUNT,
+            )
+            .into()),
+        }
+    }
+}
+
+/// Natural balance side of an account (TEP-1017)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccountKind {
+    Debit,
+    Credit,
+}
+impl AccountKind {
+    pub const DEBIT: &'static str = "debit";
+    pub const CREDIT: &'static str = "credit";
+}
+
+impl Display for AccountKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Debit => f.write_str(AccountKind::DEBIT),
+            Self::Credit => f.write_str(AccountKind::CREDIT),
+        }
+    }
+}
+
+impl TryFrom<&str> for AccountKind {
+    type Error = tackler::Error;
+
+    fn try_from(kind: &str) -> Result<AccountKind, tackler::Error> {
+        match kind {
+            AccountKind::DEBIT => Ok(AccountKind::Debit),
+            AccountKind::CREDIT => Ok(AccountKind::Credit),
+            _ => Err(format!(
+                "Unknown account kind: '{kind}'. Valid options are: {}, {}",
+                Self::DEBIT,
+                Self::CREDIT,
+            )
+            .into()),
+        }
+    }
+}
+
+/// Lot matching method for closing positions (TEP-1003)
+///
+/// If closing posting has an opening position `{ ... }`,
+/// then the lot is selected by that (specific lot).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LotMatching {
+    #[default]
+    Fifo,
+    Lifo,
+}
+impl LotMatching {
+    pub const FIFO: &'static str = "fifo";
+    pub const LIFO: &'static str = "lifo";
+}
+
+impl Display for LotMatching {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Fifo => f.write_str(LotMatching::FIFO),
+            Self::Lifo => f.write_str(LotMatching::LIFO),
+        }
+    }
+}
+
+impl TryFrom<&str> for LotMatching {
+    type Error = tackler::Error;
+
+    fn try_from(matching: &str) -> Result<LotMatching, tackler::Error> {
+        match matching {
+            LotMatching::FIFO => Ok(LotMatching::Fifo),
+            LotMatching::LIFO => Ok(LotMatching::Lifo),
+            _ => Err(format!(
+                "Unknown lot matching method: '{matching}'. Valid options are: {}, {}",
+                Self::FIFO,
+                Self::LIFO,
+            )
+            .into()),
+        }
+    }
+}
+
+/// Severity level of journal lint
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LintLevel {
+    Off,
+    #[default]
+    Warning,
+    Error,
+}
+impl LintLevel {
+    pub const OFF: &'static str = "off";
+    pub const WARNING: &'static str = "warning";
+    pub const ERROR: &'static str = "error";
+}
+
+impl Display for LintLevel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Off => f.write_str(LintLevel::OFF),
+            Self::Warning => f.write_str(LintLevel::WARNING),
+            Self::Error => f.write_str(LintLevel::ERROR),
+        }
+    }
+}
+
+impl TryFrom<&str> for LintLevel {
+    type Error = tackler::Error;
+
+    fn try_from(level: &str) -> Result<LintLevel, tackler::Error> {
+        match level {
+            LintLevel::OFF => Ok(LintLevel::Off),
+            LintLevel::WARNING => Ok(LintLevel::Warning),
+            LintLevel::ERROR => Ok(LintLevel::Error),
+            _ => Err(format!(
+                "Unknown lint severity: '{level}'. Valid options are: {}, {}, {}",
+                Self::OFF,
+                Self::WARNING,
+                Self::ERROR,
             )
             .into()),
         }
@@ -205,10 +440,14 @@ pub enum FormatType {
     #[default]
     Txt,
     Json,
+    Csv,
+    Tsv,
 }
 impl FormatType {
     pub const TXT: &'static str = "txt";
     pub const JSON: &'static str = "json";
+    pub const CSV: &'static str = "csv";
+    pub const TSV: &'static str = "tsv";
 }
 impl TryFrom<&str> for FormatType {
     type Error = tackler::Error;
@@ -217,10 +456,14 @@ impl TryFrom<&str> for FormatType {
         match t {
             Self::TXT => Ok(FormatType::Txt),
             Self::JSON => Ok(FormatType::Json),
+            Self::CSV => Ok(FormatType::Csv),
+            Self::TSV => Ok(FormatType::Tsv),
             _ => Err(format!(
-                "Unknown report output format type: '{t}'. Valid options are: {}, {}",
+                "Unknown report output format type: '{t}'. Valid options are: {}, {}, {}, {}",
                 Self::TXT,
-                Self::JSON
+                Self::JSON,
+                Self::CSV,
+                Self::TSV
             )
             .into()),
         }
@@ -240,6 +483,10 @@ pub struct Config {
     pub(crate) transaction: Transaction,
     pub(crate) report: Report,
     pub(crate) export: Export,
+    /// Named txn filters
+    pub(crate) filters: BTreeMap<String, FilterDefinition>,
+    pub(crate) lint: Lint,
+    pub(crate) dedupe: Dedupe,
 }
 
 impl Config {
@@ -249,16 +496,21 @@ impl Config {
     /// Returns `Err` in case there are syntactical or semantic errors with config
     pub fn try_from<P: AsRef<Path>>(cfg_path: P) -> Result<Config, tackler::Error> {
         let cfg_raw: ConfigRaw = toml::from_str(fs::read_to_string(&cfg_path)?.as_str())?;
+        let kernel = Kernel::try_from(&cfg_raw.kernel)?;
+        let filters = to_filters(cfg_raw.filters.as_ref(), &kernel.timestamp.timezone)?;
 
         Ok(Config {
             path: cfg_path.as_ref().to_path_buf(),
-            kernel: Kernel::try_from(&cfg_raw.kernel)?,
+            kernel,
             price: cfg_raw.price.map_or(Ok(Price::default()), |raw_price| {
                 Price::try_from(&cfg_path, &raw_price)
             })?,
             transaction: Transaction::from(&cfg_path, &cfg_raw.transaction)?,
-            report: Report::from(&cfg_raw.report)?,
+            report: Report::from(&cfg_path, &cfg_raw.report)?,
             export: { Export::from(&cfg_raw.export, &cfg_raw.report)? },
+            filters,
+            lint: Lint::try_from(cfg_raw.lint.as_ref())?,
+            dedupe: Dedupe::try_from(cfg_raw.dedupe.as_ref())?,
         })
     }
     #[must_use]
@@ -267,12 +519,31 @@ impl Config {
     }
 }
 
+/// Parse named filters
+///
+/// Filter is either JSON, ascii armored JSON or filter expression.
+fn to_filters(
+    filters_raw: Option<&BTreeMap<String, String>>,
+    tz: &TimeZone,
+) -> Result<BTreeMap<String, FilterDefinition>, tackler::Error> {
+    filters_raw
+        .into_iter()
+        .flatten()
+        .map(|(name, filt_str)| {
+            FilterDefinition::parse(filt_str, tz)
+                .map(|tf| (name.clone(), tf))
+                .map_err(|err| format!("Invalid filter '{name}': {err}").into())
+        })
+        .collect()
+}
+
 #[derive(Debug, Clone, Default)]
 pub(crate) struct Kernel {
     pub(crate) strict: bool,
     pub(crate) timestamp: Timestamp,
     pub(crate) audit: Audit,
     pub(crate) extid: ExtId,
+    pub(crate) lots: Lots,
     pub input: Input,
 }
 impl Kernel {
@@ -282,6 +553,7 @@ impl Kernel {
             timestamp: Timestamp::from(&k_raw.timestamp)?,
             audit: Audit::from(&k_raw.audit)?,
             extid: ExtId::from(k_raw.extid.as_ref()),
+            lots: Lots::try_from(k_raw.lots.as_ref())?,
             input: Input::try_from(&k_raw.input)?,
         };
         Ok(k)
@@ -372,9 +644,26 @@ impl ExtId {
     }
 }
 
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Lots {
+    pub(crate) matching: LotMatching,
+}
+
+impl Lots {
+    fn try_from(lots_raw: Option<&LotsRaw>) -> Result<Lots, tackler::Error> {
+        Ok(Lots {
+            matching: match lots_raw {
+                Some(l) => LotMatching::try_from(l.matching.as_str())?,
+                None => LotMatching::default(),
+            },
+        })
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct Input {
     pub storage: StorageType,
+    pub format: InputFormat,
     pub fs: Option<FS>,
     pub git: Option<Git>,
 }
@@ -383,6 +672,10 @@ impl Input {
         // todo: checks
         let i = Input {
             storage: StorageType::try_from(input_raw.storage.as_str())?,
+            format: match &input_raw.format {
+                Some(format) => InputFormat::try_from(format.as_str())?,
+                None => InputFormat::default(),
+            },
             fs: match &input_raw.fs {
                 Some(fs) => Some(FS::try_from(fs)?),
                 None => None,
@@ -492,7 +785,8 @@ impl Git {
 
 #[derive(Debug, Clone, Default)]
 pub(crate) struct Price {
-    pub(crate) db_path: PathBuf,
+    /// Price database files, or directories of price database shards
+    pub(crate) db_paths: Vec<PathBuf>,
     pub(crate) lookup_type: PriceLookupType,
 }
 impl Price {
@@ -500,23 +794,31 @@ impl Price {
         base_path: P,
         price_raw: &PriceRaw,
     ) -> Result<Price, tackler::Error> {
-        let db_path_str = price_raw.db_path.as_str();
         let lookup_type = PriceLookupType::try_from(price_raw.lookup_type.as_str())?;
 
-        match db_path_str {
-            NONE_VALUE => {
-                if lookup_type == PriceLookupType::None {
+        let db_paths = match &price_raw.db_path {
+            DbPathRaw::Single(db_path) if db_path == NONE_VALUE => {
+                return if lookup_type == PriceLookupType::None {
                     Ok(Price::default())
                 } else {
                     let msg = "Price database path is 'none' but lookup type is not 'none'";
                     Err(msg.into())
-                }
+                };
             }
-            _ => Ok(Price {
-                db_path: get_abs_path(base_path, db_path_str)?,
-                lookup_type,
-            }),
-        }
+            DbPathRaw::Single(db_path) => vec![db_path.as_str()],
+            DbPathRaw::List(db_paths) if db_paths.is_empty() => {
+                let msg = "Price database path list is empty";
+                return Err(msg.into());
+            }
+            DbPathRaw::List(db_paths) => db_paths.iter().map(String::as_str).collect(),
+        };
+        Ok(Price {
+            db_paths: db_paths
+                .into_iter()
+                .map(|db_path| get_abs_path(&base_path, db_path))
+                .collect::<Result<Vec<_>, _>>()?,
+            lookup_type,
+        })
     }
 }
 
@@ -525,6 +827,7 @@ pub(crate) struct Transaction {
     pub(crate) accounts: Accounts,
     pub(crate) commodities: Commodities,
     pub(crate) tags: Tags,
+    pub(crate) recurring: Vec<RecurringSpec>,
 }
 
 impl Transaction {
@@ -536,6 +839,10 @@ impl Transaction {
             accounts: Accounts::from(&path, &txn_raw.accounts)?,
             commodities: Commodities::from(&path, &txn_raw.commodities)?,
             tags: Tags::from(&path, &txn_raw.tags)?,
+            recurring: match &txn_raw.recurring {
+                Some(recurring_path_raw) => RecurringSpec::from(&path, recurring_path_raw)?,
+                None => Vec::new(),
+            },
         })
     }
 }
@@ -543,6 +850,7 @@ impl Transaction {
 #[derive(Debug, Clone, Default)]
 pub(crate) struct Accounts {
     pub names: Vec<String>,
+    pub specs: Vec<AccountSpec>,
 }
 impl Accounts {
     fn from<P: AsRef<Path>>(
@@ -563,11 +871,138 @@ impl Accounts {
                     return Err(msg.into());
                 }
             };
+            let specs = acc_raw
+                .specs
+                .iter()
+                .map(AccountSpec::try_from)
+                .collect::<Result<Vec<_>, tackler::Error>>()?;
+
+            Self::check_specs(&acc_raw.names, &specs)?;
+
             Ok(Accounts {
                 names: acc_raw.names,
+                specs,
             })
         }
     }
+
+    fn check_specs(names: &[String], specs: &[AccountSpec]) -> Result<(), tackler::Error> {
+        for spec in specs {
+            if names.contains(&spec.name) {
+                let msg = format!(
+                    "Account '{}' is defined by both 'accounts' and '[[account]]'",
+                    spec.name
+                );
+                return Err(msg.into());
+            }
+            let other_kind = specs
+                .iter()
+                .filter(|s| s.name == spec.name)
+                .find(|s| s.kind.is_some() && spec.kind.is_some() && s.kind != spec.kind);
+            if other_kind.is_some() {
+                let msg = format!(
+                    "Account '{}' has conflicting 'kind' values between its periods",
+                    spec.name
+                );
+                return Err(msg.into());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Single `[[account]]` definition (TEP-1017)
+///
+/// The `open` and `close` timestamps are kept as text, and they
+/// are resolved with the txn timestamp rules by `Settings`.
+#[derive(Debug, Clone)]
+pub(crate) struct AccountSpec {
+    pub(crate) name: String,
+    pub(crate) open: Option<String>,
+    pub(crate) close: Option<String>,
+    pub(crate) kind: Option<AccountKind>,
+}
+
+impl TryFrom<&AccountSpecRaw> for AccountSpec {
+    type Error = tackler::Error;
+
+    fn try_from(spec_raw: &AccountSpecRaw) -> Result<AccountSpec, tackler::Error> {
+        let ts_to_string = |ts: &TimestampValueRaw| match ts {
+            TimestampValueRaw::Toml(dt) => dt.to_string(),
+            TimestampValueRaw::Str(s) => s.clone(),
+        };
+        Ok(AccountSpec {
+            name: spec_raw.name.clone(),
+            open: spec_raw.open.as_ref().map(ts_to_string),
+            close: spec_raw.close.as_ref().map(ts_to_string),
+            kind: spec_raw
+                .kind
+                .as_deref()
+                .map(AccountKind::try_from)
+                .transpose()?,
+        })
+    }
+}
+
+/// Single `[[recurring]]` transaction definition
+///
+/// The txn template is kept as text, and it's parsed with
+/// the normal txn rules, when the forecast is expanded.
+/// The `until` timestamp is resolved with the txn timestamp rules by `Settings`.
+#[derive(Debug, Clone)]
+pub(crate) struct RecurringSpec {
+    pub(crate) every: RecurringPeriod,
+    pub(crate) interval: u32,
+    pub(crate) until: Option<String>,
+    pub(crate) txn: String,
+}
+
+impl RecurringSpec {
+    fn from<P: AsRef<Path>>(
+        path: P,
+        recurring_path_raw: &RecurringPathRaw,
+    ) -> Result<Vec<RecurringSpec>, tackler::Error> {
+        let recurring_path_str = recurring_path_raw.path.as_str();
+        if recurring_path_str == NONE_VALUE {
+            return Ok(Vec::new());
+        }
+        let recurring_path = get_abs_path(&path, recurring_path_str)?;
+        let recurring_raw: RecurringRaw = match fs::read_to_string(&recurring_path) {
+            Ok(s) => toml::from_str(s.as_str())?,
+            Err(err) => {
+                let msg = format!(
+                    "Recurring txns configuration error while reading file '{recurring_path_str}': {err}"
+                );
+                return Err(msg.into());
+            }
+        };
+        recurring_raw
+            .specs
+            .iter()
+            .map(RecurringSpec::try_from)
+            .collect::<Result<Vec<_>, tackler::Error>>()
+    }
+}
+
+impl TryFrom<&RecurringSpecRaw> for RecurringSpec {
+    type Error = tackler::Error;
+
+    fn try_from(spec_raw: &RecurringSpecRaw) -> Result<RecurringSpec, tackler::Error> {
+        let interval = spec_raw.interval.unwrap_or(1);
+        if interval == 0 {
+            let msg = "Recurring txn 'interval' must be greater than zero";
+            return Err(msg.into());
+        }
+        Ok(RecurringSpec {
+            every: RecurringPeriod::try_from(spec_raw.every.as_str())?,
+            interval,
+            until: spec_raw.until.as_ref().map(|ts| match ts {
+                TimestampValueRaw::Toml(dt) => dt.to_string(),
+                TimestampValueRaw::Str(s) => s.clone(),
+            }),
+            txn: spec_raw.txn.clone(),
+        })
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -644,6 +1079,10 @@ pub(crate) struct Report {
     pub register: Register,
     pub balance_group: BalanceGroup,
     pub balance: Balance,
+    pub pnl: Pnl,
+    pub budget: Budget,
+    pub income_statement: IncomeStatement,
+    pub cash_flow: CashFlow,
 }
 
 impl Default for Report {
@@ -657,12 +1096,16 @@ impl Default for Report {
             register: Register::default(),
             balance_group: BalanceGroup::default(),
             balance: Balance::default(),
+            pnl: Pnl::default(),
+            budget: Budget::default(),
+            income_statement: IncomeStatement::default(),
+            cash_flow: CashFlow::default(),
         }
     }
 }
 
 impl Report {
-    fn from(report_raw: &ReportRaw) -> Result<Report, tackler::Error> {
+    fn from<P: AsRef<Path>>(path: P, report_raw: &ReportRaw) -> Result<Report, tackler::Error> {
         let targets = to_report_targets(&report_raw.targets)?;
         let formats = to_report_formats(report_raw.formats.as_deref())?;
 
@@ -678,6 +1121,13 @@ impl Report {
             register: Register::from(&report_raw.register, report_raw)?,
             balance_group: BalanceGroup::from(&report_raw.balance_group, report_raw)?,
             balance: Balance::from(&report_raw.balance, report_raw)?,
+            pnl: Pnl::from(report_raw.pnl.as_ref(), report_raw),
+            budget: match &report_raw.budget {
+                Some(budget_raw) => Budget::from(&path, budget_raw)?,
+                None => Budget::default(),
+            },
+            income_statement: IncomeStatement::from(report_raw.income_statement.as_ref())?,
+            cash_flow: CashFlow::from(report_raw.cash_flow.as_ref())?,
         })
     }
 }
@@ -742,6 +1192,8 @@ fn get_account_selector(
 pub(crate) struct Register {
     pub title: String,
     pub timestamp_style: TimestampStyle,
+    /// Running positions (quantity, cost basis and average cost) of postings
+    pub cost_basis: bool,
     pub acc_sel: AccountSelectors,
 }
 
@@ -753,6 +1205,7 @@ impl Register {
                 Some(style) => TimestampStyle::from(style.as_str())?,
                 None => TimestampStyle::Date,
             },
+            cost_basis: reg_raw.cost_basis.unwrap_or(false),
             acc_sel: get_account_selector(reg_raw.acc_sel.as_ref(), report),
         })
     }
@@ -763,6 +1216,12 @@ pub(crate) struct BalanceGroup {
     pub title: String,
     pub bal_type: BalanceType,
     pub group_by: GroupBy,
+    /// Roll-up depth of accounts, `None` is all accounts
+    pub depth: Option<usize>,
+    /// Pivot layout: accounts as rows and groups as columns
+    pub pivot: bool,
+    /// Column totals with pivot layout
+    pub column_totals: bool,
     pub acc_sel: AccountSelectors,
 }
 
@@ -778,6 +1237,9 @@ impl BalanceGroup {
                 None => BalanceType::default(),
             },
             group_by: GroupBy::from(balgrp_raw.group_by.as_str())?,
+            depth: check_depth(balgrp_raw.depth, "balance-group")?,
+            pivot: balgrp_raw.pivot.unwrap_or(false),
+            column_totals: balgrp_raw.column_totals.unwrap_or(false),
             acc_sel: get_account_selector(balgrp_raw.acc_sel.as_ref(), report),
         })
     }
@@ -787,6 +1249,10 @@ impl BalanceGroup {
 pub(crate) struct Balance {
     pub title: String,
     pub bal_type: BalanceType,
+    /// Roll-up depth of accounts, `None` is all accounts
+    pub depth: Option<usize>,
+    /// Multi-commodity inventory mode
+    pub inventory: bool,
     pub acc_sel: AccountSelectors,
 }
 
@@ -798,15 +1264,293 @@ impl Balance {
                 Some(t) => BalanceType::try_from(t.as_str())?,
                 None => BalanceType::default(),
             },
+            depth: check_depth(bal_raw.depth, "balance")?,
+            inventory: bal_raw.inventory.unwrap_or(false),
             acc_sel: get_account_selector(bal_raw.acc_sel.as_ref(), report),
         })
     }
 }
 
+#[derive(Debug, Clone)]
+pub(crate) struct Pnl {
+    pub title: String,
+    pub acc_sel: AccountSelectors,
+}
+
+impl Default for Pnl {
+    fn default() -> Self {
+        Pnl {
+            title: "PnL Report".to_string(),
+            acc_sel: AccountSelectors::default(),
+        }
+    }
+}
+
+impl Pnl {
+    fn from(pnl_raw: Option<&PnlRaw>, report: &ReportRaw) -> Pnl {
+        match pnl_raw {
+            Some(pnl_raw) => Pnl {
+                title: pnl_raw.title.clone(),
+                acc_sel: get_account_selector(pnl_raw.acc_sel.as_ref(), report),
+            },
+            None => Pnl {
+                acc_sel: get_account_selector(None, report),
+                ..Pnl::default()
+            },
+        }
+    }
+}
+
+/// Budget definitions for Budget Report
+#[derive(Debug, Clone)]
+pub(crate) struct Budget {
+    pub title: String,
+    pub group_by: GroupBy,
+    pub items: Vec<BudgetItem>,
+}
+
+impl Default for Budget {
+    fn default() -> Self {
+        Budget {
+            title: "Budget Report".to_string(),
+            group_by: GroupBy::default(),
+            items: Vec::new(),
+        }
+    }
+}
+
+impl Budget {
+    fn from<P: AsRef<Path>>(
+        path: P,
+        budget_report_raw: &BudgetReportRaw,
+    ) -> Result<Budget, tackler::Error> {
+        let budget_path_str = budget_report_raw.path.as_str();
+        if budget_path_str == NONE_VALUE {
+            return Ok(Budget {
+                title: budget_report_raw.title.clone(),
+                ..Budget::default()
+            });
+        }
+        let budget_path = get_abs_path(&path, budget_path_str)?;
+        let budget_raw: BudgetRaw = match fs::read_to_string(&budget_path) {
+            Ok(s) => toml::from_str(s.as_str())?,
+            Err(err) => {
+                let msg = format!(
+                    "Budget configuration error while reading file '{budget_path_str}': {err}"
+                );
+                return Err(msg.into());
+            }
+        };
+        Self::try_from(&budget_report_raw.title, &budget_raw)
+    }
+
+    fn try_from(title: &str, budget_raw: &BudgetRaw) -> Result<Budget, tackler::Error> {
+        let group_by = GroupBy::from(budget_raw.group_by.as_str())?;
+
+        let items = budget_raw
+            .items
+            .iter()
+            .map(|item_raw| {
+                BudgetItem::try_from(item_raw, budget_raw.commodity.as_deref(), group_by)
+            })
+            .collect::<Result<Vec<_>, tackler::Error>>()?;
+
+        for (i, item) in items.iter().enumerate() {
+            if items[..i]
+                .iter()
+                .any(|other| other.account == item.account && other.commodity == item.commodity)
+            {
+                let msg = format!(
+                    "Budget is defined multiple times for account '{}'{}",
+                    item.account,
+                    item.commodity
+                        .as_ref()
+                        .map_or(String::new(), |c| format!(" [{}]", c.name))
+                );
+                return Err(msg.into());
+            }
+        }
+
+        Ok(Budget {
+            title: title.to_string(),
+            group_by,
+            items,
+        })
+    }
+}
+
+/// Budget of an account in a single commodity
+#[derive(Debug, Clone)]
+pub(crate) struct BudgetItem {
+    pub account: String,
+    /// If this is not set, then report commodity is used
+    pub commodity: Option<Arc<Commodity>>,
+    /// Budget for periods which are not listed in `periods`
+    pub amount: Option<Decimal>,
+    pub periods: BTreeMap<String, Decimal>,
+}
+
+impl BudgetItem {
+    fn try_from(
+        item_raw: &BudgetItemRaw,
+        default_commodity: Option<&str>,
+        group_by: GroupBy,
+    ) -> Result<BudgetItem, tackler::Error> {
+        for period in item_raw.periods.keys() {
+            Self::check_period(period, group_by).map_err(|err| {
+                let msg = format!(
+                    "Budget configuration error for account '{}': {err}",
+                    item_raw.account
+                );
+                tackler::Error::from(msg)
+            })?;
+        }
+        let commodity = match item_raw.commodity.as_deref().or(default_commodity) {
+            Some(c) => Some(Arc::new(Commodity::from(c)?)),
+            None => None,
+        };
+        Ok(BudgetItem {
+            account: item_raw.account.clone(),
+            commodity,
+            amount: item_raw.amount,
+            periods: item_raw.periods.clone(),
+        })
+    }
+
+    /// Period must be in the same format as group-by keys of transactions
+    fn check_period(period: &str, group_by: GroupBy) -> Result<(), tackler::Error> {
+        let (fmt, example) = match group_by {
+            GroupBy::Year => ("%Y", "2024"),
+            GroupBy::Month => ("%Y-%m", "2024-12"),
+            GroupBy::Date => ("%Y-%m-%d", "2024-12-31"),
+            GroupBy::IsoWeek => ("%G-W%V", "2024-W51"),
+            GroupBy::IsoWeekDate => ("%G-W%V-%u", "2024-W51-5"),
+        };
+        if period.len() == example.len() && BrokenDownTime::parse(fmt, period).is_ok() {
+            Ok(())
+        } else {
+            let msg =
+                format!("invalid budget period '{period}', period must be in form of '{example}'");
+            Err(msg.into())
+        }
+    }
+
+    /// Budgeted amount for the period, if there is any
+    pub(crate) fn amount(&self, period: &str) -> Option<Decimal> {
+        self.periods.get(period).copied().or(self.amount)
+    }
+}
+
+/// Income Statement definitions
+#[derive(Debug, Clone)]
+pub(crate) struct IncomeStatement {
+    pub title: String,
+    /// Root accounts of revenues
+    pub revenues: Vec<String>,
+    /// Root accounts of expenses
+    pub expenses: Vec<String>,
+    /// Maximum depth of reported accounts, `None` is all accounts
+    pub depth: Option<usize>,
+    /// Comparison periods, `None` is a single period
+    pub group_by: Option<GroupBy>,
+}
+
+impl Default for IncomeStatement {
+    fn default() -> Self {
+        IncomeStatement {
+            title: "Income Statement".to_string(),
+            revenues: vec!["Income".to_string()],
+            expenses: vec!["Expenses".to_string()],
+            depth: None,
+            group_by: None,
+        }
+    }
+}
+
+impl IncomeStatement {
+    fn from(is_raw: Option<&IncomeStatementRaw>) -> Result<IncomeStatement, tackler::Error> {
+        let Some(is_raw) = is_raw else {
+            return Ok(IncomeStatement::default());
+        };
+        let default = IncomeStatement::default();
+        let is = IncomeStatement {
+            title: is_raw.title.clone(),
+            revenues: is_raw.revenues.clone().unwrap_or(default.revenues),
+            expenses: is_raw.expenses.clone().unwrap_or(default.expenses),
+            depth: check_depth(is_raw.depth, "income-statement")?,
+            group_by: is_raw.group_by.as_deref().map(GroupBy::from).transpose()?,
+        };
+
+        let roots: Vec<&String> = is.revenues.iter().chain(is.expenses.iter()).collect();
+        for (i, root) in roots.iter().enumerate() {
+            if let Some(other) = roots[..i].iter().find(|other| {
+                *other == root
+                    || root.starts_with(&format!("{other}:"))
+                    || other.starts_with(&format!("{root}:"))
+            }) {
+                let msg = format!(
+                    "Income statement configuration error: root accounts '{other}' and '{root}' overlap"
+                );
+                return Err(msg.into());
+            }
+        }
+        Ok(is)
+    }
+}
+
+/// Cash Flow Report definitions
+#[derive(Debug, Clone)]
+pub(crate) struct CashFlow {
+    pub title: String,
+    /// Account selectors of cash accounts
+    pub cash_accounts: AccountSelectors,
+    /// Maximum depth of counterpart accounts, `None` is all accounts
+    pub depth: Option<usize>,
+    /// Comparison periods, `None` is a single period
+    pub group_by: Option<GroupBy>,
+}
+
+impl Default for CashFlow {
+    fn default() -> Self {
+        CashFlow {
+            title: "Cash Flow Report".to_string(),
+            cash_accounts: AccountSelectors::default(),
+            depth: None,
+            group_by: None,
+        }
+    }
+}
+
+impl CashFlow {
+    fn from(cf_raw: Option<&CashFlowRaw>) -> Result<CashFlow, tackler::Error> {
+        let Some(cf_raw) = cf_raw else {
+            return Ok(CashFlow::default());
+        };
+        Ok(CashFlow {
+            title: cf_raw.title.clone(),
+            cash_accounts: cf_raw.cash_accounts.clone(),
+            depth: check_depth(cf_raw.depth, "cash-flow")?,
+            group_by: cf_raw.group_by.as_deref().map(GroupBy::from).transpose()?,
+        })
+    }
+}
+
+fn check_depth(depth: Option<usize>, report: &str) -> Result<Option<usize>, tackler::Error> {
+    match depth {
+        Some(0) => {
+            let msg =
+                format!("Invalid value for 'report.{report}.depth': depth must be at least 1");
+            Err(msg.into())
+        }
+        d => Ok(d),
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub(crate) struct Export {
     pub targets: Vec<ExportType>,
     pub equity: Equity,
+    pub beancount: Beancount,
 }
 impl Export {
     fn from(export_raw: &ExportRaw, report: &ReportRaw) -> Result<Export, tackler::Error> {
@@ -814,6 +1558,7 @@ impl Export {
         Ok(Export {
             targets: trgs,
             equity: Equity::from(&export_raw.equity, report),
+            beancount: Beancount::from(export_raw.beancount.as_ref()),
         })
     }
 }
@@ -833,11 +1578,799 @@ impl Equity {
     }
 }
 
+#[derive(Debug, Clone)]
+pub(crate) struct Beancount {
+    /// Account of realized gains of closed lots
+    pub(crate) gains_account: String,
+}
+
+impl Beancount {
+    const DEFAULT_GAINS_ACCOUNT: &'static str = "Income:Capital-Gains";
+
+    fn from(bc_raw: Option<&BeancountRaw>) -> Beancount {
+        Beancount {
+            gains_account: bc_raw
+                .and_then(|b| b.gains_account.clone())
+                .unwrap_or_else(|| Self::DEFAULT_GAINS_ACCOUNT.to_string()),
+        }
+    }
+}
+
+impl Default for Beancount {
+    fn default() -> Self {
+        Beancount::from(None)
+    }
+}
+
+/// Single journal lint: severity and allow-list
+///
+/// Allow-list is a list of regular expressions, and the lint
+/// is not reported for the items which are matched by it.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct LintRule {
+    pub(crate) level: LintLevel,
+    pub(crate) allow: Vec<String>,
+}
+
+impl LintRule {
+    fn try_from(
+        severity: Option<&String>,
+        allow: Option<&Vec<String>>,
+    ) -> Result<LintRule, tackler::Error> {
+        Ok(LintRule {
+            level: severity
+                .map(|s| LintLevel::try_from(s.as_str()))
+                .transpose()?
+                .unwrap_or_default(),
+            allow: allow.cloned().unwrap_or_default(),
+        })
+    }
+
+    fn from_raw(rule_raw: Option<&LintRuleRaw>) -> Result<LintRule, tackler::Error> {
+        match rule_raw {
+            Some(r) => LintRule::try_from(r.severity.as_ref(), r.allow.as_ref()),
+            None => Ok(LintRule::default()),
+        }
+    }
+}
+
+/// Journal lints of `tackler check`
+#[derive(Debug, Clone)]
+pub(crate) struct Lint {
+    pub(crate) unused_accounts: LintRule,
+    pub(crate) unused_commodities: LintRule,
+    pub(crate) unused_tags: LintRule,
+    pub(crate) missing_uuid: LintRule,
+    pub(crate) trailing_whitespace: LintRule,
+    pub(crate) account_not_open: LintRule,
+    pub(crate) unsorted_shard: LintRule,
+    pub(crate) price_db_gap: LintRule,
+    /// Maximum gap of price entries of held commodity
+    pub(crate) max_gap_days: u32,
+    pub(crate) amount_precision: LintRule,
+    /// Maximum number of decimals of posting amount
+    pub(crate) max_scale: u32,
+}
+
+impl Lint {
+    const DEFAULT_MAX_GAP_DAYS: u32 = 31;
+    const DEFAULT_MAX_SCALE: u32 = 2;
+
+    fn try_from(lint_raw: Option<&LintRaw>) -> Result<Lint, tackler::Error> {
+        let Some(l) = lint_raw else {
+            return Ok(Lint::default());
+        };
+        let (price_db_gap, max_gap_days) = match &l.price_db_gap {
+            Some(r) => (
+                LintRule::try_from(r.severity.as_ref(), r.allow.as_ref())?,
+                r.max_gap_days.unwrap_or(Self::DEFAULT_MAX_GAP_DAYS),
+            ),
+            None => (LintRule::default(), Self::DEFAULT_MAX_GAP_DAYS),
+        };
+        let (amount_precision, max_scale) = match &l.amount_precision {
+            Some(r) => (
+                LintRule::try_from(r.severity.as_ref(), r.allow.as_ref())?,
+                r.max_scale.unwrap_or(Self::DEFAULT_MAX_SCALE),
+            ),
+            None => (LintRule::default(), Self::DEFAULT_MAX_SCALE),
+        };
+        Ok(Lint {
+            unused_accounts: LintRule::from_raw(l.unused_accounts.as_ref())?,
+            unused_commodities: LintRule::from_raw(l.unused_commodities.as_ref())?,
+            unused_tags: LintRule::from_raw(l.unused_tags.as_ref())?,
+            missing_uuid: LintRule::from_raw(l.missing_uuid.as_ref())?,
+            trailing_whitespace: LintRule::from_raw(l.trailing_whitespace.as_ref())?,
+            account_not_open: LintRule::from_raw(l.account_not_open.as_ref())?,
+            unsorted_shard: LintRule::from_raw(l.unsorted_shard.as_ref())?,
+            price_db_gap,
+            max_gap_days,
+            amount_precision,
+            max_scale,
+        })
+    }
+}
+
+impl Default for Lint {
+    fn default() -> Self {
+        Lint {
+            unused_accounts: LintRule::default(),
+            unused_commodities: LintRule::default(),
+            unused_tags: LintRule::default(),
+            missing_uuid: LintRule::default(),
+            trailing_whitespace: LintRule::default(),
+            account_not_open: LintRule::default(),
+            unsorted_shard: LintRule::default(),
+            price_db_gap: LintRule::default(),
+            max_gap_days: Self::DEFAULT_MAX_GAP_DAYS,
+            amount_precision: LintRule::default(),
+            max_scale: Self::DEFAULT_MAX_SCALE,
+        }
+    }
+}
+
+/// Mode of probable duplicate txns
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DedupeMode {
+    /// Duplicates are reported
+    #[default]
+    Report,
+    /// Duplicates are reported and left out
+    Skip,
+}
+impl DedupeMode {
+    pub const REPORT: &'static str = "report";
+    pub const SKIP: &'static str = "skip";
+}
+
+impl Display for DedupeMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Report => f.write_str(DedupeMode::REPORT),
+            Self::Skip => f.write_str(DedupeMode::SKIP),
+        }
+    }
+}
+
+impl TryFrom<&str> for DedupeMode {
+    type Error = tackler::Error;
+
+    fn try_from(mode: &str) -> Result<DedupeMode, tackler::Error> {
+        match mode {
+            DedupeMode::REPORT => Ok(DedupeMode::Report),
+            DedupeMode::SKIP => Ok(DedupeMode::Skip),
+            _ => Err(format!(
+                "Unknown dedupe mode: '{mode}'. Valid options are: {}, {}",
+                Self::REPORT,
+                Self::SKIP,
+            )
+            .into()),
+        }
+    }
+}
+
+/// Parameters of fuzzy matching of probable duplicate txns
+#[derive(Debug, Clone)]
+pub(crate) struct Dedupe {
+    /// Maximum distance of txn dates
+    pub(crate) max_days: u32,
+    /// Minimum similarity of txn descriptions (0.0 - 1.0)
+    pub(crate) min_similarity: f64,
+}
+
+impl Dedupe {
+    const DEFAULT_MAX_DAYS: u32 = 3;
+    const DEFAULT_MIN_SIMILARITY: f64 = 0.6;
+
+    fn try_from(dedupe_raw: Option<&DedupeRaw>) -> Result<Dedupe, tackler::Error> {
+        let Some(d) = dedupe_raw else {
+            return Ok(Dedupe::default());
+        };
+        let min_similarity = d.min_similarity.unwrap_or(Self::DEFAULT_MIN_SIMILARITY);
+        if !(0.0..=1.0).contains(&min_similarity) {
+            let msg = format!(
+                "Invalid dedupe 'min-similarity': {min_similarity}, it must be between 0.0 and 1.0"
+            );
+            return Err(msg.into());
+        }
+        Ok(Dedupe {
+            max_days: d.max_days.unwrap_or(Self::DEFAULT_MAX_DAYS),
+            min_similarity,
+        })
+    }
+}
+
+impl Default for Dedupe {
+    fn default() -> Self {
+        Dedupe {
+            max_days: Self::DEFAULT_MAX_DAYS,
+            min_similarity: Self::DEFAULT_MIN_SIMILARITY,
+        }
+    }
+}
+
+/// Sign convention of bank statement amounts
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SignConvention {
+    /// Single amount column, negative amount is money out of the account
+    #[default]
+    Signed,
+    /// Single amount column, positive amount is money out of the account
+    Inverted,
+    /// Separate columns for money out (debit) and money in (credit)
+    DebitCredit,
+}
+impl SignConvention {
+    pub const SIGNED: &'static str = "signed";
+    pub const INVERTED: &'static str = "inverted";
+    pub const DEBIT_CREDIT: &'static str = "debit-credit";
+}
+
+impl Display for SignConvention {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Signed => f.write_str(SignConvention::SIGNED),
+            Self::Inverted => f.write_str(SignConvention::INVERTED),
+            Self::DebitCredit => f.write_str(SignConvention::DEBIT_CREDIT),
+        }
+    }
+}
+
+impl TryFrom<&str> for SignConvention {
+    type Error = tackler::Error;
+
+    fn try_from(sign: &str) -> Result<SignConvention, tackler::Error> {
+        match sign {
+            SignConvention::SIGNED => Ok(SignConvention::Signed),
+            SignConvention::INVERTED => Ok(SignConvention::Inverted),
+            SignConvention::DEBIT_CREDIT => Ok(SignConvention::DebitCredit),
+            _ => Err(format!(
+                "Unknown sign convention: '{sign}'. Valid options are: {}, {}, {}",
+                Self::SIGNED,
+                Self::INVERTED,
+                Self::DEBIT_CREDIT,
+            )
+            .into()),
+        }
+    }
+}
+
+/// Columns of bank statement CSV, as zero-based indexes
+///
+/// Columns are one-based in the mapping file.
+#[derive(Debug, Clone)]
+pub(crate) struct ImportColumns {
+    pub(crate) date: usize,
+    pub(crate) amount: Option<usize>,
+    pub(crate) debit: Option<usize>,
+    pub(crate) credit: Option<usize>,
+    pub(crate) payee: Option<usize>,
+    pub(crate) memo: Option<usize>,
+    pub(crate) id: Option<usize>,
+}
+
+impl ImportColumns {
+    fn try_from(
+        columns_raw: &ImportColumnsRaw,
+        sign: SignConvention,
+    ) -> Result<ImportColumns, tackler::Error> {
+        fn index(name: &str, column: usize) -> Result<usize, tackler::Error> {
+            match column {
+                0 => Err(format!("Invalid column '{name}': columns start from 1").into()),
+                c => Ok(c - 1),
+            }
+        }
+        fn opt_index(name: &str, column: Option<usize>) -> Result<Option<usize>, tackler::Error> {
+            column.map(|c| index(name, c)).transpose()
+        }
+        let columns = ImportColumns {
+            date: index("date", columns_raw.date)?,
+            amount: opt_index("amount", columns_raw.amount)?,
+            debit: opt_index("debit", columns_raw.debit)?,
+            credit: opt_index("credit", columns_raw.credit)?,
+            payee: opt_index("payee", columns_raw.payee)?,
+            memo: opt_index("memo", columns_raw.memo)?,
+            id: opt_index("id", columns_raw.id)?,
+        };
+        let ok = match sign {
+            SignConvention::Signed | SignConvention::Inverted => columns.amount.is_some(),
+            SignConvention::DebitCredit => columns.debit.is_some() && columns.credit.is_some(),
+        };
+        if !ok {
+            let msg = format!(
+                "Sign convention '{sign}' needs columns: {}",
+                match sign {
+                    SignConvention::DebitCredit => "'debit' and 'credit'",
+                    _ => "'amount'",
+                }
+            );
+            return Err(msg.into());
+        }
+        Ok(columns)
+    }
+}
+
+/// Column layout and formats of bank statement CSV
+#[derive(Debug, Clone)]
+pub struct ImportMapping {
+    /// Account of the bank statement
+    pub(crate) account: String,
+    pub(crate) commodity: Option<String>,
+    pub(crate) delimiter: u8,
+    /// Count of rows to skip before records, e.g. header row
+    pub(crate) skip_rows: usize,
+    /// Date format for `strptime`
+    pub(crate) date_format: String,
+    pub(crate) decimal_separator: char,
+    pub(crate) thousands_separator: Option<char>,
+    pub(crate) sign: SignConvention,
+    pub(crate) extid_prefix: String,
+    pub(crate) columns: ImportColumns,
+}
+
+fn single_char(name: &str, value: &str) -> Result<char, tackler::Error> {
+    let mut chars = value.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Ok(c),
+        _ => Err(format!("Invalid '{name}': '{value}', it must be a single character").into()),
+    }
+}
+
+impl ImportMapping {
+    /// Read bank statement mapping
+    ///
+    /// # Errors
+    /// Returns `Err` in case there are syntactical or semantic errors with mapping
+    pub fn try_from<P: AsRef<Path>>(path: P) -> Result<ImportMapping, tackler::Error> {
+        match fs::read_to_string(&path) {
+            Ok(s) => Self::from_toml(&s),
+            Err(err) => {
+                let msg = format!(
+                    "Can't open import mapping: '{}' - {err}",
+                    path.as_ref().display()
+                );
+                Err(msg.into())
+            }
+        }
+    }
+
+    pub(crate) fn from_toml(mapping: &str) -> Result<ImportMapping, tackler::Error> {
+        let mapping_raw: ImportMappingRaw = toml::from_str(mapping)?;
+        let sign = mapping_raw
+            .sign
+            .as_deref()
+            .map(SignConvention::try_from)
+            .transpose()?
+            .unwrap_or_default();
+        let delimiter = single_char("delimiter", mapping_raw.delimiter.as_deref().unwrap_or(","))?;
+        let Ok(delimiter) = u8::try_from(delimiter) else {
+            let msg = format!("Invalid 'delimiter': '{delimiter}', it must be an ASCII character");
+            return Err(msg.into());
+        };
+        Ok(ImportMapping {
+            account: mapping_raw.account.clone(),
+            commodity: mapping_raw.commodity.clone(),
+            delimiter,
+            skip_rows: mapping_raw.skip_rows.unwrap_or(1),
+            date_format: mapping_raw.date_format.clone(),
+            decimal_separator: single_char(
+                "decimal-separator",
+                mapping_raw.decimal_separator.as_deref().unwrap_or("."),
+            )?,
+            thousands_separator: mapping_raw
+                .thousands_separator
+                .as_deref()
+                .map(|s| single_char("thousands-separator", s))
+                .transpose()?,
+            sign,
+            extid_prefix: mapping_raw
+                .extid_prefix
+                .clone()
+                .unwrap_or_else(|| "import".to_string()),
+            columns: ImportColumns::try_from(&mapping_raw.columns, sign)?,
+        })
+    }
+}
+
+/// Import rule, the first matching rule is used for the statement record
+///
+/// All given regular expressions (payee, memo) must match.
+#[derive(Debug, Clone)]
+pub(crate) struct ImportRule {
+    pub(crate) payee: Option<Regex>,
+    pub(crate) memo: Option<Regex>,
+    pub(crate) account: String,
+    pub(crate) description: Option<String>,
+    pub(crate) tags: Vec<String>,
+}
+
+impl ImportRule {
+    pub(crate) fn is_match(&self, payee: &str, memo: &str) -> bool {
+        self.payee.as_ref().is_none_or(|re| re.is_match(payee))
+            && self.memo.as_ref().is_none_or(|re| re.is_match(memo))
+    }
+}
+
+/// Rules to assign accounts, tags and descriptions for imported txns
+#[derive(Debug, Clone)]
+pub struct ImportRules {
+    /// Account for records without matching rule
+    pub(crate) default_account: String,
+    pub(crate) rules: Vec<ImportRule>,
+}
+
+impl ImportRules {
+    /// Read import rules
+    ///
+    /// # Errors
+    /// Returns `Err` in case there are syntactical or semantic errors with rules
+    pub fn try_from<P: AsRef<Path>>(path: P) -> Result<ImportRules, tackler::Error> {
+        match fs::read_to_string(&path) {
+            Ok(s) => Self::from_toml(&s),
+            Err(err) => {
+                let msg = format!(
+                    "Can't open import rules: '{}' - {err}",
+                    path.as_ref().display()
+                );
+                Err(msg.into())
+            }
+        }
+    }
+
+    pub(crate) fn from_toml(rules: &str) -> Result<ImportRules, tackler::Error> {
+        let rules_raw: ImportRulesRaw = toml::from_str(rules)?;
+        let regex = |name: &str, i: usize, re: Option<&String>| {
+            re.map(|re| {
+                new_full_haystack_regex(re)
+                    .map_err(|err| format!("Invalid '{name}' of import rule {}: {err}", i + 1))
+            })
+            .transpose()
+        };
+        let rules = rules_raw
+            .rules
+            .iter()
+            .enumerate()
+            .map(|(i, r)| {
+                if r.payee.is_none() && r.memo.is_none() {
+                    let msg = format!("Import rule {} must have 'payee' or 'memo'", i + 1);
+                    return Err(msg.into());
+                }
+                Ok(ImportRule {
+                    payee: regex("payee", i, r.payee.as_ref())?,
+                    memo: regex("memo", i, r.memo.as_ref())?,
+                    account: r.account.clone(),
+                    description: r.description.clone(),
+                    tags: r.tags.clone().unwrap_or_default(),
+                })
+            })
+            .collect::<Result<Vec<_>, tackler::Error>>()?;
+        Ok(ImportRules {
+            default_account: rules_raw.default_account.clone(),
+            rules,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::config::raw_items::ScaleRaw;
 
+    #[test]
+    // test: 5b7d9f1a-3c5e-4a7b-8d0f-2e4a6c8e0b1d
+    // desc: named filters are JSON or filter expressions, and invalid filter is reported
+    fn named_filters() {
+        let filters_raw: BTreeMap<String, String> = toml::from_str(
+            r#"
+            expenses = 'account =~ "Expenses:.*"'
+            all = '{"txnFilter":{"NullaryTRUE":{}}}'
+            "#,
+        )
+        .unwrap(/*:test:*/);
+        let filters = to_filters(Some(&filters_raw), &TimeZone::UTC).unwrap(/*:test:*/);
+        assert_eq!(filters.len(), 2);
+        assert_eq!(
+            filters["expenses"].to_expr(&TimeZone::UTC),
+            r#"account =~ "Expenses:.*""#
+        );
+        assert_eq!(filters["all"].to_expr(&TimeZone::UTC), "true");
+
+        let filters_raw = BTreeMap::from([("bad".to_string(), "foo".to_string())]);
+        let res = to_filters(Some(&filters_raw), &TimeZone::UTC);
+        assert_eq!(
+            res.err().map(|e| e.to_string()),
+            Some(
+                "Invalid filter 'bad': Filter expression error at column 1: unknown filter 'foo'"
+                    .to_string()
+            )
+        );
+    }
+    #[test]
+    // test: ebc60f62-45a0-4460-9589-eb29bf43f059
+    fn accounts_specs() {
+        let acc_raw: AccountsRaw = toml::from_str(
+            r#"
+            accounts = [ "a:b" ]
+
+            [[account]]
+            name = "c:d"
+            open = 2018-03-01
+            close = "2023-12-31T14:30:00+02:00"
+            kind = "debit"
+
+            [[account]]
+            name = "c:d"
+            open = 2024-03-01T10:00:00
+            kind = "debit"
+
+            [[account]]
+            name = "e:f"
+            "#,
+        )
+        .unwrap(/*:test:*/);
+
+        let specs = acc_raw
+            .specs
+            .iter()
+            .map(AccountSpec::try_from)
+            .collect::<Result<Vec<_>, tackler::Error>>()
+            .unwrap(/*:test:*/);
+        assert!(Accounts::check_specs(&acc_raw.names, &specs).is_ok());
+
+        assert_eq!(specs.len(), 3);
+        assert_eq!(specs[0].open.as_deref(), Some("2018-03-01"));
+        assert_eq!(specs[0].close.as_deref(), Some("2023-12-31T14:30:00+02:00"));
+        assert_eq!(specs[0].kind, Some(AccountKind::Debit));
+        assert_eq!(specs[1].open.as_deref(), Some("2024-03-01T10:00:00"));
+        assert_eq!(specs[2].open, None);
+        assert_eq!(specs[2].kind, None);
+    }
+
+    #[test]
+    // test: 4b7e1f0a-8c2d-4e5f-9a6b-3c1d2e0f7a8b
+    // desc: budget amounts could be given as integer, float or string
+    fn budget_items() {
+        let budget_raw: BudgetRaw = toml::from_str(
+            r#"
+            group-by = "month"
+            commodity = "EUR"
+
+            [[budget]]
+            account = "Expenses:Food"
+            amount = 400
+            periods = { "2024-12" = 550.50, "2025-01" = "0.123456789012345678901" }
+
+            [[budget]]
+            account = "Expenses:Food"
+            commodity = "USD"
+            periods = { "2024-12" = 100 }
+            "#,
+        )
+        .unwrap(/*:test:*/);
+
+        let budget = Budget::try_from("Budget", &budget_raw).unwrap(/*:test:*/);
+        assert_eq!(budget.items.len(), 2);
+
+        let food = &budget.items[0];
+        assert_eq!(
+            food.commodity.as_ref().map(|c| c.name.as_str()),
+            Some("EUR")
+        );
+        assert_eq!(food.amount("2024-11"), Some(Decimal::from(400)));
+        assert_eq!(food.amount("2024-12"), Some(Decimal::new(55050, 2)));
+        assert_eq!(
+            food.amount("2025-01").map(|a| a.to_string()),
+            Some("0.123456789012345678901".to_string())
+        );
+
+        let food_usd = &budget.items[1];
+        assert_eq!(
+            food_usd.commodity.as_ref().map(|c| c.name.as_str()),
+            Some("USD")
+        );
+        assert_eq!(food_usd.amount("2024-11"), None);
+        assert_eq!(food_usd.amount("2024-12"), Some(Decimal::from(100)));
+    }
+
+    #[test]
+    // test: 6f1c3e5a-9b2d-4c8e-a7f0-4d6b8e0a2c1f
+    // desc: recurring txn specs, interval defaults to one
+    fn recurring_specs() {
+        let recurring_raw: RecurringRaw = toml::from_str(
+            r#"
+            [[recurring]]
+            every = "month"
+            until = 2025-12-31
+            txn = """
+            2025-01-31 'rent
+             Expenses:Rent  500
+             Assets:Bank
+            """
+
+            [[recurring]]
+            every = "week"
+            interval = 2
+            txn = "2025-01-06 'lunch\n Expenses:Food  10\n Assets:Cash\n"
+            "#,
+        )
+        .unwrap(/*:test:*/);
+
+        let specs = recurring_raw
+            .specs
+            .iter()
+            .map(RecurringSpec::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap(/*:test:*/);
+        assert_eq!(specs.len(), 2);
+
+        assert_eq!(specs[0].every, RecurringPeriod::Month);
+        assert_eq!(specs[0].interval, 1);
+        assert_eq!(specs[0].until.as_deref(), Some("2025-12-31"));
+
+        assert_eq!(specs[1].every, RecurringPeriod::Week);
+        assert_eq!(specs[1].interval, 2);
+        assert_eq!(specs[1].until, None);
+    }
+
+    #[test]
+    // test: 0b8d2f4a-6c1e-4a3b-9d5f-7e9a1c3b5d2e
+    fn recurring_spec_errors() {
+        let cases = [
+            (
+                r#"every = "quarter"
+                txn = """#,
+                "Unknown recurring period: 'quarter'. Valid options are: day, week, month, year",
+            ),
+            (
+                r#"every = "day"
+                interval = 0
+                txn = """#,
+                "Recurring txn 'interval' must be greater than zero",
+            ),
+        ];
+        for (spec, msg) in cases {
+            let spec_raw: RecurringSpecRaw = toml::from_str(spec).unwrap(/*:test:*/);
+            let res = RecurringSpec::try_from(&spec_raw);
+            assert_eq!(res.err().map(|e| e.to_string()), Some(msg.to_string()));
+        }
+    }
+
+    #[test]
+    // test: 9e2a6c4d-1f3b-4a5c-8d7e-6b0f2a1c3d4e
+    fn budget_errors() {
+        let cases = [
+            (
+                r#"
+                group-by = "month"
+                [[budget]]
+                account = "a"
+                periods = { "2024-1" = 1 }
+                "#,
+                "invalid budget period '2024-1', period must be in form of '2024-12'",
+            ),
+            (
+                r#"
+                group-by = "iso-week"
+                [[budget]]
+                account = "a"
+                periods = { "2024-W54" = 1 }
+                "#,
+                "invalid budget period '2024-W54', period must be in form of '2024-W51'",
+            ),
+            (
+                r#"
+                group-by = "month"
+                commodity = "EUR"
+                [[budget]]
+                account = "a"
+                amount = 1
+                [[budget]]
+                account = "a"
+                amount = 2
+                "#,
+                "Budget is defined multiple times for account 'a' [EUR]",
+            ),
+        ];
+        for (toml_str, err_msg) in cases {
+            let budget_raw: BudgetRaw = toml::from_str(toml_str).unwrap(/*:test:*/);
+            let res = Budget::try_from("Budget", &budget_raw);
+            assert!(res.is_err());
+            assert!(
+                res.as_ref().err().unwrap(/*:test:*/).to_string().contains(err_msg),
+                "{res:?}"
+            );
+        }
+    }
+
+    #[test]
+    // test: 3b5d7f9a-1c3e-4a5b-8d7f-9a1b3c5d7e9f
+    // desc: income statement roots default to Income and Expenses, and roots must not overlap
+    fn income_statement_roots() {
+        let is_raw: IncomeStatementRaw = toml::from_str(
+            r#"
+            title = "IS"
+            revenues = [ "Income", "Revenue" ]
+            depth = 2
+            group-by = "year"
+            "#,
+        )
+        .unwrap(/*:test:*/);
+        let is = IncomeStatement::from(Some(&is_raw)).unwrap(/*:test:*/);
+        assert_eq!(is.revenues, vec!["Income", "Revenue"]);
+        assert_eq!(is.expenses, vec!["Expenses"]);
+        assert_eq!(is.depth, Some(2));
+        assert!(matches!(is.group_by, Some(GroupBy::Year)));
+
+        let cases = [
+            (
+                r#"
+                title = "IS"
+                expenses = [ "Expenses", "Income:Fees" ]
+                "#,
+                "root accounts 'Income' and 'Income:Fees' overlap",
+            ),
+            (
+                r#"
+                title = "IS"
+                depth = 0
+                "#,
+                "Invalid value for 'report.income-statement.depth'",
+            ),
+        ];
+        for (toml_str, err_msg) in cases {
+            let is_raw: IncomeStatementRaw = toml::from_str(toml_str).unwrap(/*:test:*/);
+            let res = IncomeStatement::from(Some(&is_raw));
+            assert!(res.is_err());
+            assert!(
+                res.as_ref().err().unwrap(/*:test:*/).to_string().contains(err_msg),
+                "{res:?}"
+            );
+        }
+    }
+
+    #[test]
+    // test: d9f38f6b-c21f-413b-9af4-1aea7d5c77ef
+    fn accounts_specs_errors() {
+        let spec = |name: &str, kind: Option<AccountKind>| AccountSpec {
+            name: name.to_string(),
+            open: None,
+            close: None,
+            kind,
+        };
+
+        // same name in flat list and in [[account]]
+        let names = vec!["a:b".to_string()];
+        assert!(Accounts::check_specs(&names, &[spec("a:b", None)]).is_err());
+
+        // conflicting kind between periods
+        assert!(
+            Accounts::check_specs(
+                &[],
+                &[
+                    spec("a:b", Some(AccountKind::Debit)),
+                    spec("a:b", Some(AccountKind::Credit))
+                ]
+            )
+            .is_err()
+        );
+        // kind is enough to define only once
+        assert!(
+            Accounts::check_specs(
+                &[],
+                &[spec("a:b", Some(AccountKind::Debit)), spec("a:b", None)]
+            )
+            .is_ok()
+        );
+
+        // invalid kind
+        let spec_raw = AccountSpecRaw {
+            name: "a:b".to_string(),
+            open: None,
+            close: None,
+            kind: Some("asset".to_string()),
+        };
+        assert!(AccountSpec::try_from(&spec_raw).is_err());
+    }
+
     #[test]
     // test: 195971d7-f16f-4c1c-a761-6764b28fd4db
     fn test_invalid_storage_type() {
@@ -883,4 +2416,169 @@ mod tests {
         let scale = Scale::from(&sr);
         assert!(scale.is_ok());
     }
+
+    #[test]
+    // test: 7e3a5c9d-1f4b-4d2a-8c6e-0b9f2d4a6e8c
+    // desc: lint severity, allow-list and parameters
+    fn lint_config() {
+        let lint_raw: LintRaw = toml::from_str(
+            r#"
+            unused-accounts = { severity = "error", allow = ["Equity(:.*)?"] }
+            missing-uuid = { severity = "off" }
+            price-db-gap = { max-gap-days = 7 }
+            "#,
+        )
+        .unwrap(/*:test:*/);
+
+        let lint = Lint::try_from(Some(&lint_raw)).unwrap(/*:test:*/);
+        assert_eq!(lint.unused_accounts.level, LintLevel::Error);
+        assert_eq!(lint.unused_accounts.allow, vec!["Equity(:.*)?".to_string()]);
+        assert_eq!(lint.missing_uuid.level, LintLevel::Off);
+        assert_eq!(lint.unused_tags.level, LintLevel::Warning);
+        assert_eq!(lint.price_db_gap.level, LintLevel::Warning);
+        assert_eq!(lint.max_gap_days, 7);
+        assert_eq!(lint.max_scale, 2);
+
+        let lint_raw: LintRaw = toml::from_str(
+            r#"
+            unused-tags = { severity = "fatal" }
+            "#,
+        )
+        .unwrap(/*:test:*/);
+        let res = Lint::try_from(Some(&lint_raw));
+        assert!(res.is_err());
+        assert!(
+            res.err()
+                .unwrap(/*:test:*/)
+                .to_string()
+                .contains("Unknown lint severity: 'fatal'")
+        );
+    }
+
+    #[test]
+    // test: 2c8e4a6f-0d1b-4f3e-9a7c-5e1d3b9f7a2c
+    // desc: import mapping with defaults and sign conventions
+    fn import_mapping_config() {
+        let mapping = ImportMapping::from_toml(
+            r#"
+            account = "Assets:Bank"
+            date-format = "%d.%m.%Y"
+            columns = { date = 1, amount = 3 }
+            "#,
+        )
+        .unwrap(/*:test:*/);
+        assert_eq!(mapping.delimiter, b',');
+        assert_eq!(mapping.skip_rows, 1);
+        assert_eq!(mapping.decimal_separator, '.');
+        assert_eq!(mapping.thousands_separator, None);
+        assert_eq!(mapping.sign, SignConvention::Signed);
+        assert_eq!(mapping.extid_prefix, "import");
+        assert_eq!(mapping.columns.date, 0);
+        assert_eq!(mapping.columns.amount, Some(2));
+
+        let res = ImportMapping::from_toml(
+            r#"
+            account = "Assets:Bank"
+            date-format = "%Y-%m-%d"
+            sign = "debit-credit"
+            columns = { date = 1, amount = 3 }
+            "#,
+        );
+        assert!(
+            res.err()
+                .unwrap(/*:test:*/)
+                .to_string()
+                .contains("needs columns: 'debit' and 'credit'")
+        );
+
+        let res = ImportMapping::from_toml(
+            r#"
+            account = "Assets:Bank"
+            date-format = "%Y-%m-%d"
+            decimal-separator = ",,"
+            columns = { date = 0, amount = 3 }
+            "#,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    // test: 6a0c2e4f-8b3d-4d5a-b1e7-3f9c5a7d1b4e
+    // desc: import rules must have payee or memo
+    fn import_rules_config() {
+        let rules = ImportRules::from_toml(
+            r#"
+            default-account = "Expenses:Unknown"
+
+            [[rule]]
+            payee = "(?i).*grocery.*"
+            account = "Expenses:Food"
+            tags = ["food"]
+            "#,
+        )
+        .unwrap(/*:test:*/);
+        assert_eq!(rules.rules.len(), 1);
+        assert!(rules.rules[0].is_match("Big Grocery Ltd", ""));
+        assert!(!rules.rules[0].is_match("Gas station", "grocery"));
+
+        let res = ImportRules::from_toml(
+            r#"
+            default-account = "Expenses:Unknown"
+
+            [[rule]]
+            account = "Expenses:Food"
+            "#,
+        );
+        assert!(
+            res.err()
+                .unwrap(/*:test:*/)
+                .to_string()
+                .contains("Import rule 1 must have 'payee' or 'memo'")
+        );
+    }
+
+    #[test]
+    // test: 5d7f9b1c-3e5a-4c7e-9f1b-8a0c2e4d6f9a
+    // desc: dedupe parameters
+    fn dedupe_config() {
+        let dedupe_raw: DedupeRaw = toml::from_str("max-days = 5").unwrap(/*:test:*/);
+        let dedupe = Dedupe::try_from(Some(&dedupe_raw)).unwrap(/*:test:*/);
+        assert_eq!(dedupe.max_days, 5);
+        assert!((dedupe.min_similarity - 0.6).abs() < f64::EPSILON);
+
+        let dedupe_raw: DedupeRaw = toml::from_str("min-similarity = 1.5").unwrap(/*:test:*/);
+        assert!(Dedupe::try_from(Some(&dedupe_raw)).is_err());
+
+        assert_eq!(
+            DedupeMode::try_from("skip").unwrap(/*:test:*/),
+            DedupeMode::Skip
+        );
+        assert!(DedupeMode::try_from("drop").is_err());
+    }
+
+    #[test]
+    // test: 8f0b2d4e-6a8c-4e0f-b2d4-1c3e5a7f9b0d
+    // desc: price database path could be a list of paths
+    fn price_db_paths() {
+        let cfg_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml");
+        let price = |toml_str: &str| {
+            let price_raw: PriceRaw = toml::from_str(toml_str).unwrap(/*:test:*/);
+            Price::try_from(&cfg_path, &price_raw)
+        };
+
+        let res = price("db-path = \"none\"\nlookup-type = \"none\"");
+        assert!(res.unwrap(/*:test:*/).db_paths.is_empty());
+
+        let res = price("db-path = [ \"fx.db\", \"/prices\" ]\nlookup-type = \"last-price\"");
+        assert_eq!(
+            res.unwrap(/*:test:*/).db_paths,
+            vec![
+                Path::new(env!("CARGO_MANIFEST_DIR")).join("fx.db"),
+                PathBuf::from("/prices")
+            ]
+        );
+
+        let res = price("db-path = []\nlookup-type = \"last-price\"");
+        assert!(res.is_err());
+    }
 }
@@ -6,7 +6,7 @@
 //! This module contains the overlap
 //! configuration items to be used e.g. with CLI
 
-use crate::config::{PriceLookupType, StorageType};
+use crate::config::{InputFormat, PriceLookupType, StorageType};
 use crate::kernel::settings::GitInputSelector;
 use std::path::PathBuf;
 
@@ -25,12 +25,16 @@ pub struct OverlapConfig {
     pub report: ReportOverlap,
     /// Target (reports, exports) related overlaps
     pub target: TargetOverlap,
+    /// Forecast related overlaps
+    pub forecast: ForecastOverlap,
 }
 
 #[derive(Debug, Default, Clone)]
 pub struct StorageOverlap {
     pub storage_type: Option<StorageType>,
     pub input: Option<InputOverlap>,
+    /// Format of txn journal
+    pub format: Option<InputFormat>,
 }
 
 /// Input related overlap
@@ -95,8 +99,12 @@ pub struct ReportOverlap {
     pub account_overlap: Option<Vec<String>>,
     /// Group-By operator
     pub group_by: Option<String>,
+    /// Roll-up depth of accounts for balance reports
+    pub depth: Option<usize>,
     /// Are the report values inverted?
     pub inverted: bool,
+    /// Include accounts which are closed in the report period
+    pub include_closed: bool,
 }
 
 /// Target (reports, exports) overlap configuration
@@ -109,3 +117,12 @@ pub struct TargetOverlap {
     /// Report output formats
     pub formats: Option<Vec<String>>,
 }
+
+/// Forecast overlap configuration
+#[derive(Debug, Default, Clone)]
+pub struct ForecastOverlap {
+    /// Begin of forecast window (inclusive)
+    pub begin: Option<String>,
+    /// End of forecast window (exclusive), forecast is active if this is set
+    pub end: Option<String>,
+}
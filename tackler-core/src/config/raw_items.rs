@@ -3,7 +3,9 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 use crate::config::AccountSelectors;
+use rust_decimal::Decimal;
 use serde::Deserialize;
+use std::collections::BTreeMap;
 
 #[derive(Debug, Clone, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -13,6 +15,9 @@ pub(super) struct ConfigRaw {
     pub(super) transaction: TransactionRaw,
     pub(super) report: ReportRaw,
     pub(super) export: ExportRaw,
+    pub(super) filters: Option<BTreeMap<String, String>>,
+    pub(super) lint: Option<LintRaw>,
+    pub(super) dedupe: Option<DedupeRaw>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -23,6 +28,7 @@ pub(super) struct KernelRaw {
     pub(super) audit: AuditRaw,
     #[serde(rename = "ext-id")]
     pub(super) extid: Option<ExtIdRaw>,
+    pub(super) lots: Option<LotsRaw>,
     pub(super) input: InputRaw,
 }
 
@@ -55,10 +61,17 @@ pub(super) struct ExtIdRaw {
     pub(super) unique: bool,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct LotsRaw {
+    pub(super) matching: String,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub(super) struct InputRaw {
     pub(super) storage: String,
+    pub(super) format: Option<String>,
     pub(super) fs: Option<FsRaw>,
     pub(super) git: Option<GitRaw>,
 }
@@ -95,7 +108,7 @@ pub(super) struct GitRaw {
 #[serde(deny_unknown_fields)]
 pub(super) struct PriceRaw {
     #[serde(rename = "db-path")]
-    pub(super) db_path: String,
+    pub(super) db_path: DbPathRaw,
     #[serde(rename = "lookup-type")]
     pub(super) lookup_type: String,
 }
@@ -106,6 +119,7 @@ pub(super) struct TransactionRaw {
     pub(super) accounts: AccountsPathRaw,
     pub(super) commodities: CommoditiesPathRaw,
     pub(super) tags: TagsPathRaw,
+    pub(super) recurring: Option<RecurringPathRaw>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -126,11 +140,45 @@ pub(super) struct TagsPathRaw {
     pub(super) path: String,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct RecurringPathRaw {
+    pub(super) path: String,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub(super) struct AccountsRaw {
-    #[serde(rename = "accounts")]
+    #[serde(rename = "accounts", default)]
     pub(super) names: Vec<String>,
+
+    #[serde(rename = "account", default)]
+    pub(super) specs: Vec<AccountSpecRaw>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct AccountSpecRaw {
+    pub(super) name: String,
+    pub(super) open: Option<TimestampValueRaw>,
+    pub(super) close: Option<TimestampValueRaw>,
+    pub(super) kind: Option<String>,
+}
+
+/// Price database could be a single path, or a list of paths
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub(super) enum DbPathRaw {
+    Single(String),
+    List(Vec<String>),
+}
+
+/// Timestamp could be given as TOML date / datetime, or as a string
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub(super) enum TimestampValueRaw {
+    Toml(toml::value::Datetime),
+    Str(String),
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -150,6 +198,22 @@ pub(super) struct TagsRaw {
     pub(crate) names: Vec<String>,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct RecurringRaw {
+    #[serde(rename = "recurring", default)]
+    pub(super) specs: Vec<RecurringSpecRaw>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct RecurringSpecRaw {
+    pub(super) every: String,
+    pub(super) interval: Option<u32>,
+    pub(super) until: Option<TimestampValueRaw>,
+    pub(super) txn: String,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub(super) struct ReportRaw {
@@ -164,6 +228,12 @@ pub(super) struct ReportRaw {
     #[serde(rename = "balance-group")]
     pub(super) balance_group: BalanceGroupRaw,
     pub(super) balance: BalanceRaw,
+    pub(super) pnl: Option<PnlRaw>,
+    pub(super) budget: Option<BudgetReportRaw>,
+    #[serde(rename = "income-statement")]
+    pub(super) income_statement: Option<IncomeStatementRaw>,
+    #[serde(rename = "cash-flow")]
+    pub(super) cash_flow: Option<CashFlowRaw>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -179,10 +249,19 @@ pub(super) struct BalanceRaw {
     pub(super) title: String,
     #[serde(rename = "type")]
     pub(super) bal_type: Option<String>,
+    pub(super) depth: Option<usize>,
+    pub(super) inventory: Option<bool>,
     #[serde(rename = "accounts")]
     pub(super) acc_sel: Option<AccountSelectors>,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct BeancountRaw {
+    #[serde(rename = "gains-account")]
+    pub(super) gains_account: Option<String>,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub(super) struct BalanceGroupRaw {
@@ -191,6 +270,10 @@ pub(super) struct BalanceGroupRaw {
     pub(super) bal_type: Option<String>,
     #[serde(rename = "group-by")]
     pub(super) group_by: String,
+    pub(super) depth: Option<usize>,
+    pub(super) pivot: Option<bool>,
+    #[serde(rename = "column-totals")]
+    pub(super) column_totals: Option<bool>,
     #[serde(rename = "accounts")]
     pub(super) acc_sel: Option<AccountSelectors>,
 }
@@ -201,16 +284,77 @@ pub(super) struct RegisterRaw {
     pub(super) title: String,
     #[serde(rename = "timestamp-style")]
     pub(super) timestamp_style: Option<String>,
+    #[serde(rename = "cost-basis")]
+    pub(super) cost_basis: Option<bool>,
+    #[serde(rename = "accounts")]
+    pub(super) acc_sel: Option<AccountSelectors>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct PnlRaw {
+    pub(super) title: String,
     #[serde(rename = "accounts")]
     pub(super) acc_sel: Option<AccountSelectors>,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct BudgetReportRaw {
+    pub(super) title: String,
+    pub(super) path: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct IncomeStatementRaw {
+    pub(super) title: String,
+    pub(super) revenues: Option<Vec<String>>,
+    pub(super) expenses: Option<Vec<String>>,
+    pub(super) depth: Option<usize>,
+    #[serde(rename = "group-by")]
+    pub(super) group_by: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct CashFlowRaw {
+    pub(super) title: String,
+    #[serde(rename = "cash-accounts")]
+    pub(super) cash_accounts: AccountSelectors,
+    pub(super) depth: Option<usize>,
+    #[serde(rename = "group-by")]
+    pub(super) group_by: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct BudgetRaw {
+    #[serde(rename = "group-by")]
+    pub(super) group_by: String,
+    pub(super) commodity: Option<String>,
+    #[serde(rename = "budget", default)]
+    pub(super) items: Vec<BudgetItemRaw>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct BudgetItemRaw {
+    pub(super) account: String,
+    pub(super) commodity: Option<String>,
+    pub(super) amount: Option<Decimal>,
+    #[serde(default)]
+    pub(super) periods: BTreeMap<String, Decimal>,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub(super) struct ExportRaw {
     pub(super) targets: Vec<String>,
 
     pub(super) equity: EquityRaw,
+
+    pub(super) beancount: Option<BeancountRaw>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -221,3 +365,111 @@ pub(super) struct EquityRaw {
     #[serde(rename = "accounts")]
     pub(super) acc_sel: Option<AccountSelectors>,
 }
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct LintRaw {
+    #[serde(rename = "unused-accounts")]
+    pub(super) unused_accounts: Option<LintRuleRaw>,
+    #[serde(rename = "unused-commodities")]
+    pub(super) unused_commodities: Option<LintRuleRaw>,
+    #[serde(rename = "unused-tags")]
+    pub(super) unused_tags: Option<LintRuleRaw>,
+    #[serde(rename = "missing-uuid")]
+    pub(super) missing_uuid: Option<LintRuleRaw>,
+    #[serde(rename = "trailing-whitespace")]
+    pub(super) trailing_whitespace: Option<LintRuleRaw>,
+    #[serde(rename = "account-not-open")]
+    pub(super) account_not_open: Option<LintRuleRaw>,
+    #[serde(rename = "unsorted-shard")]
+    pub(super) unsorted_shard: Option<LintRuleRaw>,
+    #[serde(rename = "price-db-gap")]
+    pub(super) price_db_gap: Option<PriceDbGapLintRaw>,
+    #[serde(rename = "amount-precision")]
+    pub(super) amount_precision: Option<AmountPrecisionLintRaw>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct LintRuleRaw {
+    pub(super) severity: Option<String>,
+    pub(super) allow: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct PriceDbGapLintRaw {
+    pub(super) severity: Option<String>,
+    pub(super) allow: Option<Vec<String>>,
+    #[serde(rename = "max-gap-days")]
+    pub(super) max_gap_days: Option<u32>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct AmountPrecisionLintRaw {
+    pub(super) severity: Option<String>,
+    pub(super) allow: Option<Vec<String>>,
+    #[serde(rename = "max-scale")]
+    pub(super) max_scale: Option<u32>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct DedupeRaw {
+    #[serde(rename = "max-days")]
+    pub(super) max_days: Option<u32>,
+    #[serde(rename = "min-similarity")]
+    pub(super) min_similarity: Option<f64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct ImportMappingRaw {
+    pub(super) account: String,
+    pub(super) commodity: Option<String>,
+    pub(super) delimiter: Option<String>,
+    #[serde(rename = "skip-rows")]
+    pub(super) skip_rows: Option<usize>,
+    #[serde(rename = "date-format")]
+    pub(super) date_format: String,
+    #[serde(rename = "decimal-separator")]
+    pub(super) decimal_separator: Option<String>,
+    #[serde(rename = "thousands-separator")]
+    pub(super) thousands_separator: Option<String>,
+    pub(super) sign: Option<String>,
+    #[serde(rename = "ext-id-prefix")]
+    pub(super) extid_prefix: Option<String>,
+    pub(super) columns: ImportColumnsRaw,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct ImportColumnsRaw {
+    pub(super) date: usize,
+    pub(super) amount: Option<usize>,
+    pub(super) debit: Option<usize>,
+    pub(super) credit: Option<usize>,
+    pub(super) payee: Option<usize>,
+    pub(super) memo: Option<usize>,
+    pub(super) id: Option<usize>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct ImportRulesRaw {
+    #[serde(rename = "default-account")]
+    pub(super) default_account: String,
+    #[serde(rename = "rule", default)]
+    pub(super) rules: Vec<ImportRuleRaw>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct ImportRuleRaw {
+    pub(super) payee: Option<String>,
+    pub(super) memo: Option<String>,
+    pub(super) account: String,
+    pub(super) description: Option<String>,
+    pub(super) tags: Option<Vec<String>>,
+}
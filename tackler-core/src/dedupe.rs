@@ -0,0 +1,369 @@
+/*
+ * Tackler-NG 2026
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Detection of probable duplicate txns
+//!
+//! Txn is a probable duplicate of another txn, if
+//! - both txns have the same `ext-id`, or
+//! - txns have the same amount, their dates are close to each other,
+//!   and their descriptions are similar (fuzzy match)
+//!
+//! Amount of the txn is the sum of its positive posting amounts,
+//! so that the same txn from different sources (e.g. card feed and
+//! bank feed) has the same amount, regardless of its accounts.
+//! Fuzzy matching is configured with `[dedupe]` section of the configuration.
+
+use crate::config::{Dedupe, DedupeMode};
+use crate::diagnostic;
+use crate::diagnostic::{Diagnostic, Severity};
+use crate::import::ImportedTxn;
+use crate::kernel::Settings;
+use crate::model::{Transaction, TxnData};
+use crate::tackler;
+use jiff::civil::Date;
+use rust_decimal::Decimal;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter, Write};
+
+/// Properties of txn which are used for matching
+#[derive(Debug, Clone)]
+pub struct TxnKey {
+    date: Date,
+    amount: Decimal,
+    description: String,
+    extid: Option<String>,
+    /// Human readable identity of the txn
+    label: String,
+}
+
+impl TxnKey {
+    /// Human readable identity of the txn: date, description and uuid or ext-id
+    #[must_use]
+    pub fn to_label(&self) -> &str {
+        &self.label
+    }
+
+    fn label(date: Date, description: &str, id: Option<String>) -> String {
+        let mut label = date.to_string();
+        if !description.is_empty() {
+            let _ = write!(label, " '{description}'");
+        }
+        if let Some(id) = id {
+            let _ = write!(label, " ({id})");
+        }
+        label
+    }
+}
+
+impl From<&Transaction> for TxnKey {
+    fn from(txn: &Transaction) -> Self {
+        let header = &txn.header;
+        let date = header.timestamp.date();
+        let description = header.description.clone().unwrap_or_default();
+        let amount = txn
+            .posts
+            .iter()
+            .map(|p| p.amount)
+            .filter(Decimal::is_sign_positive)
+            .sum::<Decimal>();
+        let id = match (&header.uuid, &header.extid) {
+            (Some(uuid), _) => Some(format!("uuid: {uuid}")),
+            (None, Some(extid)) => Some(format!("ext-id: {extid}")),
+            (None, None) => None,
+        };
+        TxnKey {
+            date,
+            amount: amount.normalize(),
+            label: TxnKey::label(date, &description, id),
+            description,
+            extid: header.extid.clone(),
+        }
+    }
+}
+
+impl From<&ImportedTxn> for TxnKey {
+    fn from(txn: &ImportedTxn) -> Self {
+        TxnKey {
+            date: txn.date(),
+            amount: txn.amount().abs().normalize(),
+            description: txn.description().to_string(),
+            extid: Some(txn.extid().to_string()),
+            label: TxnKey::label(
+                txn.date(),
+                txn.description(),
+                Some(format!("ext-id: {}", txn.extid())),
+            ),
+        }
+    }
+}
+
+/// How the duplicate was matched
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DuplicateKind {
+    /// Same ext-id
+    ExtId,
+    /// Same amount, close dates and similar descriptions
+    Fuzzy {
+        days: i32,
+        /// Similarity of descriptions (0.0 - 1.0)
+        similarity: f64,
+    },
+}
+
+/// Probable duplicate of a txn
+#[derive(Debug, Clone)]
+pub struct Duplicate {
+    /// Label of the original txn
+    pub original: String,
+    pub kind: DuplicateKind,
+}
+
+impl Display for Duplicate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            DuplicateKind::ExtId => write!(f, "{} (same ext-id)", self.original),
+            DuplicateKind::Fuzzy { days, similarity } => write!(
+                f,
+                "{} (same amount, {days} {} apart, description similarity {:.0}%)",
+                self.original,
+                if days == 1 { "day" } else { "days" },
+                similarity * 100.0
+            ),
+        }
+    }
+}
+
+/// Index of txns for finding probable duplicates
+#[derive(Debug)]
+pub struct DedupeIndex {
+    params: Dedupe,
+    keys: Vec<TxnKey>,
+    by_extid: HashMap<String, usize>,
+    by_amount: HashMap<Decimal, Vec<usize>>,
+}
+
+impl DedupeIndex {
+    #[must_use]
+    pub fn new(settings: &Settings) -> Self {
+        DedupeIndex {
+            params: settings.dedupe.clone(),
+            keys: Vec::new(),
+            by_extid: HashMap::new(),
+            by_amount: HashMap::new(),
+        }
+    }
+
+    /// Create index of all txns of the txn data
+    ///
+    /// # Errors
+    /// Returns `Err` if txn set of the txn data is not valid
+    pub fn from_txn_data(txn_data: &TxnData, settings: &Settings) -> Result<Self, tackler::Error> {
+        let mut index = DedupeIndex::new(settings);
+        for txn in txn_data.get_all()?.txns {
+            index.insert(TxnKey::from(txn));
+        }
+        Ok(index)
+    }
+
+    pub fn insert(&mut self, key: TxnKey) {
+        let i = self.keys.len();
+        if let Some(extid) = &key.extid {
+            self.by_extid.entry(extid.clone()).or_insert(i);
+        }
+        self.by_amount.entry(key.amount).or_default().push(i);
+        self.keys.push(key);
+    }
+
+    /// Find the best match of the txn from the index
+    ///
+    /// Ext-id match is preferred over fuzzy matches, and of the fuzzy
+    /// matches, the one with the most similar description is selected.
+    #[must_use]
+    pub fn find(&self, key: &TxnKey) -> Option<Duplicate> {
+        if let Some(i) = key.extid.as_ref().and_then(|e| self.by_extid.get(e)) {
+            return Some(Duplicate {
+                original: self.keys[*i].label.clone(),
+                kind: DuplicateKind::ExtId,
+            });
+        }
+        self.by_amount
+            .get(&key.amount)
+            .into_iter()
+            .flatten()
+            .filter_map(|i| {
+                let other = &self.keys[*i];
+                let days = (other.date - key.date).get_days().abs();
+                if days.unsigned_abs() > self.params.max_days {
+                    return None;
+                }
+                let similarity = similarity(&key.description, &other.description);
+                (similarity >= self.params.min_similarity).then_some((i, days, similarity))
+            })
+            .max_by(|a, b| a.2.total_cmp(&b.2).then(b.1.cmp(&a.1)))
+            .map(|(i, days, similarity)| Duplicate {
+                original: self.keys[*i].label.clone(),
+                kind: DuplicateKind::Fuzzy { days, similarity },
+            })
+    }
+}
+
+/// Normalized description: lower case alphanumerics, separated by single space
+fn normalize(s: &str) -> Vec<char> {
+    s.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+        .chars()
+        .collect()
+}
+
+/// Similarity of descriptions (overlap coefficient of character bigrams)
+///
+/// Description which is contained in the other one (e.g. "Grocery"
+/// and "Card purchase grocery ltd") is fully similar.
+/// Empty descriptions are not similar to anything.
+fn similarity(a: &str, b: &str) -> f64 {
+    let (a, b) = (normalize(a), normalize(b));
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    if a == b {
+        return 1.0;
+    }
+    if a.len() < 2 || b.len() < 2 {
+        return 0.0;
+    }
+    let mut bigrams = HashMap::new();
+    for w in a.windows(2) {
+        *bigrams.entry((w[0], w[1])).or_insert(0usize) += 1;
+    }
+    let mut common = 0usize;
+    for w in b.windows(2) {
+        if let Some(count) = bigrams.get_mut(&(w[0], w[1])) {
+            if *count > 0 {
+                *count -= 1;
+                common += 1;
+            }
+        }
+    }
+    #[allow(clippy::cast_precision_loss)] // descriptions are short
+    let overlap = common as f64 / (a.len().min(b.len()) - 1) as f64;
+    overlap
+}
+
+/// Find probable duplicate txns of the txn data
+///
+/// Each txn is compared to the preceding txns, so the first one
+/// of the duplicates is the original. Duplicates are reported as warnings,
+/// and with [`DedupeMode::Skip`] they are also removed from the txn data.
+///
+/// # Errors
+/// Returns `Err` if txn set of the txn data is not valid,
+/// or if not in check mode and duplicates are found
+pub fn check_txn_data(
+    txn_data: &mut TxnData,
+    settings: &Settings,
+    mode: DedupeMode,
+) -> Result<(), tackler::Error> {
+    let mut index = DedupeIndex::new(settings);
+    let mut diagnostics = Vec::new();
+    let mut duplicates = HashSet::new();
+    for (i, txn) in txn_data.get_all()?.txns.into_iter().enumerate() {
+        let key = TxnKey::from(txn);
+        if let Some(dup) = index.find(&key) {
+            let msg = match mode {
+                DedupeMode::Report => "Probable duplicate txn",
+                DedupeMode::Skip => "Probable duplicate txn is skipped",
+            };
+            let msg = format!("{msg}: {}\n   original txn: {dup}", key.label);
+            let mut diag = Diagnostic::error(diagnostic::PROBABLE_DUPLICATE, msg);
+            diag.severity = Severity::Warning;
+            diagnostics.push(diag);
+            duplicates.insert(i);
+        }
+        index.insert(key);
+    }
+    if mode == DedupeMode::Skip {
+        txn_data.remove_txns(&duplicates);
+    }
+    settings.check_errors(diagnostics)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+    use indoc::indoc;
+    use tackler_rs::IndocUtils;
+
+    #[test]
+    // test: 1f3b5d7a-9c2e-4e6a-8b0d-4a6c8e0f2b5d
+    // desc: similarity of descriptions
+    fn description_similarity() {
+        assert!((similarity("Grocery Ltd", "GROCERY  LTD.") - 1.0).abs() < f64::EPSILON);
+        assert!((similarity("Card purchase: GROCERY", "Grocery") - 1.0).abs() < f64::EPSILON);
+        assert!(similarity("Grocery Ltd", "Grocery Inc") > 0.6);
+        assert!(similarity("Grocery", "Gas station") < 0.2);
+        assert!(similarity("", "").abs() < f64::EPSILON);
+        assert!(similarity("a", "b").abs() < f64::EPSILON);
+    }
+
+    #[test]
+    // test: 7c9e1a3f-5b7d-4f9b-a2c4-6e8a0c2e4f7b
+    // desc: probable duplicates are reported and skipped
+    fn check_duplicates() {
+        #[rustfmt::skip]
+        let input = indoc!(
+           "|2024-01-05 'Big Grocery Ltd
+            | # ext-id: card-1
+            | e:food 12.50
+            | a:card
+            |
+            |2024-01-06 'GROCERY
+            | # ext-id: bank-1
+            | e:food 12.5
+            | a:bank
+            |
+            |2024-01-20 'Grocery
+            | e:food 12.50
+            | a:bank
+            |
+            |2024-01-21 'Kiosk
+            | # ext-id: card-1
+            | e:food 1
+            | a:card
+            |"
+        ).strip_margin();
+
+        for (mode, count) in [(DedupeMode::Report, 4), (DedupeMode::Skip, 2)] {
+            let mut settings = Settings::default();
+            settings.enable_check_mode();
+            let mut txn_data =
+                parser::string_to_txns(&mut input.as_str(), &settings).unwrap(/*:test:*/);
+            check_txn_data(&mut txn_data, &settings, mode).unwrap(/*:test:*/);
+
+            let diagnostics = settings
+                .take_diagnostics()
+                .into_iter()
+                .filter(|d| d.code == diagnostic::PROBABLE_DUPLICATE)
+                .collect::<Vec<_>>();
+            assert_eq!(diagnostics.len(), 2);
+            assert_eq!(diagnostics[0].severity, Severity::Warning);
+            assert!(
+                diagnostics[0]
+                    .message
+                    .contains("2024-01-06 'GROCERY' (ext-id: bank-1)")
+            );
+            assert!(diagnostics[0].message.ends_with(
+                "original txn: 2024-01-05 'Big Grocery Ltd' (ext-id: card-1) \
+                 (same amount, 1 day apart, description similarity 100%)"
+            ));
+            assert!(diagnostics[1].message.contains("2024-01-21 'Kiosk'"));
+            assert!(diagnostics[1].message.ends_with("(same ext-id)"));
+            assert_eq!(txn_data.len(), count);
+        }
+    }
+}
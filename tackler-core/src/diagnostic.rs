@@ -0,0 +1,422 @@
+/*
+ * Tackler-NG 2026
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Structured diagnostics of journal data
+//!
+//! Diagnostic is an error (or warning) with a stable code, and with
+//! its source location (file path or git object, line, column and span)
+//! if the location is known. Diagnostics are normal errors, and their
+//! text form is the same as the error message of the plain error.
+
+use crate::tackler;
+use serde::Serialize;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io;
+
+/// Syntax error of journal data
+pub const PARSE_ERROR: &str = "parse-error";
+/// Semantic error of journal data, without more specific code
+pub const SEMANTIC_ERROR: &str = "semantic-error";
+/// Invalid txn data, e.g. duplicate txn metadata
+pub const TXN_DATA_ERROR: &str = "txn-data-error";
+/// Sum of txn postings is not zero
+pub const UNBALANCED_TXN: &str = "unbalanced-txn";
+/// Posting with zero amount
+pub const ZERO_POSTING: &str = "zero-posting";
+/// Account is not defined in Chart of Accounts
+pub const UNKNOWN_ACCOUNT: &str = "unknown-account";
+/// Commodity is not defined in Chart of Commodities
+pub const UNKNOWN_COMMODITY: &str = "unknown-commodity";
+/// Tag is not defined in Chart of Tags
+pub const UNKNOWN_TAG: &str = "unknown-tag";
+/// Account is not open at the time of txn
+pub const ACCOUNT_NOT_OPEN: &str = "account-not-open";
+/// Txn without UUID, when UUID is mandatory
+pub const MISSING_UUID: &str = "missing-uuid";
+/// Duplicate txn UUID
+pub const DUPLICATE_UUID: &str = "duplicate-uuid";
+/// Duplicate txn external id
+pub const DUPLICATE_EXTID: &str = "duplicate-extid";
+/// Failed balance assertion
+pub const BALANCE_ASSERTION: &str = "balance-assertion";
+/// Price databases have different prices for the same timestamp and commodities
+pub const PRICE_CONFLICT: &str = "price-conflict";
+/// Any other error
+pub const ERROR: &str = "error";
+
+/// Lint: account of Chart of Accounts is not used by any txn
+pub const UNUSED_ACCOUNT: &str = "unused-account";
+/// Lint: commodity of Chart of Commodities is not used by any txn
+pub const UNUSED_COMMODITY: &str = "unused-commodity";
+/// Lint: tag of Chart of Tags is not used by any txn
+pub const UNUSED_TAG: &str = "unused-tag";
+/// Lint: txn description with trailing whitespace
+pub const TRAILING_WHITESPACE: &str = "trailing-whitespace";
+/// Lint: txns of the shard are not in chronological order
+pub const UNSORTED_SHARD: &str = "unsorted-shard";
+/// Lint: there is a too long gap in prices of held commodity
+pub const PRICE_DB_GAP: &str = "price-db-gap";
+/// Lint: posting amount has too many decimals
+pub const AMOUNT_PRECISION: &str = "amount-precision";
+/// Probable duplicate txn (same ext-id, or same amount, date and description)
+pub const PROBABLE_DUPLICATE: &str = "probable-duplicate";
+
+/// Severity of diagnostic
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// Git object of the diagnostic
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GitObject {
+    /// Commit id
+    pub commit: String,
+    /// Object id of the blob
+    pub object: String,
+}
+
+/// Byte range of the diagnostic in the source
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Structured diagnostic
+#[derive(Debug, Clone, Serialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Stable code of the diagnostic, e.g. `unbalanced-txn`
+    pub code: &'static str,
+    /// Message without source snippet
+    pub message: String,
+    /// Path of the source (file path, or path of git object)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub git: Option<GitObject>,
+    /// Line number, starting from 1
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    /// Column number (in chars), starting from 1
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub span: Option<Span>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub help: Option<String>,
+    /// Text form of the diagnostic with source snippet, if any
+    #[serde(skip)]
+    report: Option<String>,
+}
+
+impl Diagnostic {
+    #[must_use]
+    pub fn error(code: &'static str, message: impl Into<String>) -> Self {
+        Diagnostic {
+            severity: Severity::Error,
+            code,
+            message: message.into(),
+            path: None,
+            git: None,
+            line: None,
+            column: None,
+            span: None,
+            help: None,
+            report: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_git(mut self, commit: &str, object: &str) -> Self {
+        self.git = Some(GitObject {
+            commit: commit.to_string(),
+            object: object.to_string(),
+        });
+        self
+    }
+
+    /// Set location of the diagnostic
+    ///
+    /// `offset` is byte offset of the location in the `input`,
+    /// and `report` is the text form of the diagnostic.
+    #[must_use]
+    pub(crate) fn with_location(
+        mut self,
+        input: &str,
+        offset: usize,
+        span: Span,
+        report: String,
+    ) -> Self {
+        let (line, column) = line_column(input, offset);
+        self.line = Some(line);
+        self.column = Some(column);
+        self.span = Some(span);
+        self.report = Some(report);
+        self
+    }
+
+    /// Set location of the diagnostic without source snippet
+    ///
+    /// `offset` is byte offset of the location in the `input`.
+    #[must_use]
+    pub(crate) fn with_position(mut self, input: &str, offset: usize) -> Self {
+        let (line, column) = line_column(input, offset);
+        self.line = Some(line);
+        self.column = Some(column);
+        self.span = Some(char_span(input, offset));
+        self
+    }
+
+    /// Set location of the diagnostic by line and column (in chars)
+    ///
+    /// This is used when the source text is no longer available,
+    /// e.g. with errors of txn data which are found after parsing.
+    #[must_use]
+    pub(crate) fn with_line_column(mut self, line: usize, column: usize, span: Span) -> Self {
+        self.line = Some(line);
+        self.column = Some(column);
+        self.span = Some(span);
+        self
+    }
+
+    /// Find diagnostic of the error
+    ///
+    /// Diagnostic is searched from the error and its sources.
+    /// If there is none, then a new diagnostic is created
+    /// with the message of the error.
+    #[must_use]
+    pub fn from_error(err: &(dyn Error + 'static)) -> Diagnostic {
+        std::iter::successors(Some(err), |&e| e.source())
+            .find_map(|e| e.downcast_ref::<Diagnostic>())
+            .cloned()
+            .unwrap_or_else(|| Diagnostic::error(ERROR, err.to_string()))
+    }
+}
+
+/// Line and column (in chars) of the byte offset, both starting from 1
+///
+/// Offset at the end of input is reported at the end of the last line,
+/// as it's done by the winnow's parse error.
+fn line_column(input: &str, offset: usize) -> (usize, usize) {
+    if input.is_empty() {
+        return (1, offset + 1);
+    }
+    let mut index = offset.min(input.len() - 1);
+    while !input.is_char_boundary(index) {
+        index -= 1;
+    }
+    let column_offset = offset.saturating_sub(input.len() - 1);
+
+    let line_start = input[..index].rfind('\n').map_or(0, |nl| nl + 1);
+    let line = input[..line_start].matches('\n').count();
+    let column = input[line_start..index].chars().count();
+
+    (line + 1, column + column_offset + 1)
+}
+
+/// Text form of the parse error with source snippet
+///
+/// This is the same format as with the winnow's parse error of multi-line input.
+pub(crate) fn parse_report(input: &str, offset: usize, error: &impl Display) -> String {
+    let (line, column) = line_column(input, offset);
+    let gutter = " ".repeat(line.to_string().len());
+    let content = input.split('\n').nth(line - 1).unwrap_or_default();
+    format!(
+        "parse error at line {line}, column {column}\n\
+         {gutter} |\n\
+         {line} | {content}\n\
+         {gutter} | {}^\n\
+         {error}",
+        " ".repeat(column - 1)
+    )
+}
+
+/// Byte range of the char at the offset, empty at the end of input
+pub(crate) fn char_span(input: &str, offset: usize) -> Span {
+    let end = input
+        .get(offset..)
+        .and_then(|s| s.chars().next())
+        .map_or(offset, |c| offset + c.len_utf8());
+    Span { start: offset, end }
+}
+
+impl Error for Diagnostic {}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let text = self.report.as_deref().unwrap_or(&self.message);
+        // Position is part of the report, if there is one
+        let position = match (&self.report, self.line, self.column) {
+            (None, Some(line), Some(column)) => Some((line, column)),
+            _ => None,
+        };
+        let path_position = position.map_or(String::new(), |(l, c)| format!(":{l}:{c}"));
+        match (&self.git, &self.path) {
+            (Some(git), path) => write!(
+                f,
+                "\
+                GIT: Error while processing git object\n\
+                \x20  commit id: {}\n\
+                \x20  object id: {}\n\
+                \x20  path: {}{}\n\
+                \x20  msg: {}\
+                ",
+                git.commit,
+                git.object,
+                path.as_deref().unwrap_or_default(),
+                path_position,
+                text
+            ),
+            (None, Some(path)) => write!(f, "{text}\n   path: {path}{path_position}"),
+            (None, None) => match position {
+                Some((line, column)) => write!(f, "{text}\n   at line {line}, column {column}"),
+                None => write!(f, "{text}"),
+            },
+        }
+    }
+}
+
+/// List of diagnostics, e.g. all errors of the journal in check mode
+#[derive(Debug, Clone)]
+pub struct Diagnostics(pub Vec<Diagnostic>);
+
+impl Diagnostics {
+    /// Find all diagnostics of the error
+    ///
+    /// Diagnostics are searched from the error and its sources,
+    /// and if there is no list of diagnostics, then the result is
+    /// the single diagnostic of the error, see [`Diagnostic::from_error`].
+    #[must_use]
+    pub fn from_error(err: &(dyn Error + 'static)) -> Diagnostics {
+        std::iter::successors(Some(err), |&e| e.source())
+            .find_map(|e| e.downcast_ref::<Diagnostics>())
+            .cloned()
+            .unwrap_or_else(|| Diagnostics(vec![Diagnostic::from_error(err)]))
+    }
+}
+
+impl Error for Diagnostics {}
+
+impl Diagnostics {
+    /// Count of diagnostics with error severity
+    #[must_use]
+    pub fn error_count(&self) -> usize {
+        self.0
+            .iter()
+            .filter(|d| d.severity == Severity::Error)
+            .count()
+    }
+}
+
+impl Display for Diagnostics {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        fn plural(count: usize, noun: &str) -> String {
+            format!("{count} {noun}{}", if count == 1 { "" } else { "s" })
+        }
+        for diag in &self.0 {
+            if diag.severity == Severity::Warning {
+                write!(f, "warning[{}]: ", diag.code)?;
+            }
+            write!(f, "{diag}\n\n")?;
+        }
+        let errors = self.error_count();
+        let warnings = self.0.len() - errors;
+        if warnings == 0 {
+            write!(f, "Found {}", plural(errors, "error"))
+        } else {
+            write!(
+                f,
+                "Found {} and {}",
+                plural(errors, "error"),
+                plural(warnings, "warning")
+            )
+        }
+    }
+}
+
+/// Diagnostics output in JSON format
+#[derive(Debug, Serialize)]
+pub struct DiagnosticReport<'a> {
+    pub diagnostics: &'a [Diagnostic],
+}
+
+impl DiagnosticReport<'_> {
+    /// # Errors
+    /// Returns `Err` in case of IO error
+    pub fn write_json<W: io::Write>(&self, writer: &mut W) -> Result<(), tackler::Error> {
+        serde_json::to_writer_pretty(&mut *writer, self)?;
+        writeln!(writer)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    // test: 3f8a6c2e-1b4d-4e7a-9c5f-0d2b4e6a8c1f
+    fn line_and_column() {
+        let input = "ab\ncdä f\n";
+        assert_eq!(line_column(input, 0), (1, 1));
+        assert_eq!(line_column(input, 3), (2, 1));
+        // 'f' is after two-byte char
+        assert_eq!(line_column(input, 8), (2, 5));
+        assert_eq!(line_column(input, input.len()), (2, 7));
+        assert_eq!(line_column("", 0), (1, 1));
+    }
+
+    #[test]
+    // test: 6d0b8e4a-2c5f-4a9b-8e1d-3f5a7c9e1b2d
+    fn diagnostic_from_error() {
+        let err: tackler::Error = Diagnostic::error(UNBALANCED_TXN, "TXN postings do not zero: 1")
+            .with_help("help")
+            .into();
+        let diag = Diagnostic::from_error(err.as_ref());
+        assert_eq!(diag.code, UNBALANCED_TXN);
+        assert_eq!(diag.to_string(), "TXN postings do not zero: 1");
+
+        let err: tackler::Error = "plain error".into();
+        let diag = Diagnostic::from_error(err.as_ref());
+        assert_eq!(diag.code, ERROR);
+        assert_eq!(diag.message, "plain error");
+    }
+
+    #[test]
+    // test: 9b2e4f6a-7c1d-4e3b-a8f5-2d4c6e8a0b1f
+    fn diagnostics_from_error() {
+        let err: tackler::Error = Diagnostics(vec![
+            Diagnostic::error(PARSE_ERROR, "first"),
+            Diagnostic::error(DUPLICATE_UUID, "second"),
+        ])
+        .into();
+        let diags = Diagnostics::from_error(err.as_ref());
+        assert_eq!(diags.0.len(), 2);
+        assert_eq!(diags.0[1].code, DUPLICATE_UUID);
+        assert_eq!(err.to_string(), "first\n\nsecond\n\nFound 2 errors");
+
+        let err: tackler::Error = Diagnostic::error(ZERO_POSTING, "zero").into();
+        let diags = Diagnostics::from_error(err.as_ref());
+        assert_eq!(diags.0.len(), 1);
+        assert_eq!(diags.0[0].code, ZERO_POSTING);
+    }
+}
@@ -15,8 +15,10 @@ pub use identity_exporter::IdentityExporter;
 use tackler_rs::create_output_file;
 
 pub use accounts_exporter::AccountsExporter;
+pub use beancount_exporter::BeancountExporter;
 
 mod accounts_exporter;
+mod beancount_exporter;
 mod equity_exporter;
 mod identity_exporter;
 
@@ -41,7 +43,7 @@ pub fn write_exports<ProgW: io::Write + ?Sized>(
     output_name: &str,
     exports: &Vec<ExportType>,
     txn_set: &TxnSet<'_>,
-    settings: &mut Settings,
+    settings: &Settings,
     prog_writer: &mut Option<Box<ProgW>>,
 ) -> Result<(), tackler::Error> {
     for e in exports {
@@ -77,6 +79,15 @@ pub fn write_exports<ProgW: io::Write + ?Sized>(
                     writeln!(p, "{:>21} : {}", "Accounts Export", path)?;
                 }
             }
+            ExportType::Beancount => {
+                let bc_exporter = BeancountExporter {};
+                let (mut out_writer, path) =
+                    create_output_file(output_dir, output_name, "beancount", "beancount")?;
+                bc_exporter.write_export(settings, &mut out_writer, txn_set)?;
+                if let Some(p) = prog_writer.as_mut() {
+                    writeln!(p, "{:>21} : {}", "Beancount Export", path)?;
+                }
+            }
         }
     }
 
@@ -2,17 +2,18 @@
  * Tackler-NG 2026
  * SPDX-License-Identifier: Apache-2.0
  */
-use crate::model::AccountTreeNode;
+use crate::model::{AccountLifecycle, AccountPeriod, AccountTreeNode};
 use crate::{export::Export, kernel::Settings, model::TxnSet, tackler};
 use std::sync::Arc;
 use std::{collections::BTreeSet, io};
+use tackler_api::txn_ts;
 
 pub struct AccountsExporter {}
 
 impl Export for AccountsExporter {
     fn write_export<W: io::Write + ?Sized>(
         &self,
-        _cfg: &Settings,
+        cfg: &Settings,
         writer: &mut W,
         txn_data: &TxnSet<'_>,
     ) -> Result<(), tackler::Error> {
@@ -28,12 +29,60 @@ impl Export for AccountsExporter {
             }
         }
 
+        // Accounts with lifecycle attributes are exported as `[[account]]` blocks (TEP-1017)
+        let (specs, plain): (Vec<_>, Vec<_>) = accounts
+            .into_iter()
+            .map(|atn| {
+                let lc = cfg.get_account_lifecycle(atn.account.as_str());
+                (atn, lc)
+            })
+            .partition(|(_, lc)| lc.is_some());
+
         writeln!(writer, "accounts = [")?;
 
-        for i in accounts {
+        for (i, _) in plain {
             writeln!(writer, "   \"{i}\",")?;
         }
         writeln!(writer, "]")?;
+
+        for (atn, lc) in specs {
+            if let Some(lc) = lc {
+                write_account_spec(writer, &atn, lc)?;
+            }
+        }
         Ok(())
     }
 }
+
+fn write_account_spec<W: io::Write + ?Sized>(
+    writer: &mut W,
+    atn: &AccountTreeNode,
+    lc: &AccountLifecycle,
+) -> Result<(), tackler::Error> {
+    let write_block = |writer: &mut W, period: Option<&AccountPeriod>| {
+        writeln!(writer)?;
+        writeln!(writer, "[[account]]")?;
+        writeln!(writer, "name = \"{atn}\"")?;
+        if let Some(p) = period {
+            if let Some(open) = &p.open {
+                writeln!(writer, "open = {}", txn_ts::rfc_3339(open))?;
+            }
+            if let Some(close) = &p.close {
+                writeln!(writer, "close = {}", txn_ts::rfc_3339(close))?;
+            }
+        }
+        if let Some(kind) = lc.kind {
+            writeln!(writer, "kind = \"{kind}\"")?;
+        }
+        Ok::<(), io::Error>(())
+    };
+
+    if lc.periods().is_empty() {
+        write_block(writer, None)?;
+    } else {
+        for p in lc.periods() {
+            write_block(writer, Some(p))?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,535 @@
+/*
+ * Tackler-NG 2026
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Beancount export
+//!
+//! Accounts, commodities, transactions and price entries are
+//! translated to Beancount syntax, so that the books can be used
+//! with Beancount tools (e.g. Fava).
+//!
+//! Account names must be under one of Beancount's root accounts
+//! (`Assets`, `Liabilities`, `Equity`, `Income`, `Expenses`),
+//! and all postings must have a commodity. Closing postings of lots
+//! are booked with Beancount's booking method, and realized gains
+//! are posted to the gains account (`export.beancount.gains-account`).
+//!
+//! Balance assertions are not exported, because Beancount's
+//! `balance` directive has different semantics.
+
+use crate::config::LotMatching;
+use crate::export::Export;
+use crate::kernel::Settings;
+use crate::model::{Commodity, Posting, Transaction, TxnSet};
+use crate::tackler;
+use itertools::Itertools;
+use jiff::civil::Date;
+use rust_decimal::Decimal;
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, HashMap};
+use std::io;
+
+const ROOT_ACCOUNTS: [&str; 5] = ["Assets", "Liabilities", "Equity", "Income", "Expenses"];
+
+#[derive(Debug, Clone)]
+pub struct BeancountExporter {}
+
+impl Export for BeancountExporter {
+    fn write_export<W: io::Write + ?Sized>(
+        &self,
+        cfg: &Settings,
+        writer: &mut W,
+        txn_data: &TxnSet<'_>,
+    ) -> Result<(), tackler::Error> {
+        let gains_account = to_bc_account(&cfg.export.beancount.gains_account)?;
+
+        let mut commodities = BTreeMap::new();
+        let mut accounts = BTreeMap::new();
+        let mut lots = HashMap::new();
+        let mut txns = Vec::new();
+
+        for txn in &txn_data.txns {
+            let date = txn.header.timestamp.date();
+            let mut posts = Vec::new();
+            let mut has_closing = false;
+
+            for p in &txn.posts {
+                let account = add_account(&mut accounts, &p.acctn.atn.account, date)?;
+
+                for comm in [Some(&*p.acctn.comm), Some(&*p.txn_commodity)]
+                    .into_iter()
+                    .chain([p.cost.as_ref().map(|c| &*c.commodity)])
+                    .flatten()
+                    .filter(|c| c.is_any())
+                {
+                    add_commodity(&mut commodities, comm, date)?;
+                }
+
+                let is_closing = track_lots(&mut lots, p);
+                has_closing |= is_closing;
+                posts.push(posting(p, &account, is_closing)?);
+            }
+
+            if has_closing {
+                add_account(&mut accounts, &cfg.export.beancount.gains_account, date)?;
+                posts.push(format!("  {gains_account}"));
+            }
+            txns.push((txn, posts));
+        }
+
+        for pe in &cfg.price.price_db {
+            let date = pe.timestamp.date();
+            add_commodity(&mut commodities, &pe.base_commodity, date)?;
+            add_commodity(&mut commodities, &pe.eq_commodity, date)?;
+        }
+
+        let booking = match cfg.get_lot_matching() {
+            LotMatching::Fifo => "FIFO",
+            LotMatching::Lifo => "LIFO",
+        };
+        writeln!(writer, "option \"booking_method\" \"{booking}\"")?;
+        if let Some(c) = cfg.get_report_commodity() {
+            writeln!(
+                writer,
+                "option \"operating_currency\" \"{}\"",
+                to_bc_commodity(&c.name)?
+            )?;
+        }
+
+        writeln!(writer)?;
+        for (name, (date, orig)) in &commodities {
+            writeln!(writer, "{date} commodity {name}")?;
+            if name != orig {
+                writeln!(writer, "  name: \"{}\"", escape(orig))?;
+            }
+        }
+
+        writeln!(writer)?;
+        for (account, (date, _)) in &accounts {
+            writeln!(writer, "{date} open {account}")?;
+        }
+
+        if !cfg.price.price_db.is_empty() {
+            writeln!(writer)?;
+        }
+        for pe in &cfg.price.price_db {
+            writeln!(
+                writer,
+                "{} price {} {} {}",
+                pe.timestamp.date(),
+                to_bc_commodity(&pe.base_commodity.name)?,
+                pe.eq_amount,
+                to_bc_commodity(&pe.eq_commodity.name)?
+            )?;
+        }
+
+        for (txn, posts) in txns {
+            writeln!(writer)?;
+            write_txn(writer, txn, &posts)?;
+        }
+        Ok(())
+    }
+}
+
+fn write_txn<W: io::Write + ?Sized>(
+    writer: &mut W,
+    txn: &Transaction,
+    posts: &[String],
+) -> Result<(), tackler::Error> {
+    let hdr = &txn.header;
+    let tags = hdr
+        .tags
+        .as_ref()
+        .filter(|tags| !tags.is_empty())
+        .map(|tags| tags.iter().map(|t| to_bc_tag(t)).join(" #"))
+        .map(|tags| format!(" #{tags}"))
+        .unwrap_or_default();
+
+    writeln!(
+        writer,
+        "{} * \"{}\"{}",
+        hdr.timestamp.date(),
+        escape(hdr.description.as_deref().unwrap_or_default()),
+        tags
+    )?;
+    if let Some(code) = &hdr.code {
+        writeln!(writer, "  code: \"{}\"", escape(code))?;
+    }
+    if let Some(uuid) = &hdr.uuid {
+        writeln!(writer, "  uuid: \"{uuid}\"")?;
+    }
+    if let Some(extid) = &hdr.extid {
+        writeln!(writer, "  extid: \"{}\"", escape(extid))?;
+    }
+    if let Some(location) = &hdr.location {
+        writeln!(writer, "  location: \"{location}\"")?;
+    }
+    for c in hdr.comments.iter().flatten() {
+        writeln!(writer, "  ; {c}")?;
+    }
+    for p in posts {
+        writeln!(writer, "{p}")?;
+    }
+    Ok(())
+}
+
+/// Track net quantity of lots by account and commodity
+///
+/// Returns true, if the posting closes lots.
+fn track_lots(lots: &mut HashMap<(String, String), Decimal>, p: &Posting) -> bool {
+    let key = (p.acctn.atn.account.clone(), p.acctn.comm.name.clone());
+    let open = lots.get(&key).copied().unwrap_or_default();
+
+    let is_closing = !open.is_zero() && open.is_sign_positive() != p.amount.is_sign_positive();
+    if is_closing || p.cost.is_some() {
+        lots.insert(key, open + p.amount);
+    }
+    is_closing
+}
+
+fn posting(p: &Posting, account: &str, is_closing: bool) -> Result<String, tackler::Error> {
+    if !p.acctn.comm.is_any() {
+        let msg = format!(
+            "Beancount: posting of account '{}' doesn't have commodity, \
+            Beancount needs commodity for all postings",
+            p.acctn.atn.account
+        );
+        return Err(msg.into());
+    }
+    let comm = to_bc_commodity(&p.acctn.comm.name)?;
+
+    let cost = match &p.cost {
+        Some(c) => format!(" {{{} {}}}", c.amount, to_bc_commodity(&c.commodity.name)?),
+        None if is_closing => " {}".to_string(),
+        None => String::new(),
+    };
+
+    let is_valued_at_cost = p.cost.as_ref().is_some_and(|c| {
+        !p.is_total_amount
+            && c.commodity.name == p.txn_commodity.name
+            && c.amount * p.amount == p.txn_amount
+    });
+    let price = if p.txn_commodity.name == p.acctn.comm.name || is_valued_at_cost {
+        String::new()
+    } else {
+        let txn_comm = to_bc_commodity(&p.txn_commodity.name)?;
+        if p.is_total_amount {
+            format!(" @@ {} {txn_comm}", p.txn_amount.abs())
+        } else {
+            format!(" @ {} {txn_comm}", p.txn_amount / p.amount)
+        }
+    };
+
+    let comment = p
+        .comment
+        .as_ref()
+        .map(|c| format!(" ; {c}"))
+        .unwrap_or_default();
+
+    Ok(format!(
+        "  {account}  {} {comm}{cost}{price}{comment}",
+        p.amount
+    ))
+}
+
+fn add_commodity(
+    commodities: &mut BTreeMap<String, (Date, String)>,
+    comm: &Commodity,
+    date: Date,
+) -> Result<(), tackler::Error> {
+    let name = to_bc_commodity(&comm.name)?;
+    match commodities.entry(name.clone()) {
+        Entry::Occupied(mut e) => {
+            let (first, orig) = e.get_mut();
+            if *orig != comm.name {
+                let msg = format!(
+                    "Beancount: commodities '{orig}' and '{}' are both exported as '{name}'",
+                    comm.name
+                );
+                return Err(msg.into());
+            }
+            *first = (*first).min(date);
+        }
+        Entry::Vacant(e) => {
+            e.insert((date, comm.name.clone()));
+        }
+    }
+    Ok(())
+}
+
+/// Add account to the opened accounts, and return its Beancount name
+///
+/// Different accounts which have the same Beancount name are rejected.
+fn add_account(
+    accounts: &mut BTreeMap<String, (Date, String)>,
+    account: &str,
+    date: Date,
+) -> Result<String, tackler::Error> {
+    let name = to_bc_account(account)?;
+    match accounts.entry(name.clone()) {
+        Entry::Occupied(e) => {
+            let (_, orig) = e.get();
+            if orig != account {
+                let msg = format!(
+                    "Beancount: accounts '{orig}' and '{account}' are both exported as '{name}'"
+                );
+                return Err(msg.into());
+            }
+        }
+        Entry::Vacant(e) => {
+            e.insert((date, account.to_string()));
+        }
+    }
+    Ok(name)
+}
+
+/// Beancount account name
+///
+/// Root account is matched case-insensitively, and invalid characters
+/// of account name components are replaced with `-`. Components
+/// must start with a letter or a digit.
+fn to_bc_account(account: &str) -> Result<String, tackler::Error> {
+    let mut components = account.split(':');
+    let root = components.next().unwrap_or_default();
+    let Some(bc_root) = ROOT_ACCOUNTS.iter().find(|r| r.eq_ignore_ascii_case(root)) else {
+        let msg = format!(
+            "Beancount: account '{account}' must be under one of the root accounts: {}",
+            ROOT_ACCOUNTS.join(", ")
+        );
+        return Err(msg.into());
+    };
+
+    if let Some(c) = components
+        .clone()
+        .find(|c| !c.starts_with(char::is_alphanumeric))
+    {
+        let msg = format!(
+            "Beancount: account '{account}' can't be converted to Beancount account, \
+            sub-account '{c}' must start with a letter or a digit"
+        );
+        return Err(msg.into());
+    }
+
+    let sub_accounts = components.map(|c| {
+        c.chars()
+            .enumerate()
+            .flat_map(|(i, ch)| {
+                let ch = if ch.is_alphanumeric() || (i > 0 && ch == '-') {
+                    ch
+                } else {
+                    '-'
+                };
+                if i == 0 {
+                    ch.to_uppercase().collect::<Vec<_>>()
+                } else {
+                    vec![ch]
+                }
+            })
+            .collect::<String>()
+    });
+
+    Ok(std::iter::once(bc_root.to_string())
+        .chain(sub_accounts)
+        .collect::<Vec<_>>()
+        .join(":"))
+}
+
+/// Beancount commodity name
+///
+/// Name is converted to upper case, and `·` is replaced with `.`
+fn to_bc_commodity(name: &str) -> Result<String, tackler::Error> {
+    let bc_name = name.to_uppercase().replace('·', ".");
+
+    let chars: Vec<char> = bc_name.chars().collect();
+    let is_valid = match chars.as_slice() {
+        [first] => first.is_ascii_uppercase(),
+        [first, middle @ .., last] => {
+            chars.len() <= 24
+                && first.is_ascii_uppercase()
+                && (last.is_ascii_uppercase() || last.is_ascii_digit())
+                && middle
+                    .iter()
+                    .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || "'._-".contains(*c))
+        }
+        [] => false,
+    };
+    if is_valid {
+        Ok(bc_name)
+    } else {
+        let msg =
+            format!("Beancount: commodity '{name}' can't be converted to Beancount commodity");
+        Err(msg.into())
+    }
+}
+
+/// Beancount tag, hierarchy separator `:` is replaced with `/`
+fn to_bc_tag(tag: &str) -> String {
+    tag.chars()
+        .map(|c| match c {
+            ':' => '/',
+            c if c.is_ascii_alphanumeric() || "-_/.".contains(c) => c,
+            _ => '-',
+        })
+        .collect()
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+    use indoc::indoc;
+    use tackler_rs::IndocUtils;
+
+    fn export(txns_str: &str, settings: &Settings) -> Result<String, tackler::Error> {
+        let txn_data = parser::string_to_txns(&mut { txns_str }, settings)?;
+        let txn_set = txn_data.get_all()?;
+        let mut out = Vec::new();
+        BeancountExporter {}.write_export(settings, &mut out, &txn_set)?;
+        Ok(String::from_utf8(out).unwrap(/*:test:*/))
+    }
+
+    #[test]
+    // test: 6b2e8f4a-1c3d-4e5f-9a7b-0d8c2e4f6a1b
+    // desc: accounts, commodities, metadata, tags, prices and lots are exported
+    fn beancount_export() {
+        #[rustfmt::skip]
+        let txns_str = indoc!(
+           "|2024-01-02T10:00:00Z (001) 'Buy \"shares\"
+            | # uuid: 9d2e2a5c-3c6e-4f0b-8a16-1c0a9e7b5d3f
+            | # location: geo:60.17,24.94
+            | # tags: shares:buy, tax·2024
+            | ; first purchase
+            | Assets:Broker:ACME  10 ACME {100 EUR}
+            | Assets:bank·account  -1000 EUR
+            |
+            |2024-03-04 'Sell
+            | # ext-id: bank-42
+            | Assets:Broker:ACME  -4 ACME @ 120 EUR
+            | Assets:bank·account  480 EUR ; sold
+            |
+            |2024-03-05 'Exchange
+            | Expenses:Travel  100 USD = 90 EUR
+            | Assets:bank·account  -90 EUR
+            |"
+        ).strip_margin();
+
+        #[rustfmt::skip]
+        let reference = indoc!(
+           "|option \"booking_method\" \"FIFO\"
+            |
+            |2024-01-02 commodity ACME
+            |2024-01-02 commodity EUR
+            |2024-03-05 commodity USD
+            |
+            |2024-01-02 open Assets:Bank-account
+            |2024-01-02 open Assets:Broker:ACME
+            |2024-03-05 open Expenses:Travel
+            |2024-03-04 open Income:Capital-Gains
+            |
+            |2024-01-02 * \"Buy \\\"shares\\\"\" #shares/buy #tax-2024
+            |  code: \"001\"
+            |  uuid: \"9d2e2a5c-3c6e-4f0b-8a16-1c0a9e7b5d3f\"
+            |  location: \"geo:60.17,24.94\"
+            |  ; first purchase
+            |  Assets:Broker:ACME  10 ACME {100 EUR}
+            |  Assets:Bank-account  -1000 EUR
+            |
+            |2024-03-04 * \"Sell\"
+            |  extid: \"bank-42\"
+            |  Assets:Broker:ACME  -4 ACME {} @ 120 EUR
+            |  Assets:Bank-account  480 EUR ; sold
+            |  Income:Capital-Gains
+            |
+            |2024-03-05 * \"Exchange\"
+            |  Expenses:Travel  100 USD @@ 90 EUR
+            |  Assets:Bank-account  -90 EUR
+            |"
+        ).strip_margin();
+
+        let settings = Settings::default();
+        let res = export(&txns_str, &settings).unwrap(/*:test:*/);
+        assert_eq!(res, reference);
+    }
+
+    #[test]
+    // test: 2f4a6c8e-0b1d-4c3e-8f5a-7b9d1e3f5a2c
+    // desc: accounts and commodities which can't be exported are rejected
+    fn beancount_errors() {
+        let settings = Settings::default();
+        let cases = [
+            (
+                "2024-01-01\n Assets:a  1 EUR\n Pocket  -1 EUR\n",
+                "Beancount: account 'Pocket' must be under one of the root accounts: \
+                Assets, Liabilities, Equity, Income, Expenses",
+            ),
+            (
+                "2024-01-01\n Assets:a  1\n Income:b  -1\n",
+                "Beancount: posting of account 'Assets:a' doesn't have commodity, \
+                Beancount needs commodity for all postings",
+            ),
+            (
+                "2024-01-01\n Assets:a  1 €\n Income:b  -1 €\n",
+                "Beancount: commodity '€' can't be converted to Beancount commodity",
+            ),
+            (
+                "2024-01-01\n Expenses:$misc  1 EUR\n Assets:a  -1 EUR\n",
+                "Beancount: account 'Expenses:$misc' can't be converted to Beancount account, \
+                sub-account '$misc' must start with a letter or a digit",
+            ),
+            (
+                "2024-01-01\n Assets:a·b  1 EUR\n Assets:a-b  -1 EUR\n",
+                "Beancount: accounts 'Assets:a·b' and 'Assets:a-b' are both exported as 'Assets:A-b'",
+            ),
+            (
+                "2024-01-01\n Assets:a  1 EUR\n assets:a  -1 EUR\n",
+                "Beancount: accounts 'Assets:a' and 'assets:a' are both exported as 'Assets:A'",
+            ),
+            (
+                "2024-01-01\n Assets:a  1 EUR\n Assets:b  -1 EUR\n\n2024-01-02\n Assets:a  1 eur\n Assets:b  -1 eur\n",
+                "Beancount: commodities 'EUR' and 'eur' are both exported as 'EUR'",
+            ),
+        ];
+        for (txns_str, msg) in cases {
+            let res = export(txns_str, &settings);
+            assert_eq!(res.err().map(|e| e.to_string()), Some(msg.to_string()));
+        }
+    }
+
+    #[test]
+    // test: 7c1e3a5d-9f2b-4d6e-8a0c-4b6d8f0a2c4e
+    // desc: account and commodity names are mapped to Beancount names
+    fn beancount_names() {
+        let accounts = [
+            ("assets:bank", "Assets:Bank"),
+            ("EXPENSES:food", "Expenses:Food"),
+            ("Assets:bank·account", "Assets:Bank-account"),
+            ("Assets:bank-account", "Assets:Bank-account"),
+            ("Income:2024:äö", "Income:2024:Äö"),
+            ("Assets:a_b:c d", "Assets:A-b:C-d"),
+        ];
+        for (account, bc_account) in accounts {
+            assert_eq!(to_bc_account(account).unwrap(/*:test:*/), bc_account);
+        }
+        for account in ["Assets:$a", "Assets:a:°b", "Pocket:a"] {
+            assert!(to_bc_account(account).is_err(), "{account}");
+        }
+
+        let commodities = [
+            ("EUR", "EUR"),
+            ("acme", "ACME"),
+            ("TCKLR·4", "TCKLR.4"),
+            ("X_1-A'B", "X_1-A'B"),
+        ];
+        for (comm, bc_comm) in commodities {
+            assert_eq!(to_bc_commodity(comm).unwrap(/*:test:*/), bc_comm);
+        }
+        for comm in ["€", "1EUR", "EUR·", "ABCDEFGHIJKLMNOPQRSTUVWXY"] {
+            assert!(to_bc_commodity(comm).is_err(), "{comm}");
+        }
+    }
+}
@@ -78,6 +78,38 @@ impl Export for EquityExporter {
             cfg,
         )?;
 
+        // Equity txn is at the time of the last txn, so it must not
+        // have postings to accounts which are closed at that time
+        if let Some(txn) = txn_data.txns.last() {
+            let ts = &txn.header.timestamp;
+            let closed = bal
+                .bal
+                .iter()
+                .filter(|btn| !cfg.is_reported_account(&btn.acctn.atn.account, ts, ts))
+                .map(|btn| {
+                    let comm = &btn.acctn.comm;
+                    format!(
+                        "'{}' ({}{})",
+                        btn.acctn.atn.account,
+                        btn.account_sum,
+                        if comm.is_any() {
+                            format!(" {}", comm.name)
+                        } else {
+                            String::new()
+                        }
+                    )
+                })
+                .collect::<Vec<_>>();
+            if !closed.is_empty() {
+                let msg = format!(
+                    "Equity: accounts are closed at the time of equity txn ({}), but their balance is not zero: {}. Use '--include-closed' to export them.",
+                    rfc_3339(ts),
+                    closed.join(", ")
+                );
+                return Err(msg.into());
+            }
+        }
+
         if bal.is_empty() {
             // todo: log warning that equity transaction is empty and ask to check account selector
             return Ok(());
@@ -234,6 +234,7 @@ mod tests {
                 comments: None,
             },
             posts: vec![],
+            ..Default::default()
         }
     }
 
@@ -258,6 +259,7 @@ mod tests {
                 comments: None,
             },
             posts: vec![],
+            ..Default::default()
         }
     }
 
@@ -274,6 +276,7 @@ mod tests {
                 comments: None,
             },
             posts: vec![],
+            ..Default::default()
         }
     }
 
@@ -290,6 +293,7 @@ mod tests {
                 comments: None,
             },
             posts: vec![],
+            ..Default::default()
         }
     }
 
@@ -306,6 +310,7 @@ mod tests {
                 comments: None,
             },
             posts: vec![],
+            ..Default::default()
         }
     }
 
@@ -322,6 +327,7 @@ mod tests {
                 comments: None,
             },
             posts: vec![],
+            ..Default::default()
         }
     }
 
@@ -338,6 +344,7 @@ mod tests {
                 comments: None,
             },
             posts: vec![],
+            ..Default::default()
         }
     }
 
@@ -355,6 +362,7 @@ mod tests {
                     .map(|comments| comments.iter().map(|t| str::to_string(*t)).collect()),
             },
             posts: vec![],
+            ..Default::default()
         }
     }
 
@@ -366,7 +374,7 @@ mod tests {
             comm: Arc::new(Commodity::default()),
         };
 
-        let e_p = Posting::from(e_txntn, e_v, e_v, false, Arc::new(Commodity::default()), Some("comment".to_string())).unwrap(/*:test:*/);
+        let e_p = Posting::from(e_txntn, e_v, e_v, false, Arc::new(Commodity::default()), None, Some("comment".to_string())).unwrap(/*:test:*/);
 
         let a_v = Decimal::new(-e_value, 0);
         let a_acctn = Arc::new(AccountTreeNode::from(a).unwrap(/*:test:*/));
@@ -374,7 +382,7 @@ mod tests {
             atn: a_acctn,
             comm: Arc::new(Commodity::default()),
         };
-        let a_p = Posting::from(a_txntn, a_v, a_v, false, Arc::new(Commodity::default()), Some("comment".to_string())).unwrap(/*:test:*/);
+        let a_p = Posting::from(a_txntn, a_v, a_v, false, Arc::new(Commodity::default()), None, Some("comment".to_string())).unwrap(/*:test:*/);
 
         Transaction::try_from(
             TxnHeader {
@@ -418,6 +426,7 @@ mod tests {
             false,
             make_commodity(Some("txn_comm")),
             None,
+            None,
         )
         .unwrap(/*:test:*/);
 
@@ -434,6 +443,7 @@ mod tests {
             false,
             make_commodity(Some("txn_comm")),
             None,
+            None,
         )
         .unwrap(/*:test:*/);
 
@@ -40,7 +40,7 @@ mod tests {
             comm: Arc::new(Commodity::default()),
         };
 
-        let e_p = Posting::from(e_txntn, e_v, e_v, false, Arc::new(Commodity::default()), comment.map(str::to_string)).unwrap(/*:test:*/);
+        let e_p = Posting::from(e_txntn, e_v, e_v, false, Arc::new(Commodity::default()), None, comment.map(str::to_string)).unwrap(/*:test:*/);
 
         let a_v = Decimal::new(-a_value, 0);
         let a_acctn = Arc::new(AccountTreeNode::from(a).unwrap(/*:test:*/));
@@ -48,7 +48,7 @@ mod tests {
             atn: a_acctn,
             comm: Arc::new(Commodity::default()),
         };
-        let a_p = Posting::from(a_txntn, a_v, a_v, false, Arc::new(Commodity::default()), None).unwrap(/*:test:*/);
+        let a_p = Posting::from(a_txntn, a_v, a_v, false, Arc::new(Commodity::default()), None, None).unwrap(/*:test:*/);
 
         Transaction::try_from(TxnHeader::default(), vec![e_p, a_p]).unwrap(/*:test:*/)
     }
@@ -0,0 +1,316 @@
+/*
+ * Tackler-NG 2026
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Canonical formatter of Tackler txn journal
+//!
+//! Journal is validated by the parser before it's formatted, and formatting
+//! is done line by line, so that comments, amounts and elided amounts
+//! of the last posting are preserved as they are written.
+//!
+//! Canonical form of the journal is:
+//! - Txns are separated by single empty line
+//! - Indentation is three spaces, and there is no trailing whitespace
+//! - Timestamps have no trailing zeros in fractional seconds, and UTC offset is `+00:00`
+//! - Metadata is in order: `uuid`, `ext-id`, `location`, `tags`
+//! - Posting amounts of the txn are aligned on the decimal point
+
+use crate::kernel::Settings;
+use crate::parser::tackler_parser;
+use crate::tackler;
+use itertools::Itertools;
+use std::fmt::Write;
+
+const INDENT: &str = "   ";
+
+/// Format txn journal (Tackler format) in canonical form
+///
+/// # Errors
+/// Returns `Err` if the journal is not valid
+pub fn format_txns(input: &str, settings: &Settings) -> Result<String, tackler::Error> {
+    tackler_parser::txns_text(&mut &*input, settings)?;
+
+    let txns = input
+        .lines()
+        .chunk_by(|line| line.trim().is_empty())
+        .into_iter()
+        .filter(|(is_blank, _)| !is_blank)
+        .map(|(_, lines)| format_txn(&lines.collect::<Vec<_>>()))
+        .collect::<Vec<_>>();
+
+    Ok(txns.join("\n"))
+}
+
+/// Order of metadata items
+fn meta_order(key: &str) -> usize {
+    match key {
+        "uuid" => 0,
+        "ext-id" => 1,
+        "location" => 2,
+        _ => 3,
+    }
+}
+
+/// Format txn, lines are the lines of a valid txn
+fn format_txn(lines: &[&str]) -> String {
+    let mut meta = Vec::new();
+    let mut comments = Vec::new();
+    let mut postings = Vec::new();
+    for line in &lines[1..] {
+        let line = line.trim();
+        if let Some(item) = line.strip_prefix('#') {
+            meta.push(format_meta(item));
+        } else if let Some(comment) = line.strip_prefix(';') {
+            comments.push(format_comment(comment));
+        } else {
+            postings.push(PostingLine::from(line));
+        }
+    }
+    meta.sort_by_key(|(key, _)| meta_order(key));
+
+    let mut txn = format_header(lines[0]);
+    txn.push('\n');
+    for (_, item) in meta {
+        let _ = writeln!(txn, "{INDENT}{item}");
+    }
+    for comment in comments {
+        let _ = writeln!(txn, "{INDENT}{comment}");
+    }
+    for posting in format_postings(&postings) {
+        let _ = writeln!(txn, "{INDENT}{posting}");
+    }
+    txn
+}
+
+/// Format txn header: `timestamp [(code)] ['description]`
+fn format_header(line: &str) -> String {
+    let line = line.trim();
+    let (ts, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+    let mut rest = rest.trim_start();
+    let mut header = format_timestamp(ts);
+
+    if let Some(code) = rest.strip_prefix('(') {
+        let (code, tail) = code.split_once(')').unwrap_or((code, ""));
+        let _ = write!(header, " ({})", code.trim());
+        rest = tail.trim_start();
+    }
+    if rest.starts_with('\'') {
+        header.push(' ');
+        header.push_str(rest);
+    }
+    header
+}
+
+/// Normalize timestamp: trailing zeros of fractional seconds
+/// are removed, and UTC is written as `+00:00`
+fn format_timestamp(ts: &str) -> String {
+    let Some((date, time)) = ts.split_once('T') else {
+        return ts.to_string();
+    };
+    let (clock, offset) = time
+        .find(['Z', '+', '-'])
+        .map_or((time, ""), |i| time.split_at(i));
+    let clock = match clock.split_once('.') {
+        Some((hms, frac)) => match frac.trim_end_matches('0') {
+            "" => hms.to_string(),
+            frac => format!("{hms}.{frac}"),
+        },
+        None => clock.to_string(),
+    };
+    let offset = match offset {
+        "Z" | "-00:00" => "+00:00",
+        offset => offset,
+    };
+    format!("{date}T{clock}{offset}")
+}
+
+/// Format metadata item, returns the key and the formatted item
+fn format_meta(item: &str) -> (String, String) {
+    let (key, value) = item.trim().split_once(':').unwrap_or((item.trim(), ""));
+    let value = if key == "tags" {
+        value.split(',').map(str::trim).join(", ")
+    } else {
+        value.trim().to_string()
+    };
+    (key.to_string(), format!("# {key}: {value}"))
+}
+
+/// Format comment, `comment` is the text after `;`
+///
+/// Leading spaces after the first one are part of the comment.
+fn format_comment(comment: &str) -> String {
+    let text = comment.strip_prefix(' ').unwrap_or(comment).trim_end();
+    if text.is_empty() {
+        ";".to_string()
+    } else {
+        format!("; {text}")
+    }
+}
+
+/// Posting line split into its parts
+struct PostingLine<'a> {
+    account: &'a str,
+    amount: Option<&'a str>,
+    /// Commodity, positions and balance assertion
+    tail: String,
+    comment: Option<String>,
+}
+
+impl<'a> From<&'a str> for PostingLine<'a> {
+    fn from(line: &'a str) -> Self {
+        let (body, comment) = match line.split_once(';') {
+            Some((body, comment)) => (body, Some(format_comment(comment))),
+            None => (line, None),
+        };
+        let mut tokens = body.split_whitespace().peekable();
+        let account = tokens.next().unwrap_or_default();
+        // Last posting without amount could have balance assertion
+        let amount = tokens.next_if(|t| *t != "==");
+        let tail = tokens.join(" ").replace("{ ", "{").replace(" }", "}");
+        PostingLine {
+            account,
+            amount,
+            tail,
+            comment,
+        }
+    }
+}
+
+/// Split amount into integer and fractional part (with the decimal point)
+fn split_amount(amount: &str) -> (&str, &str) {
+    amount
+        .find('.')
+        .map_or((amount, ""), |i| amount.split_at(i))
+}
+
+/// Format postings of the txn, with amounts aligned on the decimal point
+fn format_postings(postings: &[PostingLine<'_>]) -> Vec<String> {
+    let width = |f: fn(&PostingLine<'_>) -> usize| postings.iter().map(f).max().unwrap_or(0);
+    // Accounts of elided amounts are not aligned
+    let account_width = width(|p| p.amount.map_or(0, |_| p.account.chars().count()));
+    let int_width = width(|p| p.amount.map_or(0, |a| split_amount(a).0.len()));
+    let frac_width = width(|p| p.amount.map_or(0, |a| split_amount(a).1.len()));
+
+    postings
+        .iter()
+        .map(|p| {
+            let mut line = match p.amount {
+                Some(amount) => {
+                    let (int, frac) = split_amount(amount);
+                    let mut line = format!("{:account_width$}  {int:>int_width$}{frac}", p.account);
+                    if !p.tail.is_empty() {
+                        line.push_str(&" ".repeat(frac_width - frac.len() + 1));
+                        line.push_str(&p.tail);
+                    }
+                    line
+                }
+                None if p.tail.is_empty() => p.account.to_string(),
+                None => format!("{} {}", p.account, p.tail),
+            };
+            if let Some(comment) = &p.comment {
+                line.push(' ');
+                line.push_str(comment);
+            }
+            line
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+    use tackler_rs::IndocUtils;
+
+    #[test]
+    // test: 5b7d9f1a-3c5e-4a7b-9d1f-6e8a0c2e4b6d
+    // desc: format txns in canonical form
+    fn format_canonical() {
+        #[rustfmt::skip]
+        let input = indoc!(
+           "|
+            |
+            |2024-01-01T10:00:00.500Z  ( 101 )   'Gold\x20\x20
+            |  # tags:  b ,a
+            |  # uuid:   9b2e4f6a-7c1d-4e3b-a8f5-2d4c6e8a0b1f
+            |  ;   indented comment\x20
+            |  Assets:Gold   1.5 XAU  @   1500 EUR ; bought
+            | Assets:Bank:Checking -2250 EUR
+            |
+            |
+            |
+            |2024-01-02
+            |    e 1.25
+            |    a:long:account ;
+            |
+            |2024-01-03
+            |    e 1
+            |    a:long:account   ==   -2.25  ; reconcile
+            |"
+        ).strip_margin();
+
+        #[rustfmt::skip]
+        let expected = indoc!(
+           "|2024-01-01T10:00:00.5+00:00 (101) 'Gold
+            |   # uuid: 9b2e4f6a-7c1d-4e3b-a8f5-2d4c6e8a0b1f
+            |   # tags: b, a
+            |   ;   indented comment
+            |   Assets:Gold               1.5 XAU @ 1500 EUR ; bought
+            |   Assets:Bank:Checking  -2250   EUR
+            |
+            |2024-01-02
+            |   e  1.25
+            |   a:long:account ;
+            |
+            |2024-01-03
+            |   e  1
+            |   a:long:account == -2.25 ; reconcile
+            |"
+        ).strip_margin();
+
+        let settings = Settings::default();
+        let formatted = format_txns(&input, &settings).unwrap(/*:test:*/);
+        assert_eq!(formatted, expected);
+        // Formatting is idempotent
+        assert_eq!(
+            format_txns(&formatted, &settings).unwrap(/*:test:*/),
+            expected
+        );
+    }
+
+    #[test]
+    // test: 9f1b3d5a-7c9e-4b1d-8f3a-2c4e6a8b0d2f
+    // desc: timestamps and cost positions are normalized
+    fn format_timestamp_and_cost() {
+        assert_eq!(format_timestamp("2024-01-01"), "2024-01-01");
+        assert_eq!(
+            format_timestamp("2024-01-01T10:00:00"),
+            "2024-01-01T10:00:00"
+        );
+        assert_eq!(
+            format_timestamp("2024-01-01T10:00:00.000"),
+            "2024-01-01T10:00:00"
+        );
+        assert_eq!(
+            format_timestamp("2024-01-01T10:00:00.120-05:00"),
+            "2024-01-01T10:00:00.12-05:00"
+        );
+        assert_eq!(
+            format_timestamp("2024-01-01T10:00:00-00:00"),
+            "2024-01-01T10:00:00+00:00"
+        );
+
+        let p = PostingLine::from("a  2  XAU   {  1000 EUR }");
+        assert_eq!(format_postings(&[p]), vec!["a  2 XAU {1000 EUR}"]);
+    }
+
+    #[test]
+    // test: 3e5a7c9f-1b3d-4f5a-8c7e-0d2f4b6a8c1e
+    // desc: invalid journal is not formatted
+    fn format_invalid() {
+        let settings = Settings::default();
+        let res = format_txns("2024-01-01 'unbalanced\n e 1\n a 2\n", &settings);
+        assert!(res.is_err());
+    }
+}
@@ -0,0 +1,445 @@
+/*
+ * Tackler-NG 2026
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Import of bank statements
+//!
+//! Bank statement CSV is read by column layout of [`ImportMapping`],
+//! and [`ImportRules`] are used to select counter account, description
+//! and tags for each statement record.
+//!
+//! Each imported txn has a deterministic `ext-id` which is derived from
+//! the statement record, so re-importing the same statement produces
+//! identical txns and shard files.
+//!
+//! Price entries are imported with [`prices`].
+
+use crate::config::{ImportMapping, ImportRules, SignConvention};
+use crate::formatter::format_txns;
+use crate::kernel::Settings;
+use crate::kernel::hash::Hash;
+use crate::tackler;
+use jiff::civil::Date;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub mod prices;
+
+/// Length of hash prefixes used in ext-ids and file names
+const HASH_LEN: usize = 16;
+
+/// Txn created from a bank statement record
+#[derive(Debug, Clone)]
+pub struct ImportedTxn {
+    pub(crate) date: Date,
+    /// Change of the bank account
+    pub(crate) amount: Decimal,
+    pub(crate) description: String,
+    pub(crate) extid: String,
+    /// Txn in canonical form
+    pub(crate) text: String,
+}
+
+impl ImportedTxn {
+    #[must_use]
+    pub fn date(&self) -> Date {
+        self.date
+    }
+
+    /// Change of the bank account
+    #[must_use]
+    pub fn amount(&self) -> Decimal {
+        self.amount
+    }
+
+    #[must_use]
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    #[must_use]
+    pub fn extid(&self) -> &str {
+        &self.extid
+    }
+
+    /// Txn as canonical journal text
+    #[must_use]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Path of the txn shard, relative to the root of the txn shards
+    ///
+    /// Shard is stored as `YYYY/MM/DD/YYYYMMDD-<hash of ext-id>.txn`
+    #[must_use]
+    pub fn path(&self) -> PathBuf {
+        let hash = hash_prefix(std::slice::from_ref(&self.extid));
+        PathBuf::from(format!(
+            "{:04}/{:02}/{:02}/{}-{hash}.txn",
+            self.date.year(),
+            self.date.month(),
+            self.date.day(),
+            self.date.strftime("%Y%m%d"),
+        ))
+    }
+}
+
+/// Result of writing imported txns
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ImportSummary {
+    /// Count of new txn shards
+    pub new: usize,
+    /// Count of txns which were already imported
+    pub existing: usize,
+    /// Count of probable duplicates which were skipped
+    pub skipped: usize,
+}
+
+fn hash_prefix(items: &[String]) -> String {
+    let mut hash = Hash::default().checksum(items, b"\x1f").value;
+    hash.truncate(HASH_LEN);
+    hash
+}
+
+/// Single line text, with all whitespace runs collapsed into one space
+fn single_line(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn field(record: &csv::StringRecord, column: usize, line: u64) -> Result<&str, tackler::Error> {
+    record
+        .get(column)
+        .map(str::trim)
+        .ok_or_else(|| format!("Missing column {} at statement line {line}", column + 1).into())
+}
+
+fn opt_field(
+    record: &csv::StringRecord,
+    column: Option<usize>,
+    line: u64,
+) -> Result<&str, tackler::Error> {
+    column.map_or(Ok(""), |c| field(record, c, line))
+}
+
+/// Parse amount with separators of the mapping, empty amount is zero
+fn parse_amount(s: &str, mapping: &ImportMapping, line: u64) -> Result<Decimal, tackler::Error> {
+    let mut amount: String = s
+        .chars()
+        .filter(|c| Some(*c) != mapping.thousands_separator && !c.is_whitespace())
+        .map(|c| {
+            if c == mapping.decimal_separator {
+                '.'
+            } else {
+                c
+            }
+        })
+        .collect();
+    if amount.is_empty() {
+        return Ok(Decimal::ZERO);
+    }
+    if amount.starts_with('+') {
+        amount.remove(0);
+    }
+    Decimal::from_str_exact(&amount)
+        .map_err(|err| format!("Invalid amount '{s}' at statement line {line}: {err}").into())
+}
+
+/// Change of the bank account by sign convention of the mapping
+fn record_amount(
+    record: &csv::StringRecord,
+    mapping: &ImportMapping,
+    line: u64,
+) -> Result<Decimal, tackler::Error> {
+    let columns = &mapping.columns;
+    let amount = |column| parse_amount(opt_field(record, column, line)?, mapping, line);
+    match mapping.sign {
+        SignConvention::Signed => amount(columns.amount),
+        SignConvention::Inverted => Ok(-amount(columns.amount)?),
+        SignConvention::DebitCredit => {
+            Ok(amount(columns.credit)?.abs() - amount(columns.debit)?.abs())
+        }
+    }
+}
+
+/// Import bank statement CSV
+///
+/// Records with zero amount are skipped.
+///
+/// # Errors
+/// Returns `Err` if statement can't be read, or if some of the records
+/// are invalid or can't be turned into a valid txn
+pub fn import_csv<R: io::Read>(
+    reader: R,
+    mapping: &ImportMapping,
+    rules: &ImportRules,
+) -> Result<Vec<ImportedTxn>, tackler::Error> {
+    let mut csv_reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .delimiter(mapping.delimiter)
+        .from_reader(reader);
+
+    let settings = Settings::default();
+    let mut seen = HashMap::<Vec<String>, usize>::new();
+    let mut txns = Vec::new();
+    for record in csv_reader.records().skip(mapping.skip_rows) {
+        let record = record?;
+        let line = record.position().map_or(0, csv::Position::line);
+
+        // Identical records are separated by their ordinal within the statement
+        let fields = record.iter().map(str::to_string).collect::<Vec<_>>();
+        let ordinal = seen.entry(fields.clone()).or_default();
+        *ordinal += 1;
+
+        if let Some(txn) =
+            import_record(&record, line, &fields, *ordinal, mapping, rules, &settings)?
+        {
+            txns.push(txn);
+        }
+    }
+    Ok(txns)
+}
+
+fn import_record(
+    record: &csv::StringRecord,
+    line: u64,
+    fields: &[String],
+    ordinal: usize,
+    mapping: &ImportMapping,
+    rules: &ImportRules,
+    settings: &Settings,
+) -> Result<Option<ImportedTxn>, tackler::Error> {
+    let columns = &mapping.columns;
+
+    let date_str = field(record, columns.date, line)?;
+    let date = jiff::fmt::strtime::parse(&mapping.date_format, date_str)
+        .and_then(|tm| tm.to_date())
+        .map_err(|err| format!("Invalid date '{date_str}' at statement line {line}: {err}"))?;
+
+    let amount = record_amount(record, mapping, line)?;
+    if amount.is_zero() {
+        return Ok(None);
+    }
+
+    let payee = single_line(opt_field(record, columns.payee, line)?);
+    let memo = single_line(opt_field(record, columns.memo, line)?);
+
+    let id = if let Some(c) = columns.id {
+        single_line(field(record, c, line)?)
+    } else {
+        let mut items = vec![mapping.account.clone()];
+        items.extend_from_slice(fields);
+        items.push(ordinal.to_string());
+        hash_prefix(&items)
+    };
+    let extid = format!("{}-{id}", mapping.extid_prefix);
+
+    let rule = rules.rules.iter().find(|r| r.is_match(&payee, &memo));
+    let account = rule.map_or(&rules.default_account, |r| &r.account);
+    let description = rule
+        .and_then(|r| r.description.clone())
+        .unwrap_or_else(|| payee.clone());
+
+    let mut txn = date.to_string();
+    if !description.is_empty() {
+        let _ = write!(txn, " '{description}");
+    }
+    let _ = writeln!(txn, "\n # ext-id: {extid}");
+    if let Some(r) = rule {
+        if !r.tags.is_empty() {
+            let _ = writeln!(txn, " # tags: {}", r.tags.join(", "));
+        }
+    }
+    if !memo.is_empty() {
+        let _ = writeln!(txn, " ; {memo}");
+    }
+    let _ = writeln!(
+        txn,
+        " {account} {}{}",
+        -amount,
+        mapping
+            .commodity
+            .as_ref()
+            .map_or(String::new(), |c| format!(" {c}"))
+    );
+    let _ = writeln!(txn, " {}", mapping.account);
+
+    let text = format_txns(&txn, settings)
+        .map_err(|err| format!("Invalid txn from statement line {line}: {err}\nTxn:\n{txn}"))?;
+
+    Ok(Some(ImportedTxn {
+        date,
+        amount,
+        description,
+        extid,
+        text,
+    }))
+}
+
+/// Write imported txns as txn shards under `output`
+///
+/// Txns which already have a shard are not written again, so
+/// importing the same statement twice doesn't change the journal.
+/// With `dry_run`, nothing is written.
+///
+/// # Errors
+/// Returns `Err` if shard can't be written
+pub fn write_txns(
+    txns: &[ImportedTxn],
+    output: &Path,
+    dry_run: bool,
+) -> Result<ImportSummary, tackler::Error> {
+    let mut summary = ImportSummary::default();
+    for txn in txns {
+        let path = output.join(txn.path());
+        if path.exists() {
+            summary.existing += 1;
+            continue;
+        }
+        summary.new += 1;
+        if dry_run {
+            continue;
+        }
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, &txn.text)
+            .map_err(|err| format!("Can't write txn: '{}' - {err}", path.display()))?;
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+    use tackler_rs::IndocUtils;
+
+    fn mapping() -> ImportMapping {
+        ImportMapping::from_toml(
+            r#"
+            account = "Assets:Bank"
+            commodity = "EUR"
+            delimiter = ";"
+            date-format = "%d.%m.%Y"
+            decimal-separator = ","
+            thousands-separator = "."
+            columns = { date = 1, payee = 2, memo = 3, amount = 4 }
+            "#,
+        )
+        .unwrap(/*:test:*/)
+    }
+
+    fn rules() -> ImportRules {
+        ImportRules::from_toml(
+            r#"
+            default-account = "Expenses:Unknown"
+
+            [[rule]]
+            payee = "(?i).*grocery.*"
+            account = "Expenses:Food"
+            description = "Groceries"
+            tags = ["food", "home"]
+
+            [[rule]]
+            memo = "salary.*"
+            account = "Income:Salary"
+            "#,
+        )
+        .unwrap(/*:test:*/)
+    }
+
+    #[test]
+    // test: 8d2f6b0a-4c7e-4a1d-9e3b-7f5c1a9d3e6b
+    // desc: import statement records with rules
+    fn import_statement() {
+        #[rustfmt::skip]
+        let statement = indoc!(
+           "|Date;Payee;Memo;Amount
+            |05.01.2024;Big  GROCERY Ltd;card;-1.234,50
+            |25.01.2024;ACME Corp;salary January;+3.000,00
+            |26.01.2024;Kiosk;;-2,5
+            |27.01.2024;Nothing;;0,00
+            |"
+        ).strip_margin();
+
+        let txns = import_csv(statement.as_bytes(), &mapping(), &rules()).unwrap(/*:test:*/);
+        assert_eq!(txns.len(), 3);
+
+        #[rustfmt::skip]
+        let expected = indoc!(
+           "|2024-01-05 'Groceries
+            |   # ext-id: import-{extid}
+            |   # tags: food, home
+            |   ; card
+            |   Expenses:Food  1234.50 EUR
+            |   Assets:Bank
+            |"
+        ).strip_margin();
+        assert_eq!(txns[0].date(), jiff::civil::date(2024, 1, 5));
+        assert_eq!(txns[0].amount(), Decimal::new(-123_450, 2));
+        assert_eq!(
+            txns[0].text(),
+            expected.replace("import-{extid}", txns[0].extid())
+        );
+        assert!(txns[1].text().contains("'ACME Corp\n"));
+        assert!(txns[1].text().contains("Income:Salary  -3000.00 EUR\n"));
+        assert!(txns[2].text().contains("Expenses:Unknown  2.5 EUR\n"));
+        assert_eq!(txns[2].path().parent(), Some(Path::new("2024/01/26")));
+    }
+
+    #[test]
+    // test: 4b6d8f0c-2e4a-4c6e-8a0b-1d3f5b7e9a2c
+    // desc: ext-ids are deterministic and identical records are separated
+    fn import_extid() {
+        let statement = "Date;Payee;Memo;Amount\n\
+                         05.01.2024;Kiosk;;-1,00\n\
+                         05.01.2024;Kiosk;;-1,00\n";
+
+        let txns = import_csv(statement.as_bytes(), &mapping(), &rules()).unwrap(/*:test:*/);
+        let again = import_csv(statement.as_bytes(), &mapping(), &rules()).unwrap(/*:test:*/);
+        assert_eq!(txns.len(), 2);
+        assert_ne!(txns[0].extid(), txns[1].extid());
+        assert_ne!(txns[0].path(), txns[1].path());
+        assert_eq!(txns[0].extid(), again[0].extid());
+        assert_eq!(txns[1].text(), again[1].text());
+    }
+
+    #[test]
+    // test: 0e2a4c6e-8f1b-4d3f-a5c7-9b1d3f5a7c8e
+    // desc: debit-credit columns, id column and errors with line numbers
+    fn import_debit_credit() {
+        let mapping = ImportMapping::from_toml(
+            r#"
+            account = "Assets:Bank"
+            date-format = "%Y-%m-%d"
+            sign = "debit-credit"
+            skip-rows = 0
+            ext-id-prefix = "bank"
+            columns = { date = 1, id = 2, payee = 3, debit = 4, credit = 5 }
+            "#,
+        )
+        .unwrap(/*:test:*/);
+
+        let statement = "2024-02-01,A-1,Shop,10.00,\n2024-02-02,A-2,Refund,,4.00\n";
+        let txns = import_csv(statement.as_bytes(), &mapping, &rules()).unwrap(/*:test:*/);
+        assert_eq!(txns[0].extid(), "bank-A-1");
+        assert_eq!(txns[0].amount(), Decimal::new(-1000, 2));
+        assert_eq!(txns[1].extid(), "bank-A-2");
+        assert_eq!(txns[1].amount(), Decimal::new(400, 2));
+
+        let statement = "2024-02-01,A-1,Shop,10.00,\n2024-13-02,A-2,Refund,,4.00\n";
+        let res = import_csv(statement.as_bytes(), &mapping, &rules());
+        assert!(
+            res.err()
+                .unwrap(/*:test:*/)
+                .to_string()
+                .contains("Invalid date '2024-13-02' at statement line 2")
+        );
+    }
+}
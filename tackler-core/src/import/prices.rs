@@ -0,0 +1,235 @@
+/*
+ * Tackler-NG 2026
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Import of price entries from CSV
+//!
+//! CSV records have four columns: timestamp (or date), commodity,
+//! price and price commodity, e.g. `2024-01-09,XAU,2659.64,USD`.
+//! Entries are appended to the price database in canonical `P` format.
+
+use crate::import::ImportSummary;
+use crate::kernel::Settings;
+use crate::model::price_entry::{PriceDb, PriceEntry, merge_price_dbs};
+use crate::parser;
+use crate::tackler;
+use itertools::Itertools;
+use jiff::civil::Time;
+use rust_decimal::Decimal;
+use std::fs;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+use tackler_api::txn_ts;
+
+/// Format of price CSV
+#[derive(Debug, Clone)]
+pub struct PriceCsv {
+    pub delimiter: u8,
+    /// Count of rows to skip before records, e.g. header row
+    pub skip_rows: usize,
+    /// Date format for `strptime`, if the first column is not a Tackler timestamp
+    pub date_format: Option<String>,
+}
+
+impl Default for PriceCsv {
+    fn default() -> Self {
+        PriceCsv {
+            delimiter: b',',
+            skip_rows: 1,
+            date_format: None,
+        }
+    }
+}
+
+/// Price entry in canonical `P` format
+///
+/// Timestamp is written as date, if it's at the start of the day.
+#[must_use]
+pub fn price_line(entry: &PriceEntry) -> String {
+    let ts = if entry.timestamp.time() == Time::midnight() {
+        entry.timestamp.date().to_string()
+    } else {
+        txn_ts::rfc_3339(&entry.timestamp)
+    };
+    format!(
+        "P {ts} {} {} {}",
+        entry.base_commodity.name, entry.eq_amount, entry.eq_commodity.name
+    )
+}
+
+/// Read price entries from CSV
+///
+/// # Errors
+/// Returns `Err` if CSV can't be read, or if some of the records are invalid
+pub fn import_prices<R: io::Read>(
+    reader: R,
+    format: &PriceCsv,
+    settings: &Settings,
+) -> Result<PriceDb, tackler::Error> {
+    let mut csv_reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .delimiter(format.delimiter)
+        .from_reader(reader);
+
+    let mut price_db = PriceDb::new();
+    for record in csv_reader.records().skip(format.skip_rows) {
+        let record = record?;
+        let line = record.position().map_or(0, csv::Position::line);
+        let fields = record.iter().map(str::trim).collect::<Vec<_>>();
+        let [ts, base, price, eq] = fields.as_slice() else {
+            let msg = format!(
+                "Invalid price record at line {line}: expected 4 columns, got {}",
+                fields.len()
+            );
+            return Err(msg.into());
+        };
+        let ts = match &format.date_format {
+            Some(date_format) => jiff::fmt::strtime::parse(date_format, ts)
+                .and_then(|tm| tm.to_date())
+                .map_err(|err| format!("Invalid date '{ts}' at line {line}: {err}"))?
+                .to_string(),
+            None => (*ts).to_string(),
+        };
+        let price = Decimal::from_str_exact(price)
+            .map_err(|err| format!("Invalid price '{price}' at line {line}: {err}"))?;
+
+        let entry = format!("P {ts} {base} {price} {eq}\n");
+        let entries = parser::pricedb_from_str(&mut entry.as_str(), settings)
+            .map_err(|err| format!("Invalid price entry at line {line}: {err}"))?;
+        price_db.extend(entries);
+    }
+    Ok(price_db)
+}
+
+/// Append price entries to the price database file
+///
+/// Entries which are already in the price database are not appended again.
+/// `source` is the name of the price entries, used for error messages.
+///
+/// # Errors
+/// Returns `Err` if the price database is invalid or it can't be written,
+/// or if the entries have different prices than the price database
+pub fn append_prices(
+    db_path: &Path,
+    source: &str,
+    prices: PriceDb,
+    settings: &Settings,
+) -> Result<ImportSummary, tackler::Error> {
+    let db_str = if db_path.exists() {
+        fs::read_to_string(db_path)
+            .map_err(|err| format!("Can't open file: '{}' - {err}", db_path.display()))?
+    } else {
+        String::new()
+    };
+    let existing = if db_str.trim().is_empty() {
+        PriceDb::new()
+    } else {
+        parser::pricedb_from_str(&mut db_str.as_str(), settings)?
+    };
+
+    let (old, new): (Vec<_>, Vec<_>) = prices
+        .iter()
+        .partition(|e| existing.binary_search(e).is_ok());
+    let (old, new) = (
+        old.len(),
+        new.into_iter()
+            .sorted()
+            .dedup()
+            .map(price_line)
+            .collect::<Vec<_>>(),
+    );
+
+    // Prices must not conflict with the price database or with each other
+    merge_price_dbs(vec![
+        (db_path.display().to_string(), existing),
+        (source.to_string(), prices),
+    ])?;
+
+    if !new.is_empty() {
+        let mut db = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(db_path)
+            .map_err(|err| format!("Can't write file: '{}' - {err}", db_path.display()))?;
+        if !db_str.is_empty() && !db_str.ends_with('\n') {
+            writeln!(db)?;
+        }
+        writeln!(db, "{}", new.iter().join("\n"))?;
+    }
+    Ok(ImportSummary {
+        new: new.len(),
+        existing: old,
+        skipped: 0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+    use tackler_rs::IndocUtils;
+
+    #[test]
+    // test: 9b1d3f5a-7c9e-4e1a-b3d5-0f2a4c6e8b9d
+    // desc: import price entries from CSV in canonical form
+    fn import_price_csv() {
+        #[rustfmt::skip]
+        let csv = indoc!(
+           "|date;commodity;price;currency
+            |09.01.2024; XAU ;2659.645203;USD
+            |08.01.2024;USD;0.91;EUR
+            |"
+        ).strip_margin();
+
+        let settings = Settings::default();
+        let format = PriceCsv {
+            delimiter: b';',
+            date_format: Some("%d.%m.%Y".to_string()),
+            ..PriceCsv::default()
+        };
+        let prices = import_prices(csv.as_bytes(), &format, &settings).unwrap(/*:test:*/);
+        let lines = prices.iter().map(price_line).collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            vec![
+                "P 2024-01-09 XAU 2659.645203 USD",
+                "P 2024-01-08 USD 0.91 EUR"
+            ]
+        );
+
+        let csv = "ts,commodity,price,currency\n2024-01-09T10:00:00+02:00,XAU,2000,USD\n";
+        let prices =
+            import_prices(csv.as_bytes(), &PriceCsv::default(), &settings).unwrap(/*:test:*/);
+        assert_eq!(
+            price_line(&prices[0]),
+            "P 2024-01-09T10:00:00+02:00 XAU 2000 USD"
+        );
+    }
+
+    #[test]
+    // test: 2e4a6c8f-0b1d-4f3a-9c5e-7a9b1d3f5e6c
+    // desc: invalid price records are reported with line number
+    fn import_price_csv_errors() {
+        let settings = Settings::default();
+        let format = PriceCsv::default();
+
+        let res = import_prices("h\n2024-01-09,XAU,2000\n".as_bytes(), &format, &settings);
+        assert!(
+            res.err()
+                .unwrap(/*:test:*/)
+                .to_string()
+                .contains("Invalid price record at line 2: expected 4 columns, got 3")
+        );
+
+        let res = import_prices("h\n2024-01-09,XAU,1e3,USD\n".as_bytes(), &format, &settings);
+        assert!(
+            res.err()
+                .unwrap(/*:test:*/)
+                .to_string()
+                .contains("Invalid price '1e3' at line 2")
+        );
+    }
+}
@@ -6,7 +6,9 @@
 pub use settings::Settings;
 pub(crate) mod accumulator;
 pub mod balance;
+pub(crate) mod forecast;
 pub mod hash;
+pub mod lots;
 pub mod price_lookup;
 pub mod report_item_selector;
 pub mod report_settings;
@@ -14,6 +16,10 @@ pub mod settings;
 
 pub use report_settings::BalanceGroupSettings;
 pub use report_settings::BalanceSettings;
+pub use report_settings::BudgetSettings;
+pub use report_settings::CashFlowSettings;
+pub use report_settings::IncomeStatementSettings;
+pub use report_settings::PnlSettings;
 pub use report_settings::RegisterSettings;
 
 ///
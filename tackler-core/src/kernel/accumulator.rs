@@ -3,23 +3,49 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use crate::config::BalanceType;
 use crate::kernel::Settings;
 use crate::kernel::balance::Balance;
 use crate::kernel::price_lookup::PriceLookupCtx;
 use crate::kernel::report_item_selector::{BalanceSelector, RegisterSelector};
-use crate::model::{RegisterEntry, RegisterPosting, Transaction, TxnAccount, TxnRefs};
+use crate::model::{Position, RegisterEntry, RegisterPosting, Transaction, TxnAccount, TxnRefs};
 use itertools::Itertools;
+use jiff::tz::TimeZone;
 use rust_decimal::Decimal;
 use std::collections::HashMap;
+use tackler_api::txn_ts;
+use tackler_api::txn_ts::GroupBy;
 
 pub(crate) type TxnGroupByOp<'a> = Box<dyn Fn(&Transaction) -> String + 'a>;
 
+pub(crate) fn group_by_op<'a>(group_by: GroupBy, tz: TimeZone) -> TxnGroupByOp<'a> {
+    match group_by {
+        GroupBy::IsoWeekDate => Box::new(move |txn: &Transaction| {
+            txn_ts::as_tz_iso_week_date(&txn.header.timestamp, tz.clone())
+        }),
+        GroupBy::IsoWeek => Box::new(move |txn: &Transaction| {
+            txn_ts::as_tz_iso_week(&txn.header.timestamp, tz.clone())
+        }),
+        GroupBy::Date => {
+            Box::new(move |txn: &Transaction| txn_ts::as_tz_date(&txn.header.timestamp, tz.clone()))
+        }
+        GroupBy::Month => Box::new(move |txn: &Transaction| {
+            txn_ts::as_tz_month(&txn.header.timestamp, tz.clone())
+        }),
+        GroupBy::Year => {
+            Box::new(move |txn: &Transaction| txn_ts::as_tz_year(&txn.header.timestamp, tz.clone()))
+        }
+    }
+}
+
 pub(crate) fn balance_groups<T>(
     txns: &TxnRefs<'_>,
     group_by_op: &TxnGroupByOp<'_>,
     price_lookup_ctx: &PriceLookupCtx<'_>,
     ras: &T,
     settings: &Settings,
+    bal_type: &BalanceType,
+    depth: Option<usize>,
 ) -> Vec<Balance>
 where
     T: BalanceSelector + ?Sized,
@@ -35,7 +61,8 @@ where
                 price_lookup_ctx,
                 ras,
                 settings,
-                settings.report.balance_group.bal_type.clone(),
+                bal_type.clone(),
+                depth,
             )
             .expect("Logic error with Balance Group: inner balance failed")
         })
@@ -53,6 +80,7 @@ where
     T: RegisterSelector<'a> + ?Sized,
 {
     let mut register_engine: HashMap<TxnAccount, Decimal> = HashMap::new();
+    let mut positions: HashMap<TxnAccount, Position> = HashMap::new();
 
     // NOTE-1
     // This must be sorted, as we are collapsing all different commodities
@@ -80,11 +108,15 @@ where
                         })
                         .or_insert(conv_amount);
 
+                    let position = positions.entry(orig_p.acctn.clone()).or_default();
+                    position.add(orig_p);
+
                     RegisterPosting {
                         post: orig_p,
                         amount: running_total,
                         target_commodity: conv_acctn.comm,
                         rate,
+                        position: position.clone(),
                     }
                 })
                 .collect();
@@ -7,12 +7,13 @@ use crate::config::BalanceType;
 use crate::kernel::Settings;
 use crate::kernel::price_lookup::PriceLookupCtx;
 use crate::kernel::report_item_selector::BalanceSelector;
-use crate::model::balance_tree_node::ord_by_btn;
+use crate::model::balance_tree_node::{BalanceValue, InventoryNode, ord_by_btn};
 use crate::model::{BalanceTreeNode, Commodity, Transaction, TxnAccount, TxnSet};
 use crate::tackler;
 use itertools::Itertools;
+use jiff::Zoned;
 use rust_decimal::Decimal;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::{collections::HashSet, sync::Arc};
 
 // Deltas must be sorted by Commodity on reports, use BTreeMap
@@ -23,12 +24,126 @@ pub struct Balance {
     pub(crate) title: String,
     pub(crate) bal: BTNs,
     pub(crate) deltas: Deltas,
+    /// Converted values of deltas, if balance is valued
+    pub(crate) delta_values: BTreeMap<Option<Arc<Commodity>>, Option<Decimal>>,
+    /// Target commodity of values, if balance is valued
+    pub(crate) value_commodity: Option<Arc<Commodity>>,
+    /// Timestamps of the first and last txn of the balance
+    pub(crate) period: Option<(Zoned, Zoned)>,
 }
 
 impl Balance {
     pub(crate) fn is_empty(&self) -> bool {
         self.bal.is_empty()
     }
+
+    /// Multi-commodity inventories of accounts
+    ///
+    /// Inventories are sorted by account, and positions by commodity.
+    pub(crate) fn inventory(&self) -> Vec<InventoryNode<'_>> {
+        self.bal
+            .iter()
+            .sorted_by(|a, b| {
+                a.acctn
+                    .atn
+                    .cmp(&b.acctn.atn)
+                    .then(a.acctn.comm.cmp(&b.acctn.comm))
+            })
+            .chunk_by(|btn| &btn.acctn.atn.account)
+            .into_iter()
+            .map(|(_, positions)| {
+                let positions: Vec<_> = positions.collect();
+                InventoryNode {
+                    atn: &positions[0].acctn.atn,
+                    positions,
+                }
+            })
+            .collect()
+    }
+
+    /// Set converted values of balance tree nodes and deltas
+    ///
+    /// This is used with multi-commodity inventory balance, which is calculated
+    /// without price conversion. Values are calculated over all transactions,
+    /// so value of account tree sum is not affected by account selectors.
+    ///
+    /// `depth` must be the same as what was used to calculate the balance.
+    pub(crate) fn with_values<'a, I>(
+        mut self,
+        txns: I,
+        price_lookup_ctx: &PriceLookupCtx<'_>,
+        inverted: bool,
+        depth: Option<usize>,
+    ) -> Balance
+    where
+        I: Iterator<Item = &'a &'a Transaction>,
+    {
+        let Some(in_commodity) = price_lookup_ctx.in_commodity() else {
+            return self;
+        };
+        if price_lookup_ctx.is_empty() {
+            return self;
+        }
+        let inv = if inverted {
+            Decimal::from(-1)
+        } else {
+            Decimal::ONE
+        };
+
+        // Input size: is "big",    ~ all transactions
+        // Output size: is "small", ~ size of CoA
+        let mut account_values: HashMap<TxnAccount, Option<Decimal>> = HashMap::new();
+        for txn in txns {
+            for (p, (acctn, amount, _)) in
+                txn.posts.iter().zip(price_lookup_ctx.convert_prices(txn))
+            {
+                let value = (acctn.comm.name == in_commodity.name).then_some(amount * inv);
+                let v = account_values
+                    .entry(p.acctn.clone())
+                    .or_insert(Some(Decimal::ZERO));
+                *v = v.zip(value).map(|(a, b)| a + b);
+            }
+        }
+
+        for btn in &mut self.bal {
+            let account = &btn.acctn.atn.account;
+            let sub_acc_prefix = format!("{account}:");
+            let sub_acc_tree_sum = account_values
+                .iter()
+                .filter(|(acctn, _)| {
+                    acctn.comm.name == btn.acctn.comm.name
+                        && (&acctn.atn.account == account
+                            || acctn.atn.account.starts_with(&sub_acc_prefix))
+                })
+                .map(|(_, v)| *v)
+                .sum::<Option<Decimal>>();
+            // Rolled up account has all of its sub-accounts
+            let account_sum = if depth.is_some_and(|d| btn.acctn.atn.depth >= d) {
+                sub_acc_tree_sum
+            } else {
+                account_values
+                    .get(&btn.acctn)
+                    .copied()
+                    .unwrap_or(Some(Decimal::ZERO))
+            };
+
+            btn.value = Some(BalanceValue {
+                sub_acc_tree_sum,
+                account_sum,
+            });
+        }
+
+        let mut delta_values = BTreeMap::new();
+        for btn in &self.bal {
+            let comm = btn.acctn.comm.is_any().then(|| btn.acctn.comm.clone());
+            let value = btn.value.as_ref().and_then(|v| v.account_sum);
+            let dv = delta_values.entry(comm).or_insert(Some(Decimal::ZERO));
+            *dv = dv.zip(value).map(|(a, b)| a + b);
+        }
+        self.delta_values = delta_values;
+        self.value_commodity = Some(in_commodity.clone());
+        self
+    }
 }
 
 impl Balance {
@@ -68,6 +183,7 @@ impl Balance {
             acctn: my_acctn.clone(),
             sub_acc_tree_sum: my_childs_sum + my_sum,
             account_sum: *my_sum,
+            value: None,
         };
 
         let mut x = vec![my_btn];
@@ -139,13 +255,17 @@ impl Balance {
 
     /// Calculate sum of postings for each account.
     ///
+    /// If `depth` is set, then accounts deeper than that
+    /// are collapsed into their ancestor at `depth`.
+    ///
     /// Input size: is "big",    ~ all transactions
     /// Output size: is "small", ~ size of Chart of Accounts
     fn calculate_account_sums<'a, I>(
         txns: I,
         price_lookup_ctx: &PriceLookupCtx<'_>,
-        inverted: bool,
-    ) -> Vec<(TxnAccount, Decimal)>
+        settings: &Settings,
+        depth: Option<usize>,
+    ) -> Result<Vec<(TxnAccount, Decimal)>, tackler::Error>
     where
         I: Iterator<Item = &'a &'a Transaction>,
     {
@@ -155,21 +275,51 @@ impl Balance {
         //
         // Input size: is "big",    ~ all transactions
         // Output size: is "small", ~ size of CoA
-        txns.flat_map(|txn| price_lookup_ctx.convert_prices(txn))
-            .sorted_by_key(|(acctn, _, _)| acctn.clone())
-            .chunk_by(|(acctn, _, _)| acctn.clone())
+        let account_sums = txns
+            .flat_map(|txn| price_lookup_ctx.convert_prices(txn))
+            .map(|(acctn, amount, _)| (acctn, amount));
+        let account_sums = Self::sum_by_account(account_sums);
+
+        let account_sums = match depth {
+            Some(depth) => {
+                // Input size:  "small", e.g. ~ size of CoA
+                // Output size: "small", e.g. ~ size of CoA
+                let rolled_up = account_sums
+                    .into_iter()
+                    .map(|(acctn, acc_sum)| {
+                        if acctn.atn.depth > depth {
+                            let ancestor = acctn.atn.account.split(':').take(depth).join(":");
+                            let acctn = settings.get_txn_account(&ancestor, &acctn.comm)?;
+                            Ok((acctn, acc_sum))
+                        } else {
+                            Ok((acctn, acc_sum))
+                        }
+                    })
+                    .collect::<Result<Vec<_>, tackler::Error>>()?;
+                Self::sum_by_account(rolled_up.into_iter())
+            }
+            None => account_sums,
+        };
+
+        if settings.inverted {
+            Ok(account_sums
+                .into_iter()
+                .map(|(acctn, acc_sum)| (acctn, acc_sum * inv))
+                .collect())
+        } else {
+            Ok(account_sums)
+        }
+    }
+
+    fn sum_by_account<I>(postings: I) -> Vec<(TxnAccount, Decimal)>
+    where
+        I: Iterator<Item = (TxnAccount, Decimal)>,
+    {
+        postings
+            .sorted_by_key(|(acctn, _)| acctn.clone())
+            .chunk_by(|(acctn, _)| acctn.clone())
             .into_iter()
-            .map(|(_, postings)| {
-                let mut ps = postings.peekable();
-                // unwrap: ok: this is inside map, hence there must be at least one element
-                let acctn = ps.peek().unwrap(/*:ok:*/).0.clone();
-                let acc_sum = ps.map(|(_, amount, _)| amount).sum::<Decimal>();
-                if inverted {
-                    (acctn, acc_sum * inv)
-                } else {
-                    (acctn, acc_sum)
-                }
-            })
+            .map(|(acctn, postings)| (acctn, postings.map(|(_, amount)| amount).sum()))
             .collect()
     }
 
@@ -184,6 +334,7 @@ impl Balance {
         txns: I,
         price_lookup_ctx: &PriceLookupCtx<'_>,
         settings: &Settings,
+        depth: Option<usize>,
     ) -> Result<Vec<BalanceTreeNode>, tackler::Error>
     where
         I: Iterator<Item = &'a &'a Transaction>,
@@ -193,7 +344,7 @@ impl Balance {
         // Input size: is "big",    ~ all transactions
         // Output size: is "small", ~ size of CoA
         let account_sums: Vec<(TxnAccount, Decimal)> =
-            Self::calculate_account_sums(txns, price_lookup_ctx, settings.inverted);
+            Self::calculate_account_sums(txns, price_lookup_ctx, settings, depth)?;
 
         // From every account bubble up and insert missing parent AccTNs.
         //
@@ -265,6 +416,7 @@ impl Balance {
             accounts,
             settings,
             settings.report.balance.bal_type.clone(),
+            None,
         )
     }
 
@@ -276,57 +428,91 @@ impl Balance {
         accounts: &T,
         settings: &Settings,
         bal_type: BalanceType,
+        depth: Option<usize>,
     ) -> Result<Balance, tackler::Error>
     where
         T: BalanceSelector + ?Sized,
         I: IntoIterator<Item = &'a &'a Transaction>,
     {
+        // Report period of the balance, see `Balance::retain_reported`
+        let mut period: Option<(&Zoned, &Zoned)> = None;
+        let txns = txns.into_iter().inspect(|txn| {
+            let ts = &txn.header.timestamp;
+            period = Some(period.map_or((ts, ts), |(begin, end)| (begin.min(ts), end.max(ts))));
+        });
         let bal = match bal_type {
-            BalanceType::Tree => {
-                Balance::balance_tree(txns.into_iter(), price_lookup_ctx, settings)?
-            }
-            BalanceType::Flat => {
-                Balance::balance_flat(txns.into_iter(), price_lookup_ctx, settings)
-            }
+            BalanceType::Tree => Balance::balance_tree(txns, price_lookup_ctx, settings, depth)?,
+            BalanceType::Flat => Balance::balance_flat(txns, price_lookup_ctx, settings, depth)?,
         };
 
         let filt_bal: Vec<_> = bal.into_iter().filter(|b| accounts.eval(b)).collect();
 
-        if filt_bal.is_empty() {
-            Ok(Balance {
-                title: title.to_string(),
-                bal: Vec::default(),
-                deltas: BTreeMap::default(),
-            })
-        } else {
-            let deltas = filt_bal
-                .iter()
-                .chunk_by(|btn| btn.acctn.comm.clone())
-                .into_iter()
-                .map(|(c, bs)| {
-                    let dsum = bs.map(|b| b.account_sum).sum();
-                    (c.is_any().then_some(c), dsum)
-                })
-                .collect();
+        Ok(Balance::from_btns(
+            title.to_string(),
+            filt_bal,
+            period.map(|(begin, end)| (begin.clone(), end.clone())),
+        ))
+    }
 
-            Ok(Balance {
-                title: title.to_string(),
-                bal: filt_bal,
-                deltas,
+    fn from_btns(title: String, bal: BTNs, period: Option<(Zoned, Zoned)>) -> Balance {
+        let deltas = bal
+            .iter()
+            .chunk_by(|btn| btn.acctn.comm.clone())
+            .into_iter()
+            .map(|(c, bs)| {
+                let dsum = bs.map(|b| b.account_sum).sum();
+                (c.is_any().then_some(c), dsum)
             })
+            .collect();
+
+        Balance {
+            title,
+            bal,
+            deltas,
+            delta_values: BTreeMap::default(),
+            value_commodity: None,
+            period,
+        }
+    }
+
+    /// Remove closed accounts from the balance
+    ///
+    /// These are accounts which are closed over the whole period of the balance,
+    /// see [`Settings::is_reported_account`] and `--include-closed`.
+    /// Only accounts with zero balance (and zero balance of their sub-accounts)
+    /// are removed, so that account tree sums and deltas stay the same.
+    ///
+    /// This must be done before [`Balance::with_values`].
+    pub(crate) fn retain_reported(self, settings: &Settings) -> Balance {
+        let Some((begin, end)) = self.period.clone() else {
+            return self;
+        };
+        let is_reported = |btn: &BalanceTreeNode| {
+            !(btn.account_sum.is_zero() && btn.sub_acc_tree_sum.is_zero())
+                || settings.is_reported_account(&btn.acctn.atn.account, &begin, &end)
+        };
+        if self.bal.iter().all(is_reported) {
+            return self;
         }
+        let bal = self
+            .bal
+            .into_iter()
+            .filter(|btn| is_reported(btn))
+            .collect();
+        Balance::from_btns(self.title, bal, self.period)
     }
 
     fn balance_flat<'a, I>(
         txns: I,
         price_lookup_ctx: &PriceLookupCtx<'_>,
         settings: &Settings,
-    ) -> Vec<BalanceTreeNode>
+        depth: Option<usize>,
+    ) -> Result<Vec<BalanceTreeNode>, tackler::Error>
     where
         I: Iterator<Item = &'a &'a Transaction>,
     {
         let account_sums: Vec<(TxnAccount, Decimal)> =
-            Self::calculate_account_sums(txns, price_lookup_ctx, settings.inverted);
+            Self::calculate_account_sums(txns, price_lookup_ctx, settings, depth)?;
 
         let mut v: Vec<BalanceTreeNode> = account_sums
             .into_iter()
@@ -334,10 +520,168 @@ impl Balance {
                 acctn: acctn.clone(),
                 sub_acc_tree_sum: Decimal::ZERO,
                 account_sum: acc_sum,
+                value: None,
             })
             .collect();
 
         v.sort_by(ord_by_btn);
-        v
+        Ok(v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kernel::price_lookup::PriceLookup;
+    use crate::kernel::report_item_selector::BalanceAllSelector;
+    use crate::parser;
+    use indoc::indoc;
+    use tackler_rs::IndocUtils;
+
+    #[test]
+    // test: 8d0c5f5e-7e0b-4b8f-93a4-64a4c3a0f1d2
+    // desc: inventory balance is valued without converting the inventory
+    fn inventory_with_values() {
+        let settings = Settings::default();
+        #[rustfmt::skip]
+        let txns_str = indoc!(
+           "|2024-01-01
+            | Assets:Broker  10 ACME {100 EUR}
+            | Assets:Cash
+            |
+            |2024-02-01
+            | Assets:Broker  2 XYZ {10 EUR}
+            | Assets:Cash
+            |"
+        ).strip_margin();
+        let txn_data = parser::string_to_txns(&mut txns_str.as_str(), &settings).unwrap(/*:test:*/);
+        let txn_set = txn_data.get_all().unwrap(/*:test:*/);
+        let price_db = parser::pricedb_from_str(
+            &mut "P 2024-03-01 ACME 120 EUR\n", &settings).unwrap(/*:test:*/);
+        let eur = settings.get_commodity("EUR").unwrap(/*:test:*/);
+        let ctx = PriceLookup::LastPriceDbEntry.make_ctx(&txn_set.txns, Some(eur), &price_db);
+
+        let bal = Balance::from_iter(
+            "",
+            &txn_set.txns,
+            &PriceLookupCtx::default(),
+            &BalanceAllSelector::default(),
+            &settings,
+            BalanceType::Tree,
+            None,
+        )
+        .unwrap(/*:test:*/)
+        .with_values(txn_set.txns.iter(), &ctx, false, None);
+
+        let inventory = bal.inventory();
+        let accounts: Vec<_> = inventory.iter().map(|i| i.atn.account.as_str()).collect();
+        assert_eq!(accounts, vec!["Assets", "Assets:Broker", "Assets:Cash"]);
+
+        let assets = &inventory[0];
+        let positions: Vec<_> = assets
+            .positions
+            .iter()
+            .map(|btn| {
+                (
+                    btn.acctn.comm.name.as_str(),
+                    btn.sub_acc_tree_sum,
+                    btn.value.as_ref().and_then(|v| v.sub_acc_tree_sum),
+                )
+            })
+            .collect();
+        assert_eq!(
+            positions,
+            vec![
+                ("ACME", Decimal::from(10), Some(Decimal::from(1200))),
+                ("EUR", Decimal::from(-1020), Some(Decimal::from(-1020))),
+                ("XYZ", Decimal::from(2), None),
+            ]
+        );
+
+        let eur = bal
+            .deltas
+            .keys()
+            .find(|c| c.as_ref().is_some_and(|c| c.name == "EUR"))
+            .unwrap(/*:test:*/);
+        assert_eq!(bal.delta_values.get(eur), Some(&Some(Decimal::from(-1020))));
+    }
+
+    #[test]
+    // test: 4d6f8a0c-2e4b-4c6d-8f0a-1c3e5a7b9d2f
+    // desc: sub-accounts below depth are collapsed into their ancestor with tree and flat balance
+    fn depth_roll_up() {
+        let settings = Settings::default();
+        #[rustfmt::skip]
+        let txns_str = indoc!(
+           "|2024-01-01
+            | Expenses:Travel:Hotel  100 EUR
+            | Expenses:Travel:Flight:Tax  20 EUR
+            | Expenses:Travel  5 EUR
+            | Expenses:Food  10 EUR
+            | Assets:Cash
+            |"
+        ).strip_margin();
+        let txn_data = parser::string_to_txns(&mut txns_str.as_str(), &settings).unwrap(/*:test:*/);
+        let txn_set = txn_data.get_all().unwrap(/*:test:*/);
+
+        let balance = |bal_type: BalanceType| -> Vec<(String, Decimal, Decimal)> {
+            Balance::from_iter(
+                "",
+                &txn_set.txns,
+                &PriceLookupCtx::default(),
+                &BalanceAllSelector::default(),
+                &settings,
+                bal_type,
+                Some(2),
+            )
+            .unwrap(/*:test:*/)
+            .bal
+            .iter()
+            .map(|btn| (btn.acctn.atn.account.clone(), btn.account_sum, btn.sub_acc_tree_sum))
+            .collect()
+        };
+
+        assert_eq!(
+            balance(BalanceType::Tree),
+            vec![
+                ("Assets".to_string(), Decimal::ZERO, Decimal::from(-135)),
+                (
+                    "Assets:Cash".to_string(),
+                    Decimal::from(-135),
+                    Decimal::from(-135)
+                ),
+                ("Expenses".to_string(), Decimal::ZERO, Decimal::from(135)),
+                (
+                    "Expenses:Food".to_string(),
+                    Decimal::from(10),
+                    Decimal::from(10)
+                ),
+                (
+                    "Expenses:Travel".to_string(),
+                    Decimal::from(125),
+                    Decimal::from(125)
+                ),
+            ]
+        );
+        assert_eq!(
+            balance(BalanceType::Flat),
+            vec![
+                (
+                    "Assets:Cash".to_string(),
+                    Decimal::from(-135),
+                    Decimal::ZERO
+                ),
+                (
+                    "Expenses:Food".to_string(),
+                    Decimal::from(10),
+                    Decimal::ZERO
+                ),
+                (
+                    "Expenses:Travel".to_string(),
+                    Decimal::from(125),
+                    Decimal::ZERO
+                ),
+            ]
+        );
     }
 }
@@ -0,0 +1,299 @@
+/*
+ * Tackler-NG 2026
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use crate::config::{RecurringPeriod, RecurringSpec};
+use crate::kernel::Settings;
+use crate::model::source::Source;
+use crate::model::{Transaction, Txns};
+use crate::parser::tackler_parser;
+use crate::tackler;
+use jiff::{Span, Zoned};
+use std::sync::Arc;
+use tackler_api::metadata::items::{ForecastInfo, MetadataItem};
+use tackler_api::txn_header::TxnHeader;
+
+/// Tag of generated forecast txns
+pub(crate) const FORECAST_TAG: &str = "forecast";
+
+/// Forecast window
+#[derive(Debug, Clone)]
+pub(crate) struct Forecast {
+    /// Begin of forecast (inclusive), default is after the last txn
+    pub(crate) begin: Option<Zoned>,
+    /// End of forecast (exclusive)
+    pub(crate) end: Zoned,
+}
+
+/// Add forecast txns of recurring txns to the txns
+///
+/// This must be done before txns are validated and sorted,
+/// so that forecast txns are part of the txn data.
+///
+/// Returns forecast metadata, if forecast is active.
+///
+/// # Errors
+/// Returns `Err` in case of invalid recurring txn template
+pub(crate) fn with_forecast(
+    txns: Txns,
+    settings: &Settings,
+) -> Result<(Txns, Option<MetadataItem>), tackler::Error> {
+    let Some(forecast) = &settings.forecast else {
+        return Ok((txns, None));
+    };
+    if settings.audit_mode {
+        let msg = "Forecast can't be used with audit mode, forecast txns don't have UUID";
+        return Err(msg.into());
+    }
+
+    let last_txn_ts = txns.iter().map(|txn| &txn.header.timestamp).max().cloned();
+    let in_window = |ts: &Zoned| {
+        let after_begin = match (&forecast.begin, &last_txn_ts) {
+            (Some(begin), _) => ts >= begin,
+            (None, Some(last)) => ts > last,
+            (None, None) => true,
+        };
+        after_begin && *ts < forecast.end
+    };
+
+    let mut forecast_txns = Vec::new();
+    for (i, spec) in settings.recurring.iter().enumerate() {
+        let recurring_txns = recurring_txns(spec, &forecast.end, settings).map_err(|err| {
+            let msg = format!("Recurring txn #{}: {err}", i + 1);
+            tackler::Error::from(msg)
+        })?;
+        forecast_txns.extend(
+            recurring_txns
+                .into_iter()
+                .filter(|txn| in_window(&txn.header.timestamp)),
+        );
+    }
+
+    let mdi = MetadataItem::ForecastInfo(ForecastInfo {
+        begin: forecast.begin.clone(),
+        end: forecast.end.clone(),
+        count: forecast_txns.len(),
+    });
+
+    let mut txns = txns;
+    txns.append(&mut forecast_txns);
+    Ok((txns, Some(mdi)))
+}
+
+/// Generate all txns of recurring txn, which are before the end
+///
+/// The timestamp of the template txn is the first occurrence, and all
+/// occurrences are calculated from it, e.g. month-end dates are clamped
+/// by month, but they don't drift.
+fn recurring_txns(
+    spec: &RecurringSpec,
+    end: &Zoned,
+    settings: &Settings,
+) -> Result<Txns, tackler::Error> {
+    let template = template_txn(spec, settings)?;
+
+    let until = spec
+        .until
+        .as_ref()
+        .map(|ts| settings.parse_timestamp(ts))
+        .transpose()?;
+
+    let mut tags = template.header.tags.clone().unwrap_or_default();
+    tags.push(Arc::new(FORECAST_TAG.to_string()));
+
+    let first = &template.header.timestamp;
+    let mut txns = Vec::new();
+    for n in 0_i64.. {
+        let steps = n * i64::from(spec.interval);
+        let span = match spec.every {
+            RecurringPeriod::Day => Span::new().try_days(steps)?,
+            RecurringPeriod::Week => Span::new().try_weeks(steps)?,
+            RecurringPeriod::Month => Span::new().try_months(steps)?,
+            RecurringPeriod::Year => Span::new().try_years(steps)?,
+        };
+        let ts = first.checked_add(span)?;
+        if &ts >= end || until.as_ref().is_some_and(|u| &ts > u) {
+            break;
+        }
+        txns.push(Transaction {
+            header: TxnHeader {
+                timestamp: ts,
+                tags: Some(tags.clone()),
+                ..template.header.clone()
+            },
+            posts: template.posts.clone(),
+            source: Source::default(),
+        });
+    }
+    Ok(txns)
+}
+
+fn template_txn(spec: &RecurringSpec, settings: &Settings) -> Result<Transaction, tackler::Error> {
+    let mut txns = tackler_parser::txns_text(&mut spec.txn.as_str(), settings)?;
+    if txns.len() != 1 {
+        let msg = format!(
+            "there must be exactly one txn in the template, found {}",
+            txns.len()
+        );
+        return Err(msg.into());
+    }
+    let txn = txns.remove(0);
+    if txn.header.uuid.is_some() || txn.header.extid.is_some() {
+        let msg = "txn template can't have 'uuid' or 'ext-id'";
+        return Err(msg.into());
+    }
+    if txn.posts.iter().any(|p| p.assertion.is_some()) {
+        let msg = "txn template can't have balance assertions";
+        return Err(msg.into());
+    }
+    Ok(txn)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::TxnData;
+    use crate::parser;
+    use indoc::indoc;
+    use tackler_rs::IndocUtils;
+
+    const TXNS_STR: &str = "2024-02-15 'salary\n Assets:Bank  2000\n Income:Salary\n";
+
+    fn forecast_settings(begin: Option<&str>, end: &str, specs: Vec<RecurringSpec>) -> Settings {
+        let mut settings = Settings::default();
+        settings.forecast = Some(Forecast {
+            begin: begin.map(|ts| settings.parse_timestamp(ts).unwrap(/*:test:*/)),
+            end: settings.parse_timestamp(end).unwrap(/*:test:*/),
+        });
+        settings.recurring = specs;
+        settings
+    }
+
+    fn rent_spec(every: RecurringPeriod, interval: u32, until: Option<&str>) -> RecurringSpec {
+        #[rustfmt::skip]
+        let txn = indoc!(
+           "|2024-01-31 'rent
+            | Expenses:Rent  500
+            | Assets:Bank
+            |"
+        ).strip_margin();
+        RecurringSpec {
+            every,
+            interval,
+            until: until.map(ToString::to_string),
+            txn,
+        }
+    }
+
+    fn forecast_dates(txn_data: &TxnData) -> Vec<String> {
+        let txn_set = txn_data.get_all().unwrap(/*:test:*/);
+        txn_set
+            .txns
+            .iter()
+            .filter(|txn| {
+                txn.header
+                    .tags
+                    .as_ref()
+                    .is_some_and(|tags| tags.iter().any(|t| t.as_str() == FORECAST_TAG))
+            })
+            .map(|txn| txn.header.timestamp.date().to_string())
+            .collect()
+    }
+
+    #[test]
+    // test: 3c8e5a2f-7d1b-4f6a-9e0c-2b4d6f8a1c3e
+    // desc: monthly forecast after last txn, month ends are clamped but they don't drift
+    fn forecast_monthly_after_last_txn() {
+        let settings = forecast_settings(
+            None,
+            "2024-07-01",
+            vec![rent_spec(RecurringPeriod::Month, 1, None)],
+        );
+        let txn_data = parser::string_to_txns(&mut { TXNS_STR }, &settings).unwrap(/*:test:*/);
+
+        assert_eq!(txn_data.len(), 6);
+        assert_eq!(
+            forecast_dates(&txn_data),
+            vec![
+                "2024-02-29",
+                "2024-03-31",
+                "2024-04-30",
+                "2024-05-31",
+                "2024-06-30"
+            ]
+        );
+
+        let txn_set = txn_data.get_all().unwrap(/*:test:*/);
+        let md = txn_set.metadata().unwrap(/*:test:*/);
+        assert!(
+            md.items
+                .iter()
+                .any(|mdi| matches!(mdi, MetadataItem::ForecastInfo(fi) if fi.count == 5))
+        );
+    }
+
+    #[test]
+    // test: 8a1f4c6e-2b9d-4e3a-b7c5-0d2e4f6a8b1c
+    // desc: explicit begin is inclusive, interval and until are honored
+    fn forecast_begin_interval_until() {
+        let settings = forecast_settings(
+            Some("2024-01-31"),
+            "2025-01-01",
+            vec![
+                rent_spec(RecurringPeriod::Month, 2, Some("2024-07-31")),
+                rent_spec(RecurringPeriod::Week, 1, Some("2024-02-14")),
+            ],
+        );
+        let txn_data = parser::string_to_txns(&mut { TXNS_STR }, &settings).unwrap(/*:test:*/);
+
+        assert_eq!(
+            forecast_dates(&txn_data),
+            vec![
+                "2024-01-31",
+                "2024-01-31",
+                "2024-02-07",
+                "2024-02-14",
+                "2024-03-31",
+                "2024-05-31",
+                "2024-07-31",
+            ]
+        );
+    }
+
+    #[test]
+    // test: 5d7b9e1a-3c4f-4a2e-8b6d-1f0e2c4a6b8d
+    // desc: invalid txn templates and audit mode are rejected
+    fn forecast_errors() {
+        let cases = [
+            (
+                "2024-01-01\n a 1\n b\n\n2024-01-02\n a 1\n b\n",
+                "Recurring txn #1: there must be exactly one txn in the template, found 2",
+            ),
+            (
+                "2024-01-01\n # uuid: 9d2e2a5c-3c6e-4f0b-8a16-1c0a9e7b5d3f\n a 1\n b\n",
+                "Recurring txn #1: txn template can't have 'uuid' or 'ext-id'",
+            ),
+            (
+                "2024-01-01\n a 1 == 1\n b\n",
+                "Recurring txn #1: txn template can't have balance assertions",
+            ),
+        ];
+        for (txn, msg) in cases {
+            let spec = RecurringSpec {
+                every: RecurringPeriod::Day,
+                interval: 1,
+                until: None,
+                txn: txn.to_string(),
+            };
+            let settings = forecast_settings(None, "2025-01-01", vec![spec]);
+            let res = parser::string_to_txns(&mut { TXNS_STR }, &settings);
+            assert_eq!(res.err().map(|e| e.to_string()), Some(msg.to_string()));
+        }
+
+        let mut settings = forecast_settings(None, "2025-01-01", Vec::new());
+        settings.audit_mode = true;
+        let res = parser::string_to_txns(&mut { TXNS_STR }, &settings);
+        assert!(res.is_err());
+    }
+}
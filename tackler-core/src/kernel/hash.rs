@@ -11,14 +11,16 @@ use tackler_api::metadata::Checksum;
 #[derive(Clone)]
 pub struct Hash {
     hash_algo: String,
-    hasher: Box<dyn DynDigest>,
+    // Hash is part of settings, which are shared between
+    // parser threads, so this is a factory instead of hasher instance
+    hasher: fn() -> Box<dyn DynDigest>,
 }
 
 impl Default for Hash {
     fn default() -> Self {
         Hash {
             hash_algo: "SHA-256".to_string(),
-            hasher: Box::new(sha2::Sha256::default()),
+            hasher: || Box::new(sha2::Sha256::default()),
         }
     }
 }
@@ -44,23 +46,23 @@ impl Hash {
         match algo {
             "SHA-256" => Ok(Hash {
                 hash_algo: "SHA-256".to_string(),
-                hasher: Box::new(sha2::Sha256::default()),
+                hasher: || Box::new(sha2::Sha256::default()),
             }),
             "SHA-512" => Ok(Hash {
                 hash_algo: "SHA-512".to_string(),
-                hasher: Box::new(sha2::Sha512::default()),
+                hasher: || Box::new(sha2::Sha512::default()),
             }),
             "SHA-512/256" => Ok(Hash {
                 hash_algo: "SHA-512/256".to_string(),
-                hasher: Box::new(sha2::Sha512_256::default()),
+                hasher: || Box::new(sha2::Sha512_256::default()),
             }),
             "SHA3-256" => Ok(Hash {
                 hash_algo: "SHA3-256".to_string(),
-                hasher: Box::new(sha3::Sha3_256::default()),
+                hasher: || Box::new(sha3::Sha3_256::default()),
             }),
             "SHA3-512" => Ok(Hash {
                 hash_algo: "SHA3-512".to_string(),
-                hasher: Box::new(sha3::Sha3_512::default()),
+                hasher: || Box::new(sha3::Sha3_512::default()),
             }),
             _ => {
                 let mut msg = format!("Unknown hash algorithm: '{algo}'. ");
@@ -73,7 +75,7 @@ impl Hash {
     /// Calculate checksum
     #[must_use]
     pub fn checksum(&self, items: &[String], separator: &[u8]) -> Checksum {
-        let mut hasher = self.hasher.clone();
+        let mut hasher = (self.hasher)();
 
         for i in items {
             hasher.update(i.as_bytes());
@@ -0,0 +1,446 @@
+/*
+ * Tackler-NG 2026
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Lot tracking of opening positions `{ ... }` (TEP-1003)
+//!
+//! Lots are tracked per account and commodity. Posting with an
+//! opening position opens a new lot, and posting which goes against
+//! open lots closes them. Closing posting is matched to lots by
+//! its opening position (specific lot), or if it doesn't have one,
+//! then by configured [`LotMatching`] method.
+
+use crate::config::LotMatching;
+use crate::model::price_entry::PriceDb;
+use crate::model::{Commodity, Posting, TxnRefs};
+use crate::tackler;
+use jiff::Zoned;
+use rust_decimal::Decimal;
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::Arc;
+use tackler_api::txn_ts;
+
+/// Lot of commodity, opened by posting with opening position
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lot {
+    /// Account of the lot
+    pub account: String,
+    /// Commodity of the lot
+    pub commodity: Arc<Commodity>,
+    /// Opening time of the lot
+    pub opened: Zoned,
+    /// Quantity of the lot, negative value is short position
+    pub quantity: Decimal,
+    /// Cost of one unit of commodity
+    pub unit_cost: Decimal,
+    /// Commodity of the cost
+    pub cost_commodity: Arc<Commodity>,
+}
+
+impl Lot {
+    /// Total cost basis of the lot
+    #[must_use]
+    pub fn cost_basis(&self) -> Decimal {
+        self.quantity * self.unit_cost
+    }
+
+    /// Profit or loss of the lot with given unit price
+    #[must_use]
+    pub fn pnl(&self, unit_price: Decimal) -> Decimal {
+        self.quantity * (unit_price - self.unit_cost)
+    }
+}
+
+/// Closed lot or closed part of the lot
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClosedLot {
+    /// Closed part of the lot, `quantity` is the closed quantity
+    pub lot: Lot,
+    /// Closing time
+    pub closed: Zoned,
+    /// Closing price of one unit in cost commodity
+    pub unit_price: Decimal,
+}
+
+impl ClosedLot {
+    /// Realized profit or loss
+    #[must_use]
+    pub fn pnl(&self) -> Decimal {
+        self.lot.pnl(self.unit_price)
+    }
+}
+
+/// Open and closed lots of transactions
+#[derive(Debug, Default)]
+pub struct LotInventory {
+    open: BTreeMap<(String, String), VecDeque<Lot>>,
+    closed: Vec<ClosedLot>,
+}
+
+impl LotInventory {
+    /// Track lots of transactions
+    ///
+    /// Transactions must be in chronological order.
+    ///
+    /// # Errors
+    /// Returns `Err` if closing position can't be matched with open lots
+    pub fn try_from(txns: &TxnRefs<'_>, matching: LotMatching) -> Result<Self, tackler::Error> {
+        let mut inventory = LotInventory::default();
+        for txn in txns {
+            for p in &txn.posts {
+                inventory
+                    .handle_posting(&txn.header.timestamp, p, matching)
+                    .map_err(|err| {
+                        let msg = format!(
+                            "Lot error with txn at {}{}: {err}",
+                            txn_ts::rfc_3339(&txn.header.timestamp),
+                            txn.header
+                                .uuid
+                                .map(|u| format!(" (uuid: {u})"))
+                                .unwrap_or_default()
+                        );
+                        msg
+                    })?;
+            }
+        }
+        Ok(inventory)
+    }
+
+    /// Open lots, ordered by account and commodity, and then by opening time
+    pub fn open_lots(&self) -> impl Iterator<Item = &Lot> {
+        self.open.values().flatten()
+    }
+
+    /// Closed lots, in closing order
+    #[must_use]
+    pub fn closed_lots(&self) -> &[ClosedLot] {
+        &self.closed
+    }
+
+    fn handle_posting(
+        &mut self,
+        ts: &Zoned,
+        p: &Posting,
+        matching: LotMatching,
+    ) -> Result<(), tackler::Error> {
+        let comm = &p.acctn.comm;
+        let key = (p.acctn.atn.account.clone(), comm.name.clone());
+
+        let closing_lots = self.open.get_mut(&key).filter(|lots| {
+            lots.front()
+                .is_some_and(|l| l.quantity.is_sign_positive() != p.amount.is_sign_positive())
+        });
+
+        if let Some(lots) = closing_lots {
+            let closed = Self::close_lots(lots, ts, p, matching)?;
+            self.closed.extend(closed);
+            if lots.is_empty() {
+                self.open.remove(&key);
+            }
+            return Ok(());
+        }
+
+        if let Some(cost) = &p.cost {
+            self.open.entry(key).or_default().push_back(Lot {
+                account: p.acctn.atn.account.clone(),
+                commodity: comm.clone(),
+                opened: ts.clone(),
+                quantity: p.amount,
+                unit_cost: cost.amount,
+                cost_commodity: cost.commodity.clone(),
+            });
+        } else if self.open.contains_key(&key) {
+            let msg = format!(
+                "Opening position '{{ ... }}' is missing for lot of '{}' in account '{}'",
+                comm.name, p.acctn.atn.account
+            );
+            return Err(msg.into());
+        }
+        Ok(())
+    }
+
+    fn close_lots(
+        lots: &mut VecDeque<Lot>,
+        ts: &Zoned,
+        p: &Posting,
+        matching: LotMatching,
+    ) -> Result<Vec<ClosedLot>, tackler::Error> {
+        if p.txn_commodity.name == p.acctn.comm.name {
+            let msg = format!(
+                "Closing position ('@' or '=') is missing for lot of '{}' in account '{}'",
+                p.acctn.comm.name, p.acctn.atn.account
+            );
+            return Err(msg.into());
+        }
+        let unit_price = p.txn_amount / p.amount;
+
+        let mut closed = Vec::new();
+        let mut remaining = p.amount.abs();
+        while !remaining.is_zero() {
+            let idx = match &p.cost {
+                Some(cost) => lots.iter().position(|l| {
+                    l.unit_cost == cost.amount && l.cost_commodity.name == cost.commodity.name
+                }),
+                None => match matching {
+                    LotMatching::Fifo => (!lots.is_empty()).then_some(0),
+                    LotMatching::Lifo => lots.len().checked_sub(1),
+                },
+            };
+            let Some(idx) = idx else {
+                let msg = format!(
+                    "Not enough open lots{} of '{}' in account '{}', missing quantity: {}",
+                    p.cost
+                        .as_ref()
+                        .map(|c| format!(" with unit cost {{{} {}}}", c.amount, c.commodity.name))
+                        .unwrap_or_default(),
+                    p.acctn.comm.name,
+                    p.acctn.atn.account,
+                    remaining
+                );
+                return Err(msg.into());
+            };
+
+            let lot = &mut lots[idx];
+            if lot.cost_commodity.name != p.txn_commodity.name {
+                let msg = format!(
+                    "Closing position commodity '{}' is different than cost commodity '{}' of the lot",
+                    p.txn_commodity.name, lot.cost_commodity.name
+                );
+                return Err(msg.into());
+            }
+
+            let qty = remaining.min(lot.quantity.abs());
+            let signed_qty = if lot.quantity.is_sign_negative() {
+                -qty
+            } else {
+                qty
+            };
+
+            closed.push(ClosedLot {
+                lot: Lot {
+                    quantity: signed_qty,
+                    ..lot.clone()
+                },
+                closed: ts.clone(),
+                unit_price,
+            });
+
+            lot.quantity -= signed_qty;
+            if lot.quantity.is_zero() {
+                lots.remove(idx);
+            }
+            remaining -= qty;
+        }
+        Ok(closed)
+    }
+}
+
+/// Market price of commodity in the cost commodity
+///
+/// Returns the latest price entry before `ts`,
+/// or the latest price if `ts` is not given.
+pub(crate) fn market_price(
+    price_db: &PriceDb,
+    commodity: &Commodity,
+    cost_commodity: &Commodity,
+    ts: Option<&Zoned>,
+) -> Option<(Zoned, Decimal)> {
+    price_db
+        .iter()
+        .filter(|e| {
+            e.base_commodity.name == commodity.name
+                && e.eq_commodity.name == cost_commodity.name
+                && ts.is_none_or(|ts| &e.timestamp < ts)
+        })
+        .max_by(|a, b| a.timestamp.cmp(&b.timestamp))
+        .map(|e| (e.timestamp.clone(), e.eq_amount))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kernel::Settings;
+    use crate::parser;
+    use indoc::indoc;
+    use rust_decimal_macros::dec;
+    use tackler_rs::IndocUtils;
+
+    fn lots(input: &str, matching: LotMatching) -> Result<LotInventory, tackler::Error> {
+        let txn_data =
+            parser::string_to_txns(&mut &*input, &Settings::default()).unwrap(/*:test:*/);
+        let txn_set = txn_data.get_all().unwrap(/*:test:*/);
+        LotInventory::try_from(&txn_set.txns, matching)
+    }
+
+    #[test]
+    // test: 523607ae-0e48-4ac7-a2b6-34ef452afd62
+    // desc: "TEP-1003 example: open and close with loss"
+    fn open_and_close() {
+        let txns = indoc!(
+            "|2014-05-01 'opening / buy
+             | inventory  1 USD {1.38 EUR}
+             | a:cash    -1.38 EUR
+             |
+             |2017-01-01 'close / sell at loss
+             | inventory  -1 USD {1.38 EUR} @ 1.04 EUR
+             | a:cash      1.04 EUR
+             |"
+        )
+        .strip_margin();
+
+        let inv = lots(&txns, LotMatching::Fifo).unwrap(/*:test:*/);
+        assert_eq!(inv.open_lots().count(), 0);
+        assert_eq!(inv.closed_lots().len(), 1);
+        assert_eq!(inv.closed_lots()[0].pnl(), dec!(-0.34));
+    }
+
+    #[test]
+    // test: d8249565-3bbf-43e1-b794-f56e2bfcf62d
+    // desc: "FIFO and LIFO matching with partial closing"
+    fn fifo_and_lifo() {
+        let txns = indoc!(
+            "|2020-01-01
+             | s  10 ACME {10 EUR}
+             | a
+             |
+             |2020-02-01
+             | s  10 ACME {20 EUR}
+             | a
+             |
+             |2020-03-01
+             | s  -15 ACME @ 30 EUR
+             | a
+             |"
+        )
+        .strip_margin();
+
+        let inv = lots(&txns, LotMatching::Fifo).unwrap(/*:test:*/);
+        let pnl: Decimal = inv.closed_lots().iter().map(ClosedLot::pnl).sum();
+        // 10 * (30 - 10) + 5 * (30 - 20)
+        assert_eq!(pnl, dec!(250));
+        let open: Vec<_> = inv.open_lots().collect();
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].quantity, dec!(5));
+        assert_eq!(open[0].unit_cost, dec!(20));
+
+        let inv = lots(&txns, LotMatching::Lifo).unwrap(/*:test:*/);
+        let pnl: Decimal = inv.closed_lots().iter().map(ClosedLot::pnl).sum();
+        // 10 * (30 - 20) + 5 * (30 - 10)
+        assert_eq!(pnl, dec!(200));
+        let open: Vec<_> = inv.open_lots().collect();
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].quantity, dec!(5));
+        assert_eq!(open[0].unit_cost, dec!(10));
+    }
+
+    #[test]
+    // test: 50b2544b-ba6f-4b68-8143-82cda122beff
+    // desc: "specific lot matching and short position"
+    fn specific_lot_and_short() {
+        let txns = indoc!(
+            "|2020-01-01
+             | s  10 ACME {10 EUR}
+             | a
+             |
+             |2020-02-01
+             | s  10 ACME {20 EUR}
+             | a
+             |
+             |2020-03-01
+             | s  -10 ACME {20 EUR} @ 15 EUR
+             | a
+             |
+             |2020-04-01
+             | x  -2 ACME {15 EUR}
+             | a
+             |
+             |2020-05-01
+             | x  2 ACME = 20 EUR
+             | a
+             |"
+        )
+        .strip_margin();
+
+        let inv = lots(&txns, LotMatching::Fifo).unwrap(/*:test:*/);
+        let closed = inv.closed_lots();
+        assert_eq!(closed.len(), 2);
+        assert_eq!(closed[0].pnl(), dec!(-50));
+        // short: -2 * (10 - 15)
+        assert_eq!(closed[1].pnl(), dec!(10));
+
+        let open: Vec<_> = inv.open_lots().collect();
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].unit_cost, dec!(10));
+    }
+
+    #[test]
+    // test: 5922357f-97e3-482a-8e48-fe73ca0f8d46
+    // desc: "lot errors"
+    fn lot_errors() {
+        let err_txns = [
+            // not enough lots
+            indoc!(
+                "|2020-01-01
+                 | s  10 ACME {10 EUR}
+                 | a
+                 |
+                 |2020-03-01
+                 | s  -11 ACME @ 30 EUR
+                 | a
+                 |"
+            ),
+            // no lot with specific cost
+            indoc!(
+                "|2020-01-01
+                 | s  10 ACME {10 EUR}
+                 | a
+                 |
+                 |2020-03-01
+                 | s  -1 ACME {11 EUR} @ 30 EUR
+                 | a
+                 |"
+            ),
+            // no closing price
+            indoc!(
+                "|2020-01-01
+                 | s  10 ACME {10 EUR}
+                 | a
+                 |
+                 |2020-03-01
+                 | s  -1 ACME
+                 | a
+                 |"
+            ),
+            // different commodity for price and cost
+            indoc!(
+                "|2020-01-01
+                 | s  10 ACME {10 EUR}
+                 | a
+                 |
+                 |2020-03-01
+                 | s  -1 ACME @ 30 USD
+                 | a
+                 |"
+            ),
+            // increasing lot without opening position
+            indoc!(
+                "|2020-01-01
+                 | s  10 ACME {10 EUR}
+                 | a
+                 |
+                 |2020-03-01
+                 | s  1 ACME @ 30 EUR
+                 | a
+                 |"
+            ),
+        ];
+        for (i, t) in err_txns.iter().enumerate() {
+            let txns = t.strip_margin();
+            assert!(
+                lots(&txns, LotMatching::Fifo).is_err(),
+                "Offending test vector item: {i}"
+            );
+        }
+    }
+}
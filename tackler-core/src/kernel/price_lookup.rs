@@ -51,6 +51,11 @@ impl PriceLookupCtx<'_> {
     pub fn is_empty(&self) -> bool {
         self.cache.is_empty()
     }
+
+    /// Target commodity of price conversion, if any
+    pub(crate) fn in_commodity(&self) -> Option<&Arc<Commodity>> {
+        self.in_commodity.as_ref()
+    }
 }
 
 impl PriceLookupCtx<'_> {
@@ -5,6 +5,7 @@
 
 use crate::kernel::Predicate;
 use crate::kernel::hash::Hash;
+use crate::kernel::lots::Lot;
 use crate::model::{BalanceTreeNode, RegisterPosting};
 use crate::tackler;
 use regex::RegexSet;
@@ -222,3 +223,68 @@ impl ReportItemSelector for RegisterAllSelector {
         }
     }
 }
+
+pub trait LotItemSelector: Predicate<Lot> {}
+pub trait LotSelector: LotItemSelector + ReportItemSelector {}
+
+pub struct LotByAccountSelector {
+    regexs: RegexSet,
+}
+
+impl LotByAccountSelector {
+    /// # Errors
+    /// Returns `Err` in case of invalid pattern
+    pub fn try_from(patterns: &[&str]) -> Result<LotByAccountSelector, tackler::Error> {
+        let las = LotByAccountSelector {
+            regexs: new_full_haystack_regex_set(patterns)?,
+        };
+        Ok(las)
+    }
+}
+
+impl LotSelector for LotByAccountSelector {}
+impl LotItemSelector for LotByAccountSelector {}
+
+impl Predicate<Lot> for LotByAccountSelector {
+    fn eval(&self, lot: &Lot) -> bool {
+        self.regexs.is_match(&lot.account)
+    }
+}
+
+impl ReportItemSelector for LotByAccountSelector {
+    fn selectors(&self) -> Vec<String> {
+        let mut accsel = peeled_patterns(&self.regexs);
+        accsel.sort();
+        accsel
+    }
+
+    fn checksum(&self, hash: Hash) -> Checksum {
+        let accsel = self.selectors();
+        hash.checksum(&accsel, "\n".as_bytes())
+    }
+}
+
+#[derive(Default)]
+pub struct LotAllSelector {}
+
+impl Predicate<Lot> for LotAllSelector {
+    fn eval(&self, _: &Lot) -> bool {
+        true
+    }
+}
+
+impl LotItemSelector for LotAllSelector {}
+impl LotSelector for LotAllSelector {}
+
+impl ReportItemSelector for LotAllSelector {
+    fn selectors(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    fn checksum(&self, _hash: Hash) -> Checksum {
+        Checksum {
+            algorithm: "None".to_string(),
+            value: "select all".to_string(),
+        }
+    }
+}
@@ -2,7 +2,7 @@
  * Tackler-NG 2025
  * SPDX-License-Identifier: Apache-2.0
  */
-use crate::config::{BalanceType, Scale};
+use crate::config::{BalanceType, BudgetItem, LotMatching, Scale};
 use crate::kernel::Settings;
 use crate::kernel::price_lookup::PriceLookup;
 use crate::model::Commodity;
@@ -15,6 +15,8 @@ use tackler_api::txn_ts::{GroupBy, TimestampStyle};
 pub struct BalanceSettings {
     pub(crate) title: String,
     pub(crate) bal_type: BalanceType,
+    pub(crate) depth: Option<usize>,
+    pub(crate) inventory: bool,
     pub(crate) ras: Vec<String>,
     pub(crate) scale: Scale,
     pub(crate) inverted: bool,
@@ -29,6 +31,8 @@ impl TryFrom<&Settings> for BalanceSettings {
         Ok(BalanceSettings {
             title: settings.report.balance.title.clone(),
             bal_type: settings.report.balance.bal_type.clone(),
+            depth: settings.report.balance.depth,
+            inventory: settings.report.balance.inventory,
             ras: settings.get_balance_ras(),
             scale: settings.report.scale.clone(),
             inverted: settings.inverted,
@@ -42,8 +46,11 @@ impl TryFrom<&Settings> for BalanceSettings {
 pub struct BalanceGroupSettings {
     pub title: String,
     pub bal_type: BalanceType,
+    pub depth: Option<usize>,
     pub ras: Vec<String>,
     pub group_by: GroupBy,
+    pub pivot: bool,
+    pub column_totals: bool,
     pub report_tz: TimeZone,
     pub scale: Scale,
     pub inverted: bool,
@@ -58,8 +65,11 @@ impl TryFrom<&Settings> for BalanceGroupSettings {
         let bgs = BalanceGroupSettings {
             title: settings.report.balance_group.title.clone(),
             bal_type: settings.report.balance_group.bal_type.clone(),
+            depth: settings.report.balance_group.depth,
             ras: settings.get_balance_group_ras(),
             group_by: settings.report.balance_group.group_by,
+            pivot: settings.report.balance_group.pivot,
+            column_totals: settings.report.balance_group.column_totals,
             report_tz: settings.report.tz.clone(),
             scale: settings.report.scale.clone(),
             inverted: settings.inverted,
@@ -75,6 +85,8 @@ impl From<BalanceGroupSettings> for BalanceSettings {
         BalanceSettings {
             title: String::default(),
             bal_type: bgs.bal_type.clone(),
+            depth: bgs.depth,
+            inventory: false,
             ras: bgs.ras.clone(),
             scale: bgs.scale.clone(),
             inverted: bgs.inverted,
@@ -94,6 +106,7 @@ pub struct RegisterSettings {
     pub inverted: bool,
     pub price_lookup: PriceLookup,
     pub timestamp_style: TimestampStyle,
+    pub cost_basis: bool,
 }
 
 impl TryFrom<&Settings> for RegisterSettings {
@@ -109,7 +122,131 @@ impl TryFrom<&Settings> for RegisterSettings {
             report_commodity: settings.get_report_commodity(),
             price_lookup: settings.get_price_lookup(),
             timestamp_style: settings.report.register.timestamp_style,
+            cost_basis: settings.report.register.cost_basis,
         };
         Ok(rs)
     }
 }
+
+#[derive(Debug, Clone)]
+pub struct PnlSettings {
+    pub title: String,
+    pub ras: Vec<String>,
+    pub report_tz: TimeZone,
+    pub(crate) scale: Scale,
+    pub lot_matching: LotMatching,
+    pub price_lookup: PriceLookup,
+}
+
+impl TryFrom<&Settings> for PnlSettings {
+    type Error = tackler::Error;
+
+    fn try_from(settings: &Settings) -> Result<PnlSettings, tackler::Error> {
+        Ok(PnlSettings {
+            title: settings.report.pnl.title.clone(),
+            ras: settings.get_pnl_ras(),
+            report_tz: settings.report.tz.clone(),
+            scale: settings.report.scale.clone(),
+            lot_matching: settings.get_lot_matching(),
+            price_lookup: settings.get_price_lookup(),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BudgetSettings {
+    pub title: String,
+    pub(crate) items: Vec<BudgetItem>,
+    pub group_by: GroupBy,
+    pub report_tz: TimeZone,
+    pub(crate) scale: Scale,
+    pub inverted: bool,
+    pub report_commodity: Option<Arc<Commodity>>,
+    pub price_lookup: PriceLookup,
+}
+
+impl TryFrom<&Settings> for BudgetSettings {
+    type Error = tackler::Error;
+
+    fn try_from(settings: &Settings) -> Result<BudgetSettings, tackler::Error> {
+        Ok(BudgetSettings {
+            title: settings.report.budget.title.clone(),
+            items: settings.report.budget.items.clone(),
+            group_by: settings.report.budget.group_by,
+            report_tz: settings.report.tz.clone(),
+            scale: settings.report.scale.clone(),
+            inverted: settings.inverted,
+            report_commodity: settings.get_report_commodity(),
+            price_lookup: settings.get_price_lookup(),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct IncomeStatementSettings {
+    pub title: String,
+    pub revenues: Vec<String>,
+    pub expenses: Vec<String>,
+    pub depth: Option<usize>,
+    pub group_by: Option<GroupBy>,
+    pub report_tz: TimeZone,
+    pub(crate) scale: Scale,
+    pub inverted: bool,
+    pub report_commodity: Option<Arc<Commodity>>,
+    pub price_lookup: PriceLookup,
+}
+
+impl TryFrom<&Settings> for IncomeStatementSettings {
+    type Error = tackler::Error;
+
+    fn try_from(settings: &Settings) -> Result<IncomeStatementSettings, tackler::Error> {
+        let is = &settings.report.income_statement;
+        Ok(IncomeStatementSettings {
+            title: is.title.clone(),
+            revenues: is.revenues.clone(),
+            expenses: is.expenses.clone(),
+            depth: is.depth,
+            group_by: is.group_by,
+            report_tz: settings.report.tz.clone(),
+            scale: settings.report.scale.clone(),
+            inverted: settings.inverted,
+            report_commodity: settings.get_report_commodity(),
+            price_lookup: settings.get_price_lookup(),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CashFlowSettings {
+    pub title: String,
+    pub cash_accounts: Vec<String>,
+    pub depth: Option<usize>,
+    pub group_by: Option<GroupBy>,
+    pub report_tz: TimeZone,
+    pub(crate) scale: Scale,
+    pub report_commodity: Option<Arc<Commodity>>,
+    pub price_lookup: PriceLookup,
+}
+
+impl TryFrom<&Settings> for CashFlowSettings {
+    type Error = tackler::Error;
+
+    fn try_from(settings: &Settings) -> Result<CashFlowSettings, tackler::Error> {
+        let cf = &settings.report.cash_flow;
+        if cf.cash_accounts.is_empty() {
+            let msg = "Cash Flow Report: there are no cash accounts, \
+                       see 'report.cash-flow.cash-accounts'";
+            return Err(msg.into());
+        }
+        Ok(CashFlowSettings {
+            title: cf.title.clone(),
+            cash_accounts: cf.cash_accounts.clone(),
+            depth: cf.depth,
+            group_by: cf.group_by,
+            report_tz: settings.report.tz.clone(),
+            scale: settings.report.scale.clone(),
+            report_commodity: settings.get_report_commodity(),
+            price_lookup: settings.get_price_lookup(),
+        })
+    }
+}
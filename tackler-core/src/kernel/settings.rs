@@ -2,21 +2,27 @@
  * Tackler-NG 2023-2025
  * SPDX-License-Identifier: Apache-2.0
  */
-use crate::config::overlaps::{InputOverlap, OverlapConfig, StorageOverlap};
+use crate::config::overlaps::{ForecastOverlap, InputOverlap, OverlapConfig, StorageOverlap};
 use crate::config::{
-    AccountSelectors, Config, Export, ExportType, Kernel, PriceLookupType, Report, ReportType,
-    StorageType,
+    AccountKind, AccountSelectors, AccountSpec, Config, Export, ExportType, InputFormat, Kernel,
+    LotMatching, PriceLookupType, RecurringSpec, Report, ReportType, StorageType,
 };
+use crate::diagnostic;
+use crate::diagnostic::Diagnostic;
+use crate::kernel::forecast::Forecast;
 use crate::kernel::hash::Hash;
 use crate::kernel::price_lookup::PriceLookup;
-use crate::model::TxnAccount;
-use crate::model::price_entry::PriceDb;
+use crate::lint::Lints;
+use crate::model::price_entry::{PriceDb, PriceEntry, merge_price_dbs};
+use crate::model::{AccountLifecycle, AccountPeriod, Posts, TxnAccount};
 use crate::model::{AccountTreeNode, Commodity};
 use crate::{config, parser, tackler};
+use itertools::Itertools;
 use jiff::Zoned;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
-use std::sync::Arc;
+use std::sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use tackler_api::filters::FilterDefinition;
 use tackler_api::txn_header::Tag;
 use tackler_api::txn_ts::GroupBy;
 use tackler_rs::normalize_extension;
@@ -37,6 +43,14 @@ pub struct FsInput {
 pub enum GitInputSelector {
     CommitId(String),
     Reference(String),
+    /// Range of commits (base, head)
+    ///
+    /// Only txns which are changed between base and head are used.
+    Range(String, String),
+    /// Staged txns (index) on top of HEAD
+    Index,
+    /// Txns of the working tree (with unstaged changes and untracked files) on top of HEAD
+    WorkTree,
 }
 
 #[derive(Debug, Clone)]
@@ -179,14 +193,56 @@ pub struct Settings {
     pub(crate) export: Export,
     strict_mode: bool,
     pub(crate) inverted: bool,
+    /// Report accounts which are closed over the whole report period
+    include_closed: bool,
     input_config: InputSettings,
     kernel: Kernel,
     pub price: Price,
     price_lookup: PriceLookup,
     global_acc_sel: Option<AccountSelectors>,
-    accounts: AccountTrees,
-    commodities: Commodities,
-    tags: HashMap<String, Arc<Tag>>,
+    // Interned accounts, commodities and tags are shared between parser threads
+    accounts: RwLock<AccountTrees>,
+    commodities: RwLock<Commodities>,
+    tags: RwLock<HashMap<String, Arc<Tag>>>,
+    account_lifecycles: HashMap<String, AccountLifecycle>,
+    pub(crate) recurring: Vec<RecurringSpec>,
+    pub(crate) forecast: Option<Forecast>,
+    pub(crate) input_format: InputFormat,
+    // Price entries of the journal (e.g. Ledger `P` lines), shared between parser threads
+    journal_prices: RwLock<PriceDb>,
+    filters: BTreeMap<String, FilterDefinition>,
+    // Errors of the journal data, shared between parser threads.
+    // These are collected only in check mode (when this is `Some`).
+    diagnostics: Option<RwLock<Vec<Diagnostic>>>,
+    pub(crate) lints: Lints,
+    pub(crate) dedupe: config::Dedupe,
+}
+
+/// Lock guards for the interning registry
+///
+/// Lock is never held while calling outside of settings,
+/// so poisoned lock can only be caused by panic inside of
+/// interning functions and their data is still consistent.
+fn unknown_account(name: &str) -> tackler::Error {
+    let msg = format!("Unknown account: '{name}'");
+    Diagnostic::error(diagnostic::UNKNOWN_ACCOUNT, msg)
+        .with_help("Add the account to the Chart of Accounts, or turn off strict mode")
+        .into()
+}
+
+fn unknown_commodity(name: &str) -> tackler::Error {
+    let msg = format!("Unknown commodity: '{name}'");
+    Diagnostic::error(diagnostic::UNKNOWN_COMMODITY, msg)
+        .with_help("Add the commodity to the Chart of Commodities, or turn off strict mode")
+        .into()
+}
+
+fn read_lock<T>(lock: &RwLock<T>) -> RwLockReadGuard<'_, T> {
+    lock.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn write_lock<T>(lock: &RwLock<T>) -> RwLockWriteGuard<'_, T> {
+    lock.write().unwrap_or_else(PoisonError::into_inner)
 }
 
 impl Default for Settings {
@@ -195,6 +251,7 @@ impl Default for Settings {
             strict_mode: false,
             audit_mode: false,
             inverted: false,
+            include_closed: false,
             input_config: InputSettings::default(),
             report: Report::default(),
             export: Export::default(),
@@ -202,9 +259,18 @@ impl Default for Settings {
             price: Price::default(),
             price_lookup: PriceLookup::default(),
             global_acc_sel: None,
-            accounts: AccountTrees::default(),
-            commodities: Commodities::default_empty_ok(),
-            tags: HashMap::new(),
+            accounts: RwLock::new(AccountTrees::default()),
+            commodities: RwLock::new(Commodities::default_empty_ok()),
+            tags: RwLock::new(HashMap::new()),
+            account_lifecycles: HashMap::new(),
+            recurring: Vec::new(),
+            forecast: None,
+            input_format: InputFormat::default(),
+            journal_prices: RwLock::new(PriceDb::new()),
+            filters: BTreeMap::new(),
+            diagnostics: None,
+            lints: Lints::default(),
+            dedupe: config::Dedupe::default(),
         }
     }
 }
@@ -247,6 +313,7 @@ impl Settings {
         let audit_mode = overlaps.audit.mode.unwrap_or(cfg.kernel.audit.mode);
 
         let input_settings = Self::input_settings(&cfg, &overlaps.storage)?;
+        let input_format = overlaps.storage.format.unwrap_or(cfg.kernel.input.format);
 
         let reports = match overlaps.target.reports {
             Some(reports) => config::to_report_targets(&reports)?,
@@ -264,9 +331,30 @@ impl Settings {
 
         let lookup_type = overlaps.price.lookup_type.unwrap_or(cfg.price.lookup_type);
 
-        let db_path = overlaps.price.db_path.unwrap_or(cfg.price.db_path.clone());
+        let db_paths = overlaps
+            .price
+            .db_path
+            .map_or_else(|| cfg.price.db_paths.clone(), |db_path| vec![db_path]);
 
-        let account_trees = AccountTrees::from(&cfg.transaction.accounts.names, strict_mode)?;
+        let account_specs = cfg.transaction.accounts.specs.clone();
+        let account_names = cfg
+            .transaction
+            .accounts
+            .names
+            .iter()
+            .chain(account_specs.iter().map(|spec| &spec.name))
+            .unique()
+            .cloned()
+            .collect::<Vec<_>>();
+        let account_trees = AccountTrees::from(&account_names, strict_mode)?;
+
+        let lints = Lints::try_from(
+            &cfg.lint,
+            &account_names,
+            &cfg.transaction.commodities.names,
+            &cfg.transaction.tags.names,
+            db_paths.clone(),
+        )?;
 
         let mut commodities = Commodities::from(&cfg)?;
 
@@ -328,6 +416,7 @@ impl Settings {
             strict_mode,
             audit_mode,
             inverted: overlaps.report.inverted,
+            include_closed: overlaps.report.include_closed,
             kernel: cfg.kernel,
             input_config: input_settings,
             price: Price::default(), // this is not real, see next one
@@ -343,11 +432,26 @@ impl Settings {
                 targets: exports,
                 ..cfg.export
             },
-            accounts: account_trees,
-            commodities,
-            tags,
+            accounts: RwLock::new(account_trees),
+            commodities: RwLock::new(commodities),
+            tags: RwLock::new(tags),
+            account_lifecycles: HashMap::new(),
+            recurring: cfg.transaction.recurring,
+            forecast: None,
+            input_format,
+            journal_prices: RwLock::new(PriceDb::new()),
+            filters: cfg.filters,
+            diagnostics: None,
+            lints,
+            dedupe: cfg.dedupe,
         };
         tmp_settings.report.balance_group.group_by = group_by;
+        if let Some(depth) = overlaps.report.depth {
+            tmp_settings.report.balance.depth = Some(depth);
+            tmp_settings.report.balance_group.depth = Some(depth);
+        }
+        tmp_settings.account_lifecycles = tmp_settings.account_lifecycles(&account_specs)?;
+        tmp_settings.forecast = tmp_settings.forecast(&overlaps.forecast)?;
 
         let given_time = overlaps.price.before_time;
 
@@ -381,7 +485,7 @@ impl Settings {
             PriceLookupType::None => Price::default(),
             _ => Price {
                 // we need half-baked settings here bc commodity and timestamp lookups
-                price_db: parser::pricedb_from_file(&db_path, &mut tmp_settings)?,
+                price_db: parser::pricedb_from_paths(&db_paths, &tmp_settings)?,
                 lookup_type,
             },
         };
@@ -413,23 +517,165 @@ impl Settings {
     ) -> Result<TxnAccount, tackler::Error> {
         let comm = self.get_commodity(commodity.name.as_str())?;
 
-        match self.accounts.defined_accounts.get(name) {
+        let accounts = read_lock(&self.accounts);
+        match accounts.defined_accounts.get(name) {
             Some(account_tree) => Ok(TxnAccount {
                 atn: account_tree.clone(),
                 comm,
             }),
             None => {
-                if let Some(acc_parent) = self.accounts.synthetic_parents.get(name) {
+                if let Some(acc_parent) = accounts.synthetic_parents.get(name) {
                     Ok(TxnAccount {
                         atn: acc_parent.clone(),
                         comm,
                     })
                 } else {
-                    let msg = format!("Unknown account: '{name}'");
-                    Err(msg.into())
+                    Err(unknown_account(name))
+                }
+            }
+        }
+    }
+
+    /// Resolve forecast window
+    ///
+    /// Timestamps are handled exactly like txn timestamps.
+    fn forecast(&self, overlap: &ForecastOverlap) -> Result<Option<Forecast>, tackler::Error> {
+        let parse_ts = |ts: &String| {
+            self.parse_timestamp(ts)
+                .map_err(|err| format!("Invalid forecast timestamp '{ts}': {err}"))
+        };
+        match (&overlap.begin, &overlap.end) {
+            (begin, Some(end)) => {
+                let begin = begin.as_ref().map(parse_ts).transpose()?;
+                let end = parse_ts(end)?;
+                if begin.as_ref().is_some_and(|b| *b >= end) {
+                    let msg = "Forecast begin must be before forecast end";
+                    return Err(msg.into());
+                }
+                Ok(Some(Forecast { begin, end }))
+            }
+            (Some(_), None) => {
+                let msg = "Forecast begin is given, but there is no forecast end";
+                Err(msg.into())
+            }
+            (None, None) => Ok(None),
+        }
+    }
+
+    /// Resolve `[[account]]` definitions into account lifecycles
+    ///
+    /// Open and close timestamps are handled exactly like txn timestamps.
+    fn account_lifecycles(
+        &self,
+        specs: &[AccountSpec],
+    ) -> Result<HashMap<String, AccountLifecycle>, tackler::Error> {
+        let mut periods: HashMap<String, (Vec<AccountPeriod>, Option<AccountKind>)> =
+            HashMap::new();
+
+        for spec in specs {
+            let parse_ts = |ts: Option<&String>| {
+                ts.map(|ts| self.parse_timestamp(ts))
+                    .transpose()
+                    .map_err(|err| {
+                        format!("Invalid Chart of Accounts: account '{}': {err}", spec.name)
+                    })
+            };
+            let open = parse_ts(spec.open.as_ref())?;
+            let close = parse_ts(spec.close.as_ref())?;
+            let period = AccountPeriod::try_from(open, close).map_err(|err| {
+                format!("Invalid Chart of Accounts: account '{}': {err}", spec.name)
+            })?;
+
+            let entry = periods
+                .entry(spec.name.clone())
+                .or_insert_with(|| (Vec::new(), None));
+            entry.0.push(period);
+            // kind is the same for all periods, it's enough to define it once
+            entry.1 = entry.1.or(spec.kind);
+        }
+
+        periods
+            .into_iter()
+            .map(|(name, (periods, kind))| {
+                // open-ended period without any bounds is "always open"
+                let periods = periods
+                    .into_iter()
+                    .filter(|p| p.open.is_some() || p.close.is_some())
+                    .collect();
+                match AccountLifecycle::try_from(periods, kind) {
+                    Ok(lc) => Ok((name, lc)),
+                    Err(err) => {
+                        let msg = format!("Invalid Chart of Accounts: account '{name}': {err}");
+                        Err(msg.into())
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// Get lifecycle attributes of the account, if it has any
+    #[must_use]
+    pub fn get_account_lifecycle(&self, name: &str) -> Option<&AccountLifecycle> {
+        self.account_lifecycles.get(name)
+    }
+
+    /// Get `kind` of the account
+    ///
+    /// The kind is inherited from the nearest ancestor
+    /// if the account doesn't declare it by itself.
+    #[must_use]
+    pub fn get_account_kind(&self, name: &str) -> Option<AccountKind> {
+        let mut account = name;
+        loop {
+            if let Some(kind) = self.account_lifecycles.get(account).and_then(|lc| lc.kind) {
+                return Some(kind);
+            }
+            match account.rfind(':') {
+                Some(idx) => account = &account[..idx],
+                None => return None,
+            }
+        }
+    }
+
+    /// Test if account is reported for the period `[begin, end]`
+    ///
+    /// Account is reported if it is open at any time of the period.
+    /// Accounts without lifecycle are always open, and with `--include-closed`
+    /// all accounts are reported.
+    pub(crate) fn is_reported_account(&self, name: &str, begin: &Zoned, end: &Zoned) -> bool {
+        self.include_closed
+            || self
+                .account_lifecycles
+                .get(name)
+                .is_none_or(|lc| lc.is_open_during(begin, end))
+    }
+
+    /// Check that all posting accounts are open at the time of txn
+    ///
+    /// This is only enforced in strict mode.
+    pub(crate) fn check_account_periods(
+        &self,
+        ts: &Zoned,
+        posts: &Posts,
+    ) -> Result<(), tackler::Error> {
+        if !self.strict_mode {
+            return Ok(());
+        }
+        for p in posts {
+            let account = p.acctn.atn.account.as_str();
+            if let Some(lc) = self.account_lifecycles.get(account) {
+                if !lc.is_open_at(ts) {
+                    let msg = format!(
+                        "Account is not open at the time of txn: '{account}' at {}",
+                        tackler_api::txn_ts::rfc_3339(ts)
+                    );
+                    let diag = Diagnostic::error(diagnostic::ACCOUNT_NOT_OPEN, msg)
+                        .with_help("Check 'open' and 'close' of the account in Chart of Accounts");
+                    return Err(diag.into());
                 }
             }
         }
+        Ok(())
     }
 
     /// Get or create `TxnAccount` by name and commodity
@@ -437,14 +683,31 @@ impl Settings {
     /// Both name and commodity must be valid name and ID
     /// e.g. this is function is supposed to be used by parser.
     pub(crate) fn get_or_create_txn_account(
-        &mut self,
+        &self,
         name: &str,
         commodity: &Arc<Commodity>,
     ) -> Result<TxnAccount, tackler::Error> {
         let comm = self.get_or_create_commodity(Some(commodity.name.as_str()))?;
 
         let strict_mode = self.strict_mode;
-        let atn_opt = self.accounts.defined_accounts.get(name).cloned();
+        {
+            // Fast path: account and its parents are already known
+            let accounts = read_lock(&self.accounts);
+            if let Some(atn) = accounts.defined_accounts.get(name) {
+                if strict_mode
+                    || atn.is_root()
+                    || accounts.defined_accounts.contains_key(atn.parent.as_str())
+                {
+                    return Ok(TxnAccount {
+                        atn: atn.clone(),
+                        comm,
+                    });
+                }
+            }
+        }
+
+        let mut accounts = write_lock(&self.accounts);
+        let atn_opt = accounts.defined_accounts.get(name).cloned();
 
         let atn = if let Some(account_tree) = atn_opt {
             TxnAccount {
@@ -453,21 +716,18 @@ impl Settings {
             }
         } else {
             if self.strict_mode {
-                let msg = format!("Unknown account: '{name}'");
-                return Err(msg.into());
+                return Err(unknown_account(name));
             }
             let atn = Arc::new(AccountTreeNode::unchecked_from(name));
-            self.accounts
-                .defined_accounts
-                .insert(name.into(), atn.clone());
-            AccountTrees::build_account_tree(&mut self.accounts.defined_accounts, &atn, None)?;
+            accounts.defined_accounts.insert(name.into(), atn.clone());
+            AccountTrees::build_account_tree(&mut accounts.defined_accounts, &atn, None)?;
 
             TxnAccount { atn, comm }
         };
         if !strict_mode {
             // Not strict mode, so we build the (missing) parents
             // directly into main Chart of Accounts
-            AccountTrees::build_account_tree(&mut self.accounts.defined_accounts, &atn.atn, None)?;
+            AccountTrees::build_account_tree(&mut accounts.defined_accounts, &atn.atn, None)?;
         }
 
         Ok(atn)
@@ -476,11 +736,10 @@ impl Settings {
     /// # Errors
     /// Returns reference for commodity, error if it doesn't exist
     pub fn get_commodity(&self, name: &str) -> Result<Arc<Commodity>, tackler::Error> {
-        if let Some(comm) = self.commodities.names.get(name) {
+        if let Some(comm) = read_lock(&self.commodities).names.get(name) {
             Ok(comm.clone())
         } else {
-            let msg = format!("Unknown commodity: '{name}'");
-            Err(msg.into())
+            Err(unknown_commodity(name))
         }
     }
 
@@ -489,10 +748,17 @@ impl Settings {
     /// The name must be a valid ID
     /// e.g. this is function is supposed to be used by parser.
     pub(crate) fn get_or_create_commodity(
-        &mut self,
+        &self,
         name: Option<&str>,
     ) -> Result<Arc<Commodity>, tackler::Error> {
-        Self::inner_get_or_create_commodity(&mut self.commodities, self.strict_mode, name)
+        if let Some(comm) = name.and_then(|n| read_lock(&self.commodities).names.get(n).cloned()) {
+            return Ok(comm);
+        }
+        Self::inner_get_or_create_commodity(
+            &mut write_lock(&self.commodities),
+            self.strict_mode,
+            name,
+        )
     }
 
     fn inner_get_or_create_commodity(
@@ -521,8 +787,7 @@ impl Settings {
                 Some(comm) => Ok(comm.clone()),
                 None => {
                     if strict_mode {
-                        let msg = format!("Unknown commodity: '{n}'");
-                        Err(msg.into())
+                        Err(unknown_commodity(n))
                     } else {
                         let comm = Arc::new(Commodity::unchecked_from(n));
                         commodities.names.insert(n.into(), comm.clone());
@@ -536,26 +801,142 @@ impl Settings {
         }
     }
 
-    pub(crate) fn get_or_create_tag(&mut self, name: &str) -> Result<Arc<Tag>, tackler::Error> {
+    pub(crate) fn get_or_create_tag(&self, name: &str) -> Result<Arc<Tag>, tackler::Error> {
         if name.is_empty() {
             let msg = "Tag name is empty string".to_string();
             return Err(msg.into());
         }
-        match self.tags.get(name) {
-            Some(tag) => Ok(tag.clone()),
-            None => {
-                if self.strict_mode {
-                    let msg = format!("Unknown tag: '{name}'");
-                    Err(msg.into())
-                } else {
-                    let tag = Arc::new(Tag::from(name));
-                    self.tags.insert(name.into(), tag.clone());
-                    Ok(tag)
-                }
+        if let Some(tag) = read_lock(&self.tags).get(name) {
+            return Ok(tag.clone());
+        }
+        if self.strict_mode {
+            let msg = format!("Unknown tag: '{name}'");
+            let diag = Diagnostic::error(diagnostic::UNKNOWN_TAG, msg)
+                .with_help("Add the tag to the Chart of Tags, or turn off strict mode");
+            Err(diag.into())
+        } else {
+            let tag = write_lock(&self.tags)
+                .entry(name.into())
+                .or_insert_with(|| Arc::new(Tag::from(name)))
+                .clone();
+            Ok(tag)
+        }
+    }
+
+    pub(crate) fn add_journal_price(&self, price_entry: PriceEntry) {
+        write_lock(&self.journal_prices).push(price_entry);
+    }
+
+    /// Merge price entries of the journal into the price database
+    ///
+    /// Some input formats (e.g. Ledger) can have price entries inside
+    /// the journal, and these are collected while txns are parsed.
+    /// Journal is merged as its own source, see [`merge_price_dbs`], so its
+    /// prices which conflict with the price database (or with each other)
+    /// are reported as errors. Same entry can be in the journal many times,
+    /// e.g. with git range input, where both ends of the range are parsed.
+    ///
+    /// This must be called after parsing, and before reports are generated.
+    ///
+    /// # Errors
+    /// Returns `Err` if journal prices conflict with the price database
+    pub fn merge_journal_prices(&mut self) -> Result<(), tackler::Error> {
+        let journal_prices = std::mem::take(
+            self.journal_prices
+                .get_mut()
+                .unwrap_or_else(PoisonError::into_inner),
+        );
+        if journal_prices.is_empty() {
+            return Ok(());
+        }
+        let price_db = std::mem::take(&mut self.price.price_db);
+        self.price.price_db = merge_price_dbs(vec![
+            ("price database".to_string(), price_db),
+            ("journal".to_string(), journal_prices),
+        ])?;
+        Ok(())
+    }
+
+    /// Activate check mode
+    ///
+    /// In check mode, parsing of the journal continues after errors,
+    /// and all parse and semantic errors are collected, see [`Settings::take_diagnostics`].
+    pub fn enable_check_mode(&mut self) {
+        self.diagnostics.get_or_insert_default();
+    }
+
+    /// Activate journal lints
+    ///
+    /// This activates also check mode, and lints are reported as diagnostics,
+    /// see [`Settings::take_diagnostics`] and [`crate::lint::check_txn_data`].
+    /// Price database is loaded for the lints if it's not yet loaded.
+    ///
+    /// # Errors
+    /// Returns `Err` if the price database can't be loaded
+    pub fn enable_lints(&mut self) -> Result<(), tackler::Error> {
+        self.enable_check_mode();
+        self.lints.enable();
+        let db_paths = &self.lints.price_db_paths;
+        if self.price.price_db.is_empty()
+            && !db_paths.is_empty()
+            && db_paths.iter().all(|db_path| db_path.exists())
+        {
+            self.price.price_db = parser::pricedb_from_paths(db_paths, self)?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn is_strict_mode(&self) -> bool {
+        self.strict_mode
+    }
+
+    #[must_use]
+    pub fn is_check_mode(&self) -> bool {
+        self.diagnostics.is_some()
+    }
+
+    /// Handle errors of the journal data
+    ///
+    /// In check mode, all errors are collected and processing continues,
+    /// otherwise the first error is returned.
+    pub(crate) fn check_errors(&self, errors: Vec<Diagnostic>) -> Result<(), tackler::Error> {
+        match &self.diagnostics {
+            Some(diagnostics) => {
+                write_lock(diagnostics).extend(errors);
+                Ok(())
             }
+            None => errors
+                .into_iter()
+                .next()
+                .map_or(Ok(()), |diag| Err(diag.into())),
         }
     }
 
+    /// Take collected diagnostics of the check mode
+    ///
+    /// Diagnostics with location are sorted by path, line and column,
+    /// and these are followed by diagnostics of the whole txn data.
+    pub fn take_diagnostics(&mut self) -> Vec<Diagnostic> {
+        let mut diagnostics = match &mut self.diagnostics {
+            Some(diagnostics) => std::mem::take(
+                diagnostics
+                    .get_mut()
+                    .unwrap_or_else(PoisonError::into_inner),
+            ),
+            None => Vec::new(),
+        };
+        let unlocated = |d: &Diagnostic| d.path.is_none() && d.line.is_none();
+        diagnostics.sort_by(|a, b| {
+            (unlocated(a), &a.path, a.line, a.column).cmp(&(
+                unlocated(b),
+                &b.path,
+                b.line,
+                b.column,
+            ))
+        });
+        diagnostics
+    }
+
     #[must_use]
     pub fn get_price_lookup(&self) -> PriceLookup {
         self.price_lookup.clone()
@@ -725,7 +1106,7 @@ impl Settings {
     ///
     /// # Errors
     /// Return `Err` timestamp is invalid
-    pub fn parse_timestamp(&mut self, ts: &str) -> Result<Zoned, tackler::Error> {
+    pub fn parse_timestamp(&self, ts: &str) -> Result<Zoned, tackler::Error> {
         Ok(winnow::Parser::parse(
             &mut crate::parser::parts::timestamp::parse_timestamp,
             winnow::Stateful {
@@ -736,6 +1117,12 @@ impl Settings {
         .map_err(|e| e.to_string())?)
     }
 
+    /// Default timezone of timestamps
+    #[must_use]
+    pub fn get_timezone(&self) -> jiff::tz::TimeZone {
+        self.kernel.timestamp.timezone.clone()
+    }
+
     /// # Errors
     /// Return `Err` if conversion to zone is not possible
     pub fn get_offset_datetime(&self, dt: jiff::civil::DateTime) -> Result<Zoned, tackler::Error> {
@@ -770,6 +1157,20 @@ impl Settings {
         self.report.targets.clone()
     }
 
+    /// Get named filter
+    ///
+    /// # Errors
+    /// Returns `Err` if there is no filter with that name
+    pub fn get_filter(&self, name: &str) -> Result<&FilterDefinition, tackler::Error> {
+        self.filters.get(name).ok_or_else(|| {
+            let msg = format!(
+                "Unknown filter: '{name}'. Valid filters are: {}",
+                self.filters.keys().join(", ")
+            );
+            msg.into()
+        })
+    }
+
     #[must_use]
     pub fn get_export_targets(&self) -> Vec<ExportType> {
         self.export.targets.clone()
@@ -805,6 +1206,21 @@ impl Settings {
         self.get_account_selector(&self.report.register.acc_sel)
     }
 
+    #[must_use]
+    pub fn get_pnl_ras(&self) -> AccountSelectors {
+        self.get_account_selector(&self.report.pnl.acc_sel)
+    }
+
+    #[must_use]
+    pub fn get_input_format(&self) -> InputFormat {
+        self.input_format
+    }
+
+    #[must_use]
+    pub fn get_lot_matching(&self) -> LotMatching {
+        self.kernel.lots.matching
+    }
+
     #[must_use]
     pub fn get_equity_ras(&self) -> AccountSelectors {
         self.get_account_selector(&self.export.equity.acc_sel)
@@ -818,10 +1234,10 @@ mod tests {
     #[test]
     fn accounts_strict_false() {
         let comm = Arc::new(Commodity::default());
-        let mut settings = Settings::default();
+        let settings = Settings::default();
 
         let txntn_1 = settings.get_or_create_txn_account("a:b:c", &comm).unwrap(/*:test:*/);
-        assert_eq!(settings.accounts.defined_accounts.len(), 3);
+        assert_eq!(read_lock(&settings.accounts).defined_accounts.len(), 3);
 
         assert_eq!(txntn_1.atn.depth, 3);
         assert_eq!(txntn_1.atn.get_root(), "a");
@@ -830,7 +1246,7 @@ mod tests {
         assert_eq!(txntn_1.atn.get_name(), "c");
 
         let txntn_2 = settings.get_txn_account("a:b:c", &comm).unwrap(/*:test:*/);
-        assert_eq!(settings.accounts.defined_accounts.len(), 3);
+        assert_eq!(read_lock(&settings.accounts).defined_accounts.len(), 3);
 
         assert_eq!(txntn_2.atn.depth, 3);
         assert_eq!(txntn_2.atn.get_root(), "a");
@@ -839,7 +1255,7 @@ mod tests {
         assert_eq!(txntn_2.atn.get_name(), "c");
 
         let txntn_3 = settings.get_or_create_txn_account("a:b:b-leaf", &comm).unwrap(/*:test:*/);
-        assert_eq!(settings.accounts.defined_accounts.len(), 4);
+        assert_eq!(read_lock(&settings.accounts).defined_accounts.len(), 4);
 
         assert_eq!(txntn_3.atn.depth, 3);
         assert_eq!(txntn_3.atn.get_root(), "a");
@@ -855,15 +1271,15 @@ mod tests {
         let accounts = vec!["a:b:c".to_string()];
 
         let acc_trees = AccountTrees::from(&accounts, true).unwrap(/*:test:*/);
-        settings.accounts = acc_trees;
+        settings.accounts = RwLock::new(acc_trees);
         settings.strict_mode = true;
 
-        assert_eq!(settings.accounts.defined_accounts.len(), 1);
-        assert_eq!(settings.accounts.synthetic_parents.len(), 2);
+        assert_eq!(read_lock(&settings.accounts).defined_accounts.len(), 1);
+        assert_eq!(read_lock(&settings.accounts).synthetic_parents.len(), 2);
 
         let txntn_1 = settings.get_or_create_txn_account("a:b:c", &comm).unwrap(/*:test:*/);
-        assert_eq!(settings.accounts.defined_accounts.len(), 1);
-        assert_eq!(settings.accounts.synthetic_parents.len(), 2);
+        assert_eq!(read_lock(&settings.accounts).defined_accounts.len(), 1);
+        assert_eq!(read_lock(&settings.accounts).synthetic_parents.len(), 2);
 
         assert_eq!(txntn_1.atn.depth, 3);
         assert_eq!(txntn_1.atn.get_root(), "a");
@@ -872,8 +1288,8 @@ mod tests {
         assert_eq!(txntn_1.atn.get_name(), "c");
 
         let txntn_2 = settings.get_txn_account("a:b:c", &comm).unwrap(/*:test:*/);
-        assert_eq!(settings.accounts.defined_accounts.len(), 1);
-        assert_eq!(settings.accounts.synthetic_parents.len(), 2);
+        assert_eq!(read_lock(&settings.accounts).defined_accounts.len(), 1);
+        assert_eq!(read_lock(&settings.accounts).synthetic_parents.len(), 2);
 
         assert_eq!(txntn_2.atn.depth, 3);
         assert_eq!(txntn_2.atn.get_root(), "a");
@@ -883,13 +1299,13 @@ mod tests {
 
         // Check that it won't create a synthetic account as real one
         assert!(settings.get_or_create_txn_account("a:b", &comm).is_err());
-        assert_eq!(settings.accounts.defined_accounts.len(), 1);
-        assert_eq!(settings.accounts.synthetic_parents.len(), 2);
+        assert_eq!(read_lock(&settings.accounts).defined_accounts.len(), 1);
+        assert_eq!(read_lock(&settings.accounts).synthetic_parents.len(), 2);
 
         // Check synthetic account
         let txntn_3 = settings.get_txn_account("a:b", &comm).unwrap(/*:test:*/);
-        assert_eq!(settings.accounts.defined_accounts.len(), 1);
-        assert_eq!(settings.accounts.synthetic_parents.len(), 2);
+        assert_eq!(read_lock(&settings.accounts).defined_accounts.len(), 1);
+        assert_eq!(read_lock(&settings.accounts).synthetic_parents.len(), 2);
 
         assert_eq!(txntn_3.atn.depth, 2);
         assert_eq!(txntn_3.atn.get_root(), "a");
@@ -899,8 +1315,8 @@ mod tests {
 
         // Check synthetic account
         let txntn_4 = settings.get_txn_account("a", &comm).unwrap(/*:test:*/);
-        assert_eq!(settings.accounts.defined_accounts.len(), 1);
-        assert_eq!(settings.accounts.synthetic_parents.len(), 2);
+        assert_eq!(read_lock(&settings.accounts).defined_accounts.len(), 1);
+        assert_eq!(read_lock(&settings.accounts).synthetic_parents.len(), 2);
 
         assert_eq!(txntn_4.atn.depth, 1);
         assert_eq!(txntn_4.atn.get_root(), "a");
@@ -916,25 +1332,25 @@ mod tests {
         let accounts = vec!["a:b:c".to_string(), "a:b".to_string(), "a".to_string()];
 
         let acc_trees = AccountTrees::from(&accounts, true).unwrap(/*:test:*/);
-        settings.accounts = acc_trees;
+        settings.accounts = RwLock::new(acc_trees);
         settings.strict_mode = true;
 
-        assert_eq!(settings.accounts.defined_accounts.len(), 3);
-        assert_eq!(settings.accounts.synthetic_parents.len(), 0);
+        assert_eq!(read_lock(&settings.accounts).defined_accounts.len(), 3);
+        assert_eq!(read_lock(&settings.accounts).synthetic_parents.len(), 0);
 
         let txntn_1 = settings.get_or_create_txn_account("a:b:c", &comm).unwrap(/*:test:*/);
-        assert_eq!(settings.accounts.defined_accounts.len(), 3);
-        assert_eq!(settings.accounts.synthetic_parents.len(), 0);
+        assert_eq!(read_lock(&settings.accounts).defined_accounts.len(), 3);
+        assert_eq!(read_lock(&settings.accounts).synthetic_parents.len(), 0);
         assert_eq!(txntn_1.atn.account, "a:b:c");
 
         let txntn_2 = settings.get_or_create_txn_account("a:b", &comm).unwrap(/*:test:*/);
-        assert_eq!(settings.accounts.defined_accounts.len(), 3);
-        assert_eq!(settings.accounts.synthetic_parents.len(), 0);
+        assert_eq!(read_lock(&settings.accounts).defined_accounts.len(), 3);
+        assert_eq!(read_lock(&settings.accounts).synthetic_parents.len(), 0);
         assert_eq!(txntn_2.atn.account, "a:b");
 
         let txntn_2 = settings.get_or_create_txn_account("a", &comm).unwrap(/*:test:*/);
-        assert_eq!(settings.accounts.defined_accounts.len(), 3);
-        assert_eq!(settings.accounts.synthetic_parents.len(), 0);
+        assert_eq!(read_lock(&settings.accounts).defined_accounts.len(), 3);
+        assert_eq!(read_lock(&settings.accounts).synthetic_parents.len(), 0);
         assert_eq!(txntn_2.atn.account, "a");
     }
 
@@ -945,28 +1361,264 @@ mod tests {
         let accounts = vec!["a:b:c:d".to_string(), "a:b".to_string(), "a".to_string()];
 
         let acc_trees = AccountTrees::from(&accounts, true).unwrap(/*:test:*/);
-        settings.accounts = acc_trees;
+        settings.accounts = RwLock::new(acc_trees);
         settings.strict_mode = true;
 
-        assert_eq!(settings.accounts.defined_accounts.len(), 3);
-        assert_eq!(settings.accounts.synthetic_parents.len(), 1);
+        assert_eq!(read_lock(&settings.accounts).defined_accounts.len(), 3);
+        assert_eq!(read_lock(&settings.accounts).synthetic_parents.len(), 1);
 
         // Check that it won't create a synthetic account as real one
         assert!(settings.get_or_create_txn_account("a:b:c", &comm).is_err());
 
         let txntn_synth = settings.get_txn_account("a:b:c", &comm).unwrap(/*:test:*/);
-        assert_eq!(settings.accounts.defined_accounts.len(), 3);
-        assert_eq!(settings.accounts.synthetic_parents.len(), 1);
+        assert_eq!(read_lock(&settings.accounts).defined_accounts.len(), 3);
+        assert_eq!(read_lock(&settings.accounts).synthetic_parents.len(), 1);
         assert_eq!(txntn_synth.atn.account, "a:b:c");
 
         let txntn_2 = settings.get_or_create_txn_account("a:b", &comm).unwrap(/*:test:*/);
-        assert_eq!(settings.accounts.defined_accounts.len(), 3);
-        assert_eq!(settings.accounts.synthetic_parents.len(), 1);
+        assert_eq!(read_lock(&settings.accounts).defined_accounts.len(), 3);
+        assert_eq!(read_lock(&settings.accounts).synthetic_parents.len(), 1);
         assert_eq!(txntn_2.atn.account, "a:b");
 
         let txntn_2 = settings.get_or_create_txn_account("a", &comm).unwrap(/*:test:*/);
-        assert_eq!(settings.accounts.defined_accounts.len(), 3);
-        assert_eq!(settings.accounts.synthetic_parents.len(), 1);
+        assert_eq!(read_lock(&settings.accounts).defined_accounts.len(), 3);
+        assert_eq!(read_lock(&settings.accounts).synthetic_parents.len(), 1);
         assert_eq!(txntn_2.atn.account, "a");
     }
+
+    fn lifecycle_settings(strict_mode: bool) -> Settings {
+        let mut settings = Settings::default();
+        let accounts = vec!["a".to_string(), "e:old".to_string(), "e:new".to_string()];
+        settings.accounts =
+            RwLock::new(AccountTrees::from(&accounts, strict_mode).unwrap(/*:test:*/));
+        settings.strict_mode = strict_mode;
+
+        let specs = vec![
+            AccountSpec {
+                name: "e:old".to_string(),
+                open: None,
+                close: Some("2024-01-01".to_string()),
+                kind: Some(AccountKind::Debit),
+            },
+            AccountSpec {
+                name: "e:new".to_string(),
+                open: Some("2024-01-01T00:00:00Z".to_string()),
+                close: None,
+                kind: None,
+            },
+        ];
+        settings.account_lifecycles = settings.account_lifecycles(&specs).unwrap(/*:test:*/);
+        settings
+    }
+
+    #[test]
+    // test: d70973d9-3225-473d-a09a-4bb9c3c2d609
+    fn accounts_lifecycle_strict() {
+        let settings = lifecycle_settings(true);
+
+        let txns_ok = "2023-12-31T23:59:59Z\n e:old 1\n a\n\n2024-01-01\n e:new 1\n a\n";
+        let res = parser::string_to_txns(&mut &*txns_ok, &settings);
+        assert!(res.is_ok(), "{:#?}", res.err());
+
+        let txns_closed = "2024-01-01\n e:old 1\n a\n";
+        let res = parser::string_to_txns(&mut &*txns_closed, &settings);
+        assert!(res.is_err());
+        let msg = res.err().unwrap(/*:test:*/).to_string();
+        assert!(
+            msg.contains("Account is not open at the time of txn: 'e:old'"),
+            "{msg}"
+        );
+
+        let txns_not_opened = "2023-12-31\n e:new 1\n a\n";
+        let res = parser::string_to_txns(&mut &*txns_not_opened, &settings);
+        assert!(res.is_err());
+    }
+
+    #[test]
+    // test: e04b7c6d-623f-4384-9ba4-e77c4e0ce935
+    fn accounts_lifecycle_non_strict() {
+        let settings = lifecycle_settings(false);
+
+        let txns = "2024-01-01\n e:old 1\n a\n\n2023-12-31\n e:new 1\n a\n";
+        let res = parser::string_to_txns(&mut &*txns, &settings);
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    // test: 110eca53-46bb-4089-8cf6-755e06c67dab
+    fn accounts_lifecycle_kind() {
+        let settings = lifecycle_settings(true);
+
+        assert_eq!(settings.get_account_kind("e:old"), Some(AccountKind::Debit));
+        assert_eq!(
+            settings.get_account_kind("e:old:sub:leaf"),
+            Some(AccountKind::Debit)
+        );
+        assert_eq!(settings.get_account_kind("e:new"), None);
+        assert_eq!(settings.get_account_kind("e"), None);
+    }
+
+    #[test]
+    // test: 1986485e-928a-4c6d-821e-d73f525fe11f
+    fn accounts_lifecycle_invalid() {
+        let settings = Settings::default();
+
+        let period = |open: &str, close: &str| AccountSpec {
+            name: "a".to_string(),
+            open: Some(open.to_string()),
+            close: Some(close.to_string()),
+            kind: None,
+        };
+
+        // open >= close
+        assert!(
+            settings
+                .account_lifecycles(&[period("2024-01-01", "2024-01-01")])
+                .is_err()
+        );
+        // overlapping periods
+        assert!(
+            settings
+                .account_lifecycles(&[
+                    period("2024-01-01", "2024-06-01"),
+                    period("2024-05-01", "2024-12-01")
+                ])
+                .is_err()
+        );
+        // invalid timestamp
+        assert!(
+            settings
+                .account_lifecycles(&[period("2024-01-01", "2024-13-01")])
+                .is_err()
+        );
+        // disjoint periods
+        assert!(
+            settings
+                .account_lifecycles(&[
+                    period("2024-05-01", "2024-12-01"),
+                    period("2024-01-01", "2024-05-01")
+                ])
+                .is_ok()
+        );
+    }
+
+    #[test]
+    // test: 5b1e9c47-2d0a-4f6e-b3c8-7a94e0d2f615
+    // desc: closed accounts with zero balance are not reported without --include-closed
+    fn accounts_lifecycle_reported() {
+        use crate::config::BalanceType;
+        use crate::kernel::balance::Balance;
+        use crate::kernel::price_lookup::PriceLookupCtx;
+        use crate::kernel::report_item_selector::BalanceAllSelector;
+        use crate::model::Transaction;
+        use rust_decimal::Decimal;
+        use tackler_rs::IndocUtils;
+
+        #[rustfmt::skip]
+        let txns = indoc::indoc!(
+           "|2023-06-01
+            | e:old 2
+            | a
+            |
+            |2024-03-01
+            | e:old 1
+            | a
+            |
+            |2024-04-01
+            | e:old -1
+            | a
+            |
+            |2024-06-01
+            | e:new 1
+            | a
+            |"
+        ).strip_margin();
+
+        for include_closed in [false, true] {
+            let mut settings = lifecycle_settings(false);
+            settings.include_closed = include_closed;
+
+            let txn_data = parser::string_to_txns(&mut txns.as_str(), &settings).unwrap(/*:test:*/);
+            let txn_set = txn_data.get_all().unwrap(/*:test:*/);
+            let balance = |txns: &[&Transaction]| -> Vec<(String, Decimal, Decimal)> {
+                Balance::from_iter(
+                    "",
+                    txns,
+                    &PriceLookupCtx::default(),
+                    &BalanceAllSelector::default(),
+                    &settings,
+                    BalanceType::Tree,
+                    None,
+                )
+                .unwrap(/*:test:*/)
+                .retain_reported(&settings)
+                .bal
+                .iter()
+                .map(|btn| {
+                    let acc = btn.acctn.atn.account.clone();
+                    (acc, btn.account_sum, btn.sub_acc_tree_sum)
+                })
+                .collect()
+            };
+            let row = |acc: &str, sum: i64, tree_sum: i64| {
+                (acc.to_string(), Decimal::from(sum), Decimal::from(tree_sum))
+            };
+
+            // e:old is open in the beginning of the period
+            assert_eq!(
+                balance(&txn_set.txns),
+                vec![
+                    row("a", -3, -3),
+                    row("e", 0, 3),
+                    row("e:new", 1, 1),
+                    row("e:old", 2, 2)
+                ]
+            );
+
+            // e:old is closed over the whole period, and its balance is zero
+            let mut expected = vec![row("a", -1, -1), row("e", 0, 1), row("e:new", 1, 1)];
+            if include_closed {
+                expected.push(row("e:old", 0, 0));
+            }
+            assert_eq!(balance(&txn_set.txns[1..]), expected);
+
+            // e:old is closed over the whole period, but its balance is not zero
+            assert_eq!(
+                balance(&txn_set.txns[1..2]),
+                vec![row("a", -1, -1), row("e", 0, 1), row("e:old", 1, 1)]
+            );
+        }
+    }
+
+    #[test]
+    // test: 8e4a2f6d-93c1-4b7e-a5d0-2c6f1b9e7a38
+    // desc: equity export fails if account is closed at equity txn, and it has balance
+    fn accounts_lifecycle_equity() {
+        use crate::export::{EquityExporter, EquitySettings, Export};
+
+        let txns = "2023-06-01\n e:old 2\n a\n\n2024-06-01\n e:new 1\n a\n";
+        for include_closed in [false, true] {
+            let mut settings = lifecycle_settings(false);
+            settings.include_closed = include_closed;
+
+            let txn_data = parser::string_to_txns(&mut &*txns, &settings).unwrap(/*:test:*/);
+            let txn_set = txn_data.get_all().unwrap(/*:test:*/);
+            let exporter = EquityExporter {
+                export_settings: EquitySettings::from(&settings),
+            };
+            let mut output = Vec::new();
+            let res = exporter.write_export(&settings, &mut output, &txn_set);
+            if include_closed {
+                assert!(res.is_ok(), "{:#?}", res.err());
+                let output = String::from_utf8(output).unwrap(/*:test:*/);
+                assert!(output.contains("   e:old  2\n"), "{output}");
+            } else {
+                let msg = res.err().unwrap(/*:test:*/).to_string();
+                assert!(
+                    msg.contains("closed at the time of equity txn (2024-06-01T00:00:00+00:00), but their balance is not zero: 'e:old' (2)"),
+                    "{msg}"
+                );
+            }
+        }
+    }
 }
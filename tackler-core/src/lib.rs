@@ -5,9 +5,14 @@
 #![forbid(unsafe_code)]
 
 pub mod config;
+pub mod dedupe;
+pub mod diagnostic;
 pub mod export;
 pub mod filter;
+pub mod formatter;
+pub mod import;
 pub mod kernel;
+pub mod lint;
 pub mod math;
 pub mod model;
 pub mod parser;
@@ -0,0 +1,526 @@
+/*
+ * Tackler-NG 2026
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Journal lints
+//!
+//! Lints are checks for journal data which is valid, but which is
+//! probably not what was meant, e.g. unused accounts in the Chart of Accounts.
+//! Each lint has a severity and an allow-list (list of regular expressions).
+//! Lints are reported only in check mode, see [`Settings::enable_lints`].
+//!
+//! Shard lints (e.g. `trailing-whitespace`) are checked while the shard is parsed,
+//! and lints of the whole txn data (e.g. `unused-account`) are checked
+//! by [`check_txn_data`].
+
+use crate::config::{Lint, LintLevel, LintRule};
+use crate::diagnostic;
+use crate::diagnostic::{Diagnostic, Severity};
+use crate::kernel::Settings;
+use crate::model::{Transaction, TxnData};
+use crate::tackler;
+use jiff::Zoned;
+use regex::RegexSet;
+use rust_decimal::Decimal;
+use std::collections::{BTreeMap, HashSet};
+use std::path::PathBuf;
+use tackler_rs::regex::new_full_haystack_regex_set;
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// Compiled lint rule
+#[derive(Debug, Clone, Default)]
+struct Rule {
+    level: LintLevel,
+    allow: RegexSet,
+}
+
+impl Rule {
+    fn try_from(name: &str, rule: &LintRule) -> Result<Rule, tackler::Error> {
+        let allow = new_full_haystack_regex_set(&rule.allow)
+            .map_err(|err| format!("Invalid allow-list of lint '{name}': {err}"))?;
+        Ok(Rule {
+            level: rule.level,
+            allow,
+        })
+    }
+
+    /// Is the lint reported for the subject
+    fn is_reported(&self, subject: &str) -> bool {
+        self.level != LintLevel::Off && !self.allow.is_match(subject)
+    }
+
+    fn diagnostic(&self, code: &'static str, message: String) -> Diagnostic {
+        let mut diag = Diagnostic::error(code, message);
+        if self.level == LintLevel::Warning {
+            diag.severity = Severity::Warning;
+        }
+        diag
+    }
+}
+
+/// Compiled journal lints with the charts of the configuration
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Lints {
+    enabled: bool,
+    unused_accounts: Rule,
+    unused_commodities: Rule,
+    unused_tags: Rule,
+    missing_uuid: Rule,
+    trailing_whitespace: Rule,
+    account_not_open: Rule,
+    unsorted_shard: Rule,
+    price_db_gap: Rule,
+    max_gap_days: u32,
+    amount_precision: Rule,
+    max_scale: u32,
+    accounts: Vec<String>,
+    commodities: Vec<String>,
+    tags: Vec<String>,
+    pub(crate) price_db_paths: Vec<PathBuf>,
+}
+
+impl Lints {
+    pub(crate) fn try_from(
+        lint: &Lint,
+        accounts: &[String],
+        commodities: &[String],
+        tags: &[String],
+        price_db_paths: Vec<PathBuf>,
+    ) -> Result<Lints, tackler::Error> {
+        Ok(Lints {
+            enabled: false,
+            unused_accounts: Rule::try_from("unused-accounts", &lint.unused_accounts)?,
+            unused_commodities: Rule::try_from("unused-commodities", &lint.unused_commodities)?,
+            unused_tags: Rule::try_from("unused-tags", &lint.unused_tags)?,
+            missing_uuid: Rule::try_from("missing-uuid", &lint.missing_uuid)?,
+            trailing_whitespace: Rule::try_from("trailing-whitespace", &lint.trailing_whitespace)?,
+            account_not_open: Rule::try_from("account-not-open", &lint.account_not_open)?,
+            unsorted_shard: Rule::try_from("unsorted-shard", &lint.unsorted_shard)?,
+            price_db_gap: Rule::try_from("price-db-gap", &lint.price_db_gap)?,
+            max_gap_days: lint.max_gap_days,
+            amount_precision: Rule::try_from("amount-precision", &lint.amount_precision)?,
+            max_scale: lint.max_scale,
+            accounts: accounts.to_vec(),
+            commodities: commodities.to_vec(),
+            tags: tags.to_vec(),
+            price_db_paths,
+        })
+    }
+
+    pub(crate) fn enable(&mut self) {
+        self.enabled = true;
+    }
+
+    /// Check lints of the txn shard
+    ///
+    /// `offsets` are the byte offsets of the `txns` in the `input`,
+    /// if these are known. The shard lints are allowed by the `path`.
+    pub(crate) fn check_shard(
+        &self,
+        input: &str,
+        path: Option<&str>,
+        txns: &[Transaction],
+        offsets: &[usize],
+        settings: &Settings,
+    ) -> Vec<Diagnostic> {
+        if !self.enabled {
+            return Vec::new();
+        }
+        let path = path.unwrap_or_default();
+        let locate = |diag: Diagnostic, i: usize| match offsets.get(i) {
+            Some(offset) => diag.with_position(input, *offset),
+            None => diag,
+        };
+
+        let mut diagnostics = Vec::new();
+        let mut prev_ts: Option<&Zoned> = None;
+        let mut unsorted = false;
+        for (i, txn) in txns.iter().enumerate() {
+            let source = offsets.get(i).map(|o| &input[*o..]);
+            let txn_diags = self.check_txn(txn, path, source, settings);
+
+            if !unsorted
+                && prev_ts.is_some_and(|prev| txn.header.timestamp < *prev)
+                && self.unsorted_shard.is_reported(path)
+            {
+                unsorted = true;
+                let msg = format!(
+                    "Txns of the shard are not in chronological order: {} is before previous txn",
+                    tackler_api::txn_ts::rfc_3339(&txn.header.timestamp)
+                );
+                diagnostics.push(locate(
+                    self.unsorted_shard
+                        .diagnostic(diagnostic::UNSORTED_SHARD, msg),
+                    i,
+                ));
+            }
+            prev_ts = Some(&txn.header.timestamp);
+
+            diagnostics.extend(txn_diags.into_iter().map(|d| locate(d, i)));
+        }
+        diagnostics
+    }
+
+    /// Check lints of the single txn
+    ///
+    /// `source` is the input starting from the txn, if it's known.
+    fn check_txn(
+        &self,
+        txn: &Transaction,
+        path: &str,
+        source: Option<&str>,
+        settings: &Settings,
+    ) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        if !settings.audit_mode && txn.header.uuid.is_none() && self.missing_uuid.is_reported(path)
+        {
+            let msg = "Txn without UUID".to_string();
+            diagnostics.push(self.missing_uuid.diagnostic(diagnostic::MISSING_UUID, msg));
+        }
+
+        let header_line = source.and_then(|s| s.lines().next()).unwrap_or_default();
+        if txn.header.description.is_some()
+            && header_line.ends_with(char::is_whitespace)
+            && self.trailing_whitespace.is_reported(path)
+        {
+            let msg = "Txn description with trailing whitespace".to_string();
+            diagnostics.push(
+                self.trailing_whitespace
+                    .diagnostic(diagnostic::TRAILING_WHITESPACE, msg),
+            );
+        }
+
+        for p in &txn.posts {
+            let account = p.acctn.atn.account.as_str();
+            if !settings.is_strict_mode()
+                && settings
+                    .get_account_lifecycle(account)
+                    .is_some_and(|lc| !lc.is_open_at(&txn.header.timestamp))
+                && self.account_not_open.is_reported(account)
+            {
+                let msg = format!(
+                    "Account is not open at the time of txn: '{account}' at {}",
+                    tackler_api::txn_ts::rfc_3339(&txn.header.timestamp)
+                );
+                diagnostics.push(
+                    self.account_not_open
+                        .diagnostic(diagnostic::ACCOUNT_NOT_OPEN, msg)
+                        .with_help("Check 'open' and 'close' of the account in Chart of Accounts"),
+                );
+            }
+
+            let commodity = p.acctn.comm.name.as_str();
+            let scale = p.amount.normalize().scale();
+            if scale > self.max_scale && self.amount_precision.is_reported(commodity) {
+                let msg = format!(
+                    "Posting amount has {scale} decimals (max {}): '{account}' {}",
+                    self.max_scale, p.amount
+                );
+                diagnostics.push(
+                    self.amount_precision
+                        .diagnostic(diagnostic::AMOUNT_PRECISION, msg),
+                );
+            }
+        }
+        diagnostics
+    }
+}
+
+/// Check lints of the whole txn data
+///
+/// This reports unused accounts, commodities and tags of the charts,
+/// and gaps in the price database for held commodities.
+/// Price entries of the journal must be merged before this,
+/// see [`Settings::merge_journal_prices`].
+///
+/// # Errors
+/// Returns `Err` if txn data can't be read
+pub fn check_txn_data(txn_data: &TxnData, settings: &Settings) -> Result<(), tackler::Error> {
+    let lints = &settings.lints;
+    if !lints.enabled {
+        return Ok(());
+    }
+    let txn_set = txn_data.get_all()?;
+    let txns = &txn_set.txns;
+
+    let mut used_accounts = HashSet::new();
+    let mut used_commodities = HashSet::new();
+    let mut used_tags = HashSet::new();
+    for txn in txns {
+        for p in &txn.posts {
+            used_accounts.insert(p.acctn.atn.account.as_str());
+            used_commodities.insert(p.acctn.comm.name.as_str());
+            used_commodities.insert(p.txn_commodity.name.as_str());
+        }
+        for tag in txn.header.tags.iter().flatten() {
+            used_tags.insert(tag.as_str());
+        }
+    }
+
+    let mut diagnostics = Vec::new();
+    for account in &lints.accounts {
+        // Parent account is used by its sub-accounts
+        let prefix = format!("{account}:");
+        if !used_accounts
+            .iter()
+            .any(|a| a == account || a.starts_with(&prefix))
+            && lints.unused_accounts.is_reported(account)
+        {
+            let msg = format!("Account is not used by any txn: '{account}'");
+            diagnostics.push(
+                lints
+                    .unused_accounts
+                    .diagnostic(diagnostic::UNUSED_ACCOUNT, msg),
+            );
+        }
+    }
+    for commodity in &lints.commodities {
+        if !used_commodities.contains(commodity.as_str())
+            && lints.unused_commodities.is_reported(commodity)
+        {
+            let msg = format!("Commodity is not used by any txn: '{commodity}'");
+            diagnostics.push(
+                lints
+                    .unused_commodities
+                    .diagnostic(diagnostic::UNUSED_COMMODITY, msg),
+            );
+        }
+    }
+    for tag in &lints.tags {
+        if !used_tags.contains(tag.as_str()) && lints.unused_tags.is_reported(tag) {
+            let msg = format!("Tag is not used by any txn: '{tag}'");
+            diagnostics.push(lints.unused_tags.diagnostic(diagnostic::UNUSED_TAG, msg));
+        }
+    }
+    diagnostics.extend(price_db_gaps(txns, settings));
+
+    settings.check_errors(diagnostics)
+}
+
+/// Hold periods of commodities with value positions (e.g. `1 XAU @ 2000 USD`)
+///
+/// Period starts when the total position of the commodity becomes non-zero,
+/// and ends when it's zero again, or at the last txn.
+fn hold_periods<'a>(txns: &[&'a Transaction]) -> BTreeMap<&'a str, Vec<(&'a Zoned, &'a Zoned)>> {
+    let mut positions = BTreeMap::new();
+    let mut periods: BTreeMap<&str, Vec<(&Zoned, &Zoned)>> = BTreeMap::new();
+    for txn in txns {
+        let ts = &txn.header.timestamp;
+        for p in &txn.posts {
+            let commodity = p.acctn.comm.name.as_str();
+            if commodity == p.txn_commodity.name {
+                continue;
+            }
+            let (position, start) = positions.entry(commodity).or_insert((Decimal::ZERO, ts));
+            if position.is_zero() {
+                *start = ts;
+            }
+            *position += p.amount;
+            if position.is_zero() {
+                periods.entry(commodity).or_default().push((*start, ts));
+            }
+        }
+    }
+    if let Some(last) = txns.last() {
+        for (commodity, (position, start)) in positions {
+            if !position.is_zero() {
+                periods
+                    .entry(commodity)
+                    .or_default()
+                    .push((start, &last.header.timestamp));
+            }
+        }
+    }
+    periods
+}
+
+fn price_db_gaps(txns: &[&Transaction], settings: &Settings) -> Vec<Diagnostic> {
+    let lints = &settings.lints;
+    let price_db = &settings.price.price_db;
+    if price_db.is_empty() {
+        return Vec::new();
+    }
+    let max_gap = i64::from(lints.max_gap_days) * SECONDS_PER_DAY;
+
+    let mut diagnostics = Vec::new();
+    for (commodity, periods) in hold_periods(txns) {
+        if !lints.price_db_gap.is_reported(commodity) {
+            continue;
+        }
+        for (start, end) in periods {
+            let prices = price_db
+                .iter()
+                .filter(|pe| pe.base_commodity.name == commodity)
+                .map(|pe| &pe.timestamp)
+                .filter(|ts| start < *ts && *ts < end);
+            let points: Vec<_> = std::iter::once(start)
+                .chain(prices)
+                .chain(std::iter::once(end))
+                .collect();
+            let gap = points
+                .windows(2)
+                .find(|w| w[1].timestamp().as_second() - w[0].timestamp().as_second() > max_gap);
+            if let Some(w) = gap {
+                let msg = format!(
+                    "No price for held commodity '{commodity}' between {} and {} (max {} days)",
+                    tackler_api::txn_ts::rfc_3339(w[0]),
+                    tackler_api::txn_ts::rfc_3339(w[1]),
+                    lints.max_gap_days
+                );
+                diagnostics.push(lints.price_db_gap.diagnostic(diagnostic::PRICE_DB_GAP, msg));
+            }
+        }
+    }
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+    use indoc::indoc;
+    use tackler_rs::IndocUtils;
+
+    fn lint_settings(lint: &Lint) -> Settings {
+        let names = |ns: &[&str]| ns.iter().map(ToString::to_string).collect::<Vec<_>>();
+        let mut settings = Settings::default();
+        settings.lints = Lints::try_from(
+            lint,
+            &names(&["a", "b", "e", "x:y"]),
+            &names(&["EUR", "USD", "XAU"]),
+            &names(&["t1", "t2"]),
+            Vec::new(),
+        )
+        .unwrap(/*:test:*/);
+        settings.enable_lints().unwrap(/*:test:*/);
+        settings
+    }
+
+    fn codes(diagnostics: &[Diagnostic]) -> Vec<(Option<usize>, &'static str, Severity)> {
+        diagnostics
+            .iter()
+            .map(|d| (d.line, d.code, d.severity))
+            .collect()
+    }
+
+    #[test]
+    // test: 4a8c2e6f-0b3d-4f5a-9e7c-1d3f5b7a9c2e
+    // desc: shard lints with severity and allow-list
+    fn shard_lints() {
+        let mut lint = Lint::default();
+        lint.missing_uuid.level = LintLevel::Error;
+        lint.amount_precision.allow = vec!["XAU".to_string()];
+        let mut settings = lint_settings(&lint);
+
+        #[rustfmt::skip]
+        let input = indoc!(
+           "|2024-02-01 'second\x20
+            | e 1.234 EUR
+            | a -1.234 EUR
+            |
+            |2024-01-01 'first
+            | # uuid: 2f4a6c8e-0b1d-4e3f-a5c7-9e1b3d5f7a9c
+            | e 1.234 XAU
+            | a -1.234 XAU
+            |"
+        ).strip_margin();
+
+        let txn_data = parser::string_to_txns(&mut input.as_str(), &settings).unwrap(/*:test:*/);
+        assert_eq!(txn_data.len(), 2);
+
+        let diagnostics = settings.take_diagnostics();
+        assert_eq!(
+            codes(&diagnostics),
+            vec![
+                (Some(1), diagnostic::MISSING_UUID, Severity::Error),
+                (Some(1), diagnostic::TRAILING_WHITESPACE, Severity::Warning),
+                (Some(1), diagnostic::AMOUNT_PRECISION, Severity::Warning),
+                (Some(1), diagnostic::AMOUNT_PRECISION, Severity::Warning),
+                (Some(5), diagnostic::UNSORTED_SHARD, Severity::Warning),
+            ]
+        );
+    }
+
+    #[test]
+    // test: 8d0f2b4e-6a1c-4c3e-b5d7-3f5b7d9f1a4c
+    // desc: txn data lints, unused charts and price-db gaps
+    fn txn_data_lints() {
+        let mut lint = Lint::default();
+        lint.missing_uuid.level = LintLevel::Off;
+        lint.unused_accounts.allow = vec!["x:.*".to_string()];
+        let mut settings = lint_settings(&lint);
+        settings.price.price_db = parser::pricedb_from_str(&mut "P 2024-01-10 XAU 10 EUR\n", &settings)
+                .unwrap(/*:test:*/);
+
+        #[rustfmt::skip]
+        let input = indoc!(
+           "|2024-01-01 'buy
+            | # tags: t1
+            | e:food 2 XAU @ 10 EUR
+            | a
+            |
+            |2024-03-15 'other
+            | e 1 EUR
+            | a
+            |"
+        ).strip_margin();
+
+        let txn_data = parser::string_to_txns(&mut input.as_str(), &settings).unwrap(/*:test:*/);
+        check_txn_data(&txn_data, &settings).unwrap(/*:test:*/);
+
+        let diagnostics = settings.take_diagnostics();
+        assert_eq!(
+            codes(&diagnostics),
+            vec![
+                (None, diagnostic::UNUSED_ACCOUNT, Severity::Warning),
+                (None, diagnostic::UNUSED_COMMODITY, Severity::Warning),
+                (None, diagnostic::UNUSED_TAG, Severity::Warning),
+                (None, diagnostic::PRICE_DB_GAP, Severity::Warning),
+            ]
+        );
+        assert_eq!(
+            diagnostics[0].message,
+            "Account is not used by any txn: 'b'"
+        );
+        assert_eq!(
+            diagnostics[1].message,
+            "Commodity is not used by any txn: 'USD'"
+        );
+        assert_eq!(diagnostics[2].message, "Tag is not used by any txn: 't2'");
+        assert!(
+            diagnostics[3]
+                .message
+                .starts_with("No price for held commodity 'XAU' between 2024-01-10")
+        );
+    }
+
+    #[test]
+    // test: 1c3e5a7f-9b2d-4e6a-8f0c-5b7d9f1a3c5e
+    // desc: lints are not reported when they are off or not enabled
+    fn lints_off() {
+        let input = "2024-01-01 'txn \n e 1.234\n a\n";
+
+        let mut lint = Lint::default();
+        lint.missing_uuid.level = LintLevel::Off;
+        lint.trailing_whitespace.level = LintLevel::Off;
+        lint.amount_precision.level = LintLevel::Off;
+        let mut settings = lint_settings(&lint);
+        let txn_data = parser::string_to_txns(&mut &*input, &settings).unwrap(/*:test:*/);
+        check_txn_data(&txn_data, &settings).unwrap(/*:test:*/);
+        assert!(
+            settings
+                .take_diagnostics()
+                .iter()
+                .all(|d| d.code.starts_with("unused-"))
+        );
+
+        let mut settings = Settings::default();
+        settings.enable_check_mode();
+        let txn_data = parser::string_to_txns(&mut &*input, &settings).unwrap(/*:test:*/);
+        check_txn_data(&txn_data, &settings).unwrap(/*:test:*/);
+        assert!(settings.take_diagnostics().is_empty());
+    }
+}
@@ -3,22 +3,28 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+pub use account_lifecycle::AccountLifecycle;
+pub use account_lifecycle::AccountPeriod;
 pub(crate) use account_tree_node::AccountTreeNode;
 pub(crate) use account_tree_node::Commodity;
 pub(crate) use account_tree_node::TxnAccount;
 pub(crate) use balance_tree_node::BalanceTreeNode;
 pub use posting::Posting;
+pub use posting::UnitCost;
+pub(crate) use register::Position;
 pub(crate) use register::RegisterEntry;
 pub(crate) use register::RegisterPosting;
 pub use transaction::Transaction;
 pub use txn_data::TxnData;
 pub use txn_data::TxnSet;
 
+pub mod account_lifecycle;
 pub(crate) mod account_tree_node;
 pub(crate) mod balance_tree_node;
 pub mod posting;
 pub mod price_entry;
 mod register;
+pub(crate) mod source;
 pub mod transaction;
 pub mod txn_data;
 
@@ -0,0 +1,239 @@
+/*
+ * Tackler-NG 2026
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use crate::config::AccountKind;
+use crate::tackler;
+use jiff::Zoned;
+use tackler_api::txn_ts;
+
+/// Single active period of an account
+///
+/// This is a half-open interval `[open, close)`,
+/// and missing end means that the period is unbounded on that side.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountPeriod {
+    pub open: Option<Zoned>,
+    pub close: Option<Zoned>,
+}
+
+impl AccountPeriod {
+    /// Create a new period
+    ///
+    /// # Errors
+    /// Returns `Err` if `open` is not before `close`
+    pub fn try_from(open: Option<Zoned>, close: Option<Zoned>) -> Result<Self, tackler::Error> {
+        if let (Some(o), Some(c)) = (&open, &close) {
+            if o >= c {
+                let msg = format!(
+                    "open ({}) must be before close ({})",
+                    txn_ts::rfc_3339(o),
+                    txn_ts::rfc_3339(c)
+                );
+                return Err(msg.into());
+            }
+        }
+        Ok(AccountPeriod { open, close })
+    }
+
+    #[must_use]
+    pub fn contains(&self, ts: &Zoned) -> bool {
+        self.open.as_ref().is_none_or(|o| o <= ts) && self.close.as_ref().is_none_or(|c| ts < c)
+    }
+}
+
+/// Lifecycle attributes of an account (TEP-1017)
+///
+/// Periods are sorted by the open timestamp, and they don't overlap.
+/// If there are no periods, the account is always open.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AccountLifecycle {
+    periods: Vec<AccountPeriod>,
+    pub kind: Option<AccountKind>,
+}
+
+impl AccountLifecycle {
+    /// Create account lifecycle from its periods
+    ///
+    /// # Errors
+    /// Returns `Err` if any of the periods overlap
+    pub fn try_from(
+        mut periods: Vec<AccountPeriod>,
+        kind: Option<AccountKind>,
+    ) -> Result<Self, tackler::Error> {
+        // None (-infinity) sorts before any timestamp
+        periods.sort_by(|a, b| a.open.cmp(&b.open));
+
+        for (before, after) in periods.iter().zip(periods.iter().skip(1)) {
+            let overlaps = match (&before.close, &after.open) {
+                (Some(c), Some(o)) => c > o,
+                _ => true,
+            };
+            if overlaps {
+                let msg = "periods are overlapping";
+                return Err(msg.into());
+            }
+        }
+        Ok(AccountLifecycle { periods, kind })
+    }
+
+    #[must_use]
+    pub fn periods(&self) -> &[AccountPeriod] {
+        &self.periods
+    }
+
+    /// Test if account is open at the given time
+    #[must_use]
+    pub fn is_open_at(&self, ts: &Zoned) -> bool {
+        if self.periods.is_empty() {
+            return true;
+        }
+        let idx = self
+            .periods
+            .partition_point(|p| p.open.as_ref().is_none_or(|o| o <= ts));
+
+        idx > 0 && self.periods[idx - 1].contains(ts)
+    }
+
+    /// Test if account is open at any time of the closed interval `[begin, end]`
+    #[must_use]
+    pub fn is_open_during(&self, begin: &Zoned, end: &Zoned) -> bool {
+        self.periods.is_empty()
+            || self.periods.iter().any(|p| {
+                p.open.as_ref().is_none_or(|o| o <= end)
+                    && p.close.as_ref().is_none_or(|c| begin < c)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tackler_api::txn_ts::rfc3339_to_zoned;
+
+    fn ts(s: &str) -> Zoned {
+        rfc3339_to_zoned(s).unwrap(/*:test:*/)
+    }
+
+    fn period(open: Option<&str>, close: Option<&str>) -> AccountPeriod {
+        AccountPeriod::try_from(open.map(ts), close.map(ts)).unwrap(/*:test:*/)
+    }
+
+    #[test]
+    // test: 2d2f5b3c-33f6-477e-b6df-cc8882d4bef3
+    fn period_open_must_be_before_close() {
+        let t = "2024-01-01T00:00:00+00:00";
+        assert!(AccountPeriod::try_from(Some(ts(t)), Some(ts(t))).is_err());
+        assert!(
+            AccountPeriod::try_from(Some(ts("2024-02-01T00:00:00+00:00")), Some(ts(t))).is_err()
+        );
+    }
+
+    #[test]
+    // test: 2f906d3f-00b0-487e-9a46-03c8c92c2a97
+    fn lifecycle_without_periods() {
+        let lc = AccountLifecycle::try_from(Vec::new(), None).unwrap(/*:test:*/);
+        assert!(lc.is_open_at(&ts("1900-01-01T00:00:00+00:00")));
+        assert!(lc.is_open_at(&ts("2100-01-01T00:00:00+00:00")));
+    }
+
+    #[test]
+    // test: 71041973-79c4-43de-8e86-95e0d8a550a3
+    fn lifecycle_half_open_interval() {
+        let lc = AccountLifecycle::try_from(
+            vec![period(Some("2018-03-01T00:00:00+00:00"), Some("2024-01-01T00:00:00+00:00"))],
+            Some(AccountKind::Debit),
+        )
+        .unwrap(/*:test:*/);
+
+        assert!(!lc.is_open_at(&ts("2018-02-28T23:59:59+00:00")));
+        assert!(lc.is_open_at(&ts("2018-03-01T00:00:00+00:00")));
+        assert!(lc.is_open_at(&ts("2023-12-31T23:59:59.999999999+00:00")));
+        assert!(!lc.is_open_at(&ts("2024-01-01T00:00:00+00:00")));
+    }
+
+    #[test]
+    // test: e1b0c617-8498-435f-9b07-e89470c43ca5
+    fn lifecycle_multiple_periods() {
+        let lc = AccountLifecycle::try_from(
+            vec![
+                period(Some("2025-12-01T00:00:00+00:00"), Some("2026-01-15T00:00:00+00:00")),
+                period(None, Some("2024-01-15T00:00:00+00:00")),
+                period(Some("2024-12-01T00:00:00+00:00"), Some("2025-01-15T00:00:00+00:00")),
+            ],
+            None,
+        )
+        .unwrap(/*:test:*/);
+
+        assert!(lc.is_open_at(&ts("2000-01-01T00:00:00+00:00")));
+        assert!(!lc.is_open_at(&ts("2024-06-01T00:00:00+00:00")));
+        assert!(lc.is_open_at(&ts("2025-01-01T00:00:00+00:00")));
+        assert!(!lc.is_open_at(&ts("2025-06-01T00:00:00+00:00")));
+        assert!(lc.is_open_at(&ts("2026-01-01T00:00:00+00:00")));
+        assert!(!lc.is_open_at(&ts("2026-01-15T00:00:00+00:00")));
+
+        let during = |begin: &str, end: &str| lc.is_open_during(&ts(begin), &ts(end));
+        assert!(during(
+            "2024-06-01T00:00:00+00:00",
+            "2024-12-01T00:00:00+00:00"
+        ));
+        assert!(during(
+            "2025-01-14T00:00:00+00:00",
+            "2025-06-01T00:00:00+00:00"
+        ));
+        assert!(!during(
+            "2025-01-15T00:00:00+00:00",
+            "2025-11-30T23:59:59+00:00"
+        ));
+        assert!(during(
+            "2024-01-14T23:59:59+00:00",
+            "2024-01-14T23:59:59+00:00"
+        ));
+        assert!(!during(
+            "2024-01-15T00:00:00+00:00",
+            "2024-01-15T00:00:00+00:00"
+        ));
+    }
+
+    #[test]
+    // test: 67871f7d-3668-42a4-924c-5261659dbf2a
+    fn lifecycle_overlapping_periods() {
+        let res = AccountLifecycle::try_from(
+            vec![
+                period(
+                    Some("2024-12-01T00:00:00+00:00"),
+                    Some("2025-01-15T00:00:00+00:00"),
+                ),
+                period(Some("2025-01-14T00:00:00+00:00"), None),
+            ],
+            None,
+        );
+        assert!(res.is_err());
+
+        let res = AccountLifecycle::try_from(
+            vec![
+                period(Some("2024-12-01T00:00:00+00:00"), None),
+                period(
+                    Some("2025-12-01T00:00:00+00:00"),
+                    Some("2026-01-15T00:00:00+00:00"),
+                ),
+            ],
+            None,
+        );
+        assert!(res.is_err());
+
+        // adjacent periods are ok: [a, b) [b, c)
+        let res = AccountLifecycle::try_from(
+            vec![
+                period(
+                    Some("2024-12-01T00:00:00+00:00"),
+                    Some("2025-01-15T00:00:00+00:00"),
+                ),
+                period(Some("2025-01-15T00:00:00+00:00"), None),
+            ],
+            None,
+        );
+        assert!(res.is_ok());
+    }
+}
@@ -1,9 +1,9 @@
 /*
- * Tackler-NG 2023-2024
+ * Tackler-NG 2023-2026
  * SPDX-License-Identifier: Apache-2.0
  */
 
-use crate::model::TxnAccount;
+use crate::model::{AccountTreeNode, TxnAccount};
 use rust_decimal::Decimal;
 use std::cmp::Ordering;
 
@@ -12,6 +12,26 @@ pub struct BalanceTreeNode {
     pub(crate) acctn: TxnAccount,
     pub(crate) sub_acc_tree_sum: Decimal,
     pub(crate) account_sum: Decimal,
+    /// Converted value of the sums, if balance is valued
+    pub(crate) value: Option<BalanceValue>,
+}
+
+/// Converted value of balance tree node
+///
+/// Value is `None`, if price is missing for any of the postings.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct BalanceValue {
+    pub(crate) sub_acc_tree_sum: Option<Decimal>,
+    pub(crate) account_sum: Option<Decimal>,
+}
+
+/// Multi-commodity inventory of an account
+///
+/// Positions are balance tree nodes of the account, one for each commodity.
+#[derive(Debug)]
+pub(crate) struct InventoryNode<'a> {
+    pub(crate) atn: &'a AccountTreeNode,
+    pub(crate) positions: Vec<&'a BalanceTreeNode>,
 }
 
 pub(crate) fn ord_by_btn(before: &BalanceTreeNode, after: &BalanceTreeNode) -> Ordering {
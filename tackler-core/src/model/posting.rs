@@ -3,14 +3,24 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use crate::diagnostic;
+use crate::diagnostic::Diagnostic;
 use crate::model::Commodity;
 use crate::model::Posts;
 use crate::model::TxnAccount;
+use crate::model::source::Position;
 use crate::tackler;
 use rust_decimal::Decimal;
 use std::fmt::{Display, Formatter};
 use std::sync::Arc;
 
+/// Unit cost of opening position `{ amount commodity }`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnitCost {
+    pub amount: Decimal,
+    pub commodity: Arc<Commodity>,
+}
+
 #[derive(Debug, Clone)]
 #[non_exhaustive]
 pub struct Posting {
@@ -20,7 +30,13 @@ pub struct Posting {
     pub txn_amount: Decimal,
     pub is_total_amount: bool,
     pub txn_commodity: Arc<Commodity>, // todo: check / fix this
+    /// Cost basis of opening position, if any
+    pub cost: Option<UnitCost>,
+    /// Asserted balance of the account after this transaction, if any
+    pub assertion: Option<Decimal>,
     pub comment: Option<String>,
+    /// Position of the posting in its shard, if known
+    pub(crate) pos: Position,
 }
 
 impl Posting {
@@ -30,11 +46,14 @@ impl Posting {
         txn_amount: Decimal,
         is_total_amount: bool,
         txn_commodity: Arc<Commodity>,
+        cost: Option<UnitCost>,
         comment: Option<String>,
     ) -> Result<Posting, tackler::Error> {
         if amount.is_zero() {
             let msg = format!("Zero sum postings are not allowed: {}", acctn.atn.account);
-            return Err(msg.into());
+            let diag = Diagnostic::error(diagnostic::ZERO_POSTING, msg)
+                .with_help("Remove the posting or give it a non-zero amount");
+            return Err(diag.into());
         }
 
         Ok(Posting {
@@ -43,7 +62,10 @@ impl Posting {
             txn_amount,
             is_total_amount,
             txn_commodity,
+            cost,
+            assertion: None,
             comment,
+            pos: Position::Unknown,
         })
     }
 }
@@ -53,6 +75,17 @@ pub fn txn_sum(posts: &Posts) -> Decimal {
     posts.iter().map(|p| p.txn_amount).sum()
 }
 
+impl Posting {
+    /// Test if the value position of this posting is the cost of the opening position
+    fn is_valued_at_cost(&self) -> bool {
+        self.cost.as_ref().is_some_and(|c| {
+            !self.is_total_amount
+                && c.commodity.name == self.txn_commodity.name
+                && c.amount * self.amount == self.txn_amount
+        })
+    }
+}
+
 impl Display for Posting {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let sign_space = if self.amount.is_sign_negative() {
@@ -64,7 +97,7 @@ impl Display for Posting {
         let comm = &self.acctn.comm;
         write!(
             f,
-            "{}  {}{}{}{}{}",
+            "{}  {}{}{}{}{}{}{}",
             self.acctn.atn,
             sign_space,
             self.amount,
@@ -73,7 +106,11 @@ impl Display for Posting {
             } else {
                 String::new()
             },
-            if self.txn_commodity.is_any() {
+            self.cost
+                .as_ref()
+                .map(|c| format!(" {{{} {}}}", c.amount, c.commodity.name))
+                .unwrap_or_default(),
+            if self.txn_commodity.is_any() && !self.is_valued_at_cost() {
                 #[allow(clippy::collapsible_else_if)]
                 // todo: old-scala comment: fix this
                 if self.txn_commodity.name == self.acctn.comm.name {
@@ -92,6 +129,13 @@ impl Display for Posting {
             } else {
                 String::default()
             },
+            self.assertion
+                .map(|a| if comm.is_any() {
+                    format!(" == {a} {}", comm.name)
+                } else {
+                    format!(" == {a}")
+                })
+                .unwrap_or_default(),
             self.comment
                 .as_ref()
                 .map(|c| format!(" ; {c}"))
@@ -123,6 +167,7 @@ mod tests {
                 false,
                 Arc::new(Commodity::default()),
                 None,
+                None,
             );
             assert!(p.is_err());
         }
@@ -140,6 +185,7 @@ mod tests {
                 false,
                 Arc::new(Commodity::default()),
                 None,
+                None,
             );
             assert!(p.is_err());
         }
@@ -165,7 +211,7 @@ mod tests {
             atn: acctn,
             comm: Arc::new(Commodity::default()),
         };
-        let p = Posting::from(txntn, v, v, false, Arc::new(Commodity::default()), None).unwrap(/*:test:*/);
+        let p = Posting::from(txntn, v, v, false, Arc::new(Commodity::default()), None, None).unwrap(/*:test:*/);
 
         let p_str = format!("{p}");
         assert_eq!(p_str, ref_str);
@@ -193,7 +239,7 @@ mod tests {
             atn: acctn,
             comm: Arc::new(Commodity::default()),
         };
-        let p = Posting::from(txntn, v, v, false, Arc::new(Commodity::default()), None).unwrap(/*:test:*/);
+        let p = Posting::from(txntn, v, v, false, Arc::new(Commodity::default()), None, None).unwrap(/*:test:*/);
         let p_str = format!("{p}");
         assert_eq!(p_str, ref_str);
         assert_eq!(p.to_string(), ref_str);
@@ -209,7 +255,7 @@ mod tests {
             atn: acctn,
             comm: Arc::new(Commodity::default()),
         };
-        let p = Posting::from(txntn, v, v, false, Arc::new(Commodity::default()), Some("comment".to_string())).unwrap(/*:test:*/);
+        let p = Posting::from(txntn, v, v, false, Arc::new(Commodity::default()), None, Some("comment".to_string())).unwrap(/*:test:*/);
 
         let p_str = format!("{p}");
         assert_eq!(p_str, "a:b   123.01 ; comment");
@@ -235,6 +281,7 @@ mod tests {
                 name: "€".to_string(),
             }),
             None,
+            None,
         )
         .unwrap(/*:test:*/);
 
@@ -260,6 +307,7 @@ mod tests {
             Arc::new(Commodity {
                 name: "€".to_string(),
             }),
+            None,
             Some("comment".to_string()),
         )
         .unwrap(/*:test:*/);
@@ -287,6 +335,7 @@ mod tests {
                 name: "€".to_string(),
             }),
             None,
+            None,
         )
         .unwrap(/*:test:*/);
 
@@ -312,10 +361,56 @@ mod tests {
             Arc::new(Commodity {
                 name: "€".to_string(),
             }),
+            None,
             Some("comment".to_string()),
         )
         .unwrap(/*:test:*/);
 
         assert_eq!(p.to_string(), "a:b   123.00 = 246.00 € ; comment");
     }
+
+    #[test]
+    // test: e15026ae-c179-49e0-92f2-18277542e558
+    // desc: "opening position"
+    fn opening_position() {
+        let usd = Arc::new(Commodity {
+            name: "USD".to_string(),
+        });
+        let eur = Arc::new(Commodity {
+            name: "EUR".to_string(),
+        });
+        let acctn = Arc::new(AccountTreeNode::from("a:b").unwrap(/*:test:*/));
+        let txntn = TxnAccount {
+            atn: acctn,
+            comm: usd,
+        };
+        let cost = UnitCost {
+            amount: Decimal::new(138, 2),
+            commodity: eur.clone(),
+        };
+
+        let p = Posting::from(
+            txntn.clone(),
+            Decimal::new(1, 0),
+            Decimal::new(138, 2),
+            false,
+            eur.clone(),
+            Some(cost.clone()),
+            None,
+        )
+        .unwrap(/*:test:*/);
+        assert_eq!(p.to_string(), "a:b   1 USD {1.38 EUR}");
+
+        let p = Posting::from(
+            txntn,
+            Decimal::new(-1, 0),
+            Decimal::new(-104, 2),
+            false,
+            eur,
+            Some(cost),
+            None,
+        )
+        .unwrap(/*:test:*/);
+        assert_eq!(p.to_string(), "a:b  -1 USD {1.38 EUR} @ 1.04 EUR");
+    }
 }
@@ -1,13 +1,18 @@
 /*
- * Tackler-NG 2025
+ * Tackler-NG 2025-2026
  * SPDX-License-Identifier: Apache-2.0
  */
 
 use std::sync::Arc;
 
+use itertools::Itertools;
 use rust_decimal::Decimal;
+use tackler_api::txn_ts;
 
 use super::Commodity;
+use crate::diagnostic;
+use crate::diagnostic::Diagnostic;
+use crate::tackler;
 
 /// Entry in the price database
 #[derive(Debug, Eq)]
@@ -48,3 +53,58 @@ impl PartialOrd for PriceEntry {
 }
 
 pub type PriceDb = Vec<PriceEntry>;
+
+/// Merge price databases of multiple sources
+///
+/// Entries are sorted and de-duplicated by their ordering (timestamp and
+/// commodity pair). If sources give different prices for the same timestamp
+/// and commodity pair, then all of these conflicts are reported as an error.
+///
+/// # Errors
+/// Returns `Err` if there are conflicting price entries
+pub fn merge_price_dbs(sources: Vec<(String, PriceDb)>) -> Result<PriceDb, tackler::Error> {
+    let (names, dbs): (Vec<_>, Vec<_>) = sources.into_iter().unzip();
+    let entries = dbs
+        .into_iter()
+        .enumerate()
+        .flat_map(|(i, db)| db.into_iter().map(move |e| (e, i)))
+        .sorted_by(|(a, _), (b, _)| a.cmp(b))
+        .collect::<Vec<_>>();
+
+    let mut conflicts = Vec::new();
+    let mut price_db = PriceDb::with_capacity(entries.len());
+    for (_, group) in &entries.into_iter().chunk_by(|(e, _)| {
+        (
+            e.timestamp.clone(),
+            e.base_commodity.clone(),
+            e.eq_commodity.clone(),
+        )
+    }) {
+        let group = group.collect::<Vec<_>>();
+        let (first, _) = &group[0];
+        if group.iter().any(|(e, _)| e.eq_amount != first.eq_amount) {
+            conflicts.push(format!(
+                "{} {} in {}: {}",
+                txn_ts::rfc_3339(&first.timestamp),
+                first.base_commodity.name,
+                first.eq_commodity.name,
+                group
+                    .iter()
+                    .map(|(e, i)| format!("{} ('{}')", e.eq_amount, names[*i]))
+                    .join(", ")
+            ));
+        }
+        price_db.extend(group.into_iter().map(|(e, _)| e).take(1));
+    }
+
+    if conflicts.is_empty() {
+        Ok(price_db)
+    } else {
+        let msg = format!(
+            "Found {} conflicting price entries:\n{}",
+            conflicts.len(),
+            conflicts.iter().take(10).join("\n")
+        );
+        Err(Diagnostic::error(diagnostic::PRICE_CONFLICT, msg).into())
+    }
+}
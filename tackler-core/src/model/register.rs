@@ -21,6 +21,123 @@ pub struct RegisterPosting<'a> {
     pub amount: Decimal,
     pub target_commodity: Arc<Commodity>,
     pub rate: Option<Decimal>,
+    /// Running position of posting's account and commodity
+    pub position: Position,
+}
+
+/// Cost basis of a position
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CostBasis {
+    pub amount: Decimal,
+    pub commodity: Arc<Commodity>,
+}
+
+/// Running position (quantity and cost basis) of an account and commodity
+///
+/// Cost basis is collected from priced postings (`@`, `=` and `{cost}`).
+/// Reducing postings remove cost basis at the average unit cost.
+/// If the position is added by a posting without price, or it is priced
+/// with different commodities, its cost basis is unknown until the position is closed.
+#[derive(Debug, Clone, Default)]
+pub struct Position {
+    /// Running quantity in account's commodity
+    pub quantity: Decimal,
+    /// Running cost basis, if it's known
+    pub cost: Option<CostBasis>,
+    mixed: bool,
+}
+
+impl Position {
+    pub(crate) fn add(&mut self, post: &Posting) {
+        let before = self.quantity;
+        let after = before + post.amount;
+        self.quantity = after;
+
+        let is_reducing =
+            !before.is_zero() && before.is_sign_positive() != post.amount.is_sign_positive();
+
+        if is_reducing && (after.is_zero() || after.is_sign_positive() == before.is_sign_positive())
+        {
+            if let Some(cost) = &mut self.cost {
+                cost.amount = cost.amount * after / before;
+            }
+        } else {
+            let basis = if is_reducing {
+                // position was flipped, the rest is opened by this posting
+                self.cost = None;
+                self.mixed = false;
+                post.txn_amount * after / post.amount
+            } else {
+                post.txn_amount
+            };
+            if post.txn_commodity == post.acctn.comm {
+                // cost of this posting is not known
+                self.cost = None;
+                self.mixed = true;
+            } else if !self.mixed {
+                match &mut self.cost {
+                    Some(cost) if cost.commodity == post.txn_commodity => cost.amount += basis,
+                    Some(_) => {
+                        self.cost = None;
+                        self.mixed = true;
+                    }
+                    None => {
+                        self.cost = Some(CostBasis {
+                            amount: basis,
+                            commodity: post.txn_commodity.clone(),
+                        });
+                    }
+                }
+            }
+        }
+        if after.is_zero() {
+            self.mixed = false;
+        }
+    }
+
+    /// Position line of register entry: quantity, cost basis and average unit cost
+    fn fmt_with_cfg(&self, comm: &Commodity, reg_cfg: &RegisterSettings) -> String {
+        let (quantity, cost) = if reg_cfg.inverted {
+            (-self.quantity, self.cost.as_ref().map(|c| -c.amount))
+        } else {
+            (self.quantity, self.cost.as_ref().map(|c| c.amount))
+        };
+        let comm = if comm.is_any() {
+            format!(" {}", comm.name)
+        } else {
+            String::new()
+        };
+        let mut line = format!(
+            "{:<33}{:>18}{comm}",
+            "  position",
+            format_with_scale(0, &quantity, &reg_cfg.scale)
+        );
+        if let (Some(cost), Some(basis)) = (&self.cost, cost) {
+            let _ = write!(
+                line,
+                ", cost {} {}",
+                format_with_scale(0, &basis, &reg_cfg.scale),
+                cost.commodity.name
+            );
+            if let Some(avg) = self.avg_cost() {
+                let _ = write!(
+                    line,
+                    ", avg {} {}",
+                    format_with_scale(0, &avg, &reg_cfg.scale),
+                    cost.commodity.name
+                );
+            }
+        }
+        line
+    }
+
+    /// Average unit cost of the position
+    pub fn avg_cost(&self) -> Option<Decimal> {
+        match &self.cost {
+            Some(cost) if !self.quantity.is_zero() => Some(cost.amount / self.quantity),
+            _ => None,
+        }
+    }
 }
 
 impl RegisterPosting<'_> {
@@ -129,8 +246,177 @@ impl RegisterEntry<'_> {
                 let _ = write!(line, " ; {comment}");
             }
             let _ = writeln!(reg_entry_txt, "{line}");
+            if reg_cfg.cost_basis {
+                let _ = writeln!(
+                    reg_entry_txt,
+                    "{indent}{}",
+                    p.position.fmt_with_cfg(&p.post.acctn.comm, reg_cfg)
+                );
+            }
         }
         let _ = writeln!(reg_entry_txt, "{}", "-".repeat(line_len));
         reg_entry_txt
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kernel::Settings;
+    use crate::parser;
+    use indoc::indoc;
+    use tackler_rs::IndocUtils;
+
+    fn positions(mut txns_str: &str) -> Vec<Position> {
+        let settings = Settings::default();
+        let txn_data = parser::string_to_txns(&mut txns_str, &settings).unwrap(/*:test:*/);
+        let mut pos = Position::default();
+        txn_data
+            .get_all()
+            .unwrap(/*:test:*/)
+            .txns
+            .iter()
+            .flat_map(|txn| txn.posts.iter())
+            .filter(|p| p.acctn.atn.account == "Assets:Broker")
+            .map(|p| {
+                pos.add(p);
+                pos.clone()
+            })
+            .collect()
+    }
+
+    #[test]
+    // test: 5b1e9d3f-7a2c-4e6b-8d0f-2c4a6e8b0d1f
+    // desc: cost basis is reduced at average cost, and flipped position is opened at price
+    fn position_average_cost() {
+        #[rustfmt::skip]
+        let txns_str = indoc!(
+           "|2024-01-05
+            | Assets:Broker  10 ACME @ 5 EUR
+            | Assets:Cash
+            |
+            |2024-01-06
+            | Assets:Broker  10 ACME {8 EUR}
+            | Assets:Cash
+            |
+            |2024-01-07
+            | Assets:Broker  -5 ACME = -40 EUR
+            | Assets:Cash
+            |
+            |2024-01-08
+            | Assets:Broker  -25 ACME @ 10 EUR
+            | Assets:Cash
+            |"
+        ).strip_margin();
+        let pos = positions(&txns_str);
+
+        let values: Vec<_> = pos
+            .iter()
+            .map(|p| {
+                (
+                    p.quantity,
+                    p.cost.as_ref().map(|c| c.amount.normalize()),
+                    p.avg_cost().map(|a| a.normalize()),
+                )
+            })
+            .collect();
+        assert_eq!(
+            values,
+            vec![
+                (
+                    Decimal::from(10),
+                    Some(Decimal::from(50)),
+                    Some(Decimal::from(5))
+                ),
+                (
+                    Decimal::from(20),
+                    Some(Decimal::from(130)),
+                    Some(Decimal::new(65, 1))
+                ),
+                (
+                    Decimal::from(15),
+                    Some(Decimal::new(975, 1)),
+                    Some(Decimal::new(65, 1))
+                ),
+                (
+                    Decimal::from(-10),
+                    Some(Decimal::from(-100)),
+                    Some(Decimal::from(10))
+                ),
+            ]
+        );
+        assert_eq!(
+            pos[3].cost.as_ref().unwrap(/*:test:*/).commodity.name,
+            "EUR"
+        );
+    }
+
+    #[test]
+    // test: 2a6e0c4f-8b1d-4f3a-a7c9-5e1b3d7f9a02
+    // desc: cost basis is unknown after posting without price until position is closed
+    fn position_unpriced_addition() {
+        #[rustfmt::skip]
+        let txns_str = indoc!(
+           "|2024-01-05
+            | Assets:Broker  10 ACME @ 150 EUR
+            | Assets:Cash
+            |
+            |2024-01-06
+            | Assets:Broker  5 ACME
+            | Income:Gifts
+            |
+            |2024-01-07
+            | Assets:Broker  -15 ACME @ 160 EUR
+            | Assets:Cash
+            |
+            |2024-01-08
+            | Assets:Broker  2 ACME @ 170 EUR
+            | Assets:Cash
+            |"
+        ).strip_margin();
+        let pos = positions(&txns_str);
+
+        assert_eq!(pos[0].avg_cost(), Some(Decimal::from(150)));
+        assert_eq!(pos[1].quantity, Decimal::from(15));
+        assert!(pos[1].cost.is_none());
+        assert_eq!(pos[1].avg_cost(), None);
+        assert!(pos[2].cost.is_none());
+        assert_eq!(pos[3].avg_cost(), Some(Decimal::from(170)));
+        assert_eq!(
+            pos[3].cost.as_ref().map(|c| c.amount),
+            Some(Decimal::from(340))
+        );
+    }
+
+    #[test]
+    // test: 9e4c2a0b-3d5f-4b7a-9c1e-6f8a0b2d4c6e
+    // desc: cost basis is unknown with mixed price commodities until position is closed
+    fn position_mixed_cost_commodities() {
+        #[rustfmt::skip]
+        let txns_str = indoc!(
+           "|2024-01-05
+            | Assets:Broker  10 ACME @ 5 EUR
+            | Assets:Cash
+            |
+            |2024-01-06
+            | Assets:Broker  10 ACME @ 6 USD
+            | Assets:Cash
+            |
+            |2024-01-07
+            | Assets:Broker  -20 ACME @ 7 USD
+            | Assets:Cash
+            |
+            |2024-01-08
+            | Assets:Broker  4 ACME @ 8 USD
+            | Assets:Cash
+            |"
+        ).strip_margin();
+        let pos = positions(&txns_str);
+
+        assert!(pos[0].cost.is_some());
+        assert!(pos[1].cost.is_none());
+        assert!(pos[2].cost.is_none());
+        assert_eq!(pos[2].avg_cost(), None);
+        assert_eq!(pos[3].avg_cost(), Some(Decimal::from(8)));
+    }
+}
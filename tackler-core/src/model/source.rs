@@ -0,0 +1,168 @@
+/*
+ * Tackler-NG 2026
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Source location of txns and postings
+//!
+//! Location is recorded while parsing, so that errors which are found
+//! only after parsing (e.g. duplicate UUIDs or failed balance assertions)
+//! could be reported at the exact path, line and column.
+
+use crate::diagnostic::{Diagnostic, GitObject, Span, char_span};
+use crate::model::Txns;
+use std::sync::Arc;
+
+/// Source shard of txns, e.g. file or git object
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct Shard {
+    /// Path of the file, or path of the git object
+    pub(crate) path: Option<String>,
+    pub(crate) git: Option<GitObject>,
+}
+
+impl Shard {
+    pub(crate) fn from_path(path: impl Into<String>) -> Shard {
+        Shard {
+            path: Some(path.into()),
+            git: None,
+        }
+    }
+
+    pub(crate) fn from_git(path: impl Into<String>, commit: &str, object: &str) -> Shard {
+        Shard {
+            path: Some(path.into()),
+            git: Some(GitObject {
+                commit: commit.to_string(),
+                object: object.to_string(),
+            }),
+        }
+    }
+
+    /// Set path (and git object) of the diagnostic to this shard
+    pub(crate) fn locate(&self, diag: Diagnostic) -> Diagnostic {
+        let diag = match &self.path {
+            Some(path) => diag.with_path(path.as_str()),
+            None => diag,
+        };
+        match &self.git {
+            Some(git) => diag.with_git(&git.commit, &git.object),
+            None => diag,
+        }
+    }
+}
+
+/// Position of txn or posting in its shard
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum Position {
+    #[default]
+    Unknown,
+    /// Count of bytes from the position to the end of the shard
+    ///
+    /// Parser records positions in this form, as it doesn't
+    /// see the start of the shard.
+    Tail(usize),
+    /// Line and column (in chars) starting from 1, and span of the position
+    Resolved {
+        line: usize,
+        column: usize,
+        span: Span,
+    },
+}
+
+/// Source location of txn
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Source {
+    pub(crate) shard: Option<Arc<Shard>>,
+    pub(crate) pos: Position,
+}
+
+impl Source {
+    /// Set location of the diagnostic to `pos` of this source
+    ///
+    /// `pos` is the position of the txn itself or one of its postings.
+    pub(crate) fn locate(&self, diag: Diagnostic, pos: Position) -> Diagnostic {
+        let diag = match &self.shard {
+            Some(shard) => shard.locate(diag),
+            None => diag,
+        };
+        match pos {
+            Position::Resolved { line, column, span } => diag.with_line_column(line, column, span),
+            Position::Unknown | Position::Tail(_) => diag,
+        }
+    }
+
+    /// Location of the txn in text form, e.g. `txns/a.txn:12:1`
+    pub(crate) fn display(&self) -> Option<String> {
+        let path = self.shard.as_ref().and_then(|s| s.path.as_deref());
+        match (path, self.pos) {
+            (Some(path), Position::Resolved { line, column, .. }) => {
+                Some(format!("{path}:{line}:{column}"))
+            }
+            (None, Position::Resolved { line, column, .. }) => {
+                Some(format!("line {line}, column {column}"))
+            }
+            (Some(path), _) => Some(path.to_string()),
+            (None, _) => None,
+        }
+    }
+}
+
+/// Resolve positions of parsed txns and their postings
+///
+/// Txns must be in input order (as they are after parsing),
+/// so that lines are counted only once over the whole input.
+pub(crate) fn resolve(input: &str, shard: Option<&Arc<Shard>>, txns: &mut Txns) {
+    let mut lines = LineCounter::new(input);
+    for txn in txns.iter_mut() {
+        txn.source.shard = shard.cloned();
+        txn.source.pos = lines.resolve(txn.source.pos);
+        for p in &mut txn.posts {
+            p.pos = lines.resolve(p.pos);
+        }
+    }
+}
+
+/// Line counter over the input with increasing byte offsets
+struct LineCounter<'a> {
+    input: &'a str,
+    /// Byte offset of the start of the current line
+    line_start: usize,
+    /// Current line number, starting from 1
+    line: usize,
+}
+
+impl LineCounter<'_> {
+    fn new(input: &str) -> LineCounter<'_> {
+        LineCounter {
+            input,
+            line_start: 0,
+            line: 1,
+        }
+    }
+
+    fn resolve(&mut self, pos: Position) -> Position {
+        let Position::Tail(tail) = pos else {
+            return pos;
+        };
+        let Some(offset) = self.input.len().checked_sub(tail) else {
+            return Position::Unknown;
+        };
+        if offset < self.line_start {
+            // out of order, start over
+            self.line_start = 0;
+            self.line = 1;
+        }
+        let skipped = &self.input[self.line_start..offset];
+        if let Some(nl) = skipped.rfind('\n') {
+            self.line += skipped.matches('\n').count();
+            self.line_start += nl + 1;
+        }
+        let column = self.input[self.line_start..offset].chars().count() + 1;
+        Position::Resolved {
+            line: self.line,
+            column,
+            span: char_span(self.input, offset),
+        }
+    }
+}
@@ -3,7 +3,10 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
-use crate::model::{Posts, posting};
+use crate::diagnostic;
+use crate::diagnostic::Diagnostic;
+use crate::model::source::Source;
+use crate::model::{Posting, Posts, posting};
 use crate::tackler;
 use jiff::tz;
 use std::cmp::Ordering;
@@ -16,6 +19,8 @@ use tackler_api::txn_ts;
 pub struct Transaction {
     pub(crate) header: TxnHeader,
     pub(crate) posts: Posts,
+    /// Source location of the txn, if known
+    pub(crate) source: Source,
 }
 
 impl Transaction {
@@ -25,10 +30,41 @@ impl Transaction {
         let txn_sum = posting::txn_sum(&posts);
         if !txn_sum.is_zero() {
             let msg = format!("TXN postings do not zero: {txn_sum}");
-            return Err(msg.into());
+            let diag = Diagnostic::error(diagnostic::UNBALANCED_TXN, msg).with_help(
+                "Sum of postings must be zero, amount of the last posting could be left out",
+            );
+            return Err(diag.into());
         }
 
-        Ok(Transaction { header, posts })
+        Ok(Transaction {
+            header,
+            posts,
+            source: Source::default(),
+        })
+    }
+
+    /// Reverse transaction by negating all of its postings
+    ///
+    /// This is used to cancel effect of the transaction, e.g. with git range input.
+    /// Balance assertions are dropped, as they are not valid for the reversed txn.
+    #[must_use]
+    pub(crate) fn into_reversed(self) -> Transaction {
+        let posts = self
+            .posts
+            .into_iter()
+            .map(|p| Posting {
+                amount: -p.amount,
+                txn_amount: -p.txn_amount,
+                assertion: None,
+                ..p
+            })
+            .collect();
+
+        Transaction {
+            header: self.header,
+            posts,
+            source: self.source,
+        }
     }
 }
 
@@ -124,6 +160,7 @@ mod tests {
             false,
             Arc::new(Commodity::default()),
             None,
+            None,
         )
         .unwrap(/*:test:*/);
         let cd_post = Posting::from(
@@ -133,6 +170,7 @@ mod tests {
             false,
             Arc::new(Commodity::default()),
             None,
+            None,
         )
         .unwrap(/*:test:*/);
         let ab_post = Posting::from(
@@ -142,6 +180,7 @@ mod tests {
             false,
             Arc::new(Commodity::default()),
             None,
+            None,
         )
         .unwrap(/*:test:*/);
 
@@ -149,6 +188,7 @@ mod tests {
             Transaction {
                 header: tnx_hdr,
                 posts: vec![ef_post, cd_post, ab_post],
+                ..Default::default()
             },
             indoc!(
                 "|2023-02-04T14:03:05.047974+02:00 'desc
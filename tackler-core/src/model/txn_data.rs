@@ -3,15 +3,23 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use crate::diagnostic;
+use crate::diagnostic::Diagnostic;
 use crate::kernel::hash::Hash;
 use crate::kernel::{Predicate, Settings};
-use crate::model::{TxnRefs, Txns, transaction};
+use crate::model::{Transaction, TxnAccount, TxnRefs, Txns, transaction};
 use crate::tackler;
 use itertools::Itertools;
+use rust_decimal::Decimal;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+
+use std::fmt::Write;
 use tackler_api::filters::FilterDefinition;
 use tackler_api::metadata::items::{MetadataItem, TxnFilterDescription, TxnSetChecksum};
 use tackler_api::metadata::{Checksum, Metadata};
-use uuid::Uuid;
+use tackler_api::txn_ts;
 
 #[derive(Debug)]
 pub struct TxnData {
@@ -59,16 +67,20 @@ impl TxnData {
     ) -> Result<TxnData, tackler::Error> {
         let metadata = mdi_opt.map(Metadata::from_mdi);
 
+        let mut errors = Vec::new();
         if settings.audit_mode {
-            check_uuids(&txns)?;
+            errors.extend(check_uuids(&txns));
         }
         if settings.is_extid_unique() {
-            check_extid(&txns)?;
+            errors.extend(check_extid(&txns));
         }
 
         let mut t = txns;
         t.sort_by(transaction::ord_by_txn);
 
+        errors.extend(check_balance_assertions(&t));
+        settings.check_errors(errors)?;
+
         Ok(TxnData {
             metadata,
             txns: t,
@@ -77,9 +89,62 @@ impl TxnData {
         })
     }
 
+    /// Create delta `TxnData` from added and removed transactions
+    ///
+    /// Removed transactions are reversed, so that the resulting txn set
+    /// is the signed change between two versions of the journal.
+    /// Added and removed transactions are validated separately,
+    /// as the same txn could be both removed and added (e.g. modified).
+    ///
+    /// # Errors
+    /// If added or removed txns are logically invalid, the method will return error
+    pub(crate) fn try_from_delta(
+        mdi_opt: Option<MetadataItem>,
+        added: Txns,
+        removed: Txns,
+        settings: &Settings,
+    ) -> Result<TxnData, tackler::Error> {
+        for txns in [&added, &removed] {
+            let mut errors = Vec::new();
+            if settings.audit_mode {
+                errors.extend(check_uuids(txns));
+            }
+            if settings.is_extid_unique() {
+                errors.extend(check_extid(txns));
+            }
+            settings.check_errors(errors)?;
+        }
+
+        let mut t = added;
+        t.extend(removed.into_iter().map(Transaction::into_reversed));
+        t.sort_by(transaction::ord_by_txn);
+
+        Ok(TxnData {
+            metadata: mdi_opt.map(Metadata::from_mdi),
+            txns: t,
+            hash: settings.get_hash().clone(),
+            unique_extid: settings.is_extid_unique(),
+        })
+    }
+
+    /// Remove txns by their index (in txn order)
+    pub(crate) fn remove_txns(&mut self, removed: &HashSet<usize>) {
+        self.txns = std::mem::take(&mut self.txns)
+            .into_iter()
+            .enumerate()
+            .filter_map(|(i, txn)| (!removed.contains(&i)).then_some(txn))
+            .collect();
+    }
+
+    /// Add metadata item to the metadata of `TxnData`
+    pub(crate) fn push_metadata(&mut self, mdi: MetadataItem) {
+        self.metadata.get_or_insert_with(Metadata::new).push(mdi);
+    }
+
     /// Append `TxnData` to existing `TxnData`
     ///
-    /// This will reset the Metadata of target `TxnData`
+    /// This will reset the Metadata of target `TxnData`.
+    /// Balance assertions are checked again over the combined txn set.
     ///
     /// # Errors
     /// Returns `Err` in case resulting txn set is invalid (e.g. there are missing UUIDs)
@@ -88,11 +153,18 @@ impl TxnData {
     pub fn append(&mut self, txn_data: &mut TxnData) -> Result<&mut Self, tackler::Error> {
         self.txns.append(&mut txn_data.txns);
 
+        let mut errors = Vec::new();
         if self.hash.is_some() {
-            check_uuids(&self.txns)?;
+            errors.extend(check_uuids(&self.txns));
         }
         if self.unique_extid {
-            check_extid(&self.txns)?;
+            errors.extend(check_extid(&self.txns));
+        }
+        let mut sorted_txns: TxnRefs<'_> = self.txns.iter().collect();
+        sorted_txns.sort_by(|a, b| transaction::ord_by_txn(a, b));
+        errors.extend(check_balance_assertions(sorted_txns));
+        if let Some(diag) = errors.into_iter().next() {
+            return Err(diag.into());
         }
 
         let metadata =
@@ -126,12 +198,35 @@ impl TxnData {
     /// # Errors
     /// Returns `Err` in case resulting Txn Set is not valid (e.g. there are missing UUIDs)
     pub fn filter(&self, tf: &FilterDefinition) -> Result<TxnSet<'_>, tackler::Error> {
-        let refvec: TxnRefs<'_> = self.txns.iter().filter(|txn| tf.eval(txn)).collect();
+        self.filter_named(&[(None, tf)])
+    }
+
+    /// Filter txns with multiple filters
+    ///
+    /// Txn is selected, if all filters select it. Each filter
+    /// is recorded to the metadata, with its name if it has one.
+    ///
+    /// # Errors
+    /// Returns `Err` in case resulting Txn Set is not valid (e.g. there are missing UUIDs)
+    pub fn filter_named(
+        &self,
+        filters: &[(Option<&str>, &FilterDefinition)],
+    ) -> Result<TxnSet<'_>, tackler::Error> {
+        let refvec: TxnRefs<'_> = self
+            .txns
+            .iter()
+            .filter(|txn| filters.iter().all(|(_, tf)| tf.eval(txn)))
+            .collect();
 
         let mut metadata =
             TxnData::make_metadata(self.hash.as_ref(), self.metadata.as_ref(), &refvec)?;
-        let filter_mdi = MetadataItem::TxnFilterDescription(TxnFilterDescription::from(tf.clone()));
-        metadata.push(filter_mdi);
+        for (name, tf) in filters {
+            let tfd = match name {
+                Some(name) => TxnFilterDescription::from_named(name, (*tf).clone()),
+                None => TxnFilterDescription::from((*tf).clone()),
+            };
+            metadata.push(MetadataItem::TxnFilterDescription(tfd));
+        }
 
         Ok(TxnSet {
             metadata: Some(metadata),
@@ -158,66 +253,135 @@ impl TxnData {
     }
 }
 
-fn check_extid(txns: &Txns) -> Result<(), tackler::Error> {
-    let dups: Vec<&String> = txns
-        .iter()
-        .filter_map(|txn| txn.header.extid.as_ref())
-        .duplicates()
-        .collect();
+/// Txns which have the same key as some earlier txn, with that earlier txn
+fn duplicates<'a, K, F>(txns: &'a Txns, key: F) -> Vec<(&'a K, &'a Transaction, &'a Transaction)>
+where
+    K: Eq + std::hash::Hash + 'a,
+    F: Fn(&'a Transaction) -> Option<&'a K>,
+{
+    let mut seen = HashMap::new();
+    txns.iter()
+        .filter_map(|txn| {
+            let k = key(txn)?;
+            match seen.entry(k) {
+                Entry::Occupied(first) => Some((k, *first.get(), txn)),
+                Entry::Vacant(e) => {
+                    e.insert(txn);
+                    None
+                }
+            }
+        })
+        .collect()
+}
 
-    if dups.is_empty() {
-        Ok(())
-    } else {
-        let dups_count = dups.len();
-        let msg = if dups_count < 10 {
-            format!(
-                "Found {} duplicate external ids.\nDuplicate ext-ids are:\n{}",
-                dups.len(),
-                dups.iter().join(",\n")
-            )
-        } else {
-            format!(
-                "Found {} duplicate external is.\nFirst ten duplicate ext-ids are:\n{}",
-                dups.len(),
-                dups[0..10].iter().join(",\n")
-            )
-        };
-        Err(msg.into())
-    }
+/// Diagnostics of duplicate keys, one for each txn which is a duplicate
+///
+/// Message starts with the count of duplicate keys, and
+/// help shows the location of the first txn with the same key.
+fn duplicate_diagnostics<K: Display>(
+    code: &'static str,
+    dups: &[(&K, &Transaction, &Transaction)],
+    what: &str,
+    key_name: &str,
+) -> Vec<Diagnostic> {
+    let count = dups.iter().map(|(k, _, _)| k.to_string()).unique().count();
+    dups.iter()
+        .map(|(k, first, txn)| {
+            let msg = format!("Found {count} duplicate {what}. Duplicate {key_name}: {k}");
+            let diag = Diagnostic::error(code, msg);
+            let diag = match first.source.display() {
+                Some(first) => {
+                    diag.with_help(format!("Txn with the same {key_name} is at {first}"))
+                }
+                None => diag,
+            };
+            txn.source.locate(diag, txn.source.pos)
+        })
+        .collect()
 }
 
-fn check_uuids(txns: &Txns) -> Result<(), tackler::Error> {
-    if txns.iter().any(|txn| txn.header.uuid.is_none()) {
-        let msg =
-            "Txn without UUID. Txn UUID is mandatory with transaction set checksum calculation.";
-        return Err(msg.into());
+fn check_extid(txns: &Txns) -> Vec<Diagnostic> {
+    let dups = duplicates(txns, |txn| txn.header.extid.as_ref());
+    duplicate_diagnostics(diagnostic::DUPLICATE_EXTID, &dups, "external ids", "ext-id")
+}
+
+/// Check balance assertions against running balances of accounts
+///
+/// Txns must be sorted. Running balance of the account is the sum of its own postings
+/// in the commodity of the posting (sub-accounts are not included, so this is
+/// not the same as the tree balance of the balance report), and it is checked
+/// after the whole transaction has been applied. All failed assertions are returned,
+/// and each of them is located at its posting.
+fn check_balance_assertions<'a>(
+    txns: impl IntoIterator<Item = &'a Transaction>,
+) -> Vec<Diagnostic> {
+    let mut balances: HashMap<&TxnAccount, Decimal> = HashMap::new();
+    let mut errors = Vec::new();
+
+    for txn in txns {
+        for p in &txn.posts {
+            *balances.entry(&p.acctn).or_default() += p.amount;
+        }
+        for p in txn.posts.iter().filter(|p| p.assertion.is_some()) {
+            let expected = p.assertion.unwrap_or_default();
+            let actual = balances.get(&p.acctn).copied().unwrap_or_default();
+            if expected != actual {
+                let mut msg = format!(
+                    "Balance assertion failed for account: {}{}",
+                    p.acctn.atn,
+                    if p.acctn.comm.is_any() {
+                        format!(" [{}]", p.acctn.comm.name)
+                    } else {
+                        String::new()
+                    }
+                );
+                let _ = write!(msg, "\n   expected: {expected}");
+                let _ = write!(msg, "\n   actual:   {actual}");
+                let _ = write!(
+                    msg,
+                    "\n   txn date: {}",
+                    txn_ts::rfc_3339(&txn.header.timestamp)
+                );
+                if let Some(code) = &txn.header.code {
+                    let _ = write!(msg, "\n   txn code: {code}");
+                }
+                if let Some(desc) = &txn.header.description {
+                    let _ = write!(msg, "\n   txn description: {desc}");
+                }
+                if let Some(uuid) = &txn.header.uuid {
+                    let _ = write!(msg, "\n   txn uuid: {uuid}");
+                }
+                let diag = Diagnostic::error(diagnostic::BALANCE_ASSERTION, msg);
+                errors.push(txn.source.locate(diag, p.pos));
+            }
+        }
     }
+    errors
+}
 
-    let dups: Vec<&Uuid> = txns
+/// Check that all txns have unique UUID
+///
+/// Missing UUIDs are reported before duplicate UUIDs.
+fn check_uuids(txns: &Txns) -> Vec<Diagnostic> {
+    let mut errors: Vec<_> = txns
         .iter()
-        .filter_map(|txn| txn.header.uuid.as_ref())
-        .duplicates()
+        .filter(|txn| txn.header.uuid.is_none())
+        .map(|txn| {
+            let msg = "Txn without UUID. Txn UUID is mandatory with transaction set checksum calculation.";
+            let diag = Diagnostic::error(diagnostic::MISSING_UUID, msg)
+                .with_help("Add '# uuid: <uuid>' to all txns, or turn off audit mode");
+            txn.source.locate(diag, txn.source.pos)
+        })
         .collect();
 
-    if dups.is_empty() {
-        Ok(())
-    } else {
-        let dups_count = dups.len();
-        let msg = if dups_count < 10 {
-            format!(
-                "Found {} duplicate txn uuids with txn set checksum.\nDuplicate ids are:\n{}",
-                dups.len(),
-                dups.iter().map(|u| { u.to_string() }).join(",\n")
-            )
-        } else {
-            format!(
-                "Found {} duplicate txn uuids with txn set checksum.\nFirst ten duplicate ids are:\n{}",
-                dups.len(),
-                dups[0..10].iter().map(|u| { u.to_string() }).join(",\n")
-            )
-        };
-        Err(msg.into())
-    }
+    let dups = duplicates(txns, |txn| txn.header.uuid.as_ref());
+    errors.extend(duplicate_diagnostics(
+        diagnostic::DUPLICATE_UUID,
+        &dups,
+        "txn uuids with txn set checksum",
+        "uuid",
+    ));
+    errors
 }
 
 fn calc_txn_checksum(txns: &TxnRefs<'_>, hasher: &Hash) -> Result<Checksum, tackler::Error> {
@@ -242,8 +406,50 @@ fn calc_txn_checksum(txns: &TxnRefs<'_>, hasher: &Hash) -> Result<Checksum, tack
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use crate::parser;
+    use jiff::tz::TimeZone;
     use uuid::Uuid;
 
+    #[test]
+    // test: 0c2e4a6b-8d1f-4e3a-9b5c-7d9f1b3e5a7c
+    // desc: all filters must select txn, and named filters are recorded by name
+    fn filter_named() {
+        let txns_str = "2025-01-01 'a\n e:a 1\n a:b\n\n\
+                        2025-02-01 'b\n e:b 1\n a:b\n\n\
+                        2025-03-01 'c\n e:a 1\n a:b\n";
+        let settings = Settings::default();
+        let txn_data = parser::string_to_txns(&mut { txns_str }, &settings).unwrap(/*:test:*/);
+
+        let tz = TimeZone::UTC;
+        let tf_acc = FilterDefinition::from_expr(r#"account =~ "e:a""#, &tz).unwrap(/*:test:*/);
+        let tf_ts = FilterDefinition::from_expr("ts >= 2025-02-01", &tz).unwrap(/*:test:*/);
+
+        let txn_set = txn_data
+            .filter_named(&[(Some("expenses"), &tf_acc), (None, &tf_ts)])
+            .unwrap(/*:test:*/);
+        assert_eq!(txn_set.txns.len(), 1);
+
+        let md_text = txn_set.metadata().unwrap(/*:test:*/).text(tz);
+        assert!(md_text.contains("Filter: expenses\n  account =~ \"e:a\"\n"));
+        assert!(md_text.contains("Filter\n  ts >= 2025-02-01\n"));
+    }
+
+    #[test]
+    // test: 4b6d8f0a-2c4e-4a6b-9d1f-5e7a9c1b3d5f
+    // desc: txn without uuid is located at the txn
+    fn missing_uuid_location() {
+        let txns_str = "2025-01-01 'a\n # uuid: 5e0f3a1c-7b9d-4c2e-8f4a-6d8b0c2e4f6a\n e 1\n a\n\n\
+                        2025-01-02 'b\n e 1\n a\n";
+        let settings = Settings::default();
+        let txn_data = parser::string_to_txns(&mut { txns_str }, &settings).unwrap(/*:test:*/);
+
+        let errors = check_uuids(&txn_data.txns);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code, diagnostic::MISSING_UUID);
+        assert_eq!((errors[0].line, errors[0].column), (Some(6), Some(1)));
+    }
+
     #[test]
     // desc: check that uuid::to_string returns normalized lower-case UUID
     fn uuid_as_lower_case() {
@@ -2,18 +2,22 @@
  * Tackler-NG 2022-2025
  * SPDX-License-Identifier: Apache-2.0
  */
-pub use crate::parser::pricedb_parser::{pricedb_from_file, pricedb_from_str};
+pub use crate::parser::pricedb_parser::{pricedb_from_file, pricedb_from_paths, pricedb_from_str};
 pub use crate::parser::tackler_txns::git_to_txns;
 pub use crate::parser::tackler_txns::paths_to_txns;
 pub use crate::parser::tackler_txns::string_to_txns;
+use itertools::Itertools;
 use std::fmt::Write;
-use winnow::error::{ErrMode, FromExternalError};
+use winnow::error::{ContextError, ErrMode, FromExternalError, ParseError};
 
 mod error;
+mod ledger_parser;
 mod pricedb_parser;
-mod tackler_parser;
+pub(crate) mod tackler_parser;
 mod tackler_txns;
 
+use crate::diagnostic;
+use crate::diagnostic::{Diagnostic, Span};
 use crate::kernel::settings::Settings;
 use crate::parser::error::TacklerTxnError;
 use crate::parser::parts::identifier::{parse_identifier, parse_multi_part_id};
@@ -22,7 +26,7 @@ use winnow::Stateful;
 
 pub(crate) mod parts;
 
-pub(crate) type Stream<'is> = Stateful<&'is str, &'is mut Settings>;
+pub(crate) type Stream<'is> = Stateful<&'is str, &'is Settings>;
 
 pub(crate) fn make_semantic_error<
     'is,
@@ -34,19 +38,55 @@ pub(crate) fn make_semantic_error<
     ErrMode::from_external_error(is, TacklerTxnError::semantic_error(msg)).cut()
 }
 
-pub(crate) fn from_error<
-    'is,
-    E: winnow::error::FromExternalError<Stream<'is>, TacklerTxnError>,
-    SE: std::error::Error + ?Sized,
->(
+pub(crate) fn from_error<'is, E: winnow::error::FromExternalError<Stream<'is>, TacklerTxnError>>(
     is: &mut Stream<'is>,
-    err: &SE,
+    err: &(dyn std::error::Error + Send + Sync + 'static),
 ) -> ErrMode<E> {
-    ErrMode::from_external_error(
-        is,
-        TacklerTxnError::semantic_error(err.to_string().as_str()),
+    let txn_err = match err.downcast_ref::<Diagnostic>() {
+        Some(diag) => TacklerTxnError::from(diag),
+        None => TacklerTxnError::semantic_error(err.to_string().as_str()),
+    };
+    ErrMode::from_external_error(is, txn_err).cut()
+}
+
+/// Diagnostic of the error without location
+///
+/// Semantic errors keep their code and help, and
+/// all other errors are syntax errors.
+fn error_diagnostic(err: &ContextError) -> Diagnostic {
+    let cause = err
+        .cause()
+        .and_then(|cause| cause.downcast_ref::<TacklerTxnError>());
+    if let Some(txn_err) = cause {
+        txn_err.diagnostic()
+    } else {
+        let msg = err.to_string().lines().join(", ");
+        Diagnostic::error(diagnostic::PARSE_ERROR, msg)
+    }
+}
+
+/// Convert parse error into diagnostic with location
+pub(crate) fn to_diagnostic(err: &ParseError<Stream<'_>, ContextError>) -> Diagnostic {
+    let span = err.char_span();
+    error_diagnostic(err.inner()).with_location(
+        err.input().input,
+        err.offset(),
+        Span {
+            start: span.start,
+            end: span.end,
+        },
+        err.to_string(),
+    )
+}
+
+/// Convert error at the byte offset of the input into diagnostic with location
+pub(crate) fn located_diagnostic(input: &str, offset: usize, err: &ContextError) -> Diagnostic {
+    error_diagnostic(err).with_location(
+        input,
+        offset,
+        diagnostic::char_span(input, offset),
+        diagnostic::parse_report(input, offset, err),
     )
-    .cut()
 }
 
 /// Check if id is a valid identifier, e.g., commodity name
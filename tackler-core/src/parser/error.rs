@@ -3,19 +3,29 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use crate::diagnostic;
+use crate::diagnostic::Diagnostic;
 use std::error::Error as StdError;
 use std::fmt::{Display, Formatter, Result};
 
 #[derive(Debug, Clone)]
 pub(crate) enum TacklerTxnError {
-    SemanticError { msg: String },
-    TxnDataError { msg: String },
+    SemanticError {
+        code: &'static str,
+        msg: String,
+        help: Option<String>,
+    },
+    TxnDataError {
+        msg: String,
+    },
 }
 
 impl TacklerTxnError {
     pub(crate) fn semantic_error(msg: &str) -> Self {
         Self::SemanticError {
+            code: diagnostic::SEMANTIC_ERROR,
             msg: msg.to_string(),
+            help: None,
         }
     }
     pub(crate) fn txn_data_error(msg: &str) -> Self {
@@ -23,6 +33,32 @@ impl TacklerTxnError {
             msg: msg.to_string(),
         }
     }
+
+    /// Code, message and help of the diagnostic
+    pub(crate) fn diagnostic(&self) -> Diagnostic {
+        match self {
+            TacklerTxnError::SemanticError { code, msg, help } => {
+                let diag = Diagnostic::error(code, msg.as_str());
+                match help {
+                    Some(help) => diag.with_help(help.as_str()),
+                    None => diag,
+                }
+            }
+            TacklerTxnError::TxnDataError { msg } => {
+                Diagnostic::error(diagnostic::TXN_DATA_ERROR, msg.as_str())
+            }
+        }
+    }
+}
+
+impl From<&Diagnostic> for TacklerTxnError {
+    fn from(diag: &Diagnostic) -> Self {
+        Self::SemanticError {
+            code: diag.code,
+            msg: diag.message.clone(),
+            help: diag.help.clone(),
+        }
+    }
 }
 
 impl StdError for TacklerTxnError {
@@ -34,7 +70,7 @@ impl StdError for TacklerTxnError {
 impl Display for TacklerTxnError {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         match self {
-            TacklerTxnError::SemanticError { msg } => {
+            TacklerTxnError::SemanticError { msg, .. } => {
                 write!(f, "semantic error - {msg}")
             }
             TacklerTxnError::TxnDataError { msg } => {
@@ -0,0 +1,239 @@
+/*
+ * Tackler-NG 2026
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use crate::kernel::Settings;
+use crate::model::Txns;
+use crate::parser::parts::ledger::{LedgerItem, parse_ledger};
+use crate::parser::{Stream, to_diagnostic};
+use crate::tackler;
+use winnow::Parser;
+
+/// Parse Ledger journal
+///
+/// Price entries (`P` lines) of the journal are collected into settings,
+/// see [`Settings::merge_journal_prices`].
+pub(crate) fn txns_text(input: &mut &str, settings: &Settings) -> Result<Txns, tackler::Error> {
+    let is = Stream {
+        input,
+        state: settings,
+    };
+    let items = parse_ledger
+        .parse(is)
+        .map_err(|err| tackler::Error::from(to_diagnostic(&err)))?;
+
+    let mut txns = Txns::new();
+    for item in items {
+        match item {
+            LedgerItem::Txn(txn) => txns.push(*txn),
+            LedgerItem::Price(price_entry) => settings.add_journal_price(price_entry),
+            LedgerItem::Skip => (),
+        }
+    }
+    Ok(txns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+    use rust_decimal::Decimal;
+    use tackler_rs::IndocUtils;
+
+    #[test]
+    // test: 7e3b1d5f-2a4c-4f8e-9b6d-0c1e3a5f7b9d
+    // desc: common subset of Ledger journal
+    fn ledger_journal() {
+        #[rustfmt::skip]
+        let journal = indoc!(
+           "|; Ledger journal
+            |# another comment
+            |P 2024/01/09 AAPL $150.00
+            |P 2024-02-01 12:00:00 AAPL 160 $
+            |
+            |comment
+            |2024-01-01 this is not a txn
+            |end comment
+            |
+            |2024/01/05 * (101) Grocery Store  ; weekly shopping
+            |    ; :food:weekly:
+            |    Expenses:Food:Groceries    $1,234.50
+            |    Assets:Checking Account
+            |2024-01-10 ! Broker
+            |    ; key: value
+            |    Assets:Broker       10 AAPL @ $150
+            |    * Assets:Checking Account   $-1500.00 ; cash out
+            |
+            |2024.01.11 Broker sell
+            |    Assets:Broker\t-5 AAPL @@ $800
+            |    Assets:Checking Account"
+        ).strip_margin();
+
+        let mut settings = Settings::default();
+        let txns = txns_text(&mut journal.as_str(), &settings).unwrap(/*:test:*/);
+        assert_eq!(txns.len(), 3);
+
+        let groceries = &txns[0];
+        assert_eq!(groceries.header.code.as_deref(), Some("101"));
+        assert_eq!(
+            groceries.header.description.as_deref(),
+            Some("Grocery Store")
+        );
+        assert_eq!(
+            groceries.header.comments,
+            Some(vec!["weekly shopping".to_string()])
+        );
+        let tags: Vec<_> = groceries
+            .header
+            .tags
+            .iter()
+            .flatten()
+            .map(|t| t.as_str())
+            .collect();
+        assert_eq!(tags, vec!["food", "weekly"]);
+        assert_eq!(
+            groceries.posts[1].acctn.atn.account,
+            "Assets:Checking_Account"
+        );
+        assert_eq!(groceries.posts[1].amount, Decimal::new(-123_450, 2));
+        assert_eq!(groceries.posts[1].acctn.comm.name, "$");
+
+        let buy = &txns[1];
+        assert_eq!(buy.header.comments, Some(vec!["key: value".to_string()]));
+        assert_eq!(buy.posts[0].amount, Decimal::from(10));
+        assert_eq!(buy.posts[0].txn_amount, Decimal::from(1500));
+        assert_eq!(buy.posts[0].txn_commodity.name, "$");
+        assert_eq!(buy.posts[1].comment.as_deref(), Some("cash out"));
+
+        let sell = &txns[2];
+        assert_eq!(sell.header.timestamp.date().to_string(), "2024-01-11");
+        assert_eq!(sell.posts[0].txn_amount, Decimal::from(-800));
+        assert!(sell.posts[0].is_total_amount);
+        assert_eq!(sell.posts[1].amount, Decimal::from(800));
+
+        settings.merge_journal_prices().unwrap(/*:test:*/);
+        assert_eq!(settings.price.price_db.len(), 2);
+        assert_eq!(settings.price.price_db[1].eq_amount, Decimal::from(160));
+    }
+
+    #[test]
+    // test: 9a3c5e71-4b2d-4f86-a0e9-7d1b3c5f8e24
+    // desc: journal prices are merged with price database, and conflicts are reported
+    fn ledger_journal_prices() {
+        let journal = "P 2024/01/09 AAPL $150.00\n\n2024-01-10 Broker\n a  1\n b\n";
+        let price_db = |db: &str, settings: &Settings| {
+            crate::parser::pricedb_from_str(&mut &*db, settings).unwrap(/*:test:*/)
+        };
+
+        // same journal price twice (e.g. both ends of git range) is not a conflict
+        let mut settings = Settings::default();
+        settings.price.price_db = price_db("P 2024-01-09 AAPL 150 $\n", &settings);
+        for _ in 0..2 {
+            txns_text(&mut &*journal, &settings).unwrap(/*:test:*/);
+        }
+        settings.merge_journal_prices().unwrap(/*:test:*/);
+        assert_eq!(settings.price.price_db.len(), 1);
+
+        let mut settings = Settings::default();
+        settings.price.price_db = price_db("P 2024-01-09 AAPL 151 $\n", &settings);
+        txns_text(&mut &*journal, &settings).unwrap(/*:test:*/);
+        let res = settings.merge_journal_prices();
+        let msg = res.err().unwrap(/*:test:*/).to_string();
+        assert!(
+            msg.contains(
+                "2024-01-09T00:00:00+00:00 AAPL in $: 151 ('price database'), 150.00 ('journal')"
+            ),
+            "{msg}"
+        );
+    }
+
+    #[test]
+    // test: 2c4e6a8b-1d3f-4b5a-8c7e-9f0a2b4c6d8e
+    // desc: unsupported Ledger constructs are reported with their location
+    fn ledger_unsupported() {
+        let cases = [
+            ("~ monthly\n a  1\n b\n", 1, 1, "periodic transaction"),
+            ("= expr true\n a  1\n", 1, 1, "automated transaction"),
+            ("\ninclude other.ledger\n", 2, 1, "directive 'include'"),
+            (
+                "2024-01-01=2024-01-02 x\n a  1\n b\n",
+                1,
+                11,
+                "auxiliary date",
+            ),
+            ("2024-01-01 x\n a  1\n (b)  -1\n", 3, 2, "virtual posting"),
+            (
+                "2024-01-01 x\n a  1 AAPL {$10}\n b\n",
+                2,
+                12,
+                "lot price or lot date",
+            ),
+            (
+                "2024-01-01 x\n a  $1 = $1\n b\n",
+                2,
+                8,
+                "balance assertion or assignment",
+            ),
+            (
+                "2024-01-01 x\n a  = $1\n b\n",
+                2,
+                5,
+                "balance assertion or assignment",
+            ),
+            ("2024-01-01 x\n a  (1 + 2)\n b\n", 2, 5, "amount expression"),
+            (
+                "2024-01-01 x\n a  1 \"AB C\"\n b\n",
+                2,
+                7,
+                "quoted commodity",
+            ),
+        ];
+        for (journal, line, column, construct) in cases {
+            let settings = Settings::default();
+            let err = txns_text(&mut { journal }, &settings)
+                .err()
+                .map(|e| e.to_string())
+                .unwrap_or_default();
+            assert!(
+                err.starts_with(&format!("parse error at line {line}, column {column}")),
+                "{journal:?}: {err}"
+            );
+            assert!(
+                err.ends_with(&format!("unsupported Ledger construct: {construct}")),
+                "{journal:?}: {err}"
+            );
+        }
+    }
+
+    #[test]
+    // test: 9a1c3e5b-7d2f-4e6a-b8c0-1d3e5f7a9b2c
+    fn ledger_errors() {
+        let cases = [
+            (
+                "2024-01-01 x\n a\n",
+                "Transaction must have at least one posting with amount",
+            ),
+            (
+                "2024-01-01 x\n a  1\n b\n c\n",
+                "Only one posting without amount is allowed inside single transaction",
+            ),
+            (
+                "2024-01-01 x\n a  1 EUR\n b  -1 USD\n",
+                "Different commodities without value positions are not allowed inside single transaction",
+            ),
+            (
+                "P 2024-01-01 AAPL 150\n",
+                "price amount must have commodity",
+            ),
+        ];
+        for (journal, msg) in cases {
+            let settings = Settings::default();
+            let err = txns_text(&mut { journal }, &settings)
+                .err()
+                .map(|e| e.to_string())
+                .unwrap_or_default();
+            assert!(err.ends_with(msg), "{journal:?}: {err}");
+        }
+    }
+}
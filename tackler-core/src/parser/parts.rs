@@ -6,6 +6,7 @@
 mod chars;
 mod comment;
 pub(crate) mod identifier;
+pub(super) mod ledger;
 pub(crate) mod number;
 mod posting_value;
 pub(super) mod pricedb;
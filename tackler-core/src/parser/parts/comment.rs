@@ -53,11 +53,11 @@ mod tests {
         let pok_tests = content_data();
 
         for t in &pok_tests {
-            let mut settings = Settings::default();
+            let settings = Settings::default();
             let i = format!("; {t}\n");
             let mut is = Stream {
                 input: i.as_str(),
-                state: &mut settings,
+                state: &settings,
             };
 
             let res = p_comment(&mut is);
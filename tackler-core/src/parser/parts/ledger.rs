@@ -0,0 +1,554 @@
+/*
+ * Tackler-NG 2026
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Ledger journal format
+//!
+//! This is the common subset of Ledger (and hledger) journal format:
+//! dates, status marks, codes, payees, `;` comments, `:tag:` comments,
+//! postings with `@` and `@@` prices, elided amounts and `P` price lines.
+//!
+//! All other constructs (automated and periodic txns, directives, virtual
+//! postings, lot prices, balance assertions, etc.) are reported as
+//! unsupported constructs, with their location.
+
+use crate::kernel::Settings;
+use crate::model::posting::txn_sum;
+use crate::model::price_entry::PriceEntry;
+use crate::model::source::{Position, Source};
+use crate::model::{Posting, Posts, Transaction};
+use crate::parser::parts::chars::content_char;
+use crate::parser::parts::txns::multispace0_line_ending;
+use crate::parser::{Stream, from_error, is_valid_identifier, is_valid_name, make_semantic_error};
+use crate::tackler;
+use itertools::Itertools;
+use jiff::Zoned;
+use jiff::civil::{Date, Time};
+use rust_decimal::Decimal;
+use std::ops::Neg;
+use tackler_api::txn_header::TxnHeader;
+use winnow::ascii::{digit1, line_ending, space0, space1};
+use winnow::combinator::{alt, cut_err, eof, opt, peek, preceded, repeat, repeat_till, terminated};
+use winnow::error::StrContext;
+use winnow::stream::AsChar;
+use winnow::token::{one_of, take_till, take_until, take_while};
+use winnow::{ModalResult, Parser, seq};
+
+/// Ledger amount: value with optional commodity
+struct Amount<'s> {
+    value: Decimal,
+    commodity: Option<&'s str>,
+}
+
+enum Price<'s> {
+    Unit(Amount<'s>),
+    Total(Amount<'s>),
+}
+
+struct LedgerPosting<'s> {
+    account: &'s str,
+    amount: Option<(Amount<'s>, Option<Price<'s>>)>,
+    comment: Option<&'s str>,
+}
+
+enum BodyLine<'s> {
+    Comment(&'s str),
+    Posting(LedgerPosting<'s>),
+}
+
+/// Top level item of Ledger journal
+pub(crate) enum LedgerItem {
+    Txn(Box<Transaction>),
+    Price(PriceEntry),
+    Skip,
+}
+
+/// Fail with unsupported construct error at the current position
+fn unsupported<'s, O>(construct: &'static str) -> impl FnMut(&mut Stream<'s>) -> ModalResult<O> {
+    move |is: &mut Stream<'s>| {
+        let msg = format!("unsupported Ledger construct: {construct}");
+        Err(make_semantic_error(is, msg.as_str()))
+    }
+}
+
+fn p_line_end(is: &mut Stream<'_>) -> ModalResult<()> {
+    alt((line_ending.void(), eof.void())).parse_next(is)
+}
+
+/// Ledger comment, the text is trimmed
+fn p_ledger_comment<'s>(is: &mut Stream<'s>) -> ModalResult<&'s str> {
+    preceded(';', take_while(0.., content_char))
+        .map(str::trim)
+        .parse_next(is)
+}
+
+/// Date `2024-01-05`, `2024/01/05` or `2024.01.05`
+fn p_ledger_date(is: &mut Stream<'_>) -> ModalResult<Date> {
+    let (y, m, d) = seq!(
+        take_while(4, AsChar::is_dec_digit),
+        _: one_of(['-', '/', '.']),
+        take_while(1..=2, AsChar::is_dec_digit),
+        _: one_of(['-', '/', '.']),
+        take_while(1..=2, AsChar::is_dec_digit),
+    )
+    .parse_next(is)?;
+
+    // all parts are only digits, and their lengths are checked
+    let to_i16 = |s: &str| s.parse::<i16>().unwrap_or_default();
+    #[allow(clippy::cast_possible_truncation)]
+    Date::new(to_i16(y), to_i16(m) as i8, to_i16(d) as i8).map_err(|err| from_error(is, &err))
+}
+
+/// Time `12:00` or `12:00:00`
+fn p_ledger_time(is: &mut Stream<'_>) -> ModalResult<Time> {
+    let (h, m, s) = seq!(
+        take_while(2, AsChar::is_dec_digit),
+        _: ':',
+        take_while(2, AsChar::is_dec_digit),
+        opt(preceded(':', take_while(2, AsChar::is_dec_digit))),
+    )
+    .parse_next(is)?;
+
+    let to_i8 = |s: &str| s.parse::<i8>().unwrap_or_default();
+    Time::new(to_i8(h), to_i8(m), s.map_or(0, to_i8), 0).map_err(|err| from_error(is, &err))
+}
+
+fn ledger_timestamp(is: &mut Stream<'_>, date: Date, time: Option<Time>) -> ModalResult<Zoned> {
+    let ts = match time {
+        Some(time) => is.state.get_offset_datetime(date.to_datetime(time)),
+        None => is.state.get_offset_date(date),
+    };
+    ts.map_err(|err| from_error(is, err.as_ref()))
+}
+
+/// Number with optional thousand separators, e.g. `1,000.00`
+fn p_ledger_number(is: &mut Stream<'_>) -> ModalResult<Decimal> {
+    let num_str = alt((
+        (
+            digit1,
+            repeat(0.., (',', digit1)).map(|()| ()),
+            opt(('.', take_while(0.., AsChar::is_dec_digit))),
+        )
+            .take(),
+        ('.', digit1).take(),
+    ))
+    .parse_next(is)?;
+
+    let num = num_str.replace(',', "");
+    let num = num.strip_suffix('.').unwrap_or(num.as_str());
+    Decimal::from_str_exact(num).map_err(|err| from_error(is, &err))
+}
+
+fn ledger_commodity_char(c: char) -> bool {
+    !(c.is_ascii_digit() || c.is_whitespace() || "-+*/^&|=<>[](){}@;,.\"".contains(c))
+}
+
+fn p_ledger_commodity<'s>(is: &mut Stream<'s>) -> ModalResult<&'s str> {
+    let comm = take_while(1.., ledger_commodity_char).parse_next(is)?;
+    is_valid_identifier(comm).map_err(|err| from_error(is, err.as_ref()))?;
+    Ok(comm)
+}
+
+/// Amount with prefix or suffix commodity, e.g. `-$10.00`, `$-10`, `10 EUR` or `10`
+fn p_amount<'s>(is: &mut Stream<'s>) -> ModalResult<Amount<'s>> {
+    alt((
+        preceded(peek('"'), unsupported("quoted commodity")),
+        preceded(peek('('), unsupported("amount expression")),
+        seq!(
+            opt('-'),
+            p_ledger_commodity,
+            _: space0,
+            opt('-'),
+            p_ledger_number
+        )
+        .map(|(s1, comm, s2, value)| Amount {
+            value: if s1.is_some() ^ s2.is_some() {
+                value.neg()
+            } else {
+                value
+            },
+            commodity: Some(comm),
+        }),
+        seq!(
+            opt('-'),
+            p_ledger_number,
+            opt(preceded(
+                space0,
+                alt((
+                    preceded(peek('"'), unsupported("quoted commodity")),
+                    p_ledger_commodity
+                ))
+            ))
+        )
+        .map(|(sign, value, comm)| Amount {
+            value: if sign.is_some() { value.neg() } else { value },
+            commodity: comm,
+        }),
+    ))
+    .parse_next(is)
+}
+
+/// Unit price `@ $1.25` or total price `@@ $125`
+fn p_price<'s>(is: &mut Stream<'s>) -> ModalResult<Price<'s>> {
+    let (price_type, amount) = seq!(
+        _: space0,
+        alt(("@@", "@")),
+        _: space0,
+        cut_err(p_amount).context(StrContext::Label("posting price")),
+    )
+    .parse_next(is)?;
+
+    if amount.commodity.is_none() {
+        return Err(make_semantic_error(is, "posting price must have commodity"));
+    }
+    Ok(match price_type {
+        "@@" => Price::Total(amount),
+        _ => Price::Unit(amount),
+    })
+}
+
+fn p_posting_value<'s>(is: &mut Stream<'s>) -> ModalResult<(Amount<'s>, Option<Price<'s>>)> {
+    let value = alt((
+        preceded(peek('='), unsupported("balance assertion or assignment")),
+        (p_amount, opt(p_price)),
+    ))
+    .parse_next(is)?;
+
+    let _: Option<()> = opt(preceded(
+        (space0, peek('{')),
+        unsupported("lot price or lot date"),
+    ))
+    .parse_next(is)?;
+    let _: Option<()> = opt(preceded(
+        (space0, peek('=')),
+        unsupported("balance assertion or assignment"),
+    ))
+    .parse_next(is)?;
+
+    Ok(value)
+}
+
+/// Account name, with single spaces inside of the name
+fn p_ledger_account<'s>(is: &mut Stream<'s>) -> ModalResult<&'s str> {
+    let acc_part = || take_while(1.., |c: char| !c.is_whitespace() && c != ';');
+    (acc_part(), repeat(0.., (' ', acc_part())).map(|()| ()))
+        .take()
+        .parse_next(is)
+}
+
+fn p_posting<'s>(is: &mut Stream<'s>) -> ModalResult<BodyLine<'s>> {
+    let (account, amount, comment) = seq!(
+        _: space1,
+        _: opt(terminated(one_of(['*', '!']), space1)),
+        _: opt(preceded(peek(one_of(['(', '['])), unsupported::<()>("virtual posting"))),
+        p_ledger_account,
+        // amount is separated by two spaces or by tab
+        opt(preceded((alt(("  ", "\t", " \t")), space0), p_posting_value)),
+        _: space0,
+        opt(p_ledger_comment),
+        _: p_line_end,
+    )
+    .parse_next(is)?;
+
+    Ok(BodyLine::Posting(LedgerPosting {
+        account,
+        amount,
+        comment,
+    }))
+}
+
+fn p_body_comment<'s>(is: &mut Stream<'s>) -> ModalResult<BodyLine<'s>> {
+    seq!(_: space1, p_ledger_comment, _: p_line_end)
+        .map(|(c,)| BodyLine::Comment(c))
+        .parse_next(is)
+}
+
+/// Tags comment, e.g. `; :tag1:tag2:`
+fn as_tags(comment: &str) -> Option<Vec<&str>> {
+    let tags = comment.strip_prefix(':')?.strip_suffix(':')?;
+    let tags: Vec<_> = tags.split(':').collect();
+    if tags
+        .iter()
+        .any(|t| t.is_empty() || t.contains(char::is_whitespace))
+    {
+        None
+    } else {
+        Some(tags)
+    }
+}
+
+fn handle_postings(
+    postings: Vec<LedgerPosting<'_>>,
+    settings: &Settings,
+) -> Result<Posts, tackler::Error> {
+    let elided = postings.iter().filter(|p| p.amount.is_none()).count();
+    if elided > 1 {
+        let msg = "Only one posting without amount is allowed inside single transaction";
+        return Err(msg.into());
+    }
+    if elided == postings.len() {
+        return Err("Transaction must have at least one posting with amount".into());
+    }
+
+    let mut posts: Vec<Option<Posting>> = Vec::with_capacity(postings.len());
+    let mut accounts = Vec::with_capacity(postings.len());
+    for p in postings {
+        // Ledger allows spaces inside of account names
+        let account = p.account.replace(' ', "_");
+        is_valid_name(&account)?;
+
+        let posting = match p.amount {
+            Some((amount, price)) => {
+                let post_comm = settings.get_or_create_commodity(amount.commodity)?;
+                let has_price = price.is_some();
+                let (txn_amount, is_total_amount, txn_comm) = match price {
+                    Some(Price::Unit(price)) => (
+                        amount.value * price.value,
+                        false,
+                        settings.get_or_create_commodity(price.commodity)?,
+                    ),
+                    Some(Price::Total(price)) => (
+                        // Ledger's total price is always positive
+                        if amount.value.is_sign_negative() {
+                            price.value.abs().neg()
+                        } else {
+                            price.value.abs()
+                        },
+                        true,
+                        settings.get_or_create_commodity(price.commodity)?,
+                    ),
+                    None => (amount.value, false, post_comm.clone()),
+                };
+                if has_price && post_comm.name == txn_comm.name {
+                    let msg = format!(
+                        "Both commodities are same for value position [{}]",
+                        txn_comm.name
+                    );
+                    return Err(msg.into());
+                }
+                let acctn = settings.get_or_create_txn_account(&account, &post_comm)?;
+                Some(Posting::from(
+                    acctn,
+                    amount.value,
+                    txn_amount,
+                    is_total_amount,
+                    txn_comm,
+                    None,
+                    p.comment.map(String::from),
+                )?)
+            }
+            None => None,
+        };
+        posts.push(posting);
+        accounts.push((account, p.comment));
+    }
+
+    let valued: Posts = posts.iter().flatten().cloned().collect();
+    if valued
+        .iter()
+        .map(|p| &p.txn_commodity.name)
+        .unique()
+        .count()
+        > 1
+    {
+        let msg = "Different commodities without value positions are not allowed inside single transaction";
+        return Err(msg.into());
+    }
+
+    posts
+        .into_iter()
+        .zip(accounts)
+        .map(|(posting, (account, comment))| {
+            if let Some(posting) = posting {
+                return Ok(posting);
+            }
+            let amount = txn_sum(&valued).neg();
+            let comm = valued[0].txn_commodity.clone();
+            let acctn = settings.get_or_create_txn_account(&account, &comm)?;
+            Posting::from(
+                acctn,
+                amount,
+                amount,
+                false,
+                comm,
+                None,
+                comment.map(String::from),
+            )
+        })
+        .collect()
+}
+
+fn handle_txn(
+    header: TxnHeader,
+    header_comment: Option<&str>,
+    body: Vec<BodyLine<'_>>,
+    settings: &Settings,
+) -> Result<Transaction, tackler::Error> {
+    let mut tags = Vec::new();
+    let mut comments = Vec::new();
+    let mut postings = Vec::new();
+    for line in header_comment
+        .into_iter()
+        .map(BodyLine::Comment)
+        .chain(body)
+    {
+        match line {
+            BodyLine::Comment(c) => match as_tags(c) {
+                Some(t) => tags.extend(t),
+                None => comments.push(c.to_string()),
+            },
+            BodyLine::Posting(p) => postings.push(p),
+        }
+    }
+    let tags = tags
+        .into_iter()
+        .unique()
+        .map(|t| settings.get_or_create_tag(t))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let posts = handle_postings(postings, settings)?;
+    settings.check_account_periods(&header.timestamp, &posts)?;
+
+    let header = TxnHeader {
+        tags: if tags.is_empty() { None } else { Some(tags) },
+        comments: if comments.is_empty() {
+            None
+        } else {
+            Some(comments)
+        },
+        ..header
+    };
+    Transaction::try_from(header, posts)
+}
+
+fn p_txn(is: &mut Stream<'_>) -> ModalResult<LedgerItem> {
+    let pos = Position::Tail(is.input.len());
+    let (date, code, payee, comment) = seq!(
+        p_ledger_date,
+        _: opt(preceded(peek('='), unsupported::<()>("auxiliary date"))),
+        // status mark is not used
+        _: opt(preceded(space1, one_of(['*', '!']))),
+        opt(preceded(space1, preceded('(', cut_err(terminated(take_till(0.., [')', '\n']), ')'))))),
+        opt(preceded(space1, take_while(1.., |c: char| content_char(c) && c != ';'))),
+        _: space0,
+        opt(p_ledger_comment),
+        _: cut_err(p_line_end).context(StrContext::Label("Txn Header")),
+    )
+    .parse_next(is)?;
+
+    let body: Vec<BodyLine<'_>> = cut_err(repeat(1.., alt((p_body_comment, p_posting))))
+        .context(StrContext::Label("Txn Postings"))
+        .parse_next(is)?;
+
+    let timestamp = ledger_timestamp(is, date, None)?;
+    let header = TxnHeader {
+        timestamp,
+        code: code.map(String::from),
+        description: payee.map(str::trim).map(String::from),
+        uuid: None,
+        extid: None,
+        location: None,
+        tags: None,
+        comments: None,
+    };
+
+    handle_txn(header, comment, body, is.state)
+        .map(|txn| {
+            LedgerItem::Txn(Box::new(Transaction {
+                source: Source { shard: None, pos },
+                ..txn
+            }))
+        })
+        .map_err(|err| from_error(is, err.as_ref()))
+}
+
+/// Price line: `P 2024-01-09 [12:00:00] XAU 2659.64 USD`
+fn p_price_line(is: &mut Stream<'_>) -> ModalResult<LedgerItem> {
+    let (date, time, base, amount, comment) = seq!(
+        _: 'P',
+        _: space1,
+        cut_err(p_ledger_date).context(StrContext::Label("price date")),
+        opt(preceded(space1, p_ledger_time)),
+        _: space1,
+        cut_err(p_ledger_commodity).context(StrContext::Label("price commodity")),
+        _: space1,
+        cut_err(p_amount).context(StrContext::Label("price amount")),
+        _: space0,
+        opt(p_ledger_comment),
+        _: cut_err(p_line_end).context(StrContext::Label("price entry")),
+    )
+    .parse_next(is)?;
+
+    let Some(eq_commodity) = amount.commodity else {
+        return Err(make_semantic_error(is, "price amount must have commodity"));
+    };
+    let timestamp = ledger_timestamp(is, date, time)?;
+    let settings = is.state;
+    let commodities = settings
+        .get_or_create_commodity(Some(base))
+        .and_then(|b| Ok((b, settings.get_or_create_commodity(Some(eq_commodity))?)));
+    let (base_commodity, eq_commodity) = commodities.map_err(|err| from_error(is, err.as_ref()))?;
+
+    Ok(LedgerItem::Price(PriceEntry {
+        timestamp,
+        base_commodity,
+        eq_amount: amount.value,
+        eq_commodity,
+        comments: comment.map(String::from),
+    }))
+}
+
+/// Top level comment line, or `comment ... end comment` block
+fn p_comment_line(is: &mut Stream<'_>) -> ModalResult<LedgerItem> {
+    alt((
+        (
+            one_of([';', '#', '%', '|', '*']),
+            take_till(0.., ['\r', '\n']),
+            p_line_end,
+        )
+            .void(),
+        (
+            "comment",
+            space0,
+            line_ending,
+            cut_err(take_until(0.., "end comment")).context(StrContext::Label("comment block")),
+            "end comment",
+            space0,
+            p_line_end,
+        )
+            .void(),
+    ))
+    .map(|()| LedgerItem::Skip)
+    .parse_next(is)
+}
+
+fn p_unsupported_line(is: &mut Stream<'_>) -> ModalResult<LedgerItem> {
+    let word: &str = peek(take_while(0.., |c: char| !c.is_whitespace())).parse_next(is)?;
+    let msg = match word.chars().next() {
+        Some('=') => "automated transaction".to_string(),
+        Some('~') => "periodic transaction".to_string(),
+        Some(_) => format!("directive '{word}'"),
+        None => "indented line outside of transaction".to_string(),
+    };
+    let msg = format!("unsupported Ledger construct: {msg}");
+    Err(make_semantic_error(is, msg.as_str()))
+}
+
+pub(crate) fn parse_ledger(is: &mut Stream<'_>) -> ModalResult<Vec<LedgerItem>> {
+    let (items, _): (Vec<LedgerItem>, _) = repeat_till(
+        0..,
+        alt((
+            multispace0_line_ending.map(|_| LedgerItem::Skip),
+            (space1, eof).map(|_| LedgerItem::Skip),
+            p_comment_line,
+            p_price_line,
+            p_txn,
+            p_unsupported_line,
+        )),
+        eof,
+    )
+    .parse_next(is)?;
+
+    Ok(items)
+}
@@ -32,11 +32,11 @@ mod tests {
 
     #[test]
     fn test_p_number_integer() {
-        let mut settings = Settings::default();
+        let settings = Settings::default();
         let input = "123";
         let mut is = Stream {
             input,
-            state: &mut settings,
+            state: &settings,
         };
 
         let res = p_number(&mut is);
@@ -48,11 +48,11 @@ mod tests {
 
     #[test]
     fn test_p_number_positive() {
-        let mut settings = Settings::default();
+        let settings = Settings::default();
         let input = "1.23";
         let mut is = Stream {
             input,
-            state: &mut settings,
+            state: &settings,
         };
 
         let res = p_number(&mut is);
@@ -64,11 +64,11 @@ mod tests {
 
     #[test]
     fn test_p_number_negative() {
-        let mut settings = Settings::default();
+        let settings = Settings::default();
         let input = "-123456789.987654321";
         let mut is = Stream {
             input,
-            state: &mut settings,
+            state: &settings,
         };
 
         let res = p_number(&mut is);
@@ -3,7 +3,7 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 use crate::kernel::Settings;
-use crate::model::Commodity;
+use crate::model::{Commodity, UnitCost};
 use crate::parser::parts::identifier::p_identifier;
 use crate::parser::parts::number::p_number;
 use crate::parser::{Stream, from_error};
@@ -11,7 +11,7 @@ use crate::tackler;
 use rust_decimal::Decimal;
 use std::sync::Arc;
 use winnow::ascii::{space0, space1};
-use winnow::combinator::{alt, opt};
+use winnow::combinator::{alt, not, opt, terminated};
 use winnow::{ModalResult, Parser, seq};
 /*
 // The old ANTLR Grammar
@@ -73,7 +73,7 @@ fn p_opening_pos<'s>(is: &mut Stream<'s>) -> ModalResult<Value<'s>> {
     Ok(Value {
         value: m.0,
         commodity: m.1,
-    }) // opening position is recognized but ignored at the moment
+    })
 }
 
 enum PriceType {
@@ -85,7 +85,8 @@ fn p_closing_pos<'s>(is: &mut Stream<'s>) -> ModalResult<(PriceType, Value<'s>)>
     const CTX_LABEL: &str = "closing position";
     let m = seq!(
         _:space1,
-        alt(('@', '=')),
+        // '==' is balance assertion, not total price
+        alt(('@', terminated('=', not('=')))),
         _:cut_err(space1)
             .context(StrContext::Label(CTX_LABEL))
             .context(StrContext::Expected(StrContextValue::Description("space"))),
@@ -160,18 +161,41 @@ pub(crate) struct ValuePosition {
     pub(crate) total_amount: bool,
     pub(crate) post_commodity: Arc<Commodity>,
     pub(crate) txn_commodity: Arc<Commodity>,
+    pub(crate) cost: Option<UnitCost>,
 }
 
+#[allow(clippy::too_many_lines)]
 fn handle_posting_value(
     amount: Decimal,
     opt_unit: Option<&(&str, Option<Positions<'_>>)>,
-    settings: &mut Settings,
+    settings: &Settings,
 ) -> Result<ValuePosition, tackler::Error> {
     let post_commodity = match &opt_unit {
         Some(u) => settings.get_or_create_commodity(Some(u.0))?,
         None => settings.get_or_create_commodity(None)?,
     };
 
+    let cost = match opt_unit
+        .and_then(|u| u.1.as_ref())
+        .and_then(|pos| pos.opening.as_ref())
+    {
+        Some(opening_pos) => {
+            let cost_commodity = settings.get_or_create_commodity(Some(opening_pos.commodity))?;
+            if post_commodity.name == cost_commodity.name {
+                let msg = format!(
+                    "Both commodities are same for opening position [{}]",
+                    cost_commodity.name
+                );
+                return Err(msg.into());
+            }
+            Some(UnitCost {
+                amount: opening_pos.value,
+                commodity: cost_commodity,
+            })
+        }
+        None => None,
+    };
+
     let txn_commodity = match &opt_unit {
         Some(u) => {
             match &u.1 {
@@ -193,7 +217,11 @@ fn handle_posting_value(
                             }
                             val_pos_commodity
                         }
-                        None => settings.get_or_create_commodity(None)?,
+                        None => match &cost {
+                            // opening position without closing position is valued at cost
+                            Some(c) => c.commodity.clone(),
+                            None => settings.get_or_create_commodity(None)?,
+                        },
                     }
                 }
                 None => {
@@ -252,10 +280,11 @@ fn handle_posting_value(
                                 }
                             }
                         }
-                        None => {
-                            // plain value, no closing position
-                            (post_amount, false)
-                        }
+                        None => match &cost {
+                            // opening position without closing position is valued at cost
+                            Some(c) => (post_amount * c.amount, false),
+                            None => (post_amount, false),
+                        },
                     }
                 }
                 None => {
@@ -273,6 +302,7 @@ fn handle_posting_value(
         total_amount: txn_amount.1,
         post_commodity,
         txn_commodity,
+        cost,
     })
 }
 
@@ -363,10 +393,10 @@ mod tests {
 
         let mut count = 0;
         for t in &pok_values {
-            let mut settings = Settings::default();
+            let settings = Settings::default();
             let mut is = Stream {
                 input: t.0.as_str(),
-                state: &mut settings,
+                state: &settings,
             };
 
             let res = parse_posting_value(&mut is);
@@ -76,11 +76,11 @@ mod tests {
         ];
 
         for s in tests {
-            let mut settings = Settings::default();
+            let settings = Settings::default();
 
             let mut is = Stream {
                 input: s,
-                state: &mut settings,
+                state: &settings,
             };
 
             let res = parse_price_entry(&mut is);
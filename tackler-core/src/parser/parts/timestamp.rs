@@ -187,11 +187,11 @@ mod tests {
 
     #[test]
     fn test_date() {
-        let mut settings = Settings::default();
+        let settings = Settings::default();
         let input = "2024-12-30";
         let mut is = Stream {
             input,
-            state: &mut settings,
+            state: &settings,
         };
 
         assert!(p_date(&mut is).is_ok());
@@ -199,11 +199,11 @@ mod tests {
 
     #[test]
     fn test_datetime() {
-        let mut settings = Settings::default();
+        let settings = Settings::default();
         let input = "2024-12-30T20:21:22";
         let mut is = Stream {
             input,
-            state: &mut settings,
+            state: &settings,
         };
 
         assert!(parse_timestamp(&mut is).is_ok());
@@ -211,11 +211,11 @@ mod tests {
 
     #[test]
     fn test_datetime_zulu() {
-        let mut settings = Settings::default();
+        let settings = Settings::default();
         let input = "2024-12-30T20:21:22Z";
         let mut is = Stream {
             input,
-            state: &mut settings,
+            state: &settings,
         };
 
         assert!(parse_timestamp(&mut is).is_ok());
@@ -223,11 +223,11 @@ mod tests {
 
     #[test]
     fn test_datetime_offset() {
-        let mut settings = Settings::default();
+        let settings = Settings::default();
         let input = "2024-12-30T20:21:22+02:00";
         let mut is = Stream {
             input,
-            state: &mut settings,
+            state: &settings,
         };
 
         assert!(parse_timestamp(&mut is).is_ok());
@@ -235,55 +235,55 @@ mod tests {
 
     #[test]
     fn test_datetime_milli() {
-        let mut settings = Settings::default();
+        let settings = Settings::default();
         let input = "2024-12-30T20:21:22.12";
         let mut is = Stream {
             input,
-            state: &mut settings,
+            state: &settings,
         };
 
         assert!(parse_timestamp(&mut is).is_ok());
     }
     #[test]
     fn test_datetime_micro() {
-        let mut settings = Settings::default();
+        let settings = Settings::default();
         let input = "2024-12-30T20:21:22.12345";
         let mut is = Stream {
             input,
-            state: &mut settings,
+            state: &settings,
         };
 
         assert!(parse_timestamp(&mut is).is_ok());
     }
     #[test]
     fn test_datetime_nano() {
-        let mut settings = Settings::default();
+        let settings = Settings::default();
         let input = "2024-12-30T20:21:22.12345678";
         let mut is = Stream {
             input,
-            state: &mut settings,
+            state: &settings,
         };
 
         assert!(parse_timestamp(&mut is).is_ok());
     }
     #[test]
     fn test_datetime_nano_offset() {
-        let mut settings = Settings::default();
+        let settings = Settings::default();
         let input = "2024-12-30T20:21:22.123456789+02:00";
         let mut is = Stream {
             input,
-            state: &mut settings,
+            state: &settings,
         };
 
         assert!(parse_timestamp(&mut is).is_ok());
     }
     #[test]
     fn test_datetime_nano_zulu() {
-        let mut settings = Settings::default();
+        let settings = Settings::default();
         let input = "2024-12-30T20:21:22.123456789Z";
         let mut is = Stream {
             input,
-            state: &mut settings,
+            state: &settings,
         };
 
         assert!(parse_timestamp(&mut is).is_ok());
@@ -291,11 +291,11 @@ mod tests {
 
     #[test]
     fn test_datetime_nano_err() {
-        let mut settings = Settings::default();
+        let settings = Settings::default();
         let input = "2024-12-30T20:21:22.1234567890+02:00";
         let mut is = Stream {
             input,
-            state: &mut settings,
+            state: &settings,
         };
 
         assert!(parse_timestamp(&mut is).is_err());
@@ -36,12 +36,12 @@ mod tests {
             (" ;\t\t\n", "\t"),
             (" ; \t \n", "\t "),
         ];
-        let mut settings = Settings::default();
+        let settings = Settings::default();
 
         for c in comments {
             let mut is = Stream {
                 input: c.0,
-                state: &mut settings,
+                state: &settings,
             };
 
             let res = parse_txn_comment(&mut is);
@@ -44,11 +44,11 @@ mod tests {
 
     #[test]
     fn test_txn_code_basic() {
-        let mut settings = Settings::default();
+        let settings = Settings::default();
         let input = "(#foo)";
         let mut is = Stream {
             input,
-            state: &mut settings,
+            state: &settings,
         };
         let res = parse_txn_code(&mut is);
         assert_eq!(res.ok(), Some("#foo"));
@@ -63,11 +63,11 @@ mod tests {
         // Skip first test vector as it contains full ascii punctuation,
         // which is not valid for code
         for t in pok_tests.iter().skip(1) {
-            let mut settings = Settings::default();
+            let settings = Settings::default();
             let i = format!("({t})");
             let mut is = Stream {
                 input: i.as_str(),
-                state: &mut settings,
+                state: &settings,
             };
             let res = parse_txn_code(&mut is);
             assert!(
@@ -33,11 +33,11 @@ mod tests {
 
     #[test]
     fn test_txn_description() {
-        let mut settings = Settings::default();
+        let settings = Settings::default();
         let input = "''hello winnow!  \n";
         let mut is = Stream {
             input,
-            state: &mut settings,
+            state: &settings,
         };
         let res = parse_txn_description(&mut is);
         assert_eq!(res.ok(), Some("'hello winnow!"));
@@ -50,11 +50,11 @@ mod tests {
         let pok_tests = content_data();
 
         for t in &pok_tests {
-            let mut settings = Settings::default();
+            let settings = Settings::default();
             let i = format!("'{t}\n");
             let mut is = Stream {
                 input: i.as_str(),
-                state: &mut settings,
+                state: &settings,
             };
 
             let res = parse_txn_description(&mut is);
@@ -45,11 +45,11 @@ mod tests {
 
     #[test]
     fn err_parse_meta_extid() {
-        let mut settings = Settings::default();
+        let settings = Settings::default();
         let input = "ext-id: \t \n";
         let mut is = Stream {
             input,
-            state: &mut settings,
+            state: &settings,
         };
 
         let res = parse_meta_extid(&mut is);
@@ -59,11 +59,11 @@ mod tests {
 
     #[test]
     fn test_parse_meta_extid() {
-        let mut settings = Settings::default();
+        let settings = Settings::default();
         let input = "ext-id:    hello \t\t there \t \n";
         let mut is = Stream {
             input,
-            state: &mut settings,
+            state: &settings,
         };
 
         let res = parse_meta_extid(&mut is);
@@ -77,11 +77,11 @@ mod tests {
 
     #[test]
     fn test_p_geo_uri() {
-        let mut settings = Settings::default();
+        let settings = Settings::default();
         let input = "geo:66.5436,25.84715,160";
         let mut is = Stream {
             input,
-            state: &mut settings,
+            state: &settings,
         };
 
         let res = p_geo_uri(&mut is);
@@ -93,11 +93,11 @@ mod tests {
 
     #[test]
     fn test_parse_meta_location() {
-        let mut settings = Settings::default();
+        let settings = Settings::default();
         let input = "location: geo:66.5436,25.84715,160\n";
         let mut is = Stream {
             input,
-            state: &mut settings,
+            state: &settings,
         };
 
         let res = parse_meta_location(&mut is);
@@ -15,7 +15,7 @@ use winnow::{ModalResult, Parser, seq};
 
 const CTX_LABEL: &str = "txn metadata tags";
 
-fn handle_tags(v: Vec<&str>, settings: &mut Settings) -> Result<Tags, tackler::Error> {
+fn handle_tags(v: Vec<&str>, settings: &Settings) -> Result<Tags, tackler::Error> {
     let mut tags = Vec::with_capacity(v.len());
 
     for t in v {
@@ -102,11 +102,11 @@ mod tests {
 
     #[test]
     fn test_p_tags() {
-        let mut settings = Settings::default();
+        let settings = Settings::default();
         let input = "first, second, third";
         let mut is = Stream {
             input,
-            state: &mut settings,
+            state: &settings,
         };
 
         let res = p_tags(&mut is);
@@ -119,11 +119,11 @@ mod tests {
 
     #[test]
     fn test_p_tags_err() {
-        let mut settings = Settings::default();
+        let settings = Settings::default();
         let input = "tags: first, , third \n";
         let mut is = Stream {
             input,
-            state: &mut settings,
+            state: &settings,
         };
 
         let res = parse_meta_tags(&mut is);
@@ -133,11 +133,11 @@ mod tests {
 
     #[test]
     fn test_parse_meta_tags() {
-        let mut settings = Settings::default();
+        let settings = Settings::default();
         let input = "tags: a, first:second:third \n";
         let mut is = Stream {
             input,
-            state: &mut settings,
+            state: &settings,
         };
 
         let res = parse_meta_tags(&mut is);
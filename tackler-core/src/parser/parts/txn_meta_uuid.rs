@@ -98,11 +98,11 @@ mod tests {
 
     #[test]
     fn test_p_uuid() {
-        let mut settings = Settings::default();
+        let settings = Settings::default();
         let input = "e009c181-45f3-4286-bd4c-b0e091c3ba47";
         let mut is = Stream {
             input,
-            state: &mut settings,
+            state: &settings,
         };
 
         let res = p_uuid(&mut is);
@@ -117,11 +117,11 @@ mod tests {
 
     #[test]
     fn test_parse_meta_uuid() {
-        let mut settings = Settings::default();
+        let settings = Settings::default();
         let input = "uuid: c51270e7-305d-40a3-a132-f9ed4b135da7\n";
         let mut is = Stream {
             input,
-            state: &mut settings,
+            state: &settings,
         };
 
         let res = parse_meta_uuid(&mut is);
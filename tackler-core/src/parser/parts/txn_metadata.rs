@@ -237,10 +237,10 @@ mod tests {
 
         let mut count = 0;
         for t in pok_meta {
-            let mut settings = Settings::default();
+            let settings = Settings::default();
             let mut is = Stream {
                 input: t.0.as_str(),
-                state: &mut settings,
+                state: &settings,
             };
 
             let res = parse_txn_meta(&mut is);
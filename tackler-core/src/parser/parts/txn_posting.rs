@@ -3,14 +3,19 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 use crate::kernel::Settings;
-use crate::model::Posting;
+use crate::model::source::Position;
+use crate::model::{Commodity, Posting};
 use crate::parser::parts::comment::p_comment;
-use crate::parser::parts::identifier::p_multi_part_id;
+use crate::parser::parts::identifier::{p_identifier, p_multi_part_id};
+use crate::parser::parts::number::p_number;
 use crate::parser::parts::posting_value::{ValuePosition, parse_posting_value};
 use crate::parser::{Stream, from_error};
 use crate::tackler;
+use rust_decimal::Decimal;
 use winnow::ascii::{line_ending, space0, space1};
-use winnow::combinator::opt;
+use winnow::combinator::{cut_err, opt, preceded};
+use winnow::error::{StrContext, StrContextValue};
+use winnow::stream::Stream as _;
 use winnow::{ModalResult, Parser, seq};
 /*
 // The old ANTLR Grammar
@@ -41,55 +46,124 @@ amount: INT | NUMBER;
 unit: ID;
  */
 
+/// Balance assertion of the posting: amount and optional commodity
+pub(crate) type Assertion<'s> = (Decimal, Option<&'s str>);
+
+/// Last posting without amount: account, comment, position and balance assertion
 pub(crate) fn parse_txn_last_posting<'s>(
     is: &mut Stream<'s>,
-) -> ModalResult<(&'s str, Option<&'s str>)> {
+) -> ModalResult<(&'s str, Option<&'s str>, Position, Option<Assertion<'s>>)> {
+    let pos = account_position(is);
     let m = seq!(
         _: space1,
         p_multi_part_id,
+        opt(p_assertion),
         _: space0,
         opt(p_comment),
         _: line_ending
     )
     .parse_next(is)?;
 
+    Ok((m.0, m.2, pos, m.1))
+}
+
+/// Position of the posting's account, which is after the indentation
+fn account_position(is: &Stream<'_>) -> Position {
+    Position::Tail(is.input.trim_start_matches([' ', '\t']).len())
+}
+
+/// Balance assertion: `== amount [commodity]`
+fn p_assertion<'s>(is: &mut Stream<'s>) -> ModalResult<Assertion<'s>> {
+    const CTX_LABEL: &str = "balance assertion";
+    let m = seq!(
+        _: space1,
+        _: "==",
+        _: cut_err(space1)
+            .context(StrContext::Label(CTX_LABEL))
+            .context(StrContext::Expected(StrContextValue::Description("space"))),
+        cut_err(p_number)
+            .context(StrContext::Label(CTX_LABEL))
+            .context(StrContext::Expected(StrContextValue::Description("number"))),
+        opt(preceded(space1, p_identifier)),
+    )
+    .parse_next(is)?;
+
     Ok((m.0, m.1))
 }
 
+/// Get asserted amount, the commodity of assertion must be the posting commodity
+pub(crate) fn assertion_amount(
+    assertion: Option<Assertion<'_>>,
+    comm: &Commodity,
+    acc_id: &str,
+) -> Result<Option<Decimal>, tackler::Error> {
+    match assertion {
+        Some((amount, assert_comm)) => {
+            if assert_comm.unwrap_or_default() != comm.name {
+                let msg = format!(
+                    "Balance assertion commodity [{}] is different than posting commodity [{}] for account: {}",
+                    assert_comm.unwrap_or_default(),
+                    comm.name,
+                    acc_id
+                );
+                return Err(msg.into());
+            }
+            Ok(Some(amount))
+        }
+        None => Ok(None),
+    }
+}
+
 fn handle_posting(
     acc_id: &str,
     vp: ValuePosition,
+    assertion: Option<Assertion<'_>>,
     comment: Option<&str>,
-    settings: &mut Settings,
+    settings: &Settings,
 ) -> Result<Posting, tackler::Error> {
     let comm = vp.post_commodity;
     let acctn = settings.get_or_create_txn_account(acc_id, &comm)?;
 
-    Posting::from(
+    let assertion = assertion_amount(assertion, &comm, acc_id)?;
+
+    let posting = Posting::from(
         acctn,
         vp.post_amount,
         vp.txn_amount,
         vp.total_amount,
         vp.txn_commodity,
+        vp.cost,
         comment.map(String::from),
-    )
+    )?;
+
+    Ok(Posting {
+        assertion,
+        ..posting
+    })
 }
 
 pub(crate) fn parse_txn_posting(is: &mut Stream<'_>) -> ModalResult<Posting> {
+    let start = is.checkpoint();
+    let pos = account_position(is);
     let m = seq!(
         _: space1,
         p_multi_part_id,
         _: space1,
         parse_posting_value,
+        opt(p_assertion),
         _: space0,
         opt(p_comment),
         _: line_ending
     )
     .parse_next(is)?;
 
-    match handle_posting(m.0, m.1, m.2, is.state) {
-        Ok(posting) => Ok(posting),
-        Err(err) => Err(from_error(is, err.as_ref())),
+    match handle_posting(m.0, m.1, m.2, m.3, is.state) {
+        Ok(posting) => Ok(Posting { pos, ..posting }),
+        Err(err) => {
+            // Semantic errors of the posting are reported at the start of the posting
+            is.reset(&start);
+            Err(from_error(is, err.as_ref()))
+        }
     }
 }
 
@@ -100,34 +174,34 @@ mod tests {
 
     #[test]
     fn test_p_last_posting() {
-        let mut settings = Settings::default();
+        let settings = Settings::default();
         let input = " abc\n";
         let mut is = Stream {
             input,
-            state: &mut settings,
+            state: &settings,
         };
 
         let res = parse_txn_last_posting(&mut is);
 
         assert!(res.is_ok());
         let acc = res.unwrap(/*:test:*/);
-        assert_eq!(acc, ("abc", None));
+        assert_eq!((acc.0, acc.1), ("abc", None));
     }
 
     #[test]
     fn test_p_last_posting_comment() {
-        let mut settings = Settings::default();
+        let settings = Settings::default();
         let input = " abc; foobar\n";
         let mut is = Stream {
             input,
-            state: &mut settings,
+            state: &settings,
         };
 
         let res = parse_txn_last_posting(&mut is);
 
         assert!(res.is_ok());
         let acc = res.unwrap(/*:test:*/);
-        assert_eq!(acc, ("abc", Some("foobar")));
+        assert_eq!((acc.0, acc.1), ("abc", Some("foobar")));
     }
 
     #[test]
@@ -140,14 +214,18 @@ mod tests {
             " a:b:c -1 ACME·INC {120 EUR} @ 123 EUR\n",
             " a:b:c -1 ACME·INC {120 EUR}\n",
             " a:b:c  1 Au·µg {1 EUR}\n",
+            " abc 123 == 123\n",
+            " abc 123 € == 246 € ; comment\n",
+            " abc 26 bar·He_50L = 32.50 EUR == 26 bar·He_50L\n",
+            " a:b:c -1 ACME·INC {120 EUR} @ 123 EUR == 0 ACME·INC\n",
         ];
 
         for s in tests {
-            let mut settings = Settings::default();
+            let settings = Settings::default();
 
             let mut is = Stream {
                 input: s,
-                state: &mut settings,
+                state: &settings,
             };
 
             let res = parse_txn_posting(&mut is);
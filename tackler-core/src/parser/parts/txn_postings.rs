@@ -4,7 +4,9 @@
  */
 use crate::model::posting::txn_sum;
 use crate::model::{Posting, Posts};
-use crate::parser::parts::txn_posting::{parse_txn_last_posting, parse_txn_posting};
+use crate::parser::parts::txn_posting::{
+    assertion_amount, parse_txn_last_posting, parse_txn_posting,
+};
 use crate::parser::{Stream, from_error};
 use std::ops::Neg;
 use winnow::combinator::{opt, repeat};
@@ -28,13 +30,20 @@ pub(crate) fn parse_txn_postings(is: &mut Stream<'_>) -> ModalResult<Posts> {
             Ok(acctn) => acctn,
             Err(err) => return Err(from_error(is, err.as_ref())),
         };
+        let assertion = match assertion_amount(p.3, &acctn.comm, p.0) {
+            Ok(assertion) => assertion,
+            Err(err) => return Err(from_error(is, err.as_ref())),
+        };
         let lp = Posting {
             acctn,
             amount,
             txn_amount: amount,
             is_total_amount: false,
             txn_commodity: comm,
+            cost: None,
+            assertion,
             comment: p.1.map(String::from),
+            pos: p.2,
         };
         postings.0.push(lp);
     }
@@ -58,10 +67,10 @@ mod tests {
         )
         .strip_margin();
 
-        let mut settings = Settings::default();
+        let settings = Settings::default();
         let mut is = Stream {
             input: &mut input,
-            state: &mut settings,
+            state: &settings,
         };
 
         let res = parse_txn_postings(&mut is);
@@ -79,10 +88,10 @@ mod tests {
         )
         .strip_margin();
 
-        let mut settings = Settings::default();
+        let settings = Settings::default();
         let mut is = Stream {
             input: &mut input,
-            state: &mut settings,
+            state: &settings,
         };
 
         let res = parse_txn_postings(&mut is);
@@ -5,6 +5,7 @@
 use itertools::Itertools;
 use winnow::{ModalResult, Parser, seq};
 
+use crate::model::source::Position;
 use crate::model::{Transaction, Txns};
 use crate::parser::parts::txn_header::parse_txn_header;
 use crate::parser::parts::txn_postings::parse_txn_postings;
@@ -12,7 +13,8 @@ use crate::parser::{Stream, from_error, make_semantic_error};
 use winnow::ascii::{line_ending, space0};
 use winnow::combinator::alt;
 use winnow::combinator::{cut_err, eof, opt, preceded, repeat, repeat_till};
-use winnow::error::StrContext;
+use winnow::error::{ContextError, StrContext};
+use winnow::stream::Stream as _;
 
 pub(crate) fn multispace0_line_ending<'s>(is: &mut Stream<'s>) -> ModalResult<&'s str> {
     // space0 can't be multispace0 as it's greedy and eats away the last line ending
@@ -23,6 +25,8 @@ pub(crate) fn multispace0_line_ending<'s>(is: &mut Stream<'s>) -> ModalResult<&'
 }
 
 fn parse_txn(is: &mut Stream<'_>) -> ModalResult<Transaction> {
+    let start = is.checkpoint();
+    let pos = Position::Tail(is.input.len());
     let txn = seq!(
         cut_err(parse_txn_header)
             .context(StrContext::Label("Txn Header")),
@@ -33,6 +37,10 @@ fn parse_txn(is: &mut Stream<'_>) -> ModalResult<Transaction> {
     .context(StrContext::Label("Transaction"))
     .parse_next(is)?;
 
+    // Semantic errors of the txn are reported at the start of the txn
+    let end = is.checkpoint();
+    is.reset(&start);
+
     if txn.1.iter().map(|p| &p.txn_commodity.name).unique().count() > 1 {
         let msg = format!(
             "Different commodities without value positions are not allowed inside single transaction.{}",
@@ -44,8 +52,16 @@ fn parse_txn(is: &mut Stream<'_>) -> ModalResult<Transaction> {
         return Err(make_semantic_error(is, msg.as_str()));
     }
 
+    if let Err(err) = is.state.check_account_periods(&txn.0.timestamp, &txn.1) {
+        return Err(from_error(is, err.as_ref()));
+    }
+
     match Transaction::try_from(txn.0, txn.1) {
-        Ok(txn) => Ok(txn),
+        Ok(mut txn) => {
+            is.reset(&end);
+            txn.source.pos = pos;
+            Ok(txn)
+        }
         Err(err) => Err(from_error(is, err.as_ref())),
     }
 }
@@ -60,6 +76,68 @@ pub(crate) fn parse_txns(input: &mut Stream<'_>) -> ModalResult<Txns> {
     Ok(txns.0)
 }
 
+/// Txns and errors of the recovering parser
+pub(crate) struct RecoveredTxns {
+    pub(crate) txns: Txns,
+    /// Byte offsets of the parsed txns
+    pub(crate) offsets: Vec<usize>,
+    /// Errors of the failed txns with their byte offsets
+    pub(crate) errors: Vec<(usize, ContextError)>,
+}
+
+/// Parse txns, and continue with the next txn after a failed one
+///
+/// Txns are separated by blank lines, so after an error the input is skipped
+/// until the first non-blank line which follows a blank line.
+pub(crate) fn parse_txns_recovering(input: &mut Stream<'_>) -> RecoveredTxns {
+    let len = input.input.len();
+    let mut recovered = RecoveredTxns {
+        txns: Txns::new(),
+        offsets: Vec::new(),
+        errors: Vec::new(),
+    };
+    loop {
+        let _ = opt(multispace0_line_ending).parse_next(input);
+        if input.input.is_empty() {
+            break;
+        }
+        let start = input.checkpoint();
+        let txn_offset = len - input.input.len();
+        match parse_txn(input) {
+            Ok(txn) => {
+                recovered.txns.push(txn);
+                recovered.offsets.push(txn_offset);
+            }
+            Err(err) => {
+                let offset = len - input.input.len();
+                recovered.errors.push((
+                    offset,
+                    err.into_inner().unwrap_or_else(|_| ContextError::new()),
+                ));
+                input.reset(&start);
+                let skip = next_txn_offset(input.input);
+                input.next_slice(skip);
+            }
+        }
+    }
+    recovered
+}
+
+/// Byte offset of the next txn, or the end of input
+fn next_txn_offset(input: &str) -> usize {
+    let mut offset = 0;
+    let mut blank = false;
+    for line in input.split_inclusive('\n') {
+        let is_blank = line.trim().is_empty();
+        if offset > 0 && blank && !is_blank {
+            break;
+        }
+        blank = is_blank;
+        offset += line.len();
+    }
+    offset
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -143,10 +221,10 @@ mod tests {
 
         let mut count = 0;
         for t in pok_txns {
-            let mut settings = Settings::default();
+            let settings = Settings::default();
             let mut is = Stream {
                 input: t.0.as_str(),
-                state: &mut settings,
+                state: &settings,
             };
 
             let res = parse_txns(&mut is);
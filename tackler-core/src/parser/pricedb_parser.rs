@@ -10,20 +10,19 @@ use winnow::{
 };
 
 use crate::kernel::Settings;
-use crate::model::price_entry::PriceDb;
-use crate::parser::Stream;
+use crate::model::price_entry::{PriceDb, merge_price_dbs};
+use crate::parser::{Stream, to_diagnostic};
 
 use super::parts::{pricedb::parse_price_entry, txns::multispace0_line_ending};
 
 use crate::tackler;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-/// # Errors
-/// Returns `Err` in case of invalid pricedb data
-pub fn pricedb_from_str(
-    input: &mut &str,
-    settings: &mut Settings,
-) -> Result<PriceDb, tackler::Error> {
+/// Parse price entries, sorted and de-duplicated
+///
+/// Entries with the same timestamp and commodity pair are de-duplicated
+/// inside the single price database, and the first one of them is used.
+fn parse_pricedb(input: &mut &str, settings: &Settings) -> Result<PriceDb, tackler::Error> {
     let is = Stream {
         input,
         state: settings,
@@ -35,17 +34,61 @@ pub fn pricedb_from_str(
     )
     .parse(is)
     .map(|(price_entries, _): (Vec<_>, _)| price_entries.into_iter().sorted().dedup().collect())
-    .map_err(|err| err.to_string().into())
+    .map_err(|err| to_diagnostic(&err).into())
 }
 
-/// # Errors
-/// Returns `Err` in case of invalid pricedb data
-pub fn pricedb_from_file(path: &Path, settings: &mut Settings) -> Result<PriceDb, tackler::Error> {
+fn read_pricedb(path: &Path, settings: &Settings) -> Result<PriceDb, tackler::Error> {
     let pricedb_str = std::fs::read_to_string(path)
         .map_err(|err| format!("Can't open file: '{}' - {}", path.display(), err))?;
 
     // todo: error log
-    pricedb_from_str(&mut pricedb_str.as_str(), settings)
+    parse_pricedb(&mut pricedb_str.as_str(), settings)
+}
+
+/// # Errors
+/// Returns `Err` in case of invalid pricedb data
+pub fn pricedb_from_str(input: &mut &str, settings: &Settings) -> Result<PriceDb, tackler::Error> {
+    parse_pricedb(input, settings)
+}
+
+/// # Errors
+/// Returns `Err` in case of invalid pricedb data
+pub fn pricedb_from_file(path: &Path, settings: &Settings) -> Result<PriceDb, tackler::Error> {
+    pricedb_from_paths(&[path.to_path_buf()], settings)
+}
+
+/// Read price database from files, or from directories of price database shards (`*.db`)
+///
+/// Price databases are merged, see [`merge_price_dbs`], and different prices
+/// for the same timestamp and commodity pair in different files are conflicts.
+/// Inside a single file, these are de-duplicated as with [`pricedb_from_str`].
+///
+/// # Errors
+/// Returns `Err` in case of invalid pricedb data, or if the price databases have conflicts
+pub fn pricedb_from_paths(
+    paths: &[PathBuf],
+    settings: &Settings,
+) -> Result<PriceDb, tackler::Error> {
+    let mut db_paths = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            let mut shards = tackler_rs::get_paths_by_ext(path, "db")?;
+            shards.sort();
+            db_paths.extend(shards);
+        } else {
+            db_paths.push(path.clone());
+        }
+    }
+    let dbs = db_paths
+        .iter()
+        .map(|db_path| {
+            Ok((
+                db_path.display().to_string(),
+                read_pricedb(db_path, settings)?,
+            ))
+        })
+        .collect::<Result<Vec<_>, tackler::Error>>()?;
+    merge_price_dbs(dbs)
 }
 
 #[cfg(test)]
@@ -112,9 +155,9 @@ mod tests {
         let mut count = 0;
         let pok_count = pok_pricedbs.len();
         for t in pok_pricedbs {
-            let mut settings = Settings::default();
+            let settings = Settings::default();
 
-            let res = pricedb_from_str(&mut t.0.as_str(), &mut settings);
+            let res = pricedb_from_str(&mut t.0.as_str(), &settings);
 
             assert!(
                 res.is_ok(),
@@ -134,4 +177,104 @@ mod tests {
         }
         assert_eq!(count, pok_count);
     }
+
+    #[test]
+    // test: 3a5c7e9b-1d3f-4b5d-8e0a-2c4e6f8a1b3d
+    // desc: price databases are merged, and conflicts are reported
+    fn test_merge_pricedbs() {
+        let settings = Settings::default();
+        let db = |s: &str| pricedb_from_str(&mut &*s, &settings).unwrap(/*:test:*/);
+
+        let merged = merge_price_dbs(vec![
+            ("a.db".to_string(), db("P 2024-01-02 XAU 2000 USD\nP 2024-01-01 XAU 1990 USD\n")),
+            ("b.db".to_string(), db("P 2024-01-02 XAU 2000.00 USD\nP 2024-01-01 XAG 25 USD\n")),
+        ])
+        .unwrap(/*:test:*/);
+        assert_eq!(merged.len(), 3);
+        assert!(merged.is_sorted());
+
+        let res = merge_price_dbs(vec![
+            ("a.db".to_string(), db("P 2024-01-02 XAU 2000 USD\n")),
+            ("b.db".to_string(), db("P 2024-01-02 XAU 2001 USD\n")),
+        ]);
+        assert!(
+            res.err().unwrap(/*:test:*/).to_string().contains(
+            "2024-01-02T00:00:00+00:00 XAU in USD: 2000 ('a.db'), 2001 ('b.db')"
+        )
+        );
+    }
+
+    #[test]
+    // test: 6d8f0a2c-4e6a-4c8e-a0b2-5d7f9b1c3e4a
+    // desc: single price database file is de-duplicated, and conflicts are between files
+    fn test_single_pricedb_conflict() {
+        let settings = Settings::default();
+        let dir = tempfile::tempdir().unwrap(/*:test:*/);
+
+        let db_path = dir.path().join("a.db");
+        std::fs::write(
+            &db_path,
+            "P 2024-01-02 XAU 2000 USD\nP 2024-01-01 XAU 1990 USD\nP 2024-01-02 XAU 2001 USD\n",
+        )
+        .unwrap(/*:test:*/);
+        let price_db =
+            pricedb_from_paths(std::slice::from_ref(&db_path), &settings).unwrap(/*:test:*/);
+        let prices: Vec<_> = price_db.iter().map(|e| e.eq_amount.to_string()).collect();
+        assert_eq!(prices, vec!["1990", "2000"]);
+
+        let other_path = dir.path().join("b.db");
+        std::fs::write(&other_path, "P 2024-01-02 XAU 2002 USD\n").unwrap(/*:test:*/);
+        let res = pricedb_from_paths(&[dir.path().to_path_buf()], &settings);
+        assert!(res.err().unwrap(/*:test:*/).to_string().contains(&format!(
+            "2024-01-02T00:00:00+00:00 XAU in USD: 2000 ('{}'), 2002 ('{}')",
+            db_path.display(),
+            other_path.display()
+        )));
+    }
+
+    #[test]
+    // test: 0f2b8d61-7c3e-4a95-b1d4-e8a6c5f3b927
+    // desc: single price database is loaded as before, sorted and de-duplicated
+    fn test_single_pricedb_as_before() {
+        let settings = Settings::default();
+        let dir = tempfile::tempdir().unwrap(/*:test:*/);
+
+        let db = indoc!(
+            "|P 2024-01-03 XAG 25 USD
+             |P 2024-01-02 XAU 2000 USD
+             |P 2024-01-01 XAU 1990 USD
+             |P 2024-01-02 XAU 2000.0 USD
+             |P 2024-01-02 XAG 24 USD
+             |"
+        )
+        .strip_margin();
+        let db_path = dir.path().join("price.db");
+        std::fs::write(&db_path, &db).unwrap(/*:test:*/);
+
+        let entries = |db: &PriceDb| -> Vec<String> {
+            db.iter()
+                .map(|e| {
+                    format!(
+                        "{} {} {} {}",
+                        e.timestamp.date(),
+                        e.base_commodity.name,
+                        e.eq_amount,
+                        e.eq_commodity.name
+                    )
+                })
+                .collect()
+        };
+        let expected = vec![
+            "2024-01-01 XAU 1990 USD",
+            "2024-01-02 XAG 24 USD",
+            "2024-01-02 XAU 2000 USD",
+            "2024-01-03 XAG 25 USD",
+        ];
+
+        let from_str = pricedb_from_str(&mut db.as_str(), &settings).unwrap(/*:test:*/);
+        assert_eq!(entries(&from_str), expected);
+
+        let from_file = pricedb_from_file(&db_path, &settings).unwrap(/*:test:*/);
+        assert_eq!(entries(&from_file), expected);
+    }
 }
@@ -3,26 +3,116 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
-use crate::parser::Stream;
-use crate::parser::parts::txns::parse_txns;
+use crate::parser::parts::txns::{parse_txns, parse_txns_recovering};
+use crate::parser::{Stream, located_diagnostic, to_diagnostic};
 
+use crate::config::InputFormat;
+use crate::diagnostic::Diagnostic;
 use crate::kernel::Settings;
 use crate::model::Txns;
+use crate::model::source;
+use crate::model::source::Shard;
+use crate::parser::ledger_parser;
 use crate::tackler;
 use std::fs::File;
 use std::io::Read;
 use std::path::Path;
+use std::sync::Arc;
 use winnow::Parser;
 
-pub(crate) fn txns_text(input: &mut &str, settings: &mut Settings) -> Result<Txns, tackler::Error> {
+pub(crate) fn txns_text(input: &mut &str, settings: &Settings) -> Result<Txns, tackler::Error> {
     let is = Stream {
         input,
         state: settings,
     };
-    parse_txns.parse(is).map_err(|err| err.to_string().into())
+    parse_txns
+        .parse(is)
+        .map_err(|err| to_diagnostic(&err).into())
 }
 
-pub(crate) fn txns_file(path: &Path, settings: &mut Settings) -> Result<Txns, tackler::Error> {
+/// Parse txn journal in the configured input format
+pub(crate) fn journal_text(input: &mut &str, settings: &Settings) -> Result<Txns, tackler::Error> {
+    match settings.input_format {
+        InputFormat::Tackler => txns_text(input, settings),
+        InputFormat::Ledger => ledger_parser::txns_text(input, settings),
+    }
+}
+
+/// Parse txn journal, and collect errors of all failed txns
+///
+/// Returns txns which are ok with their byte offsets, and diagnostics of the errors.
+/// Only Tackler format can continue after an error, with Ledger format there is
+/// at most one error, and offsets of the txns are not known.
+pub(crate) fn journal_text_checked(
+    input: &str,
+    settings: &Settings,
+) -> (Txns, Vec<usize>, Vec<Diagnostic>) {
+    match settings.input_format {
+        InputFormat::Tackler => {
+            let mut is = Stream {
+                input,
+                state: settings,
+            };
+            let recovered = parse_txns_recovering(&mut is);
+            let mut diagnostics: Vec<_> = recovered
+                .errors
+                .iter()
+                .map(|(offset, err)| located_diagnostic(input, *offset, err))
+                .collect();
+            if diagnostics.is_empty() && recovered.txns.is_empty() {
+                // e.g. there are no txns at all
+                if let Err(err) = journal_text(&mut &*input, settings) {
+                    diagnostics.push(Diagnostic::from_error(err.as_ref()));
+                }
+            }
+            (recovered.txns, recovered.offsets, diagnostics)
+        }
+        InputFormat::Ledger => match journal_text(&mut &*input, settings) {
+            Ok(txns) => (txns, Vec::new(), Vec::new()),
+            Err(err) => (
+                Txns::new(),
+                Vec::new(),
+                vec![Diagnostic::from_error(err.as_ref())],
+            ),
+        },
+    }
+}
+
+/// Parse txn shard (e.g. file or git blob)
+///
+/// Errors are located to the `shard`, if there is one, and positions of the txns
+/// and their postings are resolved, see [`source::resolve`]. In check mode, errors
+/// and lints of the shard are collected into settings, and txns of the shard which
+/// are ok are returned.
+pub(crate) fn shard_text(
+    input: &str,
+    shard: Option<Arc<Shard>>,
+    settings: &Settings,
+) -> Result<Txns, tackler::Error> {
+    let locate = |diag: Diagnostic| match &shard {
+        Some(shard) => shard.locate(diag),
+        None => diag,
+    };
+    let mut txns = if settings.is_check_mode() {
+        let path = shard.as_ref().and_then(|s| s.path.as_deref());
+        let (txns, offsets, mut diagnostics) = journal_text_checked(input, settings);
+        diagnostics.extend(
+            settings
+                .lints
+                .check_shard(input, path, &txns, &offsets, settings),
+        );
+        settings.check_errors(diagnostics.into_iter().map(locate).collect())?;
+        txns
+    } else {
+        journal_text(&mut &*input, settings).map_err(|err| -> tackler::Error {
+            locate(Diagnostic::from_error(err.as_ref())).into()
+        })?
+    };
+    source::resolve(input, shard.as_ref(), &mut txns);
+    Ok(txns)
+}
+
+pub(crate) fn txns_file(path: &Path, settings: &Settings) -> Result<Txns, tackler::Error> {
     let f = File::open(path);
 
     let mut txn_file = match f {
@@ -38,5 +128,6 @@ pub(crate) fn txns_file(path: &Path, settings: &mut Settings) -> Result<Txns, ta
     txn_file.read_to_string(&mut txns_str)?;
 
     // todo: error log
-    txns_text(&mut txns_str.as_str(), settings)
+    let shard = Shard::from_path(path.display().to_string());
+    shard_text(&txns_str, Some(Arc::new(shard)), settings)
 }
@@ -2,211 +2,1028 @@
  * Tackler-NG 2023-2025
  * SPDX-License-Identifier: Apache-2.0
  */
-use itertools::Itertools;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::error::Error;
+use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
 use std::str;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread;
 //use std::time::{SystemTime, UNIX_EPOCH};
 
+use crate::diagnostic;
+use crate::diagnostic::Diagnostic;
 use crate::kernel::Settings;
+use crate::kernel::forecast;
 use crate::kernel::settings::GitInputSelector;
-use crate::model::{TxnData, Txns};
+use crate::model::source::Shard;
+use crate::model::{Transaction, TxnData, Txns};
 use crate::parser::tackler_parser;
 use crate::tackler;
 use gix as git;
 use gix::date::time::CustomFormat;
 use gix::hash as gix_hash;
 use gix::objs::tree::EntryKind;
-use tackler_api::metadata::items::{GitInputReference, MetadataItem};
+use tackler_api::metadata::items::{GitInputBase, GitInputDirty, GitInputReference, MetadataItem};
+
+/// Create `TxnData` of parsed txns, with possible forecast txns
+fn txn_data(
+    mdi_opt: Option<MetadataItem>,
+    txns: Txns,
+    settings: &Settings,
+) -> Result<TxnData, tackler::Error> {
+    let (txns, forecast_mdi) = forecast::with_forecast(txns, settings)?;
+
+    let mut txn_data = TxnData::try_from(mdi_opt, txns, settings)?;
+    if let Some(mdi) = forecast_mdi {
+        txn_data.push_metadata(mdi);
+    }
+    Ok(txn_data)
+}
 
 /// # Errors
 /// Returns `Err` in case of parse or semantic error
-pub fn string_to_txns(
-    input: &mut &str,
-    settings: &mut Settings,
-) -> Result<TxnData, tackler::Error> {
-    let txns = tackler_parser::txns_text(input, settings)?;
+pub fn string_to_txns(input: &mut &str, settings: &Settings) -> Result<TxnData, tackler::Error> {
+    let txns = tackler_parser::shard_text(input, None, settings)?;
 
     // feature: a94d4a60-40dc-4ec0-97a3-eeb69399f01b
     // coverage: "sorted" tested by 200aad57-9275-4d16-bdad-2f1c484bcf17
 
-    TxnData::try_from(None, txns, settings)
+    txn_data(None, txns, settings)
+}
+
+/// Parse independent shards (files, git blobs) with all available cores
+///
+/// Each worker thread has its own local context (e.g. thread local git repository),
+/// which is created by `local`. Shards are handed out to the workers one by one,
+/// and when any of the shards fails, the workers stop taking new shards.
+///
+/// The result is in the same order as the shards, and in case of failure
+/// the error of the first failed shard is returned, exactly as with sequential parsing.
+fn parse_shards<S, T, C, L, P>(shards: &[S], local: L, parse: P) -> Result<Vec<T>, tackler::Error>
+where
+    S: Sync,
+    T: Send,
+    L: Fn() -> C + Sync,
+    P: Fn(&C, &S) -> Result<Vec<T>, tackler::Error> + Sync,
+{
+    let workers = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+
+    parse_shards_with(workers, shards, local, parse)
+}
+
+fn parse_shards_with<S, T, C, L, P>(
+    workers: usize,
+    shards: &[S],
+    local: L,
+    parse: P,
+) -> Result<Vec<T>, tackler::Error>
+where
+    S: Sync,
+    T: Send,
+    L: Fn() -> C + Sync,
+    P: Fn(&C, &S) -> Result<Vec<T>, tackler::Error> + Sync,
+{
+    let workers = workers.min(shards.len());
+
+    if workers <= 1 {
+        let ctx = local();
+        let mut txns = Vec::new();
+        for shard in shards {
+            txns.append(&mut parse(&ctx, shard)?);
+        }
+        return Ok(txns);
+    }
+
+    let next = AtomicUsize::new(0);
+    let failed = AtomicBool::new(false);
+
+    let worker = || {
+        let ctx = local();
+        let mut results = Vec::new();
+        while !failed.load(Ordering::Relaxed) {
+            let idx = next.fetch_add(1, Ordering::Relaxed);
+            if idx >= shards.len() {
+                break;
+            }
+            let res = parse(&ctx, &shards[idx]);
+            if res.is_err() {
+                failed.store(true, Ordering::Relaxed);
+            }
+            results.push((idx, res));
+        }
+        results
+    };
+
+    let mut results: Vec<_> = thread::scope(|s| {
+        let handles: Vec<_> = (0..workers).map(|_| s.spawn(worker)).collect();
+        handles
+            .into_iter()
+            .flat_map(|h| h.join().unwrap_or_else(|p| std::panic::resume_unwind(p)))
+            .collect()
+    });
+    // Shards are taken in order, so all shards before the first failed one are done
+    results.sort_unstable_by_key(|(idx, _)| *idx);
+
+    let mut txns = Vec::new();
+    for (_, res) in results {
+        txns.append(&mut res?);
+    }
+    Ok(txns)
 }
 
 /// # Errors
 /// Returns `Err` in case of parse or semantic error
-pub fn paths_to_txns(
-    paths: &[PathBuf],
-    settings: &mut Settings,
-) -> Result<TxnData, tackler::Error> {
-    let txns: Result<Txns, tackler::Error> = paths
-        .iter()
-        .map(|p| tackler_parser::txns_file(p, settings))
-        .flatten_ok()
+pub fn paths_to_txns(paths: &[PathBuf], settings: &Settings) -> Result<TxnData, tackler::Error> {
+    let txns = parse_shards(paths, || (), |(), p| tackler_parser::txns_file(p, settings))?;
+
+    txn_data(None, txns, settings)
+}
+
+type GitEntry = git::traverse::tree::recorder::Entry;
+
+fn find_commit_by_id<'r>(
+    repo: &'r git::Repository,
+    id: &str,
+) -> Result<git::Commit<'r>, tackler::Error> {
+    let mut candidates = Some(HashSet::default());
+    let prefix = match gix_hash::Prefix::try_from(id) {
+        Ok(v) => v,
+        Err(err) => {
+            let msg = format!("Invalid commit id '{id}': {err}");
+            return Err(msg.into());
+        }
+    };
+
+    let res = repo.objects.lookup_prefix(prefix, candidates.as_mut())?;
+    let object_id = match res {
+        Some(Ok(id)) => id,
+        Some(Err(())) => return Err(format!("Ambiguous abbreviated commit id {id}").into()),
+        None => return Err(format!("Unknown commit id '{id}'").into()),
+    };
+    // This is originally commit, so no need to peel it
+    Ok(repo.find_object(object_id)?.try_into_commit()?)
+}
+
+fn find_commit_by_ref<'r>(
+    repo: &'r git::Repository,
+    ref_str: &str,
+) -> Result<(git::Commit<'r>, Option<String>), tackler::Error> {
+    let id_res = repo.rev_parse_single(ref_str.as_bytes());
+    let id = match id_res {
+        Ok(id) => id,
+        Err(err) => {
+            let msg = if let Some(source) = err.source() {
+                format!("{source}")
+            } else {
+                format!("{err}")
+            };
+            return Err(msg.into());
+        }
+    };
+    let reference = if id.to_string().starts_with(ref_str) {
+        // This is tackler specific logic: don't show ref if it's plain commit id
+        None
+    } else {
+        Some(ref_str.to_string())
+    };
+    // Peel it so that tags are ok
+    Ok((id.object()?.peel_to_commit()?, reference))
+}
+
+/// Author, date and subject of the commit
+fn commit_info(commit: &git::Commit<'_>) -> Result<(String, String, String), tackler::Error> {
+    let signature = commit.author()?;
+    let author = format!("{} <{}>", signature.name, signature.email);
+    let date = signature
+        .time()?
+        .format(CustomFormat::new("%Y-%m-%d %H:%M:%S %z"))?
+        .clone();
+    let subject = commit.message()?.summary().to_string();
+
+    Ok((author, date, subject))
+}
+
+/// File path filter of the journal inside repository
+struct JournalFilter {
+    dir: String,
+    ext: String,
+}
+
+impl JournalFilter {
+    fn from(dir: &str, extension: &str) -> JournalFilter {
+        let dir = if dir.ends_with('/') {
+            dir.to_string()
+        } else {
+            format!("{dir}/")
+        };
+        let ext = format!(".{extension}");
+
+        JournalFilter { dir, ext }
+    }
+
+    /// Select txn shards (and links) of the journal
+    fn is_selected(&self, entry: &GitEntry) -> bool {
+        use git::objs::tree::EntryKind::{Blob, Link};
+        match EntryKind::from(entry.mode) {
+            // Blobs outside of our file path filter are skipped
+            Blob => {
+                entry.filepath.starts_with(str::as_bytes(self.dir.as_str()))
+                    && entry.filepath.ends_with(str::as_bytes(self.ext.as_str()))
+            }
+            Link => true,
+            // It's not a blob
+            _ => false,
+        }
+    }
+}
+
+/// Find all txn shards (and links) of the commit
+fn journal_entries(
+    commit: &git::Commit<'_>,
+    filter: &JournalFilter,
+) -> Result<Vec<GitEntry>, tackler::Error> {
+    let tree = commit.tree()?;
+    // fixme: Optimization
+    //      In the future, this could be optimized with custom walker,
+    //      which does the filtering in the first place.
+    let entries = tree
+        .traverse()
+        .breadthfirst
+        .files()?
+        .into_iter()
+        .filter(|entry| filter.is_selected(entry))
         .collect();
 
-    TxnData::try_from(None, txns?, settings)
+    Ok(entries)
+}
+
+/// Find all txn shards (and links) of the index
+fn index_entries(
+    repo: &git::Repository,
+    filter: &JournalFilter,
+) -> Result<Vec<GitEntry>, tackler::Error> {
+    let index = repo.index()?;
+
+    let mut entries = Vec::new();
+    for e in index.entries() {
+        let filepath = e.path(&index).to_owned();
+        if e.stage() != git::index::entry::Stage::Unconflicted {
+            let msg = format!("GIT: Unmerged path in index: {filepath}");
+            return Err(msg.into());
+        }
+        // Submodules and sparse directories are not files
+        if let Some(mode) = e.mode.to_tree_entry_mode() {
+            let entry = GitEntry {
+                mode,
+                filepath,
+                oid: e.id,
+            };
+            if filter.is_selected(&entry) {
+                entries.push(entry);
+            }
+        }
+    }
+    Ok(entries)
+}
+
+/// Find untracked txn shards of the working tree
+///
+/// These are files of the journal directory with the journal extension,
+/// which are not in the index. Ignore rules (e.g. `.gitignore`) are not used.
+fn untracked_entries(
+    workdir: &Path,
+    object_hash: gix_hash::Kind,
+    dir: &str,
+    extension: &str,
+    tracked: &[GitEntry],
+) -> Result<Vec<GitEntry>, tackler::Error> {
+    let journal_dir = workdir.join(dir.trim_matches('/'));
+    if !journal_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let tracked: HashSet<_> = tracked.iter().map(|e| &e.filepath).collect();
+
+    let mut entries = Vec::new();
+    for path in tackler_rs::get_paths_by_ext(&journal_dir, extension)? {
+        let Ok(rel_path) = path.strip_prefix(workdir) else {
+            continue;
+        };
+        let filepath =
+            git::path::to_unix_separators_on_windows(git::path::into_bstr(rel_path)).into_owned();
+        if !tracked.contains(&filepath) {
+            entries.push(GitEntry {
+                mode: EntryKind::Blob.into(),
+                filepath,
+                oid: gix_hash::ObjectId::null(object_hash),
+            });
+        }
+    }
+    entries.sort_by(|a, b| a.filepath.cmp(&b.filepath));
+    Ok(entries)
+}
+
+fn parse_git_entry(
+    repo: &git::Repository,
+    commit_id: &str,
+    entry: &GitEntry,
+    settings: &Settings,
+) -> Result<Txns, tackler::Error> {
+    let obj = repo.find_object(entry.oid)?;
+    let shard = Shard::from_git(
+        entry.filepath.to_string(),
+        commit_id,
+        obj.id.to_string().as_str(),
+    );
+    if EntryKind::from(entry.mode) == EntryKind::Link {
+        let msg = "Links inside repository are not supported";
+        return Err(shard
+            .locate(Diagnostic::error(diagnostic::ERROR, msg))
+            .into());
+    }
+    tackler_parser::shard_text(str::from_utf8(&obj.data)?, Some(Arc::new(shard)), settings)
 }
 
 /// # Errors
 /// Returns `Err` in case of parse or semantic error
-#[allow(clippy::too_many_lines)]
 pub fn git_to_txns(
     repo_path: &Path,
     dir: &str,
     extension: &str,
     input_selector: GitInputSelector,
-    settings: &mut Settings,
+    settings: &Settings,
 ) -> Result<TxnData, tackler::Error> {
-    // perf: let mut ts_par_total: u128 = 0;
     // perf: let ts_start = SystemTime::now().duration_since(UNIX_EPOCH).unwrap(/*:test:*/);
 
     let repo = git::open(repo_path)?;
 
     let (object, reference) = match input_selector {
-        GitInputSelector::CommitId(id) => {
-            let mut candidates = Some(HashSet::default());
-            let prefix = match gix_hash::Prefix::try_from(id.as_str()) {
-                Ok(v) => v,
-                Err(err) => {
-                    let msg = format!("Invalid commit id '{id}': {err}");
-                    return Err(msg.into());
-                }
-            };
-
-            let res = repo.objects.lookup_prefix(prefix, candidates.as_mut())?;
-            let object_id = match res {
-                Some(Ok(id)) => id,
-                Some(Err(())) => return Err(format!("Ambiguous abbreviated commit id {id}").into()),
-                None => return Err(format!("Unknown commit id '{id}'").into()),
-            };
-            // This is originally commit, so no need to peel it
-            (repo.find_object(object_id)?.try_into_commit()?, None)
-        }
-        GitInputSelector::Reference(ref_str) => {
-            let id_res = repo.rev_parse_single(ref_str.as_bytes());
-            let id = match id_res {
-                Ok(id) => id,
-                Err(err) => {
-                    let msg = if let Some(source) = err.source() {
-                        format!("{source}")
-                    } else {
-                        format!("{err}")
-                    };
-                    return Err(msg.into());
-                }
-            };
-            let reference = if id.to_string().starts_with(ref_str.as_str()) {
-                // This is tackler specific logic: don't show ref if it's plain commit id
-                None
-            } else {
-                Some(ref_str.clone())
-            };
-            // Peel it so that tags are ok
-            (id.object()?.peel_to_commit()?, reference)
+        GitInputSelector::CommitId(id) => (find_commit_by_id(&repo, &id)?, None),
+        GitInputSelector::Reference(ref_str) => find_commit_by_ref(&repo, &ref_str)?,
+        GitInputSelector::Range(base, head) => {
+            return git_range_to_txns(&repo, dir, extension, &base, &head, settings);
+        }
+        GitInputSelector::Index => {
+            return git_dirty_to_txns(&repo, dir, extension, false, settings);
+        }
+        GitInputSelector::WorkTree => {
+            return git_dirty_to_txns(&repo, dir, extension, true, settings);
         }
     };
 
-    let signature = object.author()?;
-    let author = format!("{} <{}>", signature.name, signature.email);
-    let date = signature
-        .time()?
-        .format(CustomFormat::new("%Y-%m-%d %H:%M:%S %z"))?
-        .clone();
-
+    let (author, date, subject) = commit_info(&object)?;
     let gitmd = GitInputReference {
         commit: object.id.to_string(),
         reference,
         dir: dir.to_string(),
         extension: extension.to_string(),
-        subject: object.message()?.summary().to_string(),
+        subject,
         author,
         date,
+        base: None,
+        dirty: None,
     };
 
-    let dir = if dir.ends_with('/') {
-        dir.to_string()
-    } else {
-        format!("{dir}/")
-    };
-    let ext = format!(".{extension}");
+    let entries = journal_entries(&object, &JournalFilter::from(dir, extension))?;
 
-    let tree = object.tree()?;
-    // fixme: Optimization
-    //      In the future, this could be optimized with custom walker,
-    //      which does the filtering in the first place.
-    let txns: Result<Txns, tackler::Error> = tree
-        .traverse()
-        .breadthfirst
-        .files()?
-        .iter()
-        .map(|entry| {
-            use git::objs::tree::EntryKind::{Blob, Link};
-            match EntryKind::from(entry.mode) {
-                Blob => {
-                    if entry.filepath.starts_with(str::as_bytes(dir.as_str()))
-                        && entry.filepath.ends_with(str::as_bytes(ext.as_str()))
-                    {
-                        let obj = repo.find_object(entry.oid)?;
-                        // perf: let ts_par_start = SystemTime::now().duration_since(UNIX_EPOCH).unwrap(/*:test:*/);
-
-                        let par_res =
-                            tackler_parser::txns_text(&mut str::from_utf8(&obj.data)?, settings);
-
-                        // perf: let ts_par_end = SystemTime::now().duration_since(UNIX_EPOCH).unwrap(/*:test:*/);
-                        // perf: ts_par_total = ts_par_total + (ts_par_end.as_millis() - ts_par_start.as_millis());
-                        match par_res {
-                            Ok(txns) => Ok(txns),
-                            Err(err) => {
-                                let msg = format!(
-                                    "\
-                                    GIT: Error while processing git object\n\
-                                    \x20  commit id: {}\n\
-                                    \x20  object id: {}\n\
-                                    \x20  path: {}\n\
-                                    \x20  msg: {}\
-                                    ",
-                                    object.id, obj.id, entry.filepath, err
-                                );
-                                Err(msg.into())
-                            }
-                        }
-                    } else {
-                        // It's blob but outside of our file path filter
-                        Ok(Vec::default())
-                    }
-                }
-                Link => {
-                    let obj = repo.find_object(entry.oid)?;
-                    let msg = format!(
-                        "\
-                        GIT: Error while processing git object\n\
-                        \x20  commit id: {}\n\
-                        \x20  object id: {}\n\
-                        \x20  path: {}\n\
-                        \x20  msg: {}\
-                        ",
-                        object.id,
-                        obj.id,
-                        entry.filepath,
-                        "Links inside repository are not supported"
-                    );
-                    Err(msg.into())
-                }
-                // It's not a blob
-                _ => Ok(Vec::default()),
-            }
-        })
-        .flatten_ok()
-        .collect::<Result<Txns, tackler::Error>>();
+    // Each worker thread needs its own handle to the repository
+    let sync_repo = repo.clone().into_sync();
+    let commit_id = object.id.to_string();
+    let txns = parse_shards(
+        &entries,
+        || sync_repo.to_thread_local(),
+        |repo, entry| parse_git_entry(repo, &commit_id, entry, settings),
+    );
 
     // perf: let ts_end = SystemTime::now().duration_since(UNIX_EPOCH).unwrap(/*:test:*/);
-    // perf: eprintln!("total time: {}ms, parse time: {}ms, git: {}ms", (ts_end.as_millis() - ts_start.as_millis()), ts_par_total, (ts_end.as_millis() - ts_start.as_millis())-ts_par_total);
+    // perf: eprintln!("total time: {}ms", (ts_end.as_millis() - ts_start.as_millis()));
 
-    TxnData::try_from(
+    txn_data(
         Some(MetadataItem::GitInputReference(gitmd)),
         txns?,
         settings,
     )
 }
+
+/// Changed txn shard between two commits
+struct DeltaShard {
+    base: Option<GitEntry>,
+    head: Option<GitEntry>,
+}
+
+impl DeltaShard {
+    fn path(&self) -> &git::bstr::BStr {
+        match (&self.head, &self.base) {
+            (Some(e), _) | (None, Some(e)) => e.filepath.as_ref(),
+            (None, None) => unreachable!("IE: delta shard without entries"),
+        }
+    }
+}
+
+enum TxnDelta {
+    Added(Transaction),
+    Removed(Transaction),
+}
+
+/// Compare two versions of the same txn shard
+///
+/// Txns are the same, if their canonical text presentations are the same.
+fn txn_delta(base: Txns, head: Txns) -> Vec<TxnDelta> {
+    let mut base_txns: BTreeMap<String, Vec<Transaction>> = BTreeMap::new();
+    for txn in base {
+        base_txns.entry(txn.to_string()).or_default().push(txn);
+    }
+
+    let mut delta = Vec::new();
+    for txn in head {
+        let unchanged = base_txns
+            .get_mut(&txn.to_string())
+            .and_then(Vec::pop)
+            .is_some();
+        if !unchanged {
+            delta.push(TxnDelta::Added(txn));
+        }
+    }
+    delta.extend(base_txns.into_values().flatten().map(TxnDelta::Removed));
+    delta
+}
+
+/// Parse only those txn shards which have been changed between base and head commits
+///
+/// The result is the signed delta of txns: txns which are introduced by the range
+/// are used as they are, and txns which are removed by the range are reversed.
+/// Txns are compared only within the same shard path, so a txn which is moved
+/// to another shard is both removed (reversed) and added.
+fn git_range_to_txns(
+    repo: &git::Repository,
+    dir: &str,
+    extension: &str,
+    base_ref: &str,
+    head_ref: &str,
+    settings: &Settings,
+) -> Result<TxnData, tackler::Error> {
+    if settings.forecast.is_some() {
+        let msg = "Forecast can't be used with git range input";
+        return Err(msg.into());
+    }
+    let (base, base_reference) = find_commit_by_ref(repo, base_ref)?;
+    let (head, head_reference) = find_commit_by_ref(repo, head_ref)?;
+
+    let (author, date, subject) = commit_info(&base)?;
+    let gitbase = GitInputBase {
+        commit: base.id.to_string(),
+        reference: base_reference,
+        author,
+        date,
+        subject,
+    };
+    let (author, date, subject) = commit_info(&head)?;
+    let gitmd = GitInputReference {
+        commit: head.id.to_string(),
+        reference: head_reference,
+        dir: dir.to_string(),
+        extension: extension.to_string(),
+        subject,
+        author,
+        date,
+        base: Some(Box::new(gitbase)),
+        dirty: None,
+    };
+
+    let filter = JournalFilter::from(dir, extension);
+    let mut base_entries: HashMap<_, _> = journal_entries(&base, &filter)?
+        .into_iter()
+        .map(|e| (e.filepath.clone(), e))
+        .collect();
+
+    let mut shards = Vec::new();
+    for entry in journal_entries(&head, &filter)? {
+        match base_entries.remove(&entry.filepath) {
+            Some(b) if b.oid == entry.oid && b.mode == entry.mode => {
+                // This shard is untouched by the range
+            }
+            b => shards.push(DeltaShard {
+                base: b,
+                head: Some(entry),
+            }),
+        }
+    }
+    shards.extend(base_entries.into_values().map(|b| DeltaShard {
+        base: Some(b),
+        head: None,
+    }));
+    shards.sort_by(|a, b| a.path().cmp(b.path()));
+
+    let sync_repo = repo.clone().into_sync();
+    let (base_id, head_id) = (base.id.to_string(), head.id.to_string());
+    let parse = |repo: &git::Repository, id: &str, entry: Option<&GitEntry>| {
+        entry
+            .map(|e| parse_git_entry(repo, id, e, settings))
+            .transpose()
+            .map(Option::unwrap_or_default)
+    };
+    let delta = parse_shards(
+        &shards,
+        || sync_repo.to_thread_local(),
+        |repo, shard| {
+            let base_txns = parse(repo, &base_id, shard.base.as_ref())?;
+            let head_txns = parse(repo, &head_id, shard.head.as_ref())?;
+            Ok(txn_delta(base_txns, head_txns))
+        },
+    )?;
+
+    let mut added = Txns::new();
+    let mut removed = Txns::new();
+    for d in delta {
+        match d {
+            TxnDelta::Added(txn) => added.push(txn),
+            TxnDelta::Removed(txn) => removed.push(txn),
+        }
+    }
+
+    TxnData::try_from_delta(
+        Some(MetadataItem::GitInputReference(gitmd)),
+        added,
+        removed,
+        settings,
+    )
+}
+
+/// Read tracked txn shard from the working tree
+///
+/// Returns the object id of the content, or `None` if the file is deleted.
+fn parse_worktree_entry(
+    workdir: &Path,
+    object_hash: gix_hash::Kind,
+    entry: &GitEntry,
+    settings: &Settings,
+) -> Result<(Option<gix_hash::ObjectId>, Txns), tackler::Error> {
+    let path = workdir.join(git::path::from_bstr(&entry.filepath));
+    let shard = Shard::from_path(path.display().to_string());
+    let fail = |err: &(dyn Error + 'static)| -> tackler::Error {
+        shard.locate(Diagnostic::from_error(err)).into()
+    };
+
+    if EntryKind::from(entry.mode) == EntryKind::Link {
+        let msg = "Links inside repository are not supported";
+        return Err(shard
+            .locate(Diagnostic::error(diagnostic::ERROR, msg))
+            .into());
+    }
+    match std::fs::read(&path) {
+        Ok(data) => {
+            let oid = git::objs::compute_hash(object_hash, git::objs::Kind::Blob, &data)
+                .map_err(|err| fail(&err))?;
+            let txns_str = str::from_utf8(&data).map_err(|err| fail(&err))?;
+            let txns =
+                tackler_parser::shard_text(txns_str, Some(Arc::new(shard.clone())), settings)?;
+            Ok((Some(oid), txns))
+        }
+        // Deleted, but not yet staged
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok((None, Txns::new())),
+        Err(err) => Err(fail(&err)),
+    }
+}
+
+/// Parse uncommitted txns from the index, or from the working tree
+///
+/// With working tree, both tracked files (those in the index) and
+/// untracked files of the journal directory are used.
+fn git_dirty_to_txns(
+    repo: &git::Repository,
+    dir: &str,
+    extension: &str,
+    worktree: bool,
+    settings: &Settings,
+) -> Result<TxnData, tackler::Error> {
+    let (head, _) = find_commit_by_ref(repo, "HEAD")?;
+    let reference = repo.head_name()?.map(|name| name.shorten().to_string());
+
+    let workdir = match (worktree, repo.workdir()) {
+        (true, None) => {
+            let msg = "GIT: Repository doesn't have a working tree";
+            return Err(msg.into());
+        }
+        (_, workdir) => workdir.map(Path::to_path_buf),
+    };
+
+    let filter = JournalFilter::from(dir, extension);
+    let object_hash = repo.object_hash();
+    let mut entries = index_entries(repo, &filter)?;
+    if let Some(workdir) = workdir.as_ref().filter(|_| worktree) {
+        let untracked = untracked_entries(workdir, object_hash, dir, extension, &entries)?;
+        entries.extend(untracked);
+    }
+
+    let sync_repo = repo.clone().into_sync();
+    let index_id = format!("{} (index)", head.id);
+    let shards = parse_shards(
+        &entries,
+        || sync_repo.to_thread_local(),
+        |repo, entry| {
+            let res = match &workdir {
+                Some(workdir) if worktree => {
+                    parse_worktree_entry(workdir, object_hash, entry, settings)?
+                }
+                _ => (
+                    Some(entry.oid),
+                    parse_git_entry(repo, &index_id, entry, settings)?,
+                ),
+            };
+            Ok(vec![(entry.filepath.clone(), res)])
+        },
+    )?;
+
+    let mut committed: HashMap<_, _> = journal_entries(&head, &filter)?
+        .into_iter()
+        .map(|e| (e.filepath, e.oid))
+        .collect();
+
+    let mut changed = 0;
+    let mut txns = Txns::new();
+    for (path, (oid, mut shard_txns)) in shards {
+        let committed_oid = committed.remove(&path);
+        if oid.is_none() || oid != committed_oid {
+            changed += 1;
+        }
+        txns.append(&mut shard_txns);
+    }
+    // These are removed from the index (or from the working tree)
+    changed += committed.len();
+
+    let (author, date, subject) = commit_info(&head)?;
+    let gitmd = GitInputReference {
+        commit: head.id.to_string(),
+        reference,
+        dir: dir.to_string(),
+        extension: extension.to_string(),
+        subject,
+        author,
+        date,
+        base: None,
+        dirty: Some(GitInputDirty {
+            input: if worktree { "worktree" } else { "index" }.to_string(),
+            changed,
+        }),
+    };
+
+    txn_data(Some(MetadataItem::GitInputReference(gitmd)), txns, settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::{formatdoc, indoc};
+    use std::sync::Arc;
+    use tackler_rs::IndocUtils;
+
+    fn shards(count: usize) -> Vec<String> {
+        (0..count)
+            .map(|i| {
+                formatdoc!(
+                    "|2024-01-01 'shard-{i}
+                     | e:shard-{}  1 EUR
+                     | a
+                     |
+                     |",
+                    i % 3
+                )
+                .strip_margin()
+            })
+            .collect()
+    }
+
+    fn parse(
+        workers: usize,
+        shards: &[String],
+        settings: &Settings,
+    ) -> Result<Txns, tackler::Error> {
+        parse_shards_with(
+            workers,
+            shards,
+            || (),
+            |(), s| tackler_parser::journal_text(&mut s.as_str(), settings),
+        )
+    }
+
+    #[test]
+    // test: 5a1b1e56-7d4c-4f9d-8c4c-0b9b6a8f2f31
+    // desc: parallel parsing keeps shard order and interns accounts only once
+    fn parallel_shards_ok() {
+        let settings = Settings::default();
+        let input = shards(64);
+
+        let txns = parse(4, &input, &settings).unwrap(/*:test:*/);
+        assert_eq!(txns.len(), 64);
+        for (i, txn) in txns.iter().enumerate() {
+            assert_eq!(
+                txn.header.description.as_deref(),
+                Some(format!("shard-{i}").as_str())
+            );
+        }
+        let first = &txns[0].posts[0].acctn;
+        let third = &txns[3].posts[0].acctn;
+        assert!(Arc::ptr_eq(&first.atn, &third.atn));
+        assert!(Arc::ptr_eq(&first.comm, &third.comm));
+
+        let seq_txns = parse(1, &input, &Settings::default()).unwrap(/*:test:*/);
+        assert_eq!(
+            txns.iter()
+                .map(|t| &t.header.description)
+                .collect::<Vec<_>>(),
+            seq_txns
+                .iter()
+                .map(|t| &t.header.description)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    // test: 0e3f6c39-7e0d-45a8-9a2b-3f8a41d5c2b7
+    // desc: parallel parsing reports the error of the first failed shard
+    fn parallel_shards_first_error() {
+        let mut input = shards(64);
+        input[10] = "2024-01-01 'bad-10\n e  1\n a  1\n\n".to_string();
+        input[40] = "2024-01-01 'bad-40\n e  2\n a  2\n\n".to_string();
+
+        for workers in [1, 4] {
+            let res = parse(workers, &input, &Settings::default());
+            assert!(res.is_err());
+            let msg = res.err().unwrap(/*:test:*/).to_string();
+            assert!(msg.contains("TXN postings do not zero: 2"), "{msg}");
+        }
+    }
+
+    #[test]
+    // test: 8f0f4c1e-2d55-4b0e-9d8b-6c2f1f3e7a10
+    // desc: txn delta of two versions of the same shard
+    fn txn_delta_of_shard() {
+        let settings = Settings::default();
+        let base_str = indoc!(
+            "|2024-01-01 'same
+             | e  1
+             | a
+             |
+             |2024-01-02 'changed
+             | e  2
+             | a
+             |
+             |2024-01-03 'removed
+             | e  3
+             | a
+             |"
+        )
+        .strip_margin();
+        let head_str = indoc!(
+            "|2024-01-01 'same
+             | e  1
+             | a
+             |
+             |2024-01-02 'changed
+             | e  20
+             | a
+             |
+             |2024-01-04 'added
+             | e  4
+             | a
+             |"
+        )
+        .strip_margin();
+        let base =
+            tackler_parser::journal_text(&mut base_str.as_str(), &settings).unwrap(/*:test:*/);
+        let head =
+            tackler_parser::journal_text(&mut head_str.as_str(), &settings).unwrap(/*:test:*/);
+
+        let delta = txn_delta(base, head);
+        let mut added = Txns::new();
+        let mut removed = Txns::new();
+        for d in delta {
+            match d {
+                TxnDelta::Added(txn) => added.push(txn),
+                TxnDelta::Removed(txn) => removed.push(txn),
+            }
+        }
+        assert_eq!(added.len(), 2);
+        assert_eq!(removed.len(), 2);
+
+        let txn_data = TxnData::try_from_delta(None, added, removed, &settings).unwrap(/*:test:*/);
+        let txn_set = txn_data.get_all().unwrap(/*:test:*/);
+        let e_amounts: Vec<_> = txn_set
+            .txns
+            .iter()
+            .map(|txn| {
+                (
+                    txn.header.description.clone().unwrap(/*:test:*/),
+                    txn.posts[0].amount.to_string(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            e_amounts,
+            vec![
+                ("changed".to_string(), "20".to_string()),
+                ("changed".to_string(), "-2".to_string()),
+                ("removed".to_string(), "-3".to_string()),
+                ("added".to_string(), "4".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    // test: 3c7d1a0b-5a8e-4b8f-a3c5-9e4f6d2b7c18
+    // desc: journal filter selects only txn shards and links inside txn dir
+    fn journal_filter() {
+        use git::objs::tree::EntryKind;
+
+        let entry = |kind: EntryKind, path: &str| GitEntry {
+            mode: kind.into(),
+            filepath: path.into(),
+            oid: gix_hash::ObjectId::null(gix_hash::Kind::Sha1),
+        };
+        for dir in ["txns", "txns/"] {
+            let filter = JournalFilter::from(dir, "txn");
+
+            assert!(filter.is_selected(&entry(EntryKind::Blob, "txns/a.txn")));
+            assert!(filter.is_selected(&entry(EntryKind::Blob, "txns/2024/a.txn")));
+            assert!(filter.is_selected(&entry(EntryKind::Link, "txns/a.txn")));
+            assert!(!filter.is_selected(&entry(EntryKind::Blob, "txns/a.txt")));
+            assert!(!filter.is_selected(&entry(EntryKind::Blob, "txns/atxn")));
+            assert!(!filter.is_selected(&entry(EntryKind::Blob, "txns-old/a.txn")));
+            assert!(!filter.is_selected(&entry(EntryKind::Blob, "a.txn")));
+            assert!(!filter.is_selected(&entry(EntryKind::Commit, "txns/a.txn")));
+        }
+    }
+
+    /// Run git command in the test repository
+    fn git(repo: &Path, args: &[&str]) {
+        let output = std::process::Command::new("git")
+            .arg("-C")
+            .arg(repo)
+            .args(["-c", "user.name=tackler", "-c", "user.email=tackler@example.com"])
+            .args(["-c", "commit.gpgsign=false"])
+            .args(args)
+            .output()
+            .unwrap(/*:test:*/);
+        assert!(output.status.success(), "git {args:?}: {output:?}");
+    }
+
+    fn write_txn(repo: &Path, path: &str, desc: &str, amount: i32) {
+        let path = repo.join(path);
+        std::fs::create_dir_all(path.parent().unwrap(/*:test:*/)).unwrap(/*:test:*/);
+        let txn = format!("2024-01-01 '{desc}\n e  {amount}\n a\n");
+        std::fs::write(path, txn).unwrap(/*:test:*/);
+    }
+
+    fn init_repo() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap(/*:test:*/);
+        git(dir.path(), &["init", "-q", "-b", "main"]);
+        dir
+    }
+
+    /// Description and the first posting amount of txns (in txn order)
+    fn txn_amounts(txn_data: &TxnData) -> Vec<(String, String)> {
+        txn_data
+            .get_all()
+            .unwrap(/*:test:*/)
+            .txns
+            .iter()
+            .map(|txn| {
+                (
+                    txn.header.description.clone().unwrap_or_default(),
+                    txn.posts[0].amount.to_string(),
+                )
+            })
+            .collect()
+    }
+
+    fn git_input(txn_data: &TxnData) -> GitInputReference {
+        let txn_set = txn_data.get_all().unwrap(/*:test:*/);
+        txn_set
+            .metadata()
+            .unwrap(/*:test:*/)
+            .items
+            .iter()
+            .find_map(|mdi| match mdi {
+                MetadataItem::GitInputReference(gitmd) => Some(gitmd.clone()),
+                _ => None,
+            })
+            .unwrap(/*:test:*/)
+    }
+
+    #[test]
+    // test: 8e2c4a6f-0b1d-4f3e-a5c7-9d1b3f5e7a20
+    // desc: index and worktree input with modified, staged new and untracked shards
+    fn git_index_and_worktree() {
+        let repo = init_repo();
+        let repo_path = repo.path();
+        write_txn(repo_path, "txns/a.txn", "a", 1);
+        write_txn(repo_path, "txns/b.txn", "b", 2);
+        write_txn(repo_path, "other/x.txn", "x", 9);
+        git(repo_path, &["add", "."]);
+        git(repo_path, &["commit", "-q", "-m", "base"]);
+
+        // modified, not staged
+        write_txn(repo_path, "txns/a.txn", "a", 10);
+        // modified and staged
+        write_txn(repo_path, "txns/b.txn", "b", 20);
+        // new and staged
+        write_txn(repo_path, "txns/c.txn", "c", 3);
+        git(repo_path, &["add", "txns/b.txn", "txns/c.txn"]);
+        // untracked
+        write_txn(repo_path, "txns/sub/d.txn", "d", 4);
+        write_txn(repo_path, "other/y.txn", "y", 8);
+
+        let settings = Settings::default();
+        let parse = |selector| {
+            git_to_txns(repo_path, "txns", "txn", selector, &settings).unwrap(/*:test:*/)
+        };
+        let pair = |desc: &str, amount: &str| (desc.to_string(), amount.to_string());
+
+        let index = parse(GitInputSelector::Index);
+        assert_eq!(
+            txn_amounts(&index),
+            vec![pair("a", "1"), pair("b", "20"), pair("c", "3")]
+        );
+        let dirty = git_input(&index).dirty.unwrap(/*:test:*/);
+        assert_eq!((dirty.input.as_str(), dirty.changed), ("index", 2));
+
+        let worktree = parse(GitInputSelector::WorkTree);
+        assert_eq!(
+            txn_amounts(&worktree),
+            vec![
+                pair("a", "10"),
+                pair("b", "20"),
+                pair("c", "3"),
+                pair("d", "4")
+            ]
+        );
+        let dirty = git_input(&worktree).dirty.unwrap(/*:test:*/);
+        assert_eq!((dirty.input.as_str(), dirty.changed), ("worktree", 4));
+
+        // deleted from working tree, but not staged
+        std::fs::remove_file(repo_path.join("txns/a.txn")).unwrap(/*:test:*/);
+        let worktree = parse(GitInputSelector::WorkTree);
+        assert_eq!(
+            txn_amounts(&worktree),
+            vec![pair("b", "20"), pair("c", "3"), pair("d", "4")]
+        );
+        assert_eq!(git_input(&worktree).dirty.unwrap(/*:test:*/).changed, 4);
+    }
+
+    #[test]
+    // test: 5f7a9c1e-3b5d-4e7f-8a0c-2d4f6b8e0a1c
+    // desc: git range with added, removed, modified and moved txns
+    fn git_range_delta() {
+        let repo = init_repo();
+        let repo_path = repo.path();
+        let write = |path: &str, txns: &[(&str, i32)]| {
+            let txns = txns
+                .iter()
+                .map(|(desc, amount)| format!("2024-01-01 '{desc}\n e  {amount}\n a\n"))
+                .collect::<Vec<_>>()
+                .join("\n");
+            std::fs::write(repo_path.join(path), txns).unwrap(/*:test:*/);
+        };
+        std::fs::create_dir(repo_path.join("txns")).unwrap(/*:test:*/);
+        write("txns/a.txn", &[("keep", 1), ("modified", 2)]);
+        write("txns/b.txn", &[("removed", 3)]);
+        write("txns/c.txn", &[("moved", 5), ("keep", 6)]);
+        write("txns/u.txn", &[("untouched", 7)]);
+        git(repo_path, &["add", "."]);
+        git(repo_path, &["commit", "-q", "-m", "base"]);
+        git(repo_path, &["tag", "base"]);
+
+        write("txns/a.txn", &[("keep", 1), ("modified", 20)]);
+        std::fs::remove_file(repo_path.join("txns/b.txn")).unwrap(/*:test:*/);
+        write("txns/c.txn", &[("keep", 6)]);
+        write("txns/d.txn", &[("moved", 5), ("added", 4)]);
+        git(repo_path, &["add", "-A"]);
+        git(repo_path, &["commit", "-q", "-m", "head"]);
+
+        let settings = Settings::default();
+        let selector = GitInputSelector::Range("base".to_string(), "main".to_string());
+        let txn_data =
+            git_to_txns(repo_path, "txns", "txn", selector, &settings).unwrap(/*:test:*/);
+
+        let mut delta = txn_amounts(&txn_data);
+        delta.sort();
+        let pair = |desc: &str, amount: &str| (desc.to_string(), amount.to_string());
+        assert_eq!(
+            delta,
+            vec![
+                pair("added", "4"),
+                pair("modified", "-2"),
+                pair("modified", "20"),
+                // moved txn is removed from the old shard and added to the new one
+                pair("moved", "-5"),
+                pair("moved", "5"),
+                pair("removed", "-3"),
+            ]
+        );
+
+        let gitmd = git_input(&txn_data);
+        assert_eq!(gitmd.subject, "head");
+        assert_eq!(gitmd.base.unwrap(/*:test:*/).subject, "base");
+    }
+}
@@ -3,6 +3,8 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 use super::*;
+use crate::diagnostic;
+use crate::diagnostic::Diagnostic;
 use crate::kernel::Settings;
 use crate::parser;
 use indoc::indoc;
@@ -182,7 +184,7 @@ fn txn_data_errors() {
     let mut count = 0;
     let should_be_count = txns_str.len();
     for t in txns_str {
-        let res = parser::string_to_txns(&mut t.0.as_ref(), &mut Settings::default());
+        let res = parser::string_to_txns(&mut t.0.as_ref(), &Settings::default());
         assert!(
             res.is_err(),
             "Testing Error: Offending test vector item: {count}"
@@ -220,7 +222,7 @@ fn test_string_txns_are_sorted() {
     )
     .strip_margin();
 
-    let res = parser::string_to_txns(&mut txns_str.as_ref(), &mut Settings::default());
+    let res = parser::string_to_txns(&mut txns_str.as_ref(), &Settings::default());
     assert!(res.is_ok());
     let txn_data = &res.unwrap(/*:test:*/);
     assert_eq!(txn_data.len(), 3);
@@ -248,7 +250,176 @@ fn test_long_string_input_with_errors() {
     let garbage: String = iter::repeat_n("garbage", 2024).collect();
     let input = txns_str + &garbage;
 
-    let res = parser::string_to_txns(&mut input.as_ref(), &mut Settings::default());
+    let res = parser::string_to_txns(&mut input.as_ref(), &Settings::default());
     let err_str = res.unwrap_err().to_string();
     assert!(err_str.len() < 512);
 }
+
+#[test]
+fn txn_data_diagnostics() {
+    #[rustfmt::skip]
+    let txns_str: Vec<(String, &str, usize, usize)> = vec![
+        (
+            // test: 1c7e5a3f-9b2d-4f6e-8a0c-4d6f8b0a2c4e
+            // desc: unbalanced txn is reported at the start of txn
+            indoc!(
+               "|2017-01-01
+                | e  1
+                | a
+                |
+                |2017-01-02
+                | e  1
+                | a  1
+                |"
+            ).strip_margin(),
+            diagnostic::UNBALANCED_TXN, 5, 1,
+        ),
+        (
+            // test: 7a9c1e3b-5d4f-4a2c-b6e8-0f2a4c6e8b1d
+            // desc: zero posting is reported at the start of posting
+            indoc!(
+               "|2017-01-01
+                | e  1
+                | b  0
+                | a
+                |"
+            ).strip_margin(),
+            diagnostic::ZERO_POSTING, 3, 1,
+        ),
+        (
+            // test: 4e6a8c0b-2d1f-4b3a-9c5e-7f9b1d3a5c7e
+            // desc: syntax error
+            indoc!(
+               "|2017-01-01
+                |a  1
+                | e
+                |"
+            ).strip_margin(),
+            diagnostic::PARSE_ERROR, 2, 1,
+        ),
+    ];
+    for (i, (txns, code, line, column)) in txns_str.iter().enumerate() {
+        let err = parser::string_to_txns(&mut txns.as_str(), &Settings::default()).unwrap_err();
+        let diag = Diagnostic::from_error(err.as_ref());
+        assert_eq!(diag.code, *code, "Offending test vector item: {i}");
+        assert_eq!(diag.line, Some(*line), "Offending test vector item: {i}");
+        assert_eq!(
+            diag.column,
+            Some(*column),
+            "Offending test vector item: {i}"
+        );
+        assert!(diag.span.is_some());
+    }
+}
+
+#[test]
+// test: 2b8d4f6a-0c3e-4a5b-9d7f-1e3a5c7b9d0f
+// desc: check mode collects all errors of the journal, and keeps txns which are ok
+fn check_mode_collects_all_errors() {
+    #[rustfmt::skip]
+    let txns_str = indoc!(
+       "|2017-01-01 'ok
+        | # uuid: 8a8b0e8e-1d6c-4a0f-9b7e-2f1c3d5e7a90
+        | e  1
+        | a
+        |
+        |2017-01-02 'unbalanced
+        | e  1
+        | a  1
+        |
+        |2017-01-03 'syntax
+        | e  1 EUR x
+        | a
+        |
+        |2017-01-04 'zero
+        | e  0
+        | a
+        |
+        |2017-01-05 'dup
+        | # uuid: 8a8b0e8e-1d6c-4a0f-9b7e-2f1c3d5e7a90
+        | e  2 == 4
+        | a
+        |
+        |2017-01-06 'ok
+        | e  3 == 7
+        | a
+        |"
+    ).strip_margin();
+
+    let mut settings = Settings::default_extid();
+    settings.audit_mode = false;
+    settings.enable_check_mode();
+    let txn_data = parser::string_to_txns(&mut txns_str.as_str(), &settings).unwrap(/*:test:*/);
+    assert_eq!(txn_data.len(), 3);
+
+    let diags = settings.take_diagnostics();
+    let locations: Vec<_> = diags.iter().map(|d| (d.code, d.line)).collect();
+    assert_eq!(
+        locations,
+        vec![
+            (diagnostic::UNBALANCED_TXN, Some(6)),
+            (diagnostic::PARSE_ERROR, Some(11)),
+            (diagnostic::ZERO_POSTING, Some(15)),
+            (diagnostic::BALANCE_ASSERTION, Some(20)),
+            (diagnostic::BALANCE_ASSERTION, Some(24)),
+        ]
+    );
+    assert!(settings.take_diagnostics().is_empty());
+
+    let mut settings = Settings::default_audit();
+    settings.enable_check_mode();
+    let txn_data = parser::string_to_txns(&mut txns_str.as_str(), &settings);
+    assert!(txn_data.is_ok());
+    let codes: Vec<_> = settings.take_diagnostics().iter().map(|d| d.code).collect();
+    assert!(codes.contains(&diagnostic::DUPLICATE_UUID), "{codes:?}");
+}
+
+#[test]
+// test: 7c9e1a3b-5d7f-4b0c-8e2a-4f6b8d0c2e1a
+// desc: uuid and ext-id errors are located at each offending txn
+fn txn_data_errors_are_located() {
+    #[rustfmt::skip]
+    let txns_str = indoc!(
+       "|2017-01-01 'first
+        | # uuid: 8a8b0e8e-1d6c-4a0f-9b7e-2f1c3d5e7a90
+        | # ext-id: x-1
+        | e  1
+        | a
+        |
+        |2017-01-02 'second
+        | # ext-id: x-1
+        | # uuid: 1c7e3b5d-9f2a-4e6c-8b0d-3a5f7c9e1b2d
+        | e  1
+        | a
+        |
+        |2017-01-03 'dup
+        | # uuid: 8a8b0e8e-1d6c-4a0f-9b7e-2f1c3d5e7a90
+        | # ext-id: x-1
+        | e  1
+        | a
+        |"
+    ).strip_margin();
+
+    let mut settings = Settings::default_extid();
+    settings.audit_mode = true;
+    settings.enable_check_mode();
+    let res = parser::string_to_txns(&mut txns_str.as_str(), &settings);
+    assert!(res.is_ok());
+
+    let diags = settings.take_diagnostics();
+    let locations: Vec<_> = diags.iter().map(|d| (d.code, d.line, d.column)).collect();
+    assert_eq!(
+        locations,
+        vec![
+            (diagnostic::DUPLICATE_EXTID, Some(7), Some(1)),
+            (diagnostic::DUPLICATE_UUID, Some(13), Some(1)),
+            (diagnostic::DUPLICATE_EXTID, Some(13), Some(1)),
+        ]
+    );
+    let dup_uuid = &diags[1];
+    assert!(dup_uuid.message.starts_with("Found 1 duplicate txn uuids"));
+    assert_eq!(
+        dup_uuid.help.as_deref(),
+        Some("Txn with the same uuid is at line 1, column 1")
+    );
+}
@@ -48,7 +48,7 @@ use tackler_rs::IndocUtils;
           |
           |").strip_margin();
 
-      let res = parser::string_to_txns(&mut txns_str.as_ref(), &mut Settings::default());
+      let res = parser::string_to_txns(&mut txns_str.as_ref(), &Settings::default());
       assert!(res.is_ok());
       assert_eq!(res.unwrap(/*:test:*/).len(), 6);
     }
@@ -123,7 +123,7 @@ use tackler_rs::IndocUtils;
         let mut count = 0;
         let should_be_count = perr_strings.len();
         for t in perr_strings {
-            let res = parser::string_to_txns(&mut t.0.as_str(), &mut Settings::default());
+            let res = parser::string_to_txns(&mut t.0.as_str(), &Settings::default());
             assert!(res.is_err(),
                     "Testing Error: Offending test vector item: {count}");
             /*
@@ -205,7 +205,7 @@ use tackler_rs::IndocUtils;
         let mut count = 0;
         let should_be_count = perr_strings.len();
         for t in perr_strings {
-            let res = parser::string_to_txns(&mut t.0.as_str(), &mut Settings::default());
+            let res = parser::string_to_txns(&mut t.0.as_str(), &Settings::default());
             assert!(res.is_err(),
                     "Testing Error: Offending test vector item: {count}");
             /*
@@ -290,7 +290,7 @@ use tackler_rs::IndocUtils;
         let mut count = 0;
         let should_be_count = perr_strings.len();
         for t in perr_strings {
-            let res = parser::string_to_txns(&mut t.0.as_str(), &mut Settings::default());
+            let res = parser::string_to_txns(&mut t.0.as_str(), &Settings::default());
             assert!(res.is_err(),
                     "Testing Error: Offending test vector item: {count}");
             /*
@@ -369,7 +369,7 @@ use tackler_rs::IndocUtils;
         let mut count = 0;
         let should_be_count = perr_strings.len();
         for t in perr_strings {
-            let res = parser::string_to_txns(&mut t.0.as_str(), &mut Settings::default());
+            let res = parser::string_to_txns(&mut t.0.as_str(), &Settings::default());
             assert!(res.is_err(),
                     "Testing Error: Offending test vector item: {count}");
             /*
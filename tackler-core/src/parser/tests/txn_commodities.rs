@@ -11,8 +11,25 @@
 use indoc::indoc;
 use crate::kernel::Settings;
 use crate::parser;
+use rust_decimal::Decimal;
 use tackler_rs::IndocUtils;
 
+use crate::model::TxnData;
+
+/// Assert txn amounts and commodities of the first and the last posting of each txn
+fn assert_txn_values(txn_data: &TxnData, expected: &[(&str, &str, &str, &str)]) {
+    let txn_set = txn_data.get_all().unwrap(/*:test:*/);
+    let values: Vec<_> = txn_set.txns.iter().map(|txn| {
+        let first = &txn.posts[0];
+        let last = &txn.posts[txn.posts.len() - 1];
+        (first.txn_amount.to_string(), first.txn_commodity.name.clone(),
+         last.txn_amount.to_string(), last.txn_commodity.name.clone())
+    }).collect();
+    let expected: Vec<_> = expected.iter().map(|e| {
+        (e.0.to_string(), e.1.to_string(), e.2.to_string(), e.3.to_string())
+    }).collect();
+    assert_eq!(values, expected);
+}
 
 //
 // "Units and Commodities") {
@@ -57,7 +74,7 @@ use tackler_rs::IndocUtils;
           |
           |").strip_margin();
 
-        let res = parser::string_to_txns(&mut txns_str.as_str(), &mut Settings::default());
+        let res = parser::string_to_txns(&mut txns_str.as_str(), &Settings::default());
         assert!(res.is_ok());
         assert_eq!(res.unwrap(/*:test:*/).len(), 8);
       }
@@ -77,7 +94,7 @@ use tackler_rs::IndocUtils;
           |
           |").strip_margin();
 
-        let res = parser::string_to_txns(&mut txns_str.as_str(), &mut Settings::default());
+        let res = parser::string_to_txns(&mut txns_str.as_str(), &Settings::default());
         assert!(res.is_ok());
         assert_eq!(res.unwrap(/*:test:*/).len(), 2);
     }
@@ -122,7 +139,7 @@ use tackler_rs::IndocUtils;
           |
           |").strip_margin();
 
-        let res = parser::string_to_txns(&mut txns_str.as_str(), &mut Settings::default());
+        let res = parser::string_to_txns(&mut txns_str.as_str(), &Settings::default());
         assert!(res.is_ok());
         assert_eq!(res.unwrap(/*:test:*/).len(), 8);
     }
@@ -142,7 +159,7 @@ use tackler_rs::IndocUtils;
           |
           |").strip_margin();
 
-        let res = parser::string_to_txns(&mut txns_str.as_str(), &mut Settings::default());
+        let res = parser::string_to_txns(&mut txns_str.as_str(), &Settings::default());
         assert!(res.is_ok());
         assert_eq!(res.unwrap(/*:test:*/).len(), 2);
     }
@@ -193,9 +210,22 @@ use tackler_rs::IndocUtils;
           |
           |").strip_margin();
 
-        let res = parser::string_to_txns(&mut txns_str.as_str(), &mut Settings::default());
+        let res = parser::string_to_txns(&mut txns_str.as_str(), &Settings::default());
         assert!(res.is_ok());
-        assert_eq!(res.unwrap(/*:test:*/).len(), 9);
+        let txn_data = res.unwrap(/*:test:*/);
+        assert_eq!(txn_data.len(), 9);
+        // opening position without closing position is valued at cost
+        assert_txn_values(&txn_data, &[
+            ("1.20", "EUR", "-1.20", "EUR"),
+            ("-1.20", "EUR", "1.20", "EUR"),
+            ("1", "€", "-1", "€"),
+            ("1", "$", "-1", "$"),
+            ("1", "£", "-1", "£"),
+            ("1", "¥", "-1", "¥"),
+            ("1", "¢", "-1", "¢"),
+            ("1", "Au·µg", "-1", "Au·µg"),
+            ("1", "EUR", "-1", "EUR"),
+        ]);
     }
 
     #[test]
@@ -214,9 +244,14 @@ use tackler_rs::IndocUtils;
           |
           |").strip_margin();
 
-        let res = parser::string_to_txns(&mut txns_str.as_str(), &mut Settings::default());
+        let res = parser::string_to_txns(&mut txns_str.as_str(), &Settings::default());
         assert!(res.is_ok());
-        assert_eq!(res.unwrap(/*:test:*/).len(), 2);
+        let txn_data = res.unwrap(/*:test:*/);
+        assert_eq!(txn_data.len(), 2);
+        assert_txn_values(&txn_data, &[
+            ("1.20", "EUR", "-1.20", "EUR"),
+            ("1.20", "EUR", "-1.20", "EUR"),
+        ]);
     }
 
     #[test]
@@ -263,9 +298,22 @@ use tackler_rs::IndocUtils;
           |
           |").strip_margin();
 
-        let res = parser::string_to_txns(&mut txns_str.as_str(), &mut Settings::default());
+        let res = parser::string_to_txns(&mut txns_str.as_str(), &Settings::default());
         assert!(res.is_ok());
-        assert_eq!(res.unwrap(/*:test:*/).len(), 9);
+        let txn_data = res.unwrap(/*:test:*/);
+        assert_eq!(txn_data.len(), 9);
+        // closing position is valued at closing price, not at cost
+        assert_txn_values(&txn_data, &[
+            ("1.09", "EUR", "-1.09", "EUR"),
+            ("-1.09", "EUR", "1.09", "EUR"),
+            ("1.09", "€", "-1.09", "€"),
+            ("1.09", "$", "-1.09", "$"),
+            ("1.09", "£", "-1.09", "£"),
+            ("1.09", "¥", "-1.09", "¥"),
+            ("1.09", "¢", "-1.09", "¢"),
+            ("1.09", "Au·µg", "-1.09", "Au·µg"),
+            ("1.09", "EUR", "-1.09", "EUR"),
+        ]);
     }
 
     #[test]
@@ -284,12 +332,64 @@ use tackler_rs::IndocUtils;
           |
           |").strip_margin();
 
-        let res = parser::string_to_txns(&mut txns_str.as_str(), &mut Settings::default());
+        let res = parser::string_to_txns(&mut txns_str.as_str(), &Settings::default());
         assert!(res.is_ok());
         assert_eq!(res.unwrap(/*:test:*/).len(), 2);
     }
 
 
+    #[test]
+    // test: 8b2faf48-c18a-499d-a994-2e81e19e1bab
+    // desc: "opening position is kept as cost basis, and it's valued at cost"
+    fn ok_opening_position_cost() {
+      let  txns_str =
+  indoc!("|
+          |2014-05-01
+          | inventory  1 USD {1.38 EUR}
+          | a:cash    -1.38 EUR
+          |
+          |2017-01-01
+          | inventory  -1 USD {1.38 EUR} @ 1.04 EUR
+          | a:cash
+          |
+          |").strip_margin();
+
+        let res = parser::string_to_txns(&mut txns_str.as_str(), &Settings::default());
+        assert!(res.is_ok(), "{:#?}", res.err());
+        let txn_data = res.unwrap(/*:test:*/);
+        let txn_set = txn_data.get_all().unwrap(/*:test:*/);
+        assert_eq!(txn_set.txns.len(), 2);
+
+        let open = &txn_set.txns[0].posts[0];
+        let cost = open.cost.as_ref().unwrap(/*:test:*/);
+        assert_eq!(cost.amount, Decimal::new(138, 2));
+        assert_eq!(cost.commodity.name, "EUR");
+        assert_eq!(open.txn_amount, Decimal::new(138, 2));
+
+        let close = &txn_set.txns[1].posts;
+        assert!(close[0].cost.is_some());
+        assert_eq!(close[1].amount, Decimal::new(104, 2));
+        assert_eq!(close[1].txn_commodity.name, "EUR");
+    }
+
+    #[test]
+    // test: d7a2748a-8e49-422e-953b-6a6631f71a8c
+    // desc: "opening position with same commodity"
+    fn err_opening_position_same_commodity() {
+      let  txns_str =
+  indoc!("|
+          |2017-01-01
+          | e   1 USD {1.20 USD}
+          | a
+          |
+          |").strip_margin();
+
+        let res = parser::string_to_txns(&mut txns_str.as_str(), &Settings::default());
+        assert!(res.is_err());
+        let msg = res.err().unwrap(/*:test:*/).to_string();
+        assert!(msg.contains("Both commodities are same for opening position"), "{msg}");
+    }
+
 //
 //  describe("Invalid inputs and errors") {
 //
@@ -308,7 +408,7 @@ use tackler_rs::IndocUtils;
             |
             |").strip_margin();
 
-          let res = parser::string_to_txns(&mut txns_str.as_str(), &mut Settings::default());
+          let res = parser::string_to_txns(&mut txns_str.as_str(), &Settings::default());
           assert!(res.is_err());
           // let msg = res.err().unwrap(/*:test:*/).to_string();
           // todo: assert!(msg.contains("Unit cost"));
@@ -327,7 +427,7 @@ use tackler_rs::IndocUtils;
             |
             |").strip_margin();
 
-          let res = parser::string_to_txns(&mut txns_str.as_str(), &mut Settings::default());
+          let res = parser::string_to_txns(&mut txns_str.as_str(), &Settings::default());
           assert!(res.is_err());
           let msg = res.err().unwrap(/*:test:*/).to_string();
           assert!(msg.contains("Unit price"));
@@ -346,7 +446,7 @@ use tackler_rs::IndocUtils;
             |
             |").strip_margin();
 
-          let res = parser::string_to_txns(&mut txns_str.as_str(), &mut Settings::default());
+          let res = parser::string_to_txns(&mut txns_str.as_str(), &Settings::default());
           assert!(res.is_err());
           //let msg = res.err().unwrap(/*:test:*/).to_string();
           //todo: assert!(msg.contains("Both commodities are same for value position [€]"));
@@ -364,7 +464,7 @@ use tackler_rs::IndocUtils;
             |
             |").strip_margin();
 
-          let res = parser::string_to_txns(&mut txns_str.as_str(), &mut Settings::default());
+          let res = parser::string_to_txns(&mut txns_str.as_str(), &Settings::default());
           assert!(res.is_err());
           let msg = res.err().unwrap(/*:test:*/).to_string();
           assert!(msg.contains("Different commodities without"));
@@ -382,7 +482,7 @@ use tackler_rs::IndocUtils;
             |
             |").strip_margin();
 
-          let res = parser::string_to_txns(&mut txns_str.as_str(), &mut Settings::default());
+          let res = parser::string_to_txns(&mut txns_str.as_str(), &Settings::default());
           assert!(res.is_err());
           let msg = res.err().unwrap(/*:test:*/).to_string();
           assert!(msg.contains("Total cost"));
@@ -401,7 +501,7 @@ use tackler_rs::IndocUtils;
             |
             |").strip_margin();
 
-          let res = parser::string_to_txns(&mut txns_str.as_str(), &mut Settings::default());
+          let res = parser::string_to_txns(&mut txns_str.as_str(), &Settings::default());
           assert!(res.is_err());
           let msg = res.err().unwrap(/*:test:*/).to_string();
           assert!(msg.contains("Total cost"));
@@ -421,7 +521,7 @@ use tackler_rs::IndocUtils;
             |
             |").strip_margin();
 
-          let res = parser::string_to_txns(&mut txns_str.as_str(), &mut Settings::default());
+          let res = parser::string_to_txns(&mut txns_str.as_str(), &Settings::default());
           assert!(res.is_err());
           assert!(res.err().unwrap(/*:test:*/).to_string().contains("Both commodities are same for value position [€]"));
       }
@@ -438,7 +538,7 @@ use tackler_rs::IndocUtils;
             |
             |").strip_margin();
 
-          let res = parser::string_to_txns(&mut txns_str.as_str(), &mut Settings::default());
+          let res = parser::string_to_txns(&mut txns_str.as_str(), &Settings::default());
           assert!(res.is_err());
           assert!(res.err().unwrap(/*:test:*/).to_string().contains("Different commodities without"));
       }
@@ -455,7 +555,7 @@ use tackler_rs::IndocUtils;
           |
           |").strip_margin();
 
-        let res = parser::string_to_txns(&mut txns_str.as_str(), &mut Settings::default());
+        let res = parser::string_to_txns(&mut txns_str.as_str(), &Settings::default());
         assert!(res.is_err());
         // todo: assert!(res.err().unwrap(/*:test:*/).to_string().contains("line: 3"));
     }
@@ -472,7 +572,7 @@ use tackler_rs::IndocUtils;
           |
           |").strip_margin();
 
-        let res = parser::string_to_txns(&mut txns_str.as_str(), &mut Settings::default());
+        let res = parser::string_to_txns(&mut txns_str.as_str(), &Settings::default());
         assert!(res.is_err());
         // todo: assert!(res.err().unwrap(/*:test:*/).to_string().contains("line: 3"));
     }
@@ -489,7 +589,7 @@ use tackler_rs::IndocUtils;
           |
           |").strip_margin();
 
-        let res = parser::string_to_txns(&mut txns_str.as_str(), &mut Settings::default());
+        let res = parser::string_to_txns(&mut txns_str.as_str(), &Settings::default());
         assert!(res.is_err());
         // todo: assert!(res.err().unwrap(/*:test:*/).to_string().contains("line: 3"));
     }
@@ -506,7 +606,7 @@ use tackler_rs::IndocUtils;
           |
           |").strip_margin();
 
-        let res = parser::string_to_txns(&mut txns_str.as_str(), &mut Settings::default());
+        let res = parser::string_to_txns(&mut txns_str.as_str(), &Settings::default());
         assert!(res.is_err());
         // todo: assert!(res.err().unwrap(/*:test:*/).to_string().contains("line: 3"));
     }
@@ -523,7 +623,7 @@ use tackler_rs::IndocUtils;
           |
           |").strip_margin();
 
-        let res = parser::string_to_txns(&mut txns_str.as_str(), &mut Settings::default());
+        let res = parser::string_to_txns(&mut txns_str.as_str(), &Settings::default());
         assert!(res.is_err());
         // todo: assert!(res.err().unwrap(/*:test:*/).to_string().contains("line: 3"));
     }
@@ -540,7 +640,7 @@ use tackler_rs::IndocUtils;
           |
           |").strip_margin();
 
-        let res = parser::string_to_txns(&mut txns_str.as_str(), &mut Settings::default());
+        let res = parser::string_to_txns(&mut txns_str.as_str(), &Settings::default());
         assert!(res.is_err());
         // todo: assert!(res.err().unwrap(/*:test:*/).to_string().contains("line: 3"));
     }
@@ -557,7 +657,7 @@ use tackler_rs::IndocUtils;
           |
           |").strip_margin();
 
-        let res = parser::string_to_txns(&mut txns_str.as_str(), &mut Settings::default());
+        let res = parser::string_to_txns(&mut txns_str.as_str(), &Settings::default());
         assert!(res.is_err());
         // todo: assert!(res.err().unwrap(/*:test:*/).to_string().contains("line: 3"));
     }
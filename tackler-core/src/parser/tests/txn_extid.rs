@@ -96,7 +96,7 @@ use tackler_rs::IndocUtils;
       ];
         let mut count = 0;
         for t in perr_strings {
-            let res = parser::string_to_txns(&mut t.0.as_str(), &mut Settings::default());
+            let res = parser::string_to_txns(&mut t.0.as_str(), &Settings::default());
             assert!(res.is_err(),
                     "Testing Error: Offending test vector item: {count}");
             /*
@@ -175,7 +175,7 @@ use tackler_rs::IndocUtils;
       ];
       let mut count = 0;
       for t in pok_strings {
-          let res = parser::string_to_txns(&mut t.0.as_str(), &mut Settings::default());
+          let res = parser::string_to_txns(&mut t.0.as_str(), &Settings::default());
           assert!(res.is_ok(), "Offending test vector item: {count}");
 
           let txn_data = res.unwrap(/*:test:*/);
@@ -146,7 +146,7 @@ use tackler_rs::IndocUtils;
          let mut count = 0;
          let ref_count = txn_strs.len();
          for t in txn_strs {
-             let res = parser::string_to_txns(&mut t.0.as_str(), &mut Settings::default());
+             let res = parser::string_to_txns(&mut t.0.as_str(), &Settings::default());
              let t_ref = t.1.to_string();
              assert!(res.is_ok(), "Offending test vector item: {count}");
              let txn_data = res.unwrap(/*:test:*/);
@@ -215,7 +215,7 @@ use tackler_rs::IndocUtils;
         let mut count = 0;
         let should_be_count = perr_strings.len();
         for t in perr_strings {
-            let res = parser::string_to_txns(&mut t.0.as_str(), &mut Settings::default());
+            let res = parser::string_to_txns(&mut t.0.as_str(), &Settings::default());
             assert!(res.is_err(),
                     "Testing Error: Offending test vector item: {count}");
             /*
@@ -293,7 +293,7 @@ use tackler_rs::IndocUtils;
         let mut count = 0;
         let should_be_count = perr_strings.len();
         for t in perr_strings {
-            let res = parser::string_to_txns(&mut t.0.as_str(), &mut Settings::default());
+            let res = parser::string_to_txns(&mut t.0.as_str(), &Settings::default());
             assert!(res.is_err(),
                     "Testing Error: Offending test vector item: {count}");
             /*
@@ -151,7 +151,7 @@ use tackler_rs::IndocUtils;
           let mut count = 0;
           let should_be_count = perr_strings.len();
           for t in perr_strings {
-              let res = parser::string_to_txns(&mut t.0.as_str(), &mut Settings::default());
+              let res = parser::string_to_txns(&mut t.0.as_str(), &Settings::default());
               assert!(res.is_err(),
                       "Testing Error: Offending test vector item: {count}");
               /*
@@ -280,7 +280,7 @@ use tackler_rs::IndocUtils;
       let mut count = 0;
       let ref_count = pok_strings.len();
       for t in pok_strings {
-        let res = parser::string_to_txns(&mut t.0.as_str(), &mut Settings::default());
+        let res = parser::string_to_txns(&mut t.0.as_str(), &Settings::default());
         //println!("{:#?}", &t.0);
         //println!("{:#?}", res);
         assert!(res.is_ok(), "Offending test vector item: {count}");
@@ -3,26 +3,61 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 use crate::config::{FormatType, ReportType};
-use crate::kernel::{BalanceGroupSettings, RegisterSettings, Settings};
+use crate::kernel::{
+    BalanceGroupSettings, BudgetSettings, CashFlowSettings, IncomeStatementSettings, PnlSettings,
+    RegisterSettings, Settings,
+};
 use crate::model::TxnSet;
 use crate::tackler;
 pub use balance_group_reporter::BalanceGroupReporter;
 pub use balance_reporter::BalanceReporter;
+pub use budget_reporter::BudgetReporter;
+pub use cash_flow_reporter::CashFlowReporter;
+pub use income_statement_reporter::IncomeStatementReporter;
+use jiff::Zoned;
+use jiff::tz::TimeZone;
+pub use pnl_reporter::PnlReporter;
 pub use register_reporter::RegisterReporter;
 use std::io;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 use tackler_api::metadata::Metadata;
 use tackler_api::metadata::items::TimeZoneInfo;
+use tackler_api::txn_ts;
 use tackler_rs::create_output_file;
 
 mod balance_group_reporter;
 mod balance_reporter;
+mod budget_reporter;
+mod cash_flow_reporter;
+mod income_statement_reporter;
+mod period_table;
+mod pnl_reporter;
 mod register_reporter;
 
 pub enum FormatWriter<'w> {
     TxtFormat(Box<dyn io::Write + 'w>),
     JsonFormat(Box<dyn io::Write + 'w>),
+    CsvFormat(Box<dyn io::Write + 'w>),
+    TsvFormat(Box<dyn io::Write + 'w>),
+}
+
+pub(crate) const CSV_DELIMITER: u8 = b',';
+pub(crate) const TSV_DELIMITER: u8 = b'\t';
+
+/// Writer for delimited (CSV and TSV) formats
+///
+/// Delimited reports have a header row with stable column names,
+/// and they don't have metadata.
+pub(crate) fn delimited_writer<W: io::Write>(writer: W, delimiter: u8) -> csv::Writer<W> {
+    csv::WriterBuilder::new()
+        .delimiter(delimiter)
+        .from_writer(writer)
+}
+
+/// ISO-8601 timestamp in report timezone, used with delimited formats
+pub(crate) fn iso_timestamp(ts: &Zoned, tz: TimeZone) -> String {
+    txn_ts::rfc_3339(&ts.with_time_zone(tz))
 }
 
 pub trait Report {
@@ -80,85 +115,58 @@ fn report_writers<'w>(
     report_type: &ReportType,
     settings: &Settings,
 ) -> Result<ReportWriters<'w>, tackler::Error> {
-    match report_type {
-        ReportType::Balance => {
-            let mut writers = Vec::new();
-            let mut paths = Vec::new();
-
-            for rt in &settings.report.formats {
-                match rt {
-                    FormatType::Txt => {
-                        let (txt_writer, txt_path) =
-                            create_output_file(output_dir, output_prefix, "bal", "txt")?;
-
-                        writers.push(FormatWriter::TxtFormat(Box::new(txt_writer)));
-                        paths.push(("TEXT".to_string(), txt_path));
-                    }
-                    FormatType::Json => {
-                        let (json_writer, json_path) =
-                            create_output_file(output_dir, output_prefix, "bal", "json")?;
-
-                        writers.push(FormatWriter::JsonFormat(Box::new(json_writer)));
-                        paths.push(("JSON".to_string(), json_path));
-                    }
-                }
+    let name = match report_type {
+        ReportType::Balance => "bal",
+        ReportType::BalanceGroup => "balgrp",
+        ReportType::Register => "reg",
+        ReportType::Pnl => "pnl",
+        ReportType::Budget => "budget",
+        ReportType::IncomeStatement => "income",
+        ReportType::CashFlow => "cashflow",
+    };
+
+    let mut writers = Vec::new();
+    let mut paths = Vec::new();
+
+    for rt in &settings.report.formats {
+        match rt {
+            FormatType::Txt => {
+                let (txt_writer, txt_path) =
+                    create_output_file(output_dir, output_prefix, name, "txt")?;
+
+                writers.push(FormatWriter::TxtFormat(Box::new(txt_writer)));
+                paths.push(("TEXT".to_string(), txt_path));
             }
-            Ok((writers, paths))
-        }
-        ReportType::BalanceGroup => {
-            let mut writers = Vec::new();
-            let mut paths = Vec::new();
-
-            for rt in &settings.report.formats {
-                match rt {
-                    FormatType::Txt => {
-                        let (txt_writer, txt_path) =
-                            create_output_file(output_dir, output_prefix, "balgrp", "txt")?;
-
-                        writers.push(FormatWriter::TxtFormat(Box::new(txt_writer)));
-                        paths.push(("TEXT".to_string(), txt_path));
-                    }
-                    FormatType::Json => {
-                        let (json_writer, json_path) =
-                            create_output_file(output_dir, output_prefix, "balgrp", "json")?;
-
-                        writers.push(FormatWriter::JsonFormat(Box::new(json_writer)));
-                        paths.push(("JSON".to_string(), json_path));
-                    }
-                }
+            FormatType::Json => {
+                let (json_writer, json_path) =
+                    create_output_file(output_dir, output_prefix, name, "json")?;
+
+                writers.push(FormatWriter::JsonFormat(Box::new(json_writer)));
+                paths.push(("JSON".to_string(), json_path));
             }
-            Ok((writers, paths))
-        }
-        ReportType::Register => {
-            let mut writers = Vec::new();
-            let mut paths = Vec::new();
-
-            for rt in &settings.report.formats {
-                match rt {
-                    FormatType::Txt => {
-                        let (txt_writer, txt_path) =
-                            create_output_file(output_dir, output_prefix, "reg", "txt")?;
-
-                        writers.push(FormatWriter::TxtFormat(Box::new(txt_writer)));
-                        paths.push(("TEXT".to_string(), txt_path));
-                    }
-                    FormatType::Json => {
-                        let (json_writer, json_path) =
-                            create_output_file(output_dir, output_prefix, "reg", "json")?;
-
-                        writers.push(FormatWriter::JsonFormat(Box::new(json_writer)));
-                        paths.push(("JSON".to_string(), json_path));
-                    }
-                }
+            FormatType::Csv => {
+                let (csv_writer, csv_path) =
+                    create_output_file(output_dir, output_prefix, name, "csv")?;
+
+                writers.push(FormatWriter::CsvFormat(Box::new(csv_writer)));
+                paths.push(("CSV".to_string(), csv_path));
+            }
+            FormatType::Tsv => {
+                let (tsv_writer, tsv_path) =
+                    create_output_file(output_dir, output_prefix, name, "tsv")?;
+
+                writers.push(FormatWriter::TsvFormat(Box::new(tsv_writer)));
+                paths.push(("TSV".to_string(), tsv_path));
             }
-            Ok((writers, paths))
         }
     }
+    Ok((writers, paths))
 }
 
 /// # Errors
 /// Return `Err` in case of error
 #[allow(clippy::too_many_arguments)]
+#[allow(clippy::too_many_lines)]
 pub fn write_txt_reports<W: io::Write + ?Sized>(
     console_writer: &mut Option<Box<W>>,
     output_dir: Option<&PathBuf>,
@@ -264,7 +272,145 @@ pub fn write_txt_reports<W: io::Write + ?Sized>(
                     writeln!(cw, "{}", "#".repeat(report_separator_len))?;
                 }
             }
+            ReportType::Pnl => {
+                let pnl_reporter = PnlReporter {
+                    report_settings: PnlSettings::try_from(settings)?,
+                };
+
+                if let (Some(output_name), Some(output_dir)) = (output_prefix, output_dir) {
+                    let (mut writers, paths) =
+                        report_writers(output_dir, output_name, r, settings)?;
+
+                    pnl_reporter.write_reports::<dyn io::Write>(
+                        settings,
+                        &mut writers,
+                        txn_set.metadata(),
+                        txn_set,
+                    )?;
+
+                    report_output(prog_writer, paths, "PnL Report")?;
+                } else {
+                    let Some(mut cw) = console_writer.as_mut() else {
+                        return Err("IE: Logic error: console output".into());
+                    };
+
+                    writeln!(cw, "{}", "*".repeat(report_separator_len))?;
+                    pnl_reporter.write_txt_report(settings, &mut cw, txn_set)?;
+                    writeln!(cw, "{}", "#".repeat(report_separator_len))?;
+                }
+            }
+            ReportType::Budget => {
+                let budget_reporter = BudgetReporter {
+                    report_settings: BudgetSettings::try_from(settings)?,
+                };
+
+                if let (Some(output_name), Some(output_dir)) = (output_prefix, output_dir) {
+                    let (mut writers, paths) =
+                        report_writers(output_dir, output_name, r, settings)?;
+
+                    budget_reporter.write_reports::<dyn io::Write>(
+                        settings,
+                        &mut writers,
+                        txn_set.metadata(),
+                        txn_set,
+                    )?;
+
+                    report_output(prog_writer, paths, "Budget Report")?;
+                } else {
+                    let Some(mut cw) = console_writer.as_mut() else {
+                        return Err("IE: Logic error: console output".into());
+                    };
+
+                    writeln!(cw, "{}", "*".repeat(report_separator_len))?;
+                    budget_reporter.write_txt_report(settings, &mut cw, txn_set)?;
+                    writeln!(cw, "{}", "#".repeat(report_separator_len))?;
+                }
+            }
+            ReportType::IncomeStatement => {
+                let is_reporter = IncomeStatementReporter {
+                    report_settings: IncomeStatementSettings::try_from(settings)?,
+                };
+
+                if let (Some(output_name), Some(output_dir)) = (output_prefix, output_dir) {
+                    let (mut writers, paths) =
+                        report_writers(output_dir, output_name, r, settings)?;
+
+                    is_reporter.write_reports::<dyn io::Write>(
+                        settings,
+                        &mut writers,
+                        txn_set.metadata(),
+                        txn_set,
+                    )?;
+
+                    report_output(prog_writer, paths, "Income Statement")?;
+                } else {
+                    let Some(mut cw) = console_writer.as_mut() else {
+                        return Err("IE: Logic error: console output".into());
+                    };
+
+                    writeln!(cw, "{}", "*".repeat(report_separator_len))?;
+                    is_reporter.write_txt_report(settings, &mut cw, txn_set)?;
+                    writeln!(cw, "{}", "#".repeat(report_separator_len))?;
+                }
+            }
+            ReportType::CashFlow => {
+                let cf_reporter = CashFlowReporter {
+                    report_settings: CashFlowSettings::try_from(settings)?,
+                };
+
+                if let (Some(output_name), Some(output_dir)) = (output_prefix, output_dir) {
+                    let (mut writers, paths) =
+                        report_writers(output_dir, output_name, r, settings)?;
+
+                    cf_reporter.write_reports::<dyn io::Write>(
+                        settings,
+                        &mut writers,
+                        txn_set.metadata(),
+                        txn_set,
+                    )?;
+
+                    report_output(prog_writer, paths, "Cash Flow Report")?;
+                } else {
+                    let Some(mut cw) = console_writer.as_mut() else {
+                        return Err("IE: Logic error: console output".into());
+                    };
+
+                    writeln!(cw, "{}", "*".repeat(report_separator_len))?;
+                    cf_reporter.write_txt_report(settings, &mut cw, txn_set)?;
+                    writeln!(cw, "{}", "#".repeat(report_separator_len))?;
+                }
+            }
         }
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tackler_api::txn_ts::rfc3339_to_zoned;
+
+    #[test]
+    // test: 6a8f3d0e-2c4b-4f5a-8e1d-9b7c6a5d4e3f
+    // desc: delimited formats quote fields only when needed
+    fn delimited_writer_quoting() {
+        for (delimiter, output) in [
+            (CSV_DELIMITER, "a,\"b,c\",\"d \"\"e\"\"\",f\tg\n"),
+            (TSV_DELIMITER, "a\tb,c\t\"d \"\"e\"\"\"\t\"f\tg\"\n"),
+        ] {
+            let mut wtr = delimited_writer(Vec::new(), delimiter);
+            wtr.write_record(["a", "b,c", "d \"e\"", "f\tg"]).unwrap(/*:test:*/);
+            let res = String::from_utf8(wtr.into_inner().unwrap(/*:test:*/)).unwrap(/*:test:*/);
+            assert_eq!(res, output);
+        }
+    }
+
+    #[test]
+    // test: 1e4c2b7a-5d3f-4a6e-b8c9-0f1e2d3c4b5a
+    // desc: delimited timestamps are in report timezone
+    fn iso_timestamp_in_report_tz() {
+        let ts = rfc3339_to_zoned("2024-01-01T22:30:00.5+00:00").unwrap(/*:test:*/);
+        let tz = TimeZone::get("Europe/Helsinki").unwrap(/*:test:*/);
+        assert_eq!(iso_timestamp(&ts, tz), "2024-01-02T00:30:00.5+02:00");
+    }
+}
@@ -3,23 +3,28 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
-use crate::kernel::accumulator::TxnGroupByOp;
+use crate::config::BalanceType;
 use crate::kernel::balance::Balance;
 use crate::kernel::report_item_selector::BalanceSelector;
 use crate::kernel::{BalanceGroupSettings, accumulator};
 use crate::kernel::{BalanceSettings, Settings};
-use crate::model::{Transaction, TxnSet};
+use crate::math::format::format_with_scale;
+use crate::model::TxnSet;
 use crate::report::Report;
-use crate::report::{BalanceReporter, FormatWriter, report_timezone};
+use crate::report::period_table::{AccountTable, TotalTable, TxtColumns};
+use crate::report::{
+    BalanceReporter, CSV_DELIMITER, FormatWriter, TSV_DELIMITER, delimited_writer, report_timezone,
+};
 use crate::tackler;
 use crate::tackler::Error;
-use jiff::tz::TimeZone;
+use rust_decimal::Decimal;
+use std::io;
 use std::io::Write;
 use tackler_api::metadata::Metadata;
 use tackler_api::metadata::items::{CreditAccountReport, MetadataItem};
-use tackler_api::reports::balance_group_report::BalanceGroupReport;
-use tackler_api::txn_ts;
-use tackler_api::txn_ts::GroupBy;
+use tackler_api::reports::balance_group_report::{
+    BalanceGroupReport, BalancePivotReport, BalancePivotRow, BalancePivotTotal,
+};
 
 #[derive(Debug, Clone)]
 pub struct BalanceGroupReporter {
@@ -31,27 +36,6 @@ impl BalanceGroupReporter {
         BalanceReporter::acc_selector(&self.report_settings.ras)
     }
 
-    fn get_group_by_op(&self) -> TxnGroupByOp<'_> {
-        let tz: TimeZone = self.report_settings.report_tz.clone();
-        match self.report_settings.group_by {
-            GroupBy::IsoWeekDate => Box::new(move |txn: &Transaction| {
-                txn_ts::as_tz_iso_week_date(&txn.header.timestamp, tz.clone())
-            }),
-            GroupBy::IsoWeek => Box::new(move |txn: &Transaction| {
-                txn_ts::as_tz_iso_week(&txn.header.timestamp, tz.clone())
-            }),
-            GroupBy::Date => Box::new(move |txn: &Transaction| {
-                txn_ts::as_tz_date(&txn.header.timestamp, tz.clone())
-            }),
-            GroupBy::Month => Box::new(move |txn: &Transaction| {
-                txn_ts::as_tz_month(&txn.header.timestamp, tz.clone())
-            }),
-            GroupBy::Year => Box::new(move |txn: &Transaction| {
-                txn_ts::as_tz_year(&txn.header.timestamp, tz.clone())
-            }),
-        }
-    }
-
     #[allow(dead_code)]
     fn to_api(&self, metadata: Option<&Metadata>, bal_groups: &[Balance]) -> BalanceGroupReport {
         let bal_settings: BalanceSettings = self.report_settings.clone().into();
@@ -68,6 +52,200 @@ impl BalanceGroupReporter {
     }
 }
 
+/// Balance groups as pivot table
+///
+/// Rows are accounts and columns are groups. Amount of a row is
+/// account tree sum with tree balance, and account sum with flat balance.
+#[derive(Debug)]
+struct Pivot {
+    groups: Vec<String>,
+    rows: AccountTable,
+    column_totals: TotalTable,
+}
+
+/// Amounts of a pivot row with row total as the last amount
+fn with_total(amounts: &[Decimal]) -> Vec<Decimal> {
+    let total = amounts.iter().sum();
+    amounts
+        .iter()
+        .copied()
+        .chain(std::iter::once(total))
+        .collect()
+}
+
+impl BalanceGroupReporter {
+    fn pivot(&self, bal_groups: &[Balance]) -> Pivot {
+        let mut rows = AccountTable::new(bal_groups.len());
+        let mut column_totals = TotalTable::new(bal_groups.len());
+
+        for (i, bal) in bal_groups.iter().enumerate() {
+            for btn in &bal.bal {
+                let amount = match self.report_settings.bal_type {
+                    BalanceType::Tree => btn.sub_acc_tree_sum,
+                    BalanceType::Flat => btn.account_sum,
+                };
+                let key = (btn.acctn.atn.account.clone(), btn.acctn.comm.name.clone());
+                rows.add(key, i, amount);
+            }
+            for (comm, delta) in &bal.deltas {
+                let comm = comm.as_ref().map_or(String::new(), |c| c.name.clone());
+                column_totals.add(comm, i, *delta);
+            }
+        }
+        Pivot {
+            groups: bal_groups.iter().map(|bal| bal.title.clone()).collect(),
+            rows,
+            column_totals,
+        }
+    }
+
+    fn write_pivot_txt<W: io::Write + ?Sized>(
+        &self,
+        writer: &mut W,
+        pivot: &Pivot,
+    ) -> Result<(), tackler::Error> {
+        let rs = &self.report_settings;
+
+        let comm_len = pivot
+            .column_totals
+            .rows
+            .keys()
+            .map(|comm| comm.chars().count())
+            .max()
+            .unwrap_or(0);
+        let cols = TxtColumns {
+            scale: &rs.scale,
+            comm_len,
+        };
+
+        let header: Vec<_> = pivot
+            .groups
+            .iter()
+            .cloned()
+            .chain(std::iter::once("total".to_string()))
+            .collect();
+        TxtColumns::write_header(writer, &header)?;
+        for ((account, comm), amounts) in &pivot.rows.rows {
+            cols.write_line(writer, &with_total(amounts), comm, account)?;
+        }
+        if rs.column_totals {
+            writeln!(writer, "{}", "-".repeat(cols.separator_len(header.len())))?;
+            for (comm, amounts) in &pivot.column_totals.rows {
+                cols.write_line(writer, &with_total(amounts), comm, "")?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Write pivot table as delimited (CSV, TSV) report
+    ///
+    /// There is a column for each group and for row total.
+    /// Column totals have an empty account.
+    fn write_pivot_delimited<W: io::Write>(
+        &self,
+        writer: W,
+        delimiter: u8,
+        pivot: &Pivot,
+    ) -> Result<(), tackler::Error> {
+        let mut wtr = delimited_writer(writer, delimiter);
+        wtr.write_record(
+            ["account", "commodity"]
+                .into_iter()
+                .chain(pivot.groups.iter().map(String::as_str))
+                .chain(std::iter::once("total")),
+        )?;
+        let column_totals = pivot
+            .column_totals
+            .rows
+            .iter()
+            .filter(|_| self.report_settings.column_totals)
+            .map(|(comm, amounts)| ((String::new(), comm.clone()), amounts));
+        for ((account, comm), amounts) in pivot
+            .rows
+            .rows
+            .iter()
+            .map(|(key, amounts)| (key.clone(), amounts))
+            .chain(column_totals)
+        {
+            wtr.write_record(
+                [account, comm]
+                    .into_iter()
+                    .chain(with_total(amounts).iter().map(Decimal::to_string)),
+            )?;
+        }
+        wtr.flush()?;
+        Ok(())
+    }
+
+    fn to_pivot_api(&self, metadata: Option<&Metadata>, pivot: &Pivot) -> BalancePivotReport {
+        let rs = &self.report_settings;
+        let fmt = |amounts: &[Decimal]| -> (Vec<String>, String) {
+            let mut amounts: Vec<_> = with_total(amounts)
+                .iter()
+                .map(|a| format_with_scale(0, a, &rs.scale))
+                .collect();
+            // unwrap: ok: there is always the row total
+            let total = amounts.pop().unwrap(/*:ok:*/);
+            (amounts, total)
+        };
+        let commodity = |comm: &String| (!comm.is_empty()).then(|| comm.clone());
+
+        BalancePivotReport {
+            metadata: metadata.cloned(),
+            title: rs.title.clone(),
+            groups: pivot.groups.clone(),
+            rows: pivot
+                .rows
+                .rows
+                .iter()
+                .map(|((account, comm), amounts)| {
+                    let (amounts, total) = fmt(amounts);
+                    BalancePivotRow {
+                        account: account.clone(),
+                        commodity: commodity(comm),
+                        amounts,
+                        total,
+                    }
+                })
+                .collect(),
+            column_totals: rs.column_totals.then(|| {
+                pivot
+                    .column_totals
+                    .rows
+                    .iter()
+                    .map(|(comm, amounts)| {
+                        let (amounts, total) = fmt(amounts);
+                        BalancePivotTotal {
+                            commodity: commodity(comm),
+                            amounts,
+                            total,
+                        }
+                    })
+                    .collect()
+            }),
+        }
+    }
+
+    fn write_delimited<W: io::Write>(
+        &self,
+        writer: W,
+        delimiter: u8,
+        bal_groups: &[Balance],
+    ) -> Result<(), tackler::Error> {
+        let bal_settings: BalanceSettings = self.report_settings.clone().into();
+
+        let mut wtr = delimited_writer(writer, delimiter);
+        wtr.write_record(std::iter::once("group").chain(BalanceReporter::DELIMITED_HEADER))?;
+        for bal in bal_groups {
+            for r in BalanceReporter::delimited_records(bal, &bal_settings) {
+                wtr.write_record(std::iter::once(&bal.title).chain(&r))?;
+            }
+        }
+        wtr.flush()?;
+        Ok(())
+    }
+}
+
 impl Report for BalanceGroupReporter {
     fn write_reports<W: Write + ?Sized>(
         &self,
@@ -86,14 +264,23 @@ impl Report for BalanceGroupReporter {
             &cfg.price.price_db,
         );
 
-        let group_by_op = self.get_group_by_op();
+        let group_by_op = accumulator::group_by_op(
+            self.report_settings.group_by,
+            self.report_settings.report_tz.clone(),
+        );
         let bal_groups = accumulator::balance_groups(
             &txn_data.txns,
             &group_by_op,
             &price_lookup_ctx,
             acc_sel.as_ref(),
             cfg,
-        );
+            &self.report_settings.bal_type,
+            self.report_settings.depth,
+        )
+        .into_iter()
+        .map(|bal| bal.retain_reported(cfg))
+        .filter(|bal| !bal.is_empty())
+        .collect::<Vec<_>>();
 
         let mut metadata = match metadata {
             Some(md) => md.clone(),
@@ -118,6 +305,8 @@ impl Report for BalanceGroupReporter {
             metadata.push(credit);
         }
 
+        let pivot = self.report_settings.pivot.then(|| self.pivot(&bal_groups));
+
         for w in writers {
             match w {
                 FormatWriter::TxtFormat(writer) => {
@@ -128,9 +317,13 @@ impl Report for BalanceGroupReporter {
                     writeln!(writer, "{title}")?;
                     writeln!(writer, "{}", "-".repeat(title.chars().count()))?;
 
-                    let bal_settings = self.report_settings.clone().into();
-                    for bal in &bal_groups {
-                        BalanceReporter::txt_report(writer, bal, &bal_settings)?;
+                    if let Some(pivot) = &pivot {
+                        self.write_pivot_txt(writer, pivot)?;
+                    } else {
+                        let bal_settings = self.report_settings.clone().into();
+                        for bal in &bal_groups {
+                            BalanceReporter::txt_report(writer, bal, &bal_settings)?;
+                        }
                     }
                 }
                 FormatWriter::JsonFormat(writer) => {
@@ -140,11 +333,157 @@ impl Report for BalanceGroupReporter {
                         Some(&metadata)
                     };
 
-                    serde_json::to_writer_pretty(&mut *writer, &self.to_api(md, &bal_groups))?;
+                    if let Some(pivot) = &pivot {
+                        let api = self.to_pivot_api(md, pivot);
+                        serde_json::to_writer_pretty(&mut *writer, &api)?;
+                    } else {
+                        let api = self.to_api(md, &bal_groups);
+                        serde_json::to_writer_pretty(&mut *writer, &api)?;
+                    }
                     writeln!(writer)?;
                 }
+                FormatWriter::CsvFormat(writer) => match &pivot {
+                    Some(pivot) => self.write_pivot_delimited(writer, CSV_DELIMITER, pivot)?,
+                    None => self.write_delimited(writer, CSV_DELIMITER, &bal_groups)?,
+                },
+                FormatWriter::TsvFormat(writer) => match &pivot {
+                    Some(pivot) => self.write_pivot_delimited(writer, TSV_DELIMITER, pivot)?,
+                    None => self.write_delimited(writer, TSV_DELIMITER, &bal_groups)?,
+                },
             }
         }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+    use indoc::indoc;
+    use tackler_api::txn_ts::GroupBy;
+    use tackler_rs::IndocUtils;
+
+    #[rustfmt::skip]
+    const TXNS: &str = indoc!(
+       "|2024-01-05
+        | Expenses:Food  300 EUR
+        | Assets:Cash
+        |
+        |2024-01-20
+        | Expenses:Food:Lunch  150 EUR
+        | Expenses:Fun  10 EUR
+        | Assets:Cash
+        |
+        |2024-02-10
+        | Expenses:Food  240 EUR
+        | Assets:Cash
+        |"
+    );
+
+    fn reporter(settings: &Settings, bal_type: BalanceType) -> BalanceGroupReporter {
+        let mut report_settings = BalanceGroupSettings::try_from(settings).unwrap(/*:test:*/);
+        report_settings.title = "Expenses".to_string();
+        report_settings.ras = vec!["Expenses(:.*)?".to_string()];
+        report_settings.group_by = GroupBy::Month;
+        report_settings.bal_type = bal_type;
+        report_settings.pivot = true;
+        report_settings.column_totals = true;
+        BalanceGroupReporter { report_settings }
+    }
+
+    #[test]
+    // test: 5c7e9a1b-3d5f-4b7c-9e1a-3c5e7a9b1d3f
+    // desc: pivot table with groups as columns, row totals and column totals
+    fn pivot_txt() {
+        let settings = Settings::default();
+        let txns_str = TXNS.strip_margin();
+        let txn_data = parser::string_to_txns(&mut txns_str.as_str(), &settings).unwrap(/*:test:*/);
+        let txn_set = txn_data.get_all().unwrap(/*:test:*/);
+
+        let mut report = Vec::<u8>::new();
+        reporter(&settings, BalanceType::Tree)
+            .write_txt_report(&settings, &mut report, &txn_set)
+            .unwrap(/*:test:*/);
+
+        #[rustfmt::skip]
+        let reference = indoc!(
+           "|Report Time Zone
+            |        TZ name : UTC
+            |
+            |
+            |Expenses
+            |--------
+            |           2024-01            2024-02              total
+            |            460.00             240.00             700.00  EUR  Expenses
+            |            450.00             240.00             690.00  EUR  Expenses:Food
+            |            150.00               0.00             150.00  EUR  Expenses:Food:Lunch
+            |             10.00               0.00              10.00  EUR  Expenses:Fun
+            |-------------------------------------------------------------
+            |            460.00             240.00             700.00  EUR
+            |"
+        ).strip_margin();
+        assert_eq!(String::from_utf8(report).unwrap(/*:test:*/), reference);
+    }
+
+    #[test]
+    // test: 7e9a1c3d-5f7b-4d9e-a1c3-5e7a9c1d3f5b
+    // desc: pivot table as CSV, with flat balance
+    fn pivot_csv() {
+        let settings = Settings::default();
+        let txns_str = TXNS.strip_margin();
+        let txn_data = parser::string_to_txns(&mut txns_str.as_str(), &settings).unwrap(/*:test:*/);
+        let txn_set = txn_data.get_all().unwrap(/*:test:*/);
+
+        let mut report = Vec::<u8>::new();
+        let mut writers = vec![FormatWriter::CsvFormat(Box::new(&mut report))];
+        reporter(&settings, BalanceType::Flat)
+            .write_reports::<dyn io::Write>(&settings, &mut writers, None, &txn_set)
+            .unwrap(/*:test:*/);
+        drop(writers);
+
+        #[rustfmt::skip]
+        let reference = indoc!(
+           "|account,commodity,2024-01,2024-02,total
+            |Expenses:Food,EUR,300,240,540
+            |Expenses:Food:Lunch,EUR,150,0,150
+            |Expenses:Fun,EUR,10,0,10
+            |,EUR,460,240,700
+            |"
+        ).strip_margin();
+        assert_eq!(String::from_utf8(report).unwrap(/*:test:*/), reference);
+    }
+
+    #[test]
+    // test: 4d6f8b0c-2e4a-4c6d-8f0b-2c4e6a8d0f1b
+    // desc: balance groups as CSV, with group column
+    fn groups_csv() {
+        let settings = Settings::default();
+        let txns_str = TXNS.strip_margin();
+        let txn_data = parser::string_to_txns(&mut txns_str.as_str(), &settings).unwrap(/*:test:*/);
+        let txn_set = txn_data.get_all().unwrap(/*:test:*/);
+
+        let mut reporter = reporter(&settings, BalanceType::Tree);
+        reporter.report_settings.pivot = false;
+
+        let mut report = Vec::<u8>::new();
+        let mut writers = vec![FormatWriter::CsvFormat(Box::new(&mut report))];
+        reporter
+            .write_reports::<dyn io::Write>(&settings, &mut writers, None, &txn_set)
+            .unwrap(/*:test:*/);
+        drop(writers);
+
+        #[rustfmt::skip]
+        let reference = indoc!(
+           "|group,account,commodity,account_sum,account_tree_sum,account_sum_value,account_tree_sum_value,value_commodity
+            |2024-01,Expenses,EUR,0,460,,,
+            |2024-01,Expenses:Food,EUR,300,450,,,
+            |2024-01,Expenses:Food:Lunch,EUR,150,150,,,
+            |2024-01,Expenses:Fun,EUR,10,10,,,
+            |2024-02,Expenses,EUR,0,240,,,
+            |2024-02,Expenses:Food,EUR,240,240,,,
+            |"
+        ).strip_margin();
+        assert_eq!(String::from_utf8(report).unwrap(/*:test:*/), reference);
+    }
+}
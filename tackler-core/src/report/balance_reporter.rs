@@ -5,23 +5,31 @@
 
 use crate::config::BalanceType;
 use crate::kernel::balance::{BTNs, Balance, Deltas};
+use crate::kernel::price_lookup::PriceLookupCtx;
 use crate::kernel::report_item_selector::{
     BalanceAllSelector, BalanceByAccountSelector, BalanceSelector,
 };
 use crate::kernel::{BalanceSettings, Settings};
 use crate::math::format::format_with_scale;
-use crate::model::{BalanceTreeNode, TxnSet};
-use crate::report::{FormatWriter, Report, report_timezone};
+use crate::model::balance_tree_node::InventoryNode;
+use crate::model::{BalanceTreeNode, Commodity, TxnSet};
+use crate::report::{
+    CSV_DELIMITER, FormatWriter, Report, TSV_DELIMITER, delimited_writer, report_timezone,
+};
 use crate::tackler;
 use crate::tackler::Error;
+use itertools::Itertools;
 use rust_decimal::Decimal;
 use rust_decimal::prelude::Zero;
 use std::cmp::max;
+use std::fmt::Write as _;
 use std::io;
 use std::io::Write;
 use tackler_api::metadata::Metadata;
 use tackler_api::metadata::items::{CreditAccountReport, MetadataItem};
-use tackler_api::reports::balance_report::{BalanceItem, BalanceReport, Delta};
+use tackler_api::reports::balance_report::{
+    BalanceItem, BalanceReport, Delta, InventoryItem, InventoryPosition,
+};
 
 #[derive(Debug, Clone)]
 pub struct BalanceReporter {
@@ -55,6 +63,99 @@ impl BalanceReporter {
 }
 
 impl BalanceReporter {
+    /// Format position of inventory: `amount commodity [(value commodity)]`
+    ///
+    /// Value is printed only if balance is valued, and missing value is `n/a`.
+    fn fmt_position(
+        amount: &Decimal,
+        comm: &Commodity,
+        value: Option<Decimal>,
+        bal_report: &Balance,
+        bal_settings: &BalanceSettings,
+    ) -> String {
+        let scale = &bal_settings.scale;
+        let mut pos = format_with_scale(0, amount, scale);
+        if comm.is_any() {
+            let _ = write!(pos, " {}", comm.name);
+        }
+        if let Some(value_comm) = &bal_report.value_commodity {
+            let _ = write!(
+                pos,
+                " ({} {})",
+                value.map_or("n/a".to_string(), |v| format_with_scale(0, &v, scale)),
+                value_comm.name
+            );
+        }
+        pos
+    }
+
+    fn inventory_txt_report<W: io::Write + ?Sized>(
+        writer: &mut W,
+        bal_report: &Balance,
+        bal_settings: &BalanceSettings,
+    ) -> Result<(), tackler::Error> {
+        let inventory = bal_report.inventory();
+        let left_ruler = " ".repeat(9);
+
+        writeln!(writer, "{}", bal_report.title)?;
+        writeln!(writer, "{}", "-".repeat(bal_report.title.chars().count()))?;
+
+        if inventory.is_empty() {
+            return Ok(());
+        }
+
+        let acc_max_len = inventory
+            .iter()
+            .map(|inv| inv.atn.account.chars().count())
+            .fold(0, max);
+
+        for inv in &inventory {
+            let positions = inv
+                .positions
+                .iter()
+                .map(|btn| {
+                    let (amount, value) = match bal_settings.bal_type {
+                        BalanceType::Tree => (
+                            &btn.sub_acc_tree_sum,
+                            btn.value.as_ref().and_then(|v| v.sub_acc_tree_sum),
+                        ),
+                        BalanceType::Flat => (
+                            &btn.account_sum,
+                            btn.value.as_ref().and_then(|v| v.account_sum),
+                        ),
+                    };
+                    Self::fmt_position(amount, &btn.acctn.comm, value, bal_report, bal_settings)
+                })
+                .join(", ");
+
+            writeln!(
+                writer,
+                "{left_ruler}{:<acc_max_len$}  {positions}",
+                inv.atn.account
+            )?;
+        }
+
+        writeln!(
+            writer,
+            "{}",
+            "=".repeat(left_ruler.chars().count() + acc_max_len)
+        )?;
+
+        for (comm, delta) in &bal_report.deltas {
+            let value = bal_report.delta_values.get(comm).copied().flatten();
+            let comm = comm
+                .as_ref()
+                .map_or(Commodity::default(), |c| (**c).clone());
+            writeln!(
+                writer,
+                "{left_ruler}{:>acc_max_len$}  {}",
+                "",
+                Self::fmt_position(delta, &comm, value, bal_report, bal_settings)
+            )?;
+        }
+        Ok(())
+    }
+
     #[allow(clippy::too_many_lines)]
     pub(crate) fn txt_report<W: io::Write + ?Sized>(
         writer: &mut W,
@@ -220,6 +321,69 @@ impl BalanceReporter {
         Ok(())
     }
 
+    /// Column names of delimited (CSV, TSV) balance report
+    pub(crate) const DELIMITED_HEADER: [&'static str; 7] = [
+        "account",
+        "commodity",
+        "account_sum",
+        "account_tree_sum",
+        "account_sum_value",
+        "account_tree_sum_value",
+        "value_commodity",
+    ];
+
+    /// Delimited (CSV, TSV) records of balance report
+    ///
+    /// Sums are with full precision, and value fields are empty, if balance is not valued.
+    pub(crate) fn delimited_records(
+        bal_report: &Balance,
+        bal_settings: &BalanceSettings,
+    ) -> Vec<[String; 7]> {
+        let tree = matches!(bal_settings.bal_type, BalanceType::Tree);
+        let to_string = |d: Option<Decimal>| d.map(|d| d.to_string()).unwrap_or_default();
+
+        bal_report
+            .bal
+            .iter()
+            .map(|btn| {
+                let comm = &btn.acctn.comm;
+                let value = btn.value.as_ref();
+                [
+                    btn.acctn.atn.account.clone(),
+                    if comm.is_any() {
+                        comm.name.clone()
+                    } else {
+                        String::new()
+                    },
+                    btn.account_sum.to_string(),
+                    to_string(tree.then_some(btn.sub_acc_tree_sum)),
+                    to_string(value.and_then(|v| v.account_sum)),
+                    to_string(value.and_then(|v| v.sub_acc_tree_sum).filter(|_| tree)),
+                    bal_report
+                        .value_commodity
+                        .as_ref()
+                        .map(|c| c.name.clone())
+                        .unwrap_or_default(),
+                ]
+            })
+            .collect()
+    }
+
+    fn write_delimited<W: io::Write>(
+        writer: W,
+        delimiter: u8,
+        bal_report: &Balance,
+        bal_settings: &BalanceSettings,
+    ) -> Result<(), tackler::Error> {
+        let mut wtr = delimited_writer(writer, delimiter);
+        wtr.write_record(Self::DELIMITED_HEADER)?;
+        for r in Self::delimited_records(bal_report, bal_settings) {
+            wtr.write_record(&r)?;
+        }
+        wtr.flush()?;
+        Ok(())
+    }
+
     fn btn_to_api(btn: &BalanceTreeNode, report_settings: &BalanceSettings) -> BalanceItem {
         let acc_sum = match report_settings.bal_type {
             BalanceType::Tree => Some(format_with_scale(
@@ -241,17 +405,59 @@ impl BalanceReporter {
         }
     }
 
+    fn inventory_to_api(
+        inv: &InventoryNode<'_>,
+        report_settings: &BalanceSettings,
+    ) -> InventoryItem {
+        let scale = &report_settings.scale;
+        let tree = matches!(report_settings.bal_type, BalanceType::Tree);
+        InventoryItem {
+            account: inv.atn.account.clone(),
+            positions: inv
+                .positions
+                .iter()
+                .map(|btn| InventoryPosition {
+                    account_sum: format_with_scale(0, &btn.account_sum, scale),
+                    account_tree_sum: tree
+                        .then(|| format_with_scale(0, &btn.sub_acc_tree_sum, scale)),
+                    commodity: btn.acctn.comm.is_any().then(|| btn.acctn.comm.name.clone()),
+                    account_sum_value: btn
+                        .value
+                        .as_ref()
+                        .and_then(|v| v.account_sum)
+                        .map(|v| format_with_scale(0, &v, scale)),
+                    account_tree_sum_value: btn
+                        .value
+                        .as_ref()
+                        .and_then(|v| v.sub_acc_tree_sum)
+                        .filter(|_| tree)
+                        .map(|v| format_with_scale(0, &v, scale)),
+                })
+                .collect(),
+        }
+    }
+
     #[must_use]
     pub fn balance_to_api(
         metadata: Option<&Metadata>,
         bal: &Balance,
         report_settings: &BalanceSettings,
     ) -> BalanceReport {
-        let balances = bal
-            .bal
-            .iter()
-            .map(|btn| Self::btn_to_api(btn, report_settings))
-            .collect::<Vec<BalanceItem>>();
+        let (balances, inventory) = if report_settings.inventory {
+            let inventory = bal
+                .inventory()
+                .iter()
+                .map(|inv| Self::inventory_to_api(inv, report_settings))
+                .collect::<Vec<InventoryItem>>();
+            (Vec::new(), Some(inventory))
+        } else {
+            let balances = bal
+                .bal
+                .iter()
+                .map(|btn| Self::btn_to_api(btn, report_settings))
+                .collect::<Vec<BalanceItem>>();
+            (balances, None)
+        };
 
         let deltas = bal
             .deltas
@@ -259,6 +465,12 @@ impl BalanceReporter {
             .map(|(c, v)| Delta {
                 commodity: c.as_ref().map(|c| c.name.clone()),
                 delta: format_with_scale(0, v, &report_settings.scale),
+                value: bal
+                    .delta_values
+                    .get(c)
+                    .copied()
+                    .flatten()
+                    .map(|v| format_with_scale(0, &v, &report_settings.scale)),
             })
             .collect::<Vec<Delta>>();
 
@@ -266,6 +478,8 @@ impl BalanceReporter {
             metadata: metadata.cloned(),
             title: bal.title.clone(),
             balances,
+            inventory,
+            value_commodity: bal.value_commodity.as_ref().map(|c| c.name.clone()),
             deltas,
         }
     }
@@ -288,13 +502,37 @@ impl Report for BalanceReporter {
             self.report_settings.report_commodity.clone(),
             &cfg.price.price_db,
         );
-        let bal_report = Balance::from(
-            &self.report_settings.title,
-            txn_data,
-            &price_lookup_ctx,
-            acc_sel.as_ref(),
-            cfg,
-        )?;
+        let rs = &self.report_settings;
+        let bal_report = if rs.inventory {
+            // Inventory is not converted, prices are used only for values
+            Balance::from_iter(
+                &rs.title,
+                &txn_data.txns,
+                &PriceLookupCtx::default(),
+                acc_sel.as_ref(),
+                cfg,
+                rs.bal_type.clone(),
+                rs.depth,
+            )?
+            .retain_reported(cfg)
+            .with_values(
+                txn_data.txns.iter(),
+                &price_lookup_ctx,
+                rs.inverted,
+                rs.depth,
+            )
+        } else {
+            Balance::from_iter(
+                &rs.title,
+                &txn_data.txns,
+                &price_lookup_ctx,
+                acc_sel.as_ref(),
+                cfg,
+                rs.bal_type.clone(),
+                rs.depth,
+            )?
+            .retain_reported(cfg)
+        };
 
         let mut metadata = match metadata {
             Some(md) => md.clone(),
@@ -326,7 +564,15 @@ impl Report for BalanceReporter {
                         writeln!(writer, "{}\n", metadata.text(cfg.report.tz.clone()))?;
                     }
 
-                    BalanceReporter::txt_report(writer, &bal_report, &self.report_settings)?;
+                    if self.report_settings.inventory {
+                        BalanceReporter::inventory_txt_report(
+                            writer,
+                            &bal_report,
+                            &self.report_settings,
+                        )?;
+                    } else {
+                        BalanceReporter::txt_report(writer, &bal_report, &self.report_settings)?;
+                    }
                 }
                 FormatWriter::JsonFormat(writer) => {
                     let md = if metadata.is_empty() {
@@ -340,8 +586,69 @@ impl Report for BalanceReporter {
                     )?;
                     writeln!(writer)?;
                 }
+                FormatWriter::CsvFormat(writer) => {
+                    Self::write_delimited(
+                        writer,
+                        CSV_DELIMITER,
+                        &bal_report,
+                        &self.report_settings,
+                    )?;
+                }
+                FormatWriter::TsvFormat(writer) => {
+                    Self::write_delimited(
+                        writer,
+                        TSV_DELIMITER,
+                        &bal_report,
+                        &self.report_settings,
+                    )?;
+                }
             }
         }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+    use indoc::indoc;
+    use tackler_rs::IndocUtils;
+
+    #[test]
+    // test: 3b5d7f9a-1c3e-4a5b-8d7f-9a1c3e5b7d9f
+    // desc: balance as CSV, sums are with full precision and not scaled
+    fn balance_csv() {
+        let settings = Settings::default();
+        #[rustfmt::skip]
+        let txns_str = indoc!(
+           "|2024-01-05
+            | Expenses:Food  12.345678901 EUR
+            | Expenses:Food:Lunch  1.5 EUR
+            | Assets:Cash
+            |"
+        ).strip_margin();
+        let txn_data = parser::string_to_txns(&mut txns_str.as_str(), &settings).unwrap(/*:test:*/);
+        let txn_set = txn_data.get_all().unwrap(/*:test:*/);
+
+        let mut report = Vec::<u8>::new();
+        let mut writers = vec![FormatWriter::CsvFormat(Box::new(&mut report))];
+        BalanceReporter::try_from(&settings)
+            .unwrap(/*:test:*/)
+            .write_reports::<dyn io::Write>(&settings, &mut writers, None, &txn_set)
+            .unwrap(/*:test:*/);
+        drop(writers);
+
+        #[rustfmt::skip]
+        let reference = indoc!(
+           "|account,commodity,account_sum,account_tree_sum,account_sum_value,account_tree_sum_value,value_commodity
+            |Assets,EUR,0,-13.845678901,,,
+            |Assets:Cash,EUR,-13.845678901,-13.845678901,,,
+            |Expenses,EUR,0,13.845678901,,,
+            |Expenses:Food,EUR,12.345678901,13.845678901,,,
+            |Expenses:Food:Lunch,EUR,1.5,1.5,,,
+            |"
+        ).strip_margin();
+        assert_eq!(String::from_utf8(report).unwrap(/*:test:*/), reference);
+    }
+}
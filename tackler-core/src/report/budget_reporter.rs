@@ -0,0 +1,386 @@
+/*
+ * Tackler-NG 2026
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use crate::config::{BalanceType, BudgetItem};
+use crate::kernel::Settings;
+use crate::kernel::accumulator;
+use crate::kernel::balance::Balance;
+use crate::kernel::report_item_selector::BalanceAllSelector;
+use crate::kernel::report_settings::BudgetSettings;
+use crate::math::format::format_with_scale;
+use crate::model::{Commodity, TxnSet};
+use crate::report::{
+    CSV_DELIMITER, FormatWriter, Report, TSV_DELIMITER, delimited_writer, report_timezone,
+};
+use crate::tackler;
+use rust_decimal::{Decimal, RoundingStrategy};
+use std::collections::BTreeSet;
+use std::io;
+use std::io::Write;
+use std::sync::Arc;
+use tackler_api::metadata::Metadata;
+use tackler_api::metadata::items::{CreditAccountReport, MetadataItem};
+use tackler_api::reports::budget_report;
+use tackler_api::reports::budget_report::{BudgetPeriod, BudgetReport};
+
+#[derive(Debug, Clone)]
+pub struct BudgetReporter {
+    pub report_settings: BudgetSettings,
+}
+
+/// Budget and actual amount of a budget item for a single period
+#[derive(Debug)]
+struct BudgetRow<'a> {
+    item: &'a BudgetItem,
+    commodity: Option<Arc<Commodity>>,
+    budget: Decimal,
+    actual: Decimal,
+}
+
+impl BudgetRow<'_> {
+    fn variance(&self) -> Decimal {
+        self.actual - self.budget
+    }
+
+    /// Actual amount as percent of the budget, if budget is not zero
+    fn percent(&self) -> Option<Decimal> {
+        self.actual
+            .checked_mul(Decimal::ONE_HUNDRED)
+            .and_then(|a| a.checked_div(self.budget))
+            .map(|p| p.round_dp_with_strategy(2, RoundingStrategy::MidpointAwayFromZero))
+    }
+
+    fn commodity_name(&self) -> &str {
+        self.commodity.as_ref().map_or("", |c| c.name.as_str())
+    }
+}
+
+type BudgetPeriods<'a> = Vec<(String, Vec<BudgetRow<'a>>)>;
+
+impl BudgetReporter {
+    /// Combine budget items with actual balances of the periods
+    ///
+    /// Periods are all periods with transactions, and all periods
+    /// which are explicitly listed in the budget.
+    /// Actual amount of an account includes all of its sub-accounts.
+    fn budget_periods<'a>(&'a self, bal_groups: &[Balance]) -> BudgetPeriods<'a> {
+        let rs = &self.report_settings;
+
+        let periods: BTreeSet<&str> = bal_groups
+            .iter()
+            .map(|bal| bal.title.as_str())
+            .chain(
+                rs.items
+                    .iter()
+                    .flat_map(|item| item.periods.keys().map(String::as_str)),
+            )
+            .collect();
+
+        periods
+            .into_iter()
+            .map(|period| {
+                let bal = bal_groups.iter().find(|bal| bal.title == period);
+                let rows: Vec<_> = rs
+                    .items
+                    .iter()
+                    .filter_map(|item| {
+                        let budget = item.amount(period)?;
+                        let commodity = item
+                            .commodity
+                            .clone()
+                            .or_else(|| rs.report_commodity.clone());
+                        let comm_name = commodity.as_ref().map_or("", |c| c.name.as_str());
+                        let actual = bal
+                            .and_then(|bal| {
+                                bal.bal.iter().find(|btn| {
+                                    btn.acctn.atn.account == item.account
+                                        && btn.acctn.comm.name == comm_name
+                                })
+                            })
+                            .map_or(Decimal::ZERO, |btn| btn.sub_acc_tree_sum);
+                        Some(BudgetRow {
+                            item,
+                            commodity,
+                            budget,
+                            actual,
+                        })
+                    })
+                    .collect();
+                (period.to_string(), rows)
+            })
+            .filter(|(_, rows)| !rows.is_empty())
+            .collect()
+    }
+
+    fn write_txt<W: io::Write + ?Sized>(
+        &self,
+        writer: &mut W,
+        periods: &BudgetPeriods<'_>,
+    ) -> Result<(), tackler::Error> {
+        let rs = &self.report_settings;
+        let scale = &rs.scale;
+
+        let title = &rs.title;
+        writeln!(writer, "{title}")?;
+        writeln!(writer, "{}", "-".repeat(title.chars().count()))?;
+
+        let comm_max_len = periods
+            .iter()
+            .flat_map(|(_, rows)| rows.iter().map(|r| r.commodity_name().chars().count()))
+            .max()
+            .unwrap_or(0);
+
+        for (period, rows) in periods {
+            writeln!(writer, "{period}")?;
+            writeln!(
+                writer,
+                "{:>18} {:>18} {:>18} {:>9}",
+                "budget", "actual", "variance", "%"
+            )?;
+            for r in rows {
+                let comm = if comm_max_len == 0 {
+                    String::new()
+                } else {
+                    format!("{: <cl$}  ", r.commodity_name(), cl = comm_max_len)
+                };
+                writeln!(
+                    writer,
+                    "{:>18} {:>18} {:>18} {:>9}  {comm}{}",
+                    format_with_scale(0, &r.budget, scale),
+                    format_with_scale(0, &r.actual, scale),
+                    format_with_scale(0, &r.variance(), scale),
+                    r.percent().map_or("n/a".to_string(), |p| format!("{p:.2}")),
+                    r.item.account,
+                )?;
+            }
+            writeln!(writer)?;
+        }
+        Ok(())
+    }
+
+    /// Write Budget report as delimited (CSV, TSV) report
+    ///
+    /// There is one record per budget item and period,
+    /// and `percent` is empty, if budget is zero.
+    fn write_delimited<W: io::Write>(
+        writer: W,
+        delimiter: u8,
+        periods: &BudgetPeriods<'_>,
+    ) -> Result<(), tackler::Error> {
+        let mut wtr = delimited_writer(writer, delimiter);
+        wtr.write_record([
+            "period",
+            "account",
+            "commodity",
+            "budget",
+            "actual",
+            "variance",
+            "percent",
+        ])?;
+        for (period, rows) in periods {
+            for r in rows {
+                wtr.write_record([
+                    period.clone(),
+                    r.item.account.clone(),
+                    r.commodity_name().to_string(),
+                    r.budget.to_string(),
+                    r.actual.to_string(),
+                    r.variance().to_string(),
+                    r.percent().map(|p| p.to_string()).unwrap_or_default(),
+                ])?;
+            }
+        }
+        wtr.flush()?;
+        Ok(())
+    }
+
+    fn to_api(&self, metadata: Metadata, periods: &BudgetPeriods<'_>) -> BudgetReport {
+        let rs = &self.report_settings;
+        let scale = &rs.scale;
+
+        BudgetReport {
+            metadata: Some(metadata),
+            title: rs.title.clone(),
+            periods: periods
+                .iter()
+                .map(|(period, rows)| BudgetPeriod {
+                    period: period.clone(),
+                    items: rows
+                        .iter()
+                        .map(|r| budget_report::BudgetItem {
+                            account: r.item.account.clone(),
+                            commodity: r.commodity.as_ref().map(|c| c.name.clone()),
+                            budget: format_with_scale(0, &r.budget, scale),
+                            actual: format_with_scale(0, &r.actual, scale),
+                            variance: format_with_scale(0, &r.variance(), scale),
+                            percent: r.percent().map(|p| format!("{p:.2}")),
+                        })
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Report for BudgetReporter {
+    fn write_reports<W: io::Write + ?Sized>(
+        &self,
+        cfg: &Settings,
+        writers: &mut Vec<FormatWriter<'_>>,
+        metadata: Option<&Metadata>,
+        txn_data: &TxnSet<'_>,
+    ) -> Result<(), tackler::Error> {
+        let rs = &self.report_settings;
+
+        let price_lookup_ctx = rs.price_lookup.make_ctx(
+            &txn_data.txns,
+            rs.report_commodity.clone(),
+            &cfg.price.price_db,
+        );
+
+        let group_by_op = accumulator::group_by_op(rs.group_by, rs.report_tz.clone());
+        let bal_groups = accumulator::balance_groups(
+            &txn_data.txns,
+            &group_by_op,
+            &price_lookup_ctx,
+            &BalanceAllSelector::default(),
+            cfg,
+            &BalanceType::Tree,
+            None,
+        );
+
+        let periods = self.budget_periods(&bal_groups);
+
+        let mut metadata = match metadata {
+            Some(md) => md.clone(),
+            None => Metadata::default(),
+        };
+
+        let rtz = MetadataItem::TimeZoneInfo(report_timezone(cfg)?);
+        metadata.push(rtz);
+
+        if !price_lookup_ctx.is_empty() {
+            let pr = MetadataItem::PriceRecords(price_lookup_ctx.metadata());
+            metadata.push(pr);
+        }
+
+        if rs.inverted {
+            let credit = MetadataItem::CreditAccountReport(CreditAccountReport {});
+            metadata.push(credit);
+        }
+
+        for w in writers {
+            match w {
+                FormatWriter::TxtFormat(writer) => {
+                    // There is always at least TimeZoneInfo
+                    writeln!(writer, "{}\n", metadata.text(cfg.report.tz.clone()))?;
+                    self.write_txt(writer, &periods)?;
+                }
+                FormatWriter::JsonFormat(writer) => {
+                    let budget_report = self.to_api(metadata.clone(), &periods);
+                    serde_json::to_writer_pretty(&mut *writer, &budget_report)?;
+                    writeln!(writer)?;
+                }
+                FormatWriter::CsvFormat(writer) => {
+                    Self::write_delimited(writer, CSV_DELIMITER, &periods)?;
+                }
+                FormatWriter::TsvFormat(writer) => {
+                    Self::write_delimited(writer, TSV_DELIMITER, &periods)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+    use indoc::indoc;
+    use std::collections::BTreeMap;
+    use tackler_rs::IndocUtils;
+
+    #[test]
+    // test: 2f6d8a1c-5b3e-4c7d-9e0a-1b2c3d4e5f60
+    // desc: budget vs. actual per period, actual includes sub-accounts
+    fn budget_vs_actual() {
+        let settings = Settings::default();
+        #[rustfmt::skip]
+        let txns_str = indoc!(
+           "|2024-01-05
+            | Expenses:Food  300 EUR
+            | Assets:Cash
+            |
+            |2024-01-20
+            | Expenses:Food:Lunch  150 EUR
+            | Expenses:Fun  10 EUR
+            | Assets:Cash
+            |
+            |2024-02-10
+            | Expenses:Food  240 EUR
+            | Assets:Cash
+            |
+            |2024-02-11
+            | Expenses:Food  5 USD
+            | Assets:Cash
+            |"
+        ).strip_margin();
+        let txn_data = parser::string_to_txns(&mut txns_str.as_str(), &settings).unwrap(/*:test:*/);
+        let txn_set = txn_data.get_all().unwrap(/*:test:*/);
+
+        let eur = settings.get_commodity("EUR").unwrap(/*:test:*/);
+        let mut report_settings = BudgetSettings::try_from(&settings).unwrap(/*:test:*/);
+        report_settings.title = "Budget".to_string();
+        report_settings.items = vec![
+            BudgetItem {
+                account: "Expenses:Food".to_string(),
+                commodity: Some(eur.clone()),
+                amount: Some(Decimal::from(400)),
+                periods: BTreeMap::from([
+                    ("2024-02".to_string(), Decimal::from(300)),
+                    ("2024-03".to_string(), Decimal::from(500)),
+                ]),
+            },
+            BudgetItem {
+                account: "Expenses:Fun".to_string(),
+                commodity: Some(eur),
+                amount: None,
+                periods: BTreeMap::from([("2024-01".to_string(), Decimal::ZERO)]),
+            },
+        ];
+        let reporter = BudgetReporter { report_settings };
+
+        let mut report = Vec::<u8>::new();
+        reporter
+            .write_txt_report(&settings, &mut report, &txn_set)
+            .unwrap(/*:test:*/);
+
+        #[rustfmt::skip]
+        let reference = indoc!(
+           "|Report Time Zone
+            |        TZ name : UTC
+            |
+            |
+            |Budget
+            |------
+            |2024-01
+            |            budget             actual           variance         %
+            |            400.00             450.00              50.00    112.50  EUR  Expenses:Food
+            |              0.00              10.00              10.00       n/a  EUR  Expenses:Fun
+            |
+            |2024-02
+            |            budget             actual           variance         %
+            |            300.00             240.00             -60.00     80.00  EUR  Expenses:Food
+            |
+            |2024-03
+            |            budget             actual           variance         %
+            |            500.00               0.00            -500.00      0.00  EUR  Expenses:Food
+            |
+            |"
+        ).strip_margin();
+        assert_eq!(String::from_utf8(report).unwrap(/*:test:*/), reference);
+    }
+}
@@ -0,0 +1,355 @@
+/*
+ * Tackler-NG 2026
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use crate::kernel::Settings;
+use crate::kernel::price_lookup::PriceLookupCtx;
+use crate::kernel::report_settings::CashFlowSettings;
+use crate::model::{TxnRefs, TxnSet};
+use crate::report::period_table::{AccountTable, TotalTable, TxtColumns, period_op};
+use crate::report::{
+    CSV_DELIMITER, FormatWriter, Report, TSV_DELIMITER, delimited_writer, report_timezone,
+};
+use crate::tackler;
+use itertools::Itertools;
+use regex::RegexSet;
+use rust_decimal::Decimal;
+use std::collections::BTreeMap;
+use std::io;
+use std::io::Write;
+use tackler_api::metadata::Metadata;
+use tackler_api::metadata::items::MetadataItem;
+use tackler_api::reports::cash_flow_report::CashFlowReport;
+use tackler_rs::regex::new_full_haystack_regex_set;
+
+#[derive(Debug, Clone)]
+pub struct CashFlowReporter {
+    pub report_settings: CashFlowSettings,
+}
+
+#[derive(Debug)]
+struct CashFlow {
+    periods: Vec<String>,
+    counterparts: AccountTable,
+    inflows: TotalTable,
+    outflows: TotalTable,
+    net_change: TotalTable,
+}
+
+impl CashFlowReporter {
+    /// Collect cash movements of periods
+    ///
+    /// Cash movement of a transaction is the sum of its cash postings,
+    /// and it is inflow or outflow by commodity. Counterpart amount is
+    /// the negated amount of a non-cash posting of the same transaction,
+    /// so transfers between cash accounts don't have any counterparts.
+    fn cash_flow(
+        &self,
+        txns: &TxnRefs<'_>,
+        price_lookup_ctx: &PriceLookupCtx<'_>,
+    ) -> Result<CashFlow, tackler::Error> {
+        let rs = &self.report_settings;
+
+        let cash_accounts: RegexSet = new_full_haystack_regex_set(&rs.cash_accounts)?;
+        let group_by_op = period_op(rs.group_by, rs.report_tz.clone());
+
+        let cash_txns: Vec<_> = txns
+            .iter()
+            .filter(|txn| {
+                txn.posts
+                    .iter()
+                    .any(|p| cash_accounts.is_match(&p.acctn.atn.account))
+            })
+            .map(|txn| (group_by_op(txn), txn))
+            .collect();
+
+        let periods: Vec<String> = cash_txns
+            .iter()
+            .map(|(period, _)| period.clone())
+            .sorted()
+            .dedup()
+            .collect();
+
+        let mut cf = CashFlow {
+            counterparts: AccountTable::new(periods.len()),
+            inflows: TotalTable::new(periods.len()),
+            outflows: TotalTable::new(periods.len()),
+            net_change: TotalTable::new(periods.len()),
+            periods,
+        };
+
+        for (period, txn) in cash_txns {
+            // unwrap: ok: periods are collected from these transactions
+            let i = cf.periods.binary_search(&period).unwrap(/*:ok:*/);
+
+            let mut cash = BTreeMap::<String, Decimal>::new();
+            for (acctn, amount, _) in price_lookup_ctx.convert_prices(txn) {
+                let account = &acctn.atn.account;
+                let comm = acctn.comm.name.clone();
+                if cash_accounts.is_match(account) {
+                    *cash.entry(comm).or_default() += amount;
+                } else {
+                    let account = match rs.depth {
+                        Some(depth) => account.split(':').take(depth).join(":"),
+                        None => account.clone(),
+                    };
+                    cf.counterparts.add((account, comm), i, -amount);
+                }
+            }
+            for (comm, amount) in cash {
+                if amount.is_sign_positive() {
+                    cf.inflows.add(comm.clone(), i, amount);
+                    cf.outflows.add(comm.clone(), i, Decimal::ZERO);
+                } else {
+                    cf.inflows.add(comm.clone(), i, Decimal::ZERO);
+                    cf.outflows.add(comm.clone(), i, amount);
+                }
+                cf.net_change.add(comm, i, amount);
+            }
+        }
+        Ok(cf)
+    }
+
+    fn write_txt<W: io::Write + ?Sized>(
+        &self,
+        writer: &mut W,
+        cf: &CashFlow,
+    ) -> Result<(), tackler::Error> {
+        let rs = &self.report_settings;
+
+        let title = &rs.title;
+        writeln!(writer, "{title}")?;
+        writeln!(writer, "{}", "-".repeat(title.chars().count()))?;
+
+        let comm_len = cf
+            .counterparts
+            .rows
+            .keys()
+            .map(|(_, comm)| comm)
+            .chain(cf.net_change.rows.keys())
+            .map(|comm| comm.chars().count())
+            .max()
+            .unwrap_or(0);
+        let cols = TxtColumns {
+            scale: &rs.scale,
+            comm_len,
+        };
+
+        TxtColumns::write_header(writer, &cf.periods)?;
+        for ((account, comm), amounts) in &cf.counterparts.rows {
+            cols.write_line(writer, amounts, comm, account)?;
+        }
+        writeln!(
+            writer,
+            "{}",
+            "-".repeat(cols.separator_len(cf.periods.len()))
+        )?;
+        for (title, totals) in [
+            ("inflows", &cf.inflows),
+            ("outflows", &cf.outflows),
+            ("net change", &cf.net_change),
+        ] {
+            for (comm, amounts) in &totals.rows {
+                cols.write_line(writer, amounts, comm, title)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Write Cash Flow Report as delimited (CSV, TSV) report
+    ///
+    /// There is one record per counterpart account, commodity and period.
+    /// Inflows, outflows and net change have an empty account.
+    fn write_delimited<W: io::Write>(
+        writer: W,
+        delimiter: u8,
+        cf: &CashFlow,
+    ) -> Result<(), tackler::Error> {
+        let mut wtr = delimited_writer(writer, delimiter);
+        wtr.write_record(["period", "section", "account", "commodity", "amount"])?;
+        for (i, period) in cf.periods.iter().enumerate() {
+            for ((account, comm), amounts) in &cf.counterparts.rows {
+                wtr.write_record([
+                    period,
+                    "counterpart",
+                    account,
+                    comm,
+                    &amounts[i].to_string(),
+                ])?;
+            }
+            for (section, totals) in [
+                ("inflows", &cf.inflows),
+                ("outflows", &cf.outflows),
+                ("net-change", &cf.net_change),
+            ] {
+                for (comm, amounts) in &totals.rows {
+                    wtr.write_record([period, section, "", comm, &amounts[i].to_string()])?;
+                }
+            }
+        }
+        wtr.flush()?;
+        Ok(())
+    }
+
+    fn to_api(&self, metadata: Metadata, cf: &CashFlow) -> CashFlowReport {
+        let rs = &self.report_settings;
+        let scale = &rs.scale;
+
+        CashFlowReport {
+            metadata: Some(metadata),
+            title: rs.title.clone(),
+            cash_accounts: rs.cash_accounts.clone(),
+            periods: cf.periods.clone(),
+            counterparts: cf.counterparts.to_api(scale),
+            inflows: cf.inflows.to_api(scale),
+            outflows: cf.outflows.to_api(scale),
+            net_change: cf.net_change.to_api(scale),
+        }
+    }
+}
+
+impl Report for CashFlowReporter {
+    fn write_reports<W: io::Write + ?Sized>(
+        &self,
+        cfg: &Settings,
+        writers: &mut Vec<FormatWriter<'_>>,
+        metadata: Option<&Metadata>,
+        txn_data: &TxnSet<'_>,
+    ) -> Result<(), tackler::Error> {
+        let rs = &self.report_settings;
+
+        let price_lookup_ctx = rs.price_lookup.make_ctx(
+            &txn_data.txns,
+            rs.report_commodity.clone(),
+            &cfg.price.price_db,
+        );
+
+        let cf = self.cash_flow(&txn_data.txns, &price_lookup_ctx)?;
+
+        let mut metadata = match metadata {
+            Some(md) => md.clone(),
+            None => Metadata::default(),
+        };
+
+        let rtz = MetadataItem::TimeZoneInfo(report_timezone(cfg)?);
+        metadata.push(rtz);
+
+        if !price_lookup_ctx.is_empty() {
+            let pr = MetadataItem::PriceRecords(price_lookup_ctx.metadata());
+            metadata.push(pr);
+        }
+
+        for w in writers {
+            match w {
+                FormatWriter::TxtFormat(writer) => {
+                    // There is always at least TimeZoneInfo
+                    writeln!(writer, "{}\n", metadata.text(cfg.report.tz.clone()))?;
+                    self.write_txt(writer, &cf)?;
+                }
+                FormatWriter::JsonFormat(writer) => {
+                    let report = self.to_api(metadata.clone(), &cf);
+                    serde_json::to_writer_pretty(&mut *writer, &report)?;
+                    writeln!(writer)?;
+                }
+                FormatWriter::CsvFormat(writer) => {
+                    Self::write_delimited(writer, CSV_DELIMITER, &cf)?;
+                }
+                FormatWriter::TsvFormat(writer) => {
+                    Self::write_delimited(writer, TSV_DELIMITER, &cf)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+    use indoc::indoc;
+    use tackler_api::txn_ts::GroupBy;
+    use tackler_rs::IndocUtils;
+
+    #[test]
+    // test: 8c0e2a4b-6d8f-4a1c-9e3b-5f7a9c1e3d5f
+    // desc: cash movements by counterpart, transfers between cash accounts are ignored
+    fn cash_flow_by_counterpart() {
+        let settings = Settings::default();
+        #[rustfmt::skip]
+        let txns_str = indoc!(
+           "|2024-01-05
+            | Expenses:Food  300 EUR
+            | Assets:Cash
+            |
+            |2024-01-20
+            | Expenses:Food:Lunch  150 EUR
+            | Assets:Bank
+            |
+            |2024-01-25
+            | Income:Salary  -1000 EUR
+            | Assets:Bank
+            |
+            |2024-02-01
+            | Assets:Cash  200 EUR
+            | Assets:Bank
+            |
+            |2024-02-10
+            | Expenses:Food  240 EUR
+            | Assets:Cash
+            |
+            |2024-02-11
+            | Expenses:Rent  500 EUR
+            | Liabilities:Card
+            |"
+        ).strip_margin();
+        let txn_data = parser::string_to_txns(&mut txns_str.as_str(), &settings).unwrap(/*:test:*/);
+        let txn_set = txn_data.get_all().unwrap(/*:test:*/);
+
+        let mut settings = settings;
+        settings.report.cash_flow.cash_accounts = vec!["Assets:(Cash|Bank)".to_string()];
+        let mut report_settings = CashFlowSettings::try_from(&settings).unwrap(/*:test:*/);
+        report_settings.depth = Some(2);
+        report_settings.group_by = Some(GroupBy::Month);
+        let reporter = CashFlowReporter { report_settings };
+
+        let mut report = Vec::<u8>::new();
+        reporter
+            .write_txt_report(&settings, &mut report, &txn_set)
+            .unwrap(/*:test:*/);
+
+        #[rustfmt::skip]
+        let reference = indoc!(
+           "|Report Time Zone
+            |        TZ name : UTC
+            |
+            |
+            |Cash Flow Report
+            |----------------
+            |           2024-01            2024-02
+            |           -450.00            -240.00  EUR  Expenses:Food
+            |           1000.00               0.00  EUR  Income:Salary
+            |------------------------------------------
+            |           1000.00               0.00  EUR  inflows
+            |           -450.00            -240.00  EUR  outflows
+            |            550.00            -240.00  EUR  net change
+            |"
+        ).strip_margin();
+        assert_eq!(String::from_utf8(report).unwrap(/*:test:*/), reference);
+    }
+
+    #[test]
+    // test: 0f2b4d6a-8c1e-4b3d-a5f7-9b1d3f5a7c9e
+    fn cash_flow_without_cash_accounts() {
+        let settings = Settings::default();
+        let res = CashFlowSettings::try_from(&settings);
+        assert!(res.is_err());
+        assert!(
+            res.err()
+                .unwrap(/*:test:*/)
+                .to_string()
+                .contains("report.cash-flow.cash-accounts")
+        );
+    }
+}
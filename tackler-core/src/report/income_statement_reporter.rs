@@ -0,0 +1,422 @@
+/*
+ * Tackler-NG 2026
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use crate::config::{AccountKind, BalanceType};
+use crate::kernel::Settings;
+use crate::kernel::accumulator;
+use crate::kernel::balance::Balance;
+use crate::kernel::report_item_selector::BalanceAllSelector;
+use crate::kernel::report_settings::IncomeStatementSettings;
+use crate::model::TxnSet;
+use crate::report::period_table::{AccountTable, TotalTable, TxtColumns, period_op};
+use crate::report::{
+    CSV_DELIMITER, FormatWriter, Report, TSV_DELIMITER, delimited_writer, report_timezone,
+};
+use crate::tackler;
+use std::io;
+use std::io::Write;
+use tackler_api::metadata::Metadata;
+use tackler_api::metadata::items::{CreditAccountReport, MetadataItem};
+use tackler_api::reports::income_statement_report::{IncomeStatementReport, StatementSection};
+
+#[derive(Debug, Clone)]
+pub struct IncomeStatementReporter {
+    pub report_settings: IncomeStatementSettings,
+}
+
+/// Revenues or expenses of Income Statement
+#[derive(Debug)]
+struct Section {
+    title: &'static str,
+    /// Delimited format name of the section
+    name: &'static str,
+    accounts: AccountTable,
+    totals: TotalTable,
+}
+
+#[derive(Debug)]
+struct Statement {
+    periods: Vec<String>,
+    revenues: Section,
+    expenses: Section,
+    net_income: TotalTable,
+}
+
+impl IncomeStatementReporter {
+    /// Collect section from tree balances of periods
+    ///
+    /// Amounts are shown on the natural side of the root account,
+    /// so credit root accounts are negated. If root account doesn't
+    /// have `kind`, then `default_kind` is used.
+    ///
+    /// Net income of the section is accumulated into `net_income`.
+    fn section(
+        &self,
+        cfg: &Settings,
+        (title, name): (&'static str, &'static str),
+        roots: &[String],
+        default_kind: AccountKind,
+        bal_groups: &[Balance],
+        net_income: &mut TotalTable,
+    ) -> Section {
+        let rs = &self.report_settings;
+
+        let mut accounts = AccountTable::new(bal_groups.len());
+        let mut totals = TotalTable::new(bal_groups.len());
+
+        for (period, bal) in bal_groups.iter().enumerate() {
+            for btn in &bal.bal {
+                let atn = &btn.acctn.atn;
+                let Some(root) = roots.iter().find(|root| {
+                    atn.account == **root
+                        || atn
+                            .account
+                            .strip_prefix(root.as_str())
+                            .is_some_and(|sub| sub.starts_with(':'))
+                }) else {
+                    continue;
+                };
+                let amount = match cfg.get_account_kind(root).unwrap_or(default_kind) {
+                    AccountKind::Debit => btn.sub_acc_tree_sum,
+                    AccountKind::Credit => -btn.sub_acc_tree_sum,
+                };
+                let comm = btn.acctn.comm.name.clone();
+
+                if atn.account == *root {
+                    net_income.add(comm.clone(), period, -btn.sub_acc_tree_sum);
+                    totals.add(comm, period, amount);
+                } else if rs.depth.is_none_or(|depth| atn.depth <= depth) {
+                    accounts.add((atn.account.clone(), comm), period, amount);
+                }
+            }
+        }
+        Section {
+            title,
+            name,
+            accounts,
+            totals,
+        }
+    }
+
+    fn statement(&self, cfg: &Settings, bal_groups: &[Balance]) -> Statement {
+        let rs = &self.report_settings;
+
+        let mut net_income = TotalTable::new(bal_groups.len());
+        let revenues = self.section(
+            cfg,
+            ("Revenues", "revenues"),
+            &rs.revenues,
+            AccountKind::Credit,
+            bal_groups,
+            &mut net_income,
+        );
+        let expenses = self.section(
+            cfg,
+            ("Expenses", "expenses"),
+            &rs.expenses,
+            AccountKind::Debit,
+            bal_groups,
+            &mut net_income,
+        );
+        Statement {
+            periods: bal_groups.iter().map(|bal| bal.title.clone()).collect(),
+            revenues,
+            expenses,
+            net_income,
+        }
+    }
+
+    fn write_txt<W: io::Write + ?Sized>(
+        &self,
+        writer: &mut W,
+        stmt: &Statement,
+    ) -> Result<(), tackler::Error> {
+        let rs = &self.report_settings;
+
+        let title = &rs.title;
+        writeln!(writer, "{title}")?;
+        writeln!(writer, "{}", "-".repeat(title.chars().count()))?;
+
+        let comm_len = stmt
+            .net_income
+            .rows
+            .keys()
+            .map(|comm| comm.chars().count())
+            .max()
+            .unwrap_or(0);
+        let cols = TxtColumns {
+            scale: &rs.scale,
+            comm_len,
+        };
+        let separator = "-".repeat(cols.separator_len(stmt.periods.len()));
+
+        TxtColumns::write_header(writer, &stmt.periods)?;
+        for section in [&stmt.revenues, &stmt.expenses] {
+            writeln!(writer, "{}", section.title)?;
+            for ((account, comm), amounts) in &section.accounts.rows {
+                cols.write_line(writer, amounts, comm, account)?;
+            }
+            writeln!(writer, "{separator}")?;
+            for (comm, amounts) in &section.totals.rows {
+                cols.write_line(writer, amounts, comm, "")?;
+            }
+            writeln!(writer)?;
+        }
+        writeln!(writer, "Net Income")?;
+        for (comm, amounts) in &stmt.net_income.rows {
+            cols.write_line(writer, amounts, comm, "")?;
+        }
+        Ok(())
+    }
+
+    /// Write Income Statement as delimited (CSV, TSV) report
+    ///
+    /// There is one record per account, commodity and period.
+    /// Totals of sections and net income have an empty account.
+    fn write_delimited<W: io::Write>(
+        writer: W,
+        delimiter: u8,
+        stmt: &Statement,
+    ) -> Result<(), tackler::Error> {
+        let mut wtr = delimited_writer(writer, delimiter);
+        wtr.write_record(["period", "section", "account", "commodity", "amount"])?;
+        for (i, period) in stmt.periods.iter().enumerate() {
+            for section in [&stmt.revenues, &stmt.expenses] {
+                let totals = section
+                    .totals
+                    .rows
+                    .iter()
+                    .map(|(comm, amounts)| (("", comm.as_str()), amounts));
+                for ((account, comm), amounts) in section
+                    .accounts
+                    .rows
+                    .iter()
+                    .map(|((account, comm), amounts)| ((account.as_str(), comm.as_str()), amounts))
+                    .chain(totals)
+                {
+                    wtr.write_record([
+                        period,
+                        section.name,
+                        account,
+                        comm,
+                        &amounts[i].to_string(),
+                    ])?;
+                }
+            }
+            for (comm, amounts) in &stmt.net_income.rows {
+                wtr.write_record([period, "net-income", "", comm, &amounts[i].to_string()])?;
+            }
+        }
+        wtr.flush()?;
+        Ok(())
+    }
+
+    fn to_api(&self, metadata: Metadata, stmt: &Statement) -> IncomeStatementReport {
+        let rs = &self.report_settings;
+        let scale = &rs.scale;
+
+        let section = |s: &Section| StatementSection {
+            title: s.title.to_string(),
+            accounts: s.accounts.to_api(scale),
+            totals: s.totals.to_api(scale),
+        };
+
+        IncomeStatementReport {
+            metadata: Some(metadata),
+            title: rs.title.clone(),
+            periods: stmt.periods.clone(),
+            revenues: section(&stmt.revenues),
+            expenses: section(&stmt.expenses),
+            net_income: stmt.net_income.to_api(scale),
+        }
+    }
+}
+
+impl Report for IncomeStatementReporter {
+    fn write_reports<W: io::Write + ?Sized>(
+        &self,
+        cfg: &Settings,
+        writers: &mut Vec<FormatWriter<'_>>,
+        metadata: Option<&Metadata>,
+        txn_data: &TxnSet<'_>,
+    ) -> Result<(), tackler::Error> {
+        let rs = &self.report_settings;
+
+        let price_lookup_ctx = rs.price_lookup.make_ctx(
+            &txn_data.txns,
+            rs.report_commodity.clone(),
+            &cfg.price.price_db,
+        );
+
+        let group_by_op = period_op(rs.group_by, rs.report_tz.clone());
+        let bal_groups = accumulator::balance_groups(
+            &txn_data.txns,
+            &group_by_op,
+            &price_lookup_ctx,
+            &BalanceAllSelector::default(),
+            cfg,
+            &BalanceType::Tree,
+            None,
+        );
+
+        let stmt = self.statement(cfg, &bal_groups);
+
+        let mut metadata = match metadata {
+            Some(md) => md.clone(),
+            None => Metadata::default(),
+        };
+
+        let rtz = MetadataItem::TimeZoneInfo(report_timezone(cfg)?);
+        metadata.push(rtz);
+
+        if !price_lookup_ctx.is_empty() {
+            let pr = MetadataItem::PriceRecords(price_lookup_ctx.metadata());
+            metadata.push(pr);
+        }
+
+        if rs.inverted {
+            let credit = MetadataItem::CreditAccountReport(CreditAccountReport {});
+            metadata.push(credit);
+        }
+
+        for w in writers {
+            match w {
+                FormatWriter::TxtFormat(writer) => {
+                    // There is always at least TimeZoneInfo
+                    writeln!(writer, "{}\n", metadata.text(cfg.report.tz.clone()))?;
+                    self.write_txt(writer, &stmt)?;
+                }
+                FormatWriter::JsonFormat(writer) => {
+                    let report = self.to_api(metadata.clone(), &stmt);
+                    serde_json::to_writer_pretty(&mut *writer, &report)?;
+                    writeln!(writer)?;
+                }
+                FormatWriter::CsvFormat(writer) => {
+                    Self::write_delimited(writer, CSV_DELIMITER, &stmt)?;
+                }
+                FormatWriter::TsvFormat(writer) => {
+                    Self::write_delimited(writer, TSV_DELIMITER, &stmt)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+    use indoc::indoc;
+    use tackler_api::txn_ts::GroupBy;
+    use tackler_rs::IndocUtils;
+
+    #[rustfmt::skip]
+    const TXNS: &str = indoc!(
+       "|2024-01-05
+        | Expenses:Food  300 EUR
+        | Assets:Cash
+        |
+        |2024-01-20
+        | Expenses:Food:Lunch  150 EUR
+        | Expenses:Fun  10 EUR
+        | Assets:Cash
+        |
+        |2024-01-25
+        | Income:Salary  -1000 EUR
+        | Assets:Bank
+        |
+        |2024-02-10
+        | Expenses:Food  240 EUR
+        | Assets:Cash
+        |
+        |2024-02-25
+        | Income:Salary  -1000 EUR
+        | Income:Bonus  -200 EUR
+        | Assets:Bank
+        |"
+    );
+
+    #[test]
+    // test: 6a1c3e5f-7b9d-4f2a-8c4e-0d2f4a6c8e1b
+    // desc: income statement with comparison periods and depth
+    fn income_statement_periods() {
+        let settings = Settings::default();
+        let txns_str = TXNS.strip_margin();
+        let txn_data = parser::string_to_txns(&mut txns_str.as_str(), &settings).unwrap(/*:test:*/);
+        let txn_set = txn_data.get_all().unwrap(/*:test:*/);
+
+        let mut report_settings = IncomeStatementSettings::try_from(&settings).unwrap(/*:test:*/);
+        report_settings.depth = Some(2);
+        report_settings.group_by = Some(GroupBy::Month);
+        let reporter = IncomeStatementReporter { report_settings };
+
+        let mut report = Vec::<u8>::new();
+        reporter
+            .write_txt_report(&settings, &mut report, &txn_set)
+            .unwrap(/*:test:*/);
+
+        #[rustfmt::skip]
+        let reference = indoc!(
+           "|Report Time Zone
+            |        TZ name : UTC
+            |
+            |
+            |Income Statement
+            |----------------
+            |           2024-01            2024-02
+            |Revenues
+            |              0.00             200.00  EUR  Income:Bonus
+            |           1000.00            1000.00  EUR  Income:Salary
+            |------------------------------------------
+            |           1000.00            1200.00  EUR
+            |
+            |Expenses
+            |            450.00             240.00  EUR  Expenses:Food
+            |             10.00               0.00  EUR  Expenses:Fun
+            |------------------------------------------
+            |            460.00             240.00  EUR
+            |
+            |Net Income
+            |            540.00             960.00  EUR
+            |"
+        ).strip_margin();
+        assert_eq!(String::from_utf8(report).unwrap(/*:test:*/), reference);
+    }
+
+    #[test]
+    // test: 2e4a6c8e-0b1d-4f3a-9c5e-7a9c1e3f5b7d
+    // desc: income statement as CSV, with a single period
+    fn income_statement_csv() {
+        let settings = Settings::default();
+        let txns_str = TXNS.strip_margin();
+        let txn_data = parser::string_to_txns(&mut txns_str.as_str(), &settings).unwrap(/*:test:*/);
+        let txn_set = txn_data.get_all().unwrap(/*:test:*/);
+
+        let mut report_settings = IncomeStatementSettings::try_from(&settings).unwrap(/*:test:*/);
+        report_settings.expenses = vec!["Expenses:Food".to_string()];
+        let reporter = IncomeStatementReporter { report_settings };
+
+        let mut report = Vec::<u8>::new();
+        let mut writers = vec![FormatWriter::CsvFormat(Box::new(&mut report))];
+        reporter
+            .write_reports::<dyn io::Write>(&settings, &mut writers, None, &txn_set)
+            .unwrap(/*:test:*/);
+        drop(writers);
+
+        #[rustfmt::skip]
+        let reference = indoc!(
+           "|period,section,account,commodity,amount
+            |total,revenues,Income:Bonus,EUR,200
+            |total,revenues,Income:Salary,EUR,2000
+            |total,revenues,,EUR,2200
+            |total,expenses,Expenses:Food:Lunch,EUR,150
+            |total,expenses,,EUR,690
+            |total,net-income,,EUR,1510
+            |"
+        ).strip_margin();
+        assert_eq!(String::from_utf8(report).unwrap(/*:test:*/), reference);
+    }
+}
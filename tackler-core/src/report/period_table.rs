@@ -0,0 +1,149 @@
+/*
+ * Tackler-NG 2026
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use crate::config::Scale;
+use crate::kernel::accumulator;
+use crate::kernel::accumulator::TxnGroupByOp;
+use crate::math::format::format_with_scale;
+use crate::model::Transaction;
+use crate::tackler;
+use itertools::Itertools;
+use jiff::tz::TimeZone;
+use rust_decimal::Decimal;
+use std::collections::BTreeMap;
+use std::io;
+use tackler_api::reports::income_statement_report::{StatementRow, StatementTotal};
+use tackler_api::txn_ts::GroupBy;
+
+/// Name of the single period, if report is not grouped
+pub(super) const TOTAL_PERIOD: &str = "total";
+
+/// Group-by operation for period columns
+pub(super) fn period_op<'a>(group_by: Option<GroupBy>, tz: TimeZone) -> TxnGroupByOp<'a> {
+    match group_by {
+        Some(group_by) => accumulator::group_by_op(group_by, tz),
+        None => Box::new(|_: &Transaction| TOTAL_PERIOD.to_string()),
+    }
+}
+
+/// Amounts of report rows, one amount for each period
+///
+/// Rows are sorted by key, and commodity is empty string,
+/// if there is no commodity.
+#[derive(Debug)]
+pub(super) struct PeriodTable<K: Ord> {
+    periods: usize,
+    pub(super) rows: BTreeMap<K, Vec<Decimal>>,
+}
+
+impl<K: Ord> PeriodTable<K> {
+    pub(super) fn new(periods: usize) -> Self {
+        PeriodTable {
+            periods,
+            rows: BTreeMap::new(),
+        }
+    }
+
+    pub(super) fn add(&mut self, key: K, period: usize, amount: Decimal) {
+        self.rows
+            .entry(key)
+            .or_insert_with(|| vec![Decimal::ZERO; self.periods])[period] += amount;
+    }
+}
+
+/// Account rows, key is (account, commodity)
+pub(super) type AccountTable = PeriodTable<(String, String)>;
+
+/// Total rows, key is commodity
+pub(super) type TotalTable = PeriodTable<String>;
+
+fn to_api_amounts(amounts: &[Decimal], scale: &Scale) -> Vec<String> {
+    amounts
+        .iter()
+        .map(|a| format_with_scale(0, a, scale))
+        .collect()
+}
+
+fn to_api_commodity(comm: &str) -> Option<String> {
+    (!comm.is_empty()).then(|| comm.to_string())
+}
+
+impl AccountTable {
+    pub(super) fn to_api(&self, scale: &Scale) -> Vec<StatementRow> {
+        self.rows
+            .iter()
+            .map(|((account, comm), amounts)| StatementRow {
+                account: account.clone(),
+                commodity: to_api_commodity(comm),
+                amounts: to_api_amounts(amounts, scale),
+            })
+            .collect()
+    }
+}
+
+impl TotalTable {
+    pub(super) fn to_api(&self, scale: &Scale) -> Vec<StatementTotal> {
+        self.rows
+            .iter()
+            .map(|(comm, amounts)| StatementTotal {
+                commodity: to_api_commodity(comm),
+                amounts: to_api_amounts(amounts, scale),
+            })
+            .collect()
+    }
+}
+
+/// Writer of TXT report lines with period columns
+pub(super) struct TxtColumns<'s> {
+    pub(super) scale: &'s Scale,
+    /// Width of commodity column, zero if there are no commodities
+    pub(super) comm_len: usize,
+}
+
+impl TxtColumns<'_> {
+    const WIDTH: usize = 18;
+
+    pub(super) fn separator_len(&self, periods: usize) -> usize {
+        let comm = if self.comm_len == 0 {
+            0
+        } else {
+            self.comm_len + 2
+        };
+        (periods * (Self::WIDTH + 1)).saturating_sub(1) + comm
+    }
+
+    pub(super) fn write_header<W: io::Write + ?Sized>(
+        writer: &mut W,
+        periods: &[String],
+    ) -> Result<(), tackler::Error> {
+        let header = periods
+            .iter()
+            .map(|p| format!("{p:>w$}", w = Self::WIDTH))
+            .join(" ");
+        writeln!(writer, "{header}")?;
+        Ok(())
+    }
+
+    pub(super) fn write_line<W: io::Write + ?Sized>(
+        &self,
+        writer: &mut W,
+        amounts: &[Decimal],
+        comm: &str,
+        name: &str,
+    ) -> Result<(), tackler::Error> {
+        let amounts = amounts
+            .iter()
+            .map(|a| format_with_scale(Self::WIDTH, a, self.scale))
+            .join(" ");
+        let comm = if self.comm_len == 0 {
+            String::new()
+        } else {
+            format!("  {comm: <cl$}", cl = self.comm_len)
+        };
+        let line = format!("{amounts}{comm}  {name}");
+        writeln!(writer, "{}", line.trim_end())?;
+        Ok(())
+    }
+}
@@ -0,0 +1,347 @@
+/*
+ * Tackler-NG 2026
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use crate::kernel::Settings;
+use crate::kernel::lots::{ClosedLot, Lot, LotInventory, market_price};
+use crate::kernel::price_lookup::PriceLookup;
+use crate::kernel::report_item_selector::{LotAllSelector, LotByAccountSelector, LotSelector};
+use crate::kernel::report_settings::PnlSettings;
+use crate::math::format::format_with_scale;
+use crate::model::TxnSet;
+use crate::report::{
+    CSV_DELIMITER, FormatWriter, Report, TSV_DELIMITER, delimited_writer, iso_timestamp,
+    report_timezone,
+};
+use crate::tackler;
+use rust_decimal::Decimal;
+use std::collections::BTreeMap;
+use std::io;
+use std::io::Write;
+use tackler_api::metadata::Metadata;
+use tackler_api::metadata::items::MetadataItem;
+use tackler_api::reports::pnl_report::{PnlReport, PnlTotal, RealizedItem, UnrealizedItem};
+use tackler_api::txn_ts;
+
+#[derive(Debug, Clone)]
+pub struct PnlReporter {
+    pub report_settings: PnlSettings,
+}
+
+/// Open lot with its market price, if it's known
+struct UnrealizedLot<'a> {
+    lot: &'a Lot,
+    market_price: Option<Decimal>,
+}
+
+impl UnrealizedLot<'_> {
+    fn pnl(&self) -> Option<Decimal> {
+        self.market_price.map(|p| self.lot.pnl(p))
+    }
+}
+
+struct Total {
+    realized: Decimal,
+    unrealized: Option<Decimal>,
+}
+
+impl Default for Total {
+    fn default() -> Self {
+        Total {
+            realized: Decimal::ZERO,
+            unrealized: Some(Decimal::ZERO),
+        }
+    }
+}
+
+impl PnlReporter {
+    fn get_acc_selector(&self) -> Result<Box<dyn LotSelector>, tackler::Error> {
+        let ras = &self.report_settings.ras;
+        if ras.is_empty() {
+            Ok(Box::<LotAllSelector>::default())
+        } else {
+            let s: Vec<_> = ras.iter().map(String::as_str).collect();
+            let ras = LotByAccountSelector::try_from(&s)?;
+
+            Ok(Box::new(ras))
+        }
+    }
+
+    fn totals(closed: &[&ClosedLot], open: &[UnrealizedLot<'_>]) -> BTreeMap<String, Total> {
+        let mut totals: BTreeMap<String, Total> = BTreeMap::new();
+        for cl in closed {
+            let t = totals
+                .entry(cl.lot.cost_commodity.name.clone())
+                .or_default();
+            t.realized += cl.pnl();
+        }
+        for ul in open {
+            let t = totals
+                .entry(ul.lot.cost_commodity.name.clone())
+                .or_default();
+            // Unrealized total is unknown, if any of the market prices is unknown
+            t.unrealized = t.unrealized.zip(ul.pnl()).map(|(a, b)| a + b);
+        }
+        totals
+    }
+
+    fn write_txt<W: io::Write + ?Sized>(
+        &self,
+        writer: &mut W,
+        closed: &[&ClosedLot],
+        open: &[UnrealizedLot<'_>],
+        totals: &BTreeMap<String, Total>,
+    ) -> Result<(), tackler::Error> {
+        let rs = &self.report_settings;
+        let scale = &rs.scale;
+        let fmt_date = |ts| txn_ts::as_tz_date(ts, rs.report_tz.clone());
+
+        let title = &rs.title;
+        writeln!(writer, "{title}")?;
+        writeln!(writer, "{}", "-".repeat(title.chars().count()))?;
+
+        writeln!(writer, "Realized")?;
+        for cl in closed {
+            let lot = &cl.lot;
+            writeln!(
+                writer,
+                "  {}  {}  {:<33}{:>18} {} {{{} {}}} @ {}{:>18} {}",
+                fmt_date(&lot.opened),
+                fmt_date(&cl.closed),
+                lot.account,
+                format_with_scale(0, &lot.quantity, scale),
+                lot.commodity.name,
+                format_with_scale(0, &lot.unit_cost, scale),
+                lot.cost_commodity.name,
+                format_with_scale(0, &cl.unit_price, scale),
+                format_with_scale(0, &cl.pnl(), scale),
+                lot.cost_commodity.name,
+            )?;
+        }
+        writeln!(writer)?;
+
+        writeln!(writer, "Unrealized")?;
+        for ul in open {
+            let lot = ul.lot;
+            writeln!(
+                writer,
+                "  {}  {:<33}{:>18} {} {{{} {}}} @ {}{:>18} {}",
+                fmt_date(&lot.opened),
+                lot.account,
+                format_with_scale(0, &lot.quantity, scale),
+                lot.commodity.name,
+                format_with_scale(0, &lot.unit_cost, scale),
+                lot.cost_commodity.name,
+                ul.market_price
+                    .map_or("n/a".to_string(), |p| format_with_scale(0, &p, scale)),
+                ul.pnl()
+                    .map_or("n/a".to_string(), |p| format_with_scale(0, &p, scale)),
+                lot.cost_commodity.name,
+            )?;
+        }
+        writeln!(writer)?;
+
+        writeln!(writer, "{:>18} {:>18}", "Realized", "Unrealized")?;
+        for (comm, t) in totals {
+            writeln!(
+                writer,
+                "{:>18} {:>18} {}",
+                format_with_scale(0, &t.realized, scale),
+                t.unrealized
+                    .map_or("n/a".to_string(), |u| format_with_scale(0, &u, scale)),
+                comm
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Write `PnL` report as delimited (CSV, TSV) report
+    ///
+    /// There is one record per lot, `closed` and `unit_price` are empty for open lots,
+    /// and `unit_price` and `pnl` are empty, if the market price is unknown.
+    fn write_delimited<W: io::Write>(
+        &self,
+        writer: W,
+        delimiter: u8,
+        closed: &[&ClosedLot],
+        open: &[UnrealizedLot<'_>],
+    ) -> Result<(), tackler::Error> {
+        let tz = &self.report_settings.report_tz;
+
+        let mut wtr = delimited_writer(writer, delimiter);
+        wtr.write_record([
+            "status",
+            "opened",
+            "closed",
+            "account",
+            "quantity",
+            "commodity",
+            "unit_cost",
+            "unit_price",
+            "pnl",
+            "cost_commodity",
+        ])?;
+        for cl in closed {
+            let lot = &cl.lot;
+            wtr.write_record([
+                "realized".to_string(),
+                iso_timestamp(&lot.opened, tz.clone()),
+                iso_timestamp(&cl.closed, tz.clone()),
+                lot.account.clone(),
+                lot.quantity.to_string(),
+                lot.commodity.name.clone(),
+                lot.unit_cost.to_string(),
+                cl.unit_price.to_string(),
+                cl.pnl().to_string(),
+                lot.cost_commodity.name.clone(),
+            ])?;
+        }
+        for ul in open {
+            let lot = ul.lot;
+            wtr.write_record([
+                "unrealized".to_string(),
+                iso_timestamp(&lot.opened, tz.clone()),
+                String::new(),
+                lot.account.clone(),
+                lot.quantity.to_string(),
+                lot.commodity.name.clone(),
+                lot.unit_cost.to_string(),
+                ul.market_price.map(|p| p.to_string()).unwrap_or_default(),
+                ul.pnl().map(|p| p.to_string()).unwrap_or_default(),
+                lot.cost_commodity.name.clone(),
+            ])?;
+        }
+        wtr.flush()?;
+        Ok(())
+    }
+
+    fn to_api(
+        &self,
+        metadata: Metadata,
+        closed: &[&ClosedLot],
+        open: &[UnrealizedLot<'_>],
+        totals: &BTreeMap<String, Total>,
+    ) -> PnlReport {
+        let rs = &self.report_settings;
+        let scale = &rs.scale;
+        let fmt_date = |ts| txn_ts::as_tz_date(ts, rs.report_tz.clone());
+
+        PnlReport {
+            metadata: Some(metadata),
+            title: rs.title.clone(),
+            realized: closed
+                .iter()
+                .map(|cl| RealizedItem {
+                    opened: fmt_date(&cl.lot.opened),
+                    closed: fmt_date(&cl.closed),
+                    account: cl.lot.account.clone(),
+                    quantity: format_with_scale(0, &cl.lot.quantity, scale),
+                    commodity: cl.lot.commodity.name.clone(),
+                    unit_cost: format_with_scale(0, &cl.lot.unit_cost, scale),
+                    unit_price: format_with_scale(0, &cl.unit_price, scale),
+                    pnl: format_with_scale(0, &cl.pnl(), scale),
+                    cost_commodity: cl.lot.cost_commodity.name.clone(),
+                })
+                .collect(),
+            unrealized: open
+                .iter()
+                .map(|ul| UnrealizedItem {
+                    opened: fmt_date(&ul.lot.opened),
+                    account: ul.lot.account.clone(),
+                    quantity: format_with_scale(0, &ul.lot.quantity, scale),
+                    commodity: ul.lot.commodity.name.clone(),
+                    unit_cost: format_with_scale(0, &ul.lot.unit_cost, scale),
+                    market_price: ul.market_price.map(|p| format_with_scale(0, &p, scale)),
+                    pnl: ul.pnl().map(|p| format_with_scale(0, &p, scale)),
+                    cost_commodity: ul.lot.cost_commodity.name.clone(),
+                })
+                .collect(),
+            totals: totals
+                .iter()
+                .map(|(comm, t)| PnlTotal {
+                    realized: format_with_scale(0, &t.realized, scale),
+                    unrealized: t.unrealized.map(|u| format_with_scale(0, &u, scale)),
+                    commodity: comm.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Report for PnlReporter {
+    fn write_reports<W: io::Write + ?Sized>(
+        &self,
+        cfg: &Settings,
+        writers: &mut Vec<FormatWriter<'_>>,
+        metadata: Option<&Metadata>,
+        txn_data: &TxnSet<'_>,
+    ) -> Result<(), tackler::Error> {
+        let acc_sel = self.get_acc_selector()?;
+
+        let inventory = LotInventory::try_from(&txn_data.txns, self.report_settings.lot_matching)?;
+
+        let valuation_ts = match &self.report_settings.price_lookup {
+            PriceLookup::GivenTime(ts) => Some(ts),
+            _ => None,
+        };
+
+        let closed: Vec<_> = inventory
+            .closed_lots()
+            .iter()
+            .filter(|cl| acc_sel.eval(&cl.lot))
+            .collect();
+
+        let open: Vec<_> = inventory
+            .open_lots()
+            .filter(|lot| acc_sel.eval(lot))
+            .map(|lot| UnrealizedLot {
+                lot,
+                market_price: market_price(
+                    &cfg.price.price_db,
+                    &lot.commodity,
+                    &lot.cost_commodity,
+                    valuation_ts,
+                )
+                .map(|(_, price)| price),
+            })
+            .collect();
+
+        let totals = Self::totals(&closed, &open);
+
+        let mut metadata = match metadata {
+            Some(md) => md.clone(),
+            None => Metadata::default(),
+        };
+
+        if let Some(hash) = cfg.get_hash() {
+            let asc = acc_sel.account_selector_metadata(hash);
+            metadata.push(asc);
+        }
+
+        let rtz = MetadataItem::TimeZoneInfo(report_timezone(cfg)?);
+        metadata.push(rtz);
+
+        for w in writers {
+            match w {
+                FormatWriter::TxtFormat(writer) => {
+                    // There is always at least TimeZoneInfo
+                    writeln!(writer, "{}\n", metadata.text(cfg.report.tz.clone()))?;
+                    self.write_txt(writer, &closed, &open, &totals)?;
+                }
+                FormatWriter::JsonFormat(writer) => {
+                    let pnl_report = self.to_api(metadata.clone(), &closed, &open, &totals);
+                    serde_json::to_writer_pretty(&mut *writer, &pnl_report)?;
+                    writeln!(writer)?;
+                }
+                FormatWriter::CsvFormat(writer) => {
+                    self.write_delimited(writer, CSV_DELIMITER, &closed, &open)?;
+                }
+                FormatWriter::TsvFormat(writer) => {
+                    self.write_delimited(writer, TSV_DELIMITER, &closed, &open)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
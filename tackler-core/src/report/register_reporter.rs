@@ -10,8 +10,12 @@ use crate::kernel::report_item_selector::{
 };
 use crate::kernel::report_settings::RegisterSettings;
 use crate::math::format::format_with_scale;
+use crate::model;
 use crate::model::{RegisterEntry, TxnSet};
-use crate::report::{FormatWriter, Report, report_timezone};
+use crate::report::{
+    CSV_DELIMITER, FormatWriter, Report, TSV_DELIMITER, delimited_writer, iso_timestamp,
+    report_timezone,
+};
 use crate::tackler;
 use crate::tackler::Error;
 use jiff::Zoned;
@@ -21,7 +25,9 @@ use std::io;
 use std::io::Write;
 use tackler_api::metadata::Metadata;
 use tackler_api::metadata::items::{CreditAccountReport, MetadataItem};
-use tackler_api::reports::register_report::{RegisterPosting, RegisterReport, RegisterTxn};
+use tackler_api::reports::register_report::{
+    RegisterPosition, RegisterPosting, RegisterReport, RegisterTxn,
+};
 use tackler_api::txn_ts;
 use tackler_api::txn_ts::TimestampStyle;
 
@@ -115,6 +121,9 @@ fn register_entry_to_api(
                 commodity,
                 rate: p.rate.map(|r| format_with_scale(0, &r, scale)),
                 base_commodity,
+                position: register_settings
+                    .cost_basis
+                    .then(|| position_to_api(p, register_settings)),
             }
         })
         .collect();
@@ -126,6 +135,131 @@ fn register_entry_to_api(
     })
 }
 
+fn position_to_api(
+    p: &model::RegisterPosting<'_>,
+    register_settings: &RegisterSettings,
+) -> RegisterPosition {
+    let scale = &register_settings.scale;
+    let (quantity, cost_basis) = position_amounts(p, register_settings.inverted);
+
+    RegisterPosition {
+        quantity: format_with_scale(0, &quantity, scale),
+        commodity: p
+            .post
+            .acctn
+            .comm
+            .is_any()
+            .then(|| p.post.acctn.comm.name.clone()),
+        cost_basis: cost_basis.map(|c| format_with_scale(0, &c, scale)),
+        avg_cost: p
+            .position
+            .avg_cost()
+            .map(|a| format_with_scale(0, &a, scale)),
+        cost_commodity: p.position.cost.as_ref().map(|c| c.commodity.name.clone()),
+    }
+}
+
+/// Running quantity and cost basis of posting's position, inverted if needed
+fn position_amounts(p: &model::RegisterPosting<'_>, inverted: bool) -> (Decimal, Option<Decimal>) {
+    let pos = &p.position;
+    let cost_basis = pos.cost.as_ref().map(|c| c.amount);
+    if inverted {
+        (-pos.quantity, cost_basis.map(|c| -c))
+    } else {
+        (pos.quantity, cost_basis)
+    }
+}
+
+/// Column names of delimited (CSV, TSV) register report
+const DELIMITED_HEADER: [&str; 11] = [
+    "timestamp",
+    "code",
+    "description",
+    "uuid",
+    "account",
+    "amount",
+    "running_total",
+    "commodity",
+    "rate",
+    "base_commodity",
+    "comment",
+];
+
+/// Column names of running positions, see `report.register.cost-basis`
+const DELIMITED_POSITION_HEADER: [&str; 4] =
+    ["quantity", "cost_basis", "avg_cost", "cost_commodity"];
+
+/// Write register as delimited (CSV, TSV) report
+///
+/// There is one record per posting, amounts are with full precision
+/// and timestamps are ISO-8601 timestamps in report timezone.
+fn write_delimited<W: io::Write>(
+    writer: W,
+    delimiter: u8,
+    register: &[RegisterEntry<'_>],
+    register_settings: &RegisterSettings,
+) -> Result<(), tackler::Error> {
+    let inverter = Decimal::from(-1);
+    let report_tz = &register_settings.report_tz;
+
+    let mut wtr = delimited_writer(writer, delimiter);
+    if register_settings.cost_basis {
+        wtr.write_record(DELIMITED_HEADER.iter().chain(&DELIMITED_POSITION_HEADER))?;
+    } else {
+        wtr.write_record(DELIMITED_HEADER)?;
+    }
+    for re in register {
+        let hdr = &re.txn.header;
+        for p in &re.posts {
+            let (a, rt) = if register_settings.inverted {
+                (p.post.amount * inverter, p.amount * inverter)
+            } else {
+                (p.post.amount, p.amount)
+            };
+            let mut record = vec![
+                iso_timestamp(&hdr.timestamp, report_tz.clone()),
+                hdr.code.clone().unwrap_or_default(),
+                hdr.description.clone().unwrap_or_default(),
+                hdr.uuid.map(|u| u.to_string()).unwrap_or_default(),
+                p.post.acctn.atn.account.clone(),
+                a.to_string(),
+                rt.to_string(),
+                if p.target_commodity.is_any() {
+                    p.target_commodity.name.clone()
+                } else {
+                    String::new()
+                },
+                p.rate.map(|r| r.to_string()).unwrap_or_default(),
+                if p.is_commodity_conv() {
+                    p.post.acctn.comm.name.clone()
+                } else {
+                    String::new()
+                },
+                p.post.comment.clone().unwrap_or_default(),
+            ];
+            if register_settings.cost_basis {
+                let (quantity, cost_basis) = position_amounts(p, register_settings.inverted);
+                record.extend([
+                    quantity.to_string(),
+                    cost_basis.map(|c| c.to_string()).unwrap_or_default(),
+                    p.position
+                        .avg_cost()
+                        .map(|a| a.to_string())
+                        .unwrap_or_default(),
+                    p.position
+                        .cost
+                        .as_ref()
+                        .map(|c| c.commodity.name.clone())
+                        .unwrap_or_default(),
+                ]);
+            }
+            wtr.write_record(record)?;
+        }
+    }
+    wtr.flush()?;
+    Ok(())
+}
+
 impl Report for RegisterReporter {
     fn write_reports<W: Write + ?Sized>(
         &self,
@@ -201,8 +335,115 @@ impl Report for RegisterReporter {
                     serde_json::to_writer_pretty(&mut *writer, &rr)?;
                     writeln!(writer)?;
                 }
+                FormatWriter::CsvFormat(writer) => {
+                    write_delimited(writer, CSV_DELIMITER, &register, &self.report_settings)?;
+                }
+                FormatWriter::TsvFormat(writer) => {
+                    write_delimited(writer, TSV_DELIMITER, &register, &self.report_settings)?;
+                }
             }
         }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+    use indoc::indoc;
+    use tackler_rs::IndocUtils;
+
+    #[test]
+    // test: 2d7f1b5e-0c3a-4e9d-b6f8-4a2c0e8d6b1f
+    // desc: register with running positions, cost basis and average cost
+    fn register_cost_basis() {
+        let mut settings = Settings::default();
+        settings.report.register.title = "Register Report".to_string();
+        settings.report.register.cost_basis = true;
+        #[rustfmt::skip]
+        let txns_str = indoc!(
+           "|2024-01-05 'buy
+            | Assets:Broker  10 ACME @ 5 EUR
+            | Assets:Cash
+            |
+            |2024-01-08 'sell
+            | Assets:Broker  -4 ACME @ 7 EUR
+            | Assets:Cash
+            |"
+        ).strip_margin();
+        let txn_data = parser::string_to_txns(&mut txns_str.as_str(), &settings).unwrap(/*:test:*/);
+        let txn_set = txn_data.get_all().unwrap(/*:test:*/);
+
+        let mut report_settings = RegisterSettings::try_from(&settings).unwrap(/*:test:*/);
+        report_settings.ras = vec!["Assets:Broker".to_string()];
+        report_settings.timestamp_style = TimestampStyle::Date;
+        let reporter = RegisterReporter { report_settings };
+
+        let mut report = Vec::<u8>::new();
+        reporter
+            .write_txt_report(&settings, &mut report, &txn_set)
+            .unwrap(/*:test:*/);
+
+        #[rustfmt::skip]
+        let reference = indoc!(
+           "|Report Time Zone
+            |        TZ name : UTC
+            |
+            |
+            |Register Report
+            |---------------
+            |2024-01-05 'buy
+            |            Assets:Broker                                 10.00              10.00 ACME
+            |              position                                    10.00 ACME, cost 50.00 EUR, avg 5.00 EUR
+            |---------------------------------------------------------------------------------------
+            |2024-01-08 'sell
+            |            Assets:Broker                                 -4.00               6.00 ACME
+            |              position                                     6.00 ACME, cost 30.00 EUR, avg 5.00 EUR
+            |---------------------------------------------------------------------------------------
+            |"
+        ).strip_margin();
+        assert_eq!(String::from_utf8(report).unwrap(/*:test:*/), reference);
+    }
+
+    #[test]
+    // test: 8c0e2a4b-6d8f-4c1e-9a3b-5d7f9b1c3e5a
+    // desc: register as TSV, with full precision amounts
+    fn register_tsv() {
+        let settings = Settings::default();
+        #[rustfmt::skip]
+        let txns_str = indoc!(
+           "|2024-01-05T10:00:00Z (101) 'lunch
+            | # uuid: 1f3a5c7e-9b2d-4f6a-8c0e-2a4c6e8a0b1d
+            | Expenses:Food  12.345678901 EUR ; with tip
+            | Assets:Cash
+            |
+            |2024-01-08 'dinner
+            | Expenses:Food  20 EUR
+            | Assets:Cash
+            |"
+        ).strip_margin();
+        let txn_data = parser::string_to_txns(&mut txns_str.as_str(), &settings).unwrap(/*:test:*/);
+        let txn_set = txn_data.get_all().unwrap(/*:test:*/);
+
+        let mut report_settings = RegisterSettings::try_from(&settings).unwrap(/*:test:*/);
+        report_settings.ras = vec!["Expenses:Food".to_string()];
+        let reporter = RegisterReporter { report_settings };
+
+        let mut report = Vec::<u8>::new();
+        let mut writers = vec![FormatWriter::TsvFormat(Box::new(&mut report))];
+        reporter
+            .write_reports::<dyn io::Write>(&settings, &mut writers, None, &txn_set)
+            .unwrap(/*:test:*/);
+        drop(writers);
+
+        #[rustfmt::skip]
+        let reference = indoc!(
+           "|timestamp\tcode\tdescription\tuuid\taccount\tamount\trunning_total\tcommodity\trate\tbase_commodity\tcomment
+            |2024-01-05T10:00:00+00:00\t101\tlunch\t1f3a5c7e-9b2d-4f6a-8c0e-2a4c6e8a0b1d\tExpenses:Food\t12.345678901\t12.345678901\tEUR\t\t\twith tip
+            |2024-01-08T00:00:00+00:00\t\tdinner\t\tExpenses:Food\t20\t32.345678901\tEUR\t\t\t
+            |"
+        ).strip_margin();
+        assert_eq!(String::from_utf8(report).unwrap(/*:test:*/), reference);
+    }
+}
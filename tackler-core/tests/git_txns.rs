@@ -86,7 +86,7 @@ mod git_txns {
             "txns/2016",
             "txn",
             GitInputSelector::Reference("set-1e1".to_string()),
-            &mut Settings::default_audit(),
+            &Settings::default_audit(),
         );
         verify_git_run(&result, TXN_SET_1E1_COMMIT_ID, TXN_SET_1E1_CHECKSUM);
     }
@@ -109,13 +109,13 @@ mod git_txns {
             "txns/2016",
             "txn",
             GitInputSelector::Reference("set-1e1".to_string()),
-            &mut Settings::default_audit(),
+            &Settings::default_audit(),
         );
         verify_git_run(&result, TXN_SET_1E1_COMMIT_ID, TXN_SET_1E1_CHECKSUM);
 
         let mut txns = result.unwrap();
         let mut txns_01 = parser::string_to_txns(
-        &mut str_txn_01.as_str(), &mut Settings::default_audit()).unwrap(/*:test:*/);
+        &mut str_txn_01.as_str(), &Settings::default_audit()).unwrap(/*:test:*/);
 
         let txn_set = txns.append(&mut txns_01).unwrap(/*:test:*/).get_all().unwrap(/*:test:*/);
         match txn_set.metadata() {
@@ -145,7 +145,7 @@ mod git_txns {
             "txns/2016",
             "txn",
             GitInputSelector::Reference("set-1e5".to_string()),
-            &mut Settings::default_audit(),
+            &Settings::default_audit(),
         );
 
         verify_git_run(&result, TXN_SET_1E5_COMMIT_ID, TXN_SET_1E5_CHECKSUM);
@@ -168,7 +168,7 @@ mod git_txns {
             "txns/2016",
             "txn",
             GitInputSelector::Reference("err-1e2".to_string()),
-            &mut Settings::default_audit(),
+            &Settings::default_audit(),
         );
 
         assert!(result.is_err());
@@ -0,0 +1,189 @@
+/*
+ * Tackler-NG 2026
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+mod txns_balance_assertion {
+    use indoc::indoc;
+    use tackler_core::diagnostic;
+    use tackler_core::diagnostic::Diagnostic;
+    use tackler_core::kernel::Settings;
+    use tackler_core::parser;
+    use tackler_rs::IndocUtils;
+
+    #[test]
+    // test: 0c1a3e5c-3b0e-4d53-9f63-7d4c0e6a1a2f
+    // desc: balance assertions are checked in txn order, not in input order
+    fn assertions_ok() {
+        #[rustfmt::skip]
+        let input = indoc!(
+            "|2026-01-03 'txn03
+             | Expenses:Food  5 EUR
+             | Assets:Cash  -5 EUR == 85 EUR
+             |
+             |2026-01-01 'txn01
+             | Assets:Cash  100 EUR == 100 EUR
+             | Equity:Opening
+             |
+             |2026-01-02 'txn02
+             | Expenses:Food  10
+             | Expenses:Food  -10 == 0
+             |
+             |2026-01-02 'txn02
+             | Expenses:Food  10 EUR
+             | Assets:Cash  -5 EUR
+             | Assets:Cash  -5 EUR == 90 EUR
+             |"
+        ).strip_margin();
+
+        let txns = parser::string_to_txns(&mut input.as_str(), &Settings::default());
+
+        assert!(txns.is_ok(), "{:?}", txns.err());
+    }
+
+    #[test]
+    // test: 5e2b8a0e-43f5-4b7c-9a60-2f3c1a6cbb8e
+    // desc: failing balance assertion is reported with its txn and account
+    fn assertion_fails() {
+        #[rustfmt::skip]
+        let input = indoc!(
+            "|2026-01-01 'txn01
+             | Assets:Cash  100 EUR == 100 EUR
+             | Equity:Opening
+             |
+             |2026-01-02 (#002) 'txn02
+             | # uuid: 1a62bfb3-6d1b-4f1c-9a0e-3bda4f32b3a0
+             | Expenses:Food  10 EUR
+             | Assets:Cash:Wallet  -5 EUR
+             | Assets:Cash  -5 EUR == 90 EUR
+             |"
+        ).strip_margin();
+
+        let txns = parser::string_to_txns(&mut input.as_str(), &Settings::default());
+
+        let err_msg = txns.expect_err("test case went wonky").to_string();
+
+        assert!(err_msg.contains("Balance assertion failed for account: Assets:Cash [EUR]"));
+        assert!(err_msg.contains("expected: 90"));
+        assert!(err_msg.contains("actual:   95"));
+        assert!(err_msg.contains("txn date: 2026-01-02T00:00:00+00:00"));
+        assert!(err_msg.contains("txn code: #002"));
+        assert!(err_msg.contains("txn uuid: 1a62bfb3-6d1b-4f1c-9a0e-3bda4f32b3a0"));
+    }
+
+    #[test]
+    // test: 3d7b1f9e-5a2c-4e8d-b6f0-8c1e3a5d7f92
+    // desc: failing balance assertion is located at its posting
+    fn assertion_fails_location() {
+        #[rustfmt::skip]
+        let shards = [
+            ("a.txn", indoc!(
+                "|2026-01-01 'txn01
+                 | Assets:Cash  100 EUR == 100 EUR
+                 | Equity:Opening
+                 |"
+            ).strip_margin()),
+            ("b.txn", indoc!(
+                "|2026-01-02 'txn02
+                 | Expenses:Food  10 EUR
+                 | Assets:Cash  -10 EUR == 90 EUR
+                 |
+                 |2026-01-03 'txn03
+                 | Expenses:Food  5 EUR
+                 |   Assets:Cash  -5 EUR == 90 EUR
+                 |"
+            ).strip_margin()),
+        ];
+        let dir = tempfile::tempdir().unwrap(/*:test:*/);
+        let paths: Vec<_> = shards
+            .iter()
+            .map(|(name, txns)| {
+                let path = dir.path().join(name);
+                std::fs::write(&path, txns).unwrap(/*:test:*/);
+                path
+            })
+            .collect();
+
+        let err =
+            parser::paths_to_txns(&paths, &Settings::default()).expect_err("test case went wonky");
+        let diag = Diagnostic::from_error(err.as_ref());
+
+        assert_eq!(diag.code, diagnostic::BALANCE_ASSERTION);
+        assert_eq!(diag.path, Some(paths[1].display().to_string()));
+        assert_eq!(diag.line, Some(7));
+        assert_eq!(diag.column, Some(4));
+        assert!(diag.message.contains("actual:   85"));
+        assert!(
+            err.to_string()
+                .contains(&format!("path: {}:7:4", paths[1].display()))
+        );
+    }
+
+    #[test]
+    // test: 9f0d54f1-1a0c-4a3c-b0f6-7b7d1e0c2d55
+    // desc: assertion commodity must match the posting commodity
+    fn assertion_commodity_mismatch() {
+        #[rustfmt::skip]
+        let input = indoc!(
+            "|2026-01-01 'txn01
+             | Assets:Cash  100 EUR == 100 USD
+             | Equity:Opening
+             |"
+        ).strip_margin();
+
+        let txns = parser::string_to_txns(&mut input.as_str(), &Settings::default());
+
+        let err_msg = txns.expect_err("test case went wonky").to_string();
+
+        assert!(err_msg.contains("Balance assertion commodity [USD]"));
+    }
+
+    #[test]
+    // test: 4b8e2c6a-1f3d-4a7e-9c05-d2e6b8f1a3c7
+    // desc: balance assertion on the last posting without amount
+    fn assertion_last_posting() {
+        #[rustfmt::skip]
+        let input = indoc!(
+            "|2026-01-01 'txn01
+             | Assets:Cash  100 EUR
+             | Equity:Opening
+             |
+             |2026-01-02 'txn02
+             | Expenses:Food  10 EUR
+             | Assets:Cash == 90 EUR ; reconcile
+             |"
+        ).strip_margin();
+
+        let txns = parser::string_to_txns(&mut input.as_str(), &Settings::default());
+        assert!(txns.is_ok(), "{:?}", txns.err());
+
+        let input = input.replace("== 90 EUR", "== 95 EUR");
+        let txns = parser::string_to_txns(&mut input.as_str(), &Settings::default());
+        let err_msg = txns.expect_err("test case went wonky").to_string();
+        assert!(err_msg.contains("Balance assertion failed for account: Assets:Cash [EUR]"));
+        assert!(err_msg.contains("actual:   90"));
+
+        let input = input.replace("== 95 EUR", "== 90 USD");
+        let txns = parser::string_to_txns(&mut input.as_str(), &Settings::default());
+        let err_msg = txns.expect_err("test case went wonky").to_string();
+        assert!(err_msg.contains("Balance assertion commodity [USD]"));
+    }
+
+    #[test]
+    // test: 7c1e5a9d-3b6f-4e2a-8d40-f5a9c3e7b1d6
+    // desc: balance assertions are checked again when txn data is appended
+    fn assertion_append() {
+        let settings = Settings::default();
+        let input_a = "2026-01-01 'txn01\n Assets:Cash  100 EUR == 100 EUR\n Equity:Opening\n";
+        let input_b = "2026-01-02 'txn02\n Assets:Cash  50 EUR == 50 EUR\n Equity:Opening\n";
+
+        let mut txn_data = parser::string_to_txns(&mut &*input_a, &settings).unwrap(/*:test:*/);
+        let mut txn_data_b = parser::string_to_txns(&mut &*input_b, &settings).unwrap(/*:test:*/);
+
+        let res = txn_data.append(&mut txn_data_b);
+        let err_msg = res.err().unwrap(/*:test:*/).to_string();
+        assert!(err_msg.contains("Balance assertion failed for account: Assets:Cash [EUR]"));
+        assert!(err_msg.contains("expected: 50"));
+        assert!(err_msg.contains("actual:   150"));
+    }
+}
@@ -87,10 +87,10 @@ mod txns_checksum {
         ).strip_margin();
 
         let mut txns_audit = parser::string_to_txns(
-            &mut str_uuids.as_str(), &mut Settings::default_audit()).unwrap(/*:test:*/);
+            &mut str_uuids.as_str(), &Settings::default_audit()).unwrap(/*:test:*/);
 
         let mut txns_plain = parser::string_to_txns(
-            &mut str_no_uuid.as_str(), &mut Settings::default()).unwrap(/*:test:*/);
+            &mut str_no_uuid.as_str(), &Settings::default()).unwrap(/*:test:*/);
 
         let err = txns_audit.append(&mut txns_plain);
         let err_msg = err.expect_err("test case went wonky").to_string();
@@ -133,10 +133,10 @@ mod txns_checksum {
         ).strip_margin();
 
         let mut txns_audit = parser::string_to_txns(
-            &mut str_uuids.as_str(), &mut Settings::default_audit()).unwrap(/*:test:*/);
+            &mut str_uuids.as_str(), &Settings::default_audit()).unwrap(/*:test:*/);
 
         let mut txns_dup = parser::string_to_txns(
-            &mut str_duplicate_uuid.as_str(), &mut Settings::default()).unwrap(/*:test:*/);
+            &mut str_duplicate_uuid.as_str(), &Settings::default()).unwrap(/*:test:*/);
 
         let err = txns_audit.append(&mut txns_dup);
         let err_msg = err.expect_err("test case went wonky").to_string();
@@ -178,11 +178,11 @@ mod txns_checksum {
             ).strip_margin();
 
             let mut txns_01 = parser::string_to_txns(
-                &mut str_txn_01.as_str(), &mut Settings::default_audit()).unwrap(/*:test:*/);
+                &mut str_txn_01.as_str(), &Settings::default_audit()).unwrap(/*:test:*/);
             let mut txns_02 = parser::string_to_txns(
-                &mut str_txn_02.as_str(), &mut Settings::default_audit()).unwrap(/*:test:*/);
+                &mut str_txn_02.as_str(), &Settings::default_audit()).unwrap(/*:test:*/);
             let mut txns_03 = parser::string_to_txns(
-                &mut str_txn_03.as_str(), &mut Settings::default_audit()).unwrap(/*:test:*/);
+                &mut str_txn_03.as_str(), &Settings::default_audit()).unwrap(/*:test:*/);
 
             txns_01.append(&mut txns_02)?.append(&mut txns_03)?;
             Ok(txns_01)
@@ -217,7 +217,7 @@ mod txns_checksum {
         ).strip_margin();
 
         let txns_all = parser::string_to_txns(
-            &mut str_uuids.as_str(), &mut Settings::default_audit()).unwrap(/*:test:*/);
+            &mut str_uuids.as_str(), &Settings::default_audit()).unwrap(/*:test:*/);
         //verify_checksum(&Ok(txns_all), TXN_SET_ALL_CHECKSUM);
 
         let ts_begin = TxnFilterTxnTSBegin {
@@ -292,7 +292,7 @@ mod txns_checksum {
         ).strip_margin();
 
         let txns_all = parser::string_to_txns(
-            &mut str_uuids.as_str(), &mut Settings::default_audit()).unwrap(/*:test:*/);
+            &mut str_uuids.as_str(), &Settings::default_audit()).unwrap(/*:test:*/);
 
         let filter_json_str = r#"{"txnFilter":{"TxnFilterAND":{"txnFilters":[
             {"TxnFilterTxnTSBegin":{"begin":"2019-02-01T00:00:00Z"}},
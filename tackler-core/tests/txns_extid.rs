@@ -58,8 +58,7 @@ mod txns_extid {
     // test: f3c3f4fb-2c58-47d8-82a6-82b04a752e2e
     // desc: try_from accepts duplicate ext-ids
     fn txns_try_from_accepts_dup_extid() {
-        let txns =
-            parser::string_to_txns(&mut make_dups_extid().as_str(), &mut Settings::default());
+        let txns = parser::string_to_txns(&mut make_dups_extid().as_str(), &Settings::default());
 
         assert!(txns.is_ok());
     }
@@ -68,10 +67,8 @@ mod txns_extid {
     // test: c4905afd-ea7a-460f-8f0b-ab46803f63be
     // desc: try_from detects duplicate ext-ids
     fn txns_try_from_detects_dup_extid() {
-        let txns = parser::string_to_txns(
-            &mut make_dups_extid().as_str(),
-            &mut Settings::default_extid(),
-        );
+        let txns =
+            parser::string_to_txns(&mut make_dups_extid().as_str(), &Settings::default_extid());
 
         let err_msg = txns.expect_err("test case went wonky").to_string();
 
@@ -84,10 +81,10 @@ mod txns_extid {
     // desc: append accepts duplicate ext-ids
     fn txns_append_accepts_dup_extid() {
         let mut txns = parser::string_to_txns(
-            &mut make_extids().as_str(), &mut Settings::default()).unwrap(/*:test:*/);
+            &mut make_extids().as_str(), &Settings::default()).unwrap(/*:test:*/);
 
         let mut txns_dup = parser::string_to_txns(
-            &mut dup_extid().as_str(), &mut Settings::default()).unwrap(/*:test:*/);
+            &mut dup_extid().as_str(), &Settings::default()).unwrap(/*:test:*/);
 
         let res = txns.append(&mut txns_dup);
 
@@ -99,10 +96,10 @@ mod txns_extid {
     // desc: append detects duplicate ext-ids
     fn txns_append_detects_dup_extid() {
         let mut txns = parser::string_to_txns(
-            &mut make_extids().as_str(), &mut Settings::default_extid()).unwrap(/*:test:*/);
+            &mut make_extids().as_str(), &Settings::default_extid()).unwrap(/*:test:*/);
 
         let mut txns_dup = parser::string_to_txns(
-            &mut dup_extid().as_str(), &mut Settings::default()).unwrap(/*:test:*/);
+            &mut dup_extid().as_str(), &Settings::default()).unwrap(/*:test:*/);
 
         let err = txns.append(&mut txns_dup);
         let err_msg = err.expect_err("test case went wonky").to_string();
@@ -75,6 +75,27 @@ cmp_result $module $test_name json reg
 cmp_result $module $test_name txn identity
 echo ": ok"
 
+#
+# txn-tags-filter-02, filter expression
+#
+# test: 3d5f7a9c-1e2b-4c6d-8f0a-2b4c6e8f0a1d
+rm -f $OUTPUT_DIR/*
+test_name=txn-tags-filter-02
+echo "test: $module/$test_name (expr): "
+
+$TACKLER_SH \
+    --config $SUITE_PATH/$module/ok.toml \
+    --output.dir $OUTPUT_DIR \
+    --output.prefix $test_name \
+    --input.file $SUITE_PATH/$module/ok/txn-tags-filter.txn \
+    --api-filter-def 'tag:"reindeer:.*" and not tag:"reindeer:Normal·Nose"'
+
+echo -n "check:"
+cmp_result $module $test_name txt reg
+cmp_result $module $test_name json reg
+cmp_result $module $test_name txn identity
+echo ": ok"
+
 #
 # identity-01
 #